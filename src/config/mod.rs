@@ -0,0 +1,163 @@
+pub mod config {
+    //! Module that reads the `lojidoc.toml` configuration file of a project
+
+    use std::fmt;
+    use std::fs;
+    use std::io;
+    use std::path::Path;
+
+    use toml;
+
+    /// The name of the configuration file which is read from the working directory
+    pub const CONFIG_FILE: &str = "lojidoc.toml";
+
+    /// The commented configuration file written by `--init`
+    pub const CONFIG_TEMPLATE: &str = "# Configuration of lojidoc. Options on the command line take precedence.
+# Paths are relative to the directory lojidoc is run in.
+
+# The directory the documentation is written to
+# destination = \"./generated/\"
+
+# The format of the documentation: markdown, html, asciidoc, rst or json
+# format = \"markdown\"
+
+# Glob patterns of the files and directories which are not documented
+# exclude = [\"**/test/**\", \"**/generated/**\"]
+
+# Glob patterns of the java files which are documented, all files when empty
+# include = [\"**/api/**\"]
+
+# The number of threads, a single thread is used when it is not set
+# jobs = 4
+
+# The url of the repository used for the source links
+# context = \"https://github.com/user/project/tree/master\"
+
+# The template of the source links, which is used instead of the context. `{path}` is
+# the path of the java file relative to the input directory, `{line}` the line of the
+# declaration.
+# source_url = \"https://github.com/user/project/blob/master/src/{path}#L{line}\"
+
+# The least visible types and members which are documented: public, protected,
+# package or private. Everything is documented when it is not set.
+# min_visibility = \"public\"
+";
+
+    /// The formats which can be set in the configuration file
+    const FORMATS: &[&str] = &["markdown", "html", "asciidoc", "rst", "json"];
+
+    /// The visibilities which can be set in the configuration file
+    const VISIBILITIES: &[&str] = &["public", "protected", "package", "private"];
+
+    /// Errors which stop a configuration file from being read
+    #[derive(Debug)]
+    pub enum ConfigError {
+        /// The file could not be opened or read
+        Io(io::Error),
+        /// The file is not valid, the message names the key or line
+        Invalid(String),
+    }
+
+    impl fmt::Display for ConfigError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                ConfigError::Io(err) => write!(f, "{}", err),
+                ConfigError::Invalid(message) => write!(f, "{}", message),
+            }
+        }
+    }
+
+    impl From<io::Error> for ConfigError {
+        fn from(err: io::Error) -> ConfigError {
+            ConfigError::Io(err)
+        }
+    }
+
+    #[derive(Debug, Default, Deserialize, PartialEq)]
+    #[serde(deny_unknown_fields)]
+    /// Settings read from a `lojidoc.toml` file. Every setting is optional.
+    pub struct ConfigFile {
+        pub destination: Option<String>,
+        pub format: Option<String>,
+        pub exclude: Option<Vec<String>>,
+        pub include: Option<Vec<String>>,
+        pub jobs: Option<usize>,
+        pub context: Option<String>,
+        pub source_url: Option<String>,
+        pub min_visibility: Option<String>,
+    }
+
+    /// Adds the line of the key named in an error message of the toml parser, which only
+    /// names the line of syntax errors
+    fn error_line(text: &str, message: String) -> String {
+        if message.contains(" at line ") {
+            return message;
+        }
+
+        let key = ["for key `", "unknown field `"].iter().find_map(|prefix| {
+            let start = message.find(prefix)? + prefix.len();
+            let end = message[start..].find('`')?;
+
+            Some(message[start..start + end].to_string())
+        });
+        let line = key.and_then(|key| {
+            text.lines().position(|line| {
+                let line = line.trim_start();
+                line.starts_with(key.as_str())
+                    && line[key.len()..].trim_start().starts_with('=')
+            })
+        });
+
+        match line {
+            Some(line) => format!("{} at line {}", message, line + 1),
+            None => message,
+        }
+    }
+
+    /// Parses the contents of a configuration file
+    pub fn parse_config(text: &str) -> Result<ConfigFile, ConfigError> {
+        let config: ConfigFile = toml::from_str(text)
+            .map_err(|err| ConfigError::Invalid(error_line(text, err.to_string())))?;
+
+        if let Some(ref format) = config.format {
+            if !FORMATS.contains(&format.as_str()) {
+                let message = format!(
+                    "unknown format `{}` for key `format`, expected one of {}",
+                    format,
+                    FORMATS.join(", ")
+                );
+                return Err(ConfigError::Invalid(error_line(text, message)));
+            }
+        }
+        if let Some(ref visibility) = config.min_visibility {
+            if !VISIBILITIES.contains(&visibility.as_str()) {
+                let message = format!(
+                    "unknown visibility `{}` for key `min_visibility`, expected one of {}",
+                    visibility,
+                    VISIBILITIES.join(", ")
+                );
+                return Err(ConfigError::Invalid(error_line(text, message)));
+            }
+        }
+        if config.jobs == Some(0) {
+            let message = "the number of jobs must be a positive number for key `jobs`".to_string();
+            return Err(ConfigError::Invalid(error_line(text, message)));
+        }
+
+        Ok(config)
+    }
+
+    /// Reads a configuration file
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the `lojidoc.toml` file
+    pub fn read_config(path: &Path) -> Result<ConfigFile, ConfigError> {
+        let text = fs::read_to_string(path)?;
+
+        parse_config(&text)
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,35 @@
+use config::config::*;
+
+#[test]
+fn test_parse_config() {
+    let config = parse_config(
+        "destination = \"docs\"\nformat = \"html\"\nexclude = [\"**/test/**\"]\njobs = 4\n",
+    ).unwrap();
+
+    assert_eq!(Some("docs".to_string()), config.destination);
+    assert_eq!(Some("html".to_string()), config.format);
+    assert_eq!(Some(vec!["**/test/**".to_string()]), config.exclude);
+    assert_eq!(None, config.include);
+    assert_eq!(Some(4), config.jobs);
+    assert_eq!(ConfigFile::default(), parse_config(CONFIG_TEMPLATE).unwrap());
+}
+
+#[test]
+fn test_invalid_config() {
+    let message = |text: &str| parse_config(text).unwrap_err().to_string();
+
+    assert_eq!(
+        "unknown field `destinaton`, expected one of `destination`, `format`, `exclude`, \
+         `include`, `jobs`, `context`, `source_url`, `min_visibility` at line 2",
+        message("destination = \"docs\"\ndestinaton = \"x\"\n")
+    );
+    assert_eq!("newline in string found at line 1", message("destination = \"docs\n"));
+    assert!(message("destination = \"docs\"\njobs = \"four\"\n").contains("line 2"));
+    assert!(message("jobs = 2\nformat = \"pdf\"\n").ends_with("at line 2"));
+    assert!(message("jobs = 0\n").contains("`jobs`"));
+    assert_eq!(
+        "unknown visibility `internal` for key `min_visibility`, expected one of public, \
+         protected, package, private at line 1",
+        message("min_visibility = \"internal\"\n")
+    );
+}
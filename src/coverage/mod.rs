@@ -0,0 +1,536 @@
+pub mod coverage {
+    //! Module that measures how much of the API of a project is documented
+
+    use std::collections::BTreeMap;
+    use std::fs;
+    use std::path::Path;
+    use std::time::Duration;
+
+    use serde_json;
+
+    use document::document::is_documented;
+    use document::document::is_public;
+    use document::render::Markdown;
+    use document::render::Renderer;
+    use error::error::LojidocError;
+    use model::model::Annotation;
+    use model::model::Class;
+    use model::model::Enumeration;
+    use model::model::Interface;
+    use model::model::Method;
+    use model::model::ObjectType;
+    use model::model::Project;
+
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Serialize)]
+    /// The number of documented items out of the counted items
+    pub struct Count {
+        pub documented: usize,
+        pub total: usize,
+    }
+
+    impl Count {
+        /// Counts an item
+        pub fn add(&mut self, documented: bool) {
+            self.total += 1;
+
+            if documented {
+                self.documented += 1;
+            }
+        }
+        pub fn merge(&mut self, other: Count) {
+            self.documented += other.documented;
+            self.total += other.total;
+        }
+        /// The percentage of documented items, 100 when nothing is counted
+        pub fn percent(&self) -> f64 {
+            if self.total == 0 {
+                100.0
+            } else {
+                self.documented as f64 * 100.0 / self.total as f64
+            }
+        }
+    }
+
+    #[derive(Debug, Default, Clone, PartialEq, Serialize)]
+    /// The documented methods and parameters of a type
+    pub struct TypeCoverage {
+        /// Whether the type has a javadoc
+        pub documented: bool,
+        pub methods: Count,
+        pub params: Count,
+    }
+
+    impl TypeCoverage {
+        /// The count of the type, its methods and parameters together
+        pub fn all(&self) -> Count {
+            let mut count = Count {
+                documented: self.documented as usize,
+                total: 1,
+            };
+            count.merge(self.methods);
+            count.merge(self.params);
+
+            count
+        }
+    }
+
+    #[derive(Debug, Default, Clone, PartialEq, Serialize)]
+    /// The documented types, methods and parameters of a package
+    pub struct PackageCoverage {
+        pub types: Count,
+        pub methods: Count,
+        pub params: Count,
+        /// The coverage of each type by name, nested types are named `Outer.Inner`
+        pub classes: BTreeMap<String, TypeCoverage>,
+    }
+
+    impl PackageCoverage {
+        /// The count of every type, method and parameter together
+        pub fn all(&self) -> Count {
+            let mut count = self.types;
+            count.merge(self.methods);
+            count.merge(self.params);
+
+            count
+        }
+        pub fn merge(&mut self, other: PackageCoverage) {
+            self.types.merge(other.types);
+            self.methods.merge(other.methods);
+            self.params.merge(other.params);
+            self.classes.extend(other.classes);
+        }
+    }
+
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Serialize)]
+    /// The documented types of each kind, nested types included
+    pub struct KindCounts {
+        pub classes: Count,
+        pub interfaces: Count,
+        pub enumerations: Count,
+        pub annotations: Count,
+    }
+
+    impl KindCounts {
+        pub fn merge(&mut self, other: KindCounts) {
+            self.classes.merge(other.classes);
+            self.interfaces.merge(other.interfaces);
+            self.enumerations.merge(other.enumerations);
+            self.annotations.merge(other.annotations);
+        }
+    }
+
+    #[derive(Debug, Default, Clone, Copy, PartialEq)]
+    /// The documented types of each kind and the documented methods and parameters of
+    /// every type found, whatever their visibility
+    pub struct Found {
+        pub kinds: KindCounts,
+        pub methods: Count,
+        pub params: Count,
+    }
+
+    impl Found {
+        pub fn merge(&mut self, other: Found) {
+            self.kinds.merge(other.kinds);
+            self.methods.merge(other.methods);
+            self.params.merge(other.params);
+        }
+    }
+
+    #[derive(Debug, Default, Serialize)]
+    /// The documentation coverage of a project by package
+    pub struct Coverage {
+        /// Whether members which are not public are counted too
+        #[serde(skip)]
+        pub count_private: bool,
+        pub packages: BTreeMap<String, PackageCoverage>,
+        /// Every type and member found, counted or not, for the summary of a run
+        #[serde(skip)]
+        pub found: Found,
+    }
+
+    impl Coverage {
+        pub fn new(count_private: bool) -> Coverage {
+            Coverage {
+                count_private,
+                packages: BTreeMap::new(),
+                found: Found::default(),
+            }
+        }
+        /// Counts the types of a project and their members
+        pub fn add_project(&mut self, proj: &Project) {
+            for class in &proj.classes {
+                self.add_class(class, "", true);
+            }
+            for inter in &proj.interfaces {
+                self.add_interface(inter, "", true);
+            }
+            for enumeration in &proj.enumerations {
+                self.add_enumeration(enumeration, "", true);
+            }
+            for annotation in &proj.annotations {
+                self.add_annotation(annotation, "", true);
+            }
+        }
+        /// Adds the counts of another project, such as the files parsed by another thread
+        pub fn merge(&mut self, other: Coverage) {
+            for (name, package) in other.packages {
+                self.package(&name).merge(package);
+            }
+            self.found.merge(other.found);
+        }
+        /// The count of every type, method and parameter in the project
+        pub fn total(&self) -> Count {
+            let mut count = Count::default();
+
+            for package in self.packages.values() {
+                count.merge(package.all());
+            }
+
+            count
+        }
+        /// The coverage of each package as a table
+        pub fn summary(&self) -> String {
+            let rows: Vec<Vec<String>> = self
+                .packages
+                .iter()
+                .map(|(name, package)| {
+                    vec![
+                        package_name(name),
+                        count_cell(package.types),
+                        count_cell(package.methods),
+                        count_cell(package.params),
+                        percent_cell(package.all()),
+                    ]
+                }).collect();
+
+            Markdown.table(&["Package", "Types", "Methods", "Parameters", "Coverage"], &rows)
+        }
+        /// A markdown report with the overall coverage, the coverage of each package
+        /// and the coverage of each type
+        pub fn report(&self) -> String {
+            let mut doc = String::from("# Documentation coverage\n\n");
+            let total = self.total();
+
+            doc.push_str(
+                format!(
+                    "{} of {} types, methods and parameters are documented ({}).\n\n",
+                    total.documented,
+                    total.total,
+                    percent_cell(total)
+                ).as_str(),
+            );
+            doc.push_str(self.summary().as_str());
+
+            for (name, package) in &self.packages {
+                let rows: Vec<Vec<String>> = package
+                    .classes
+                    .iter()
+                    .map(|(name, class)| {
+                        vec![
+                            name.clone(),
+                            if class.documented { "yes" } else { "no" }.to_string(),
+                            count_cell(class.methods),
+                            count_cell(class.params),
+                            percent_cell(class.all()),
+                        ]
+                    }).collect();
+
+                doc.push_str(format!("\n## {}\n\n", package_name(name)).as_str());
+                doc.push_str(
+                    Markdown
+                        .table(&["Type", "Javadoc", "Methods", "Parameters", "Coverage"], &rows)
+                        .as_str(),
+                );
+            }
+
+            doc
+        }
+        /// The coverage as json with the overall percentage
+        pub fn to_json(&self) -> String {
+            #[derive(Serialize)]
+            struct Report<'a> {
+                percent: f64,
+                total: Count,
+                packages: &'a BTreeMap<String, PackageCoverage>,
+            }
+
+            let total = self.total();
+            let report = Report {
+                percent: total.percent(),
+                total,
+                packages: &self.packages,
+            };
+
+            serde_json::to_string_pretty(&report).expect("Not able to serialize the coverage")
+        }
+        /// The shields.io endpoint json of a badge with the overall coverage, e.g.
+        /// `{"schemaVersion":1,"label":"doc coverage","message":"87%","color":"yellow"}`
+        pub fn to_badge(&self, colors: BadgeColors) -> String {
+            #[derive(Serialize)]
+            #[serde(rename_all = "camelCase")]
+            struct Badge {
+                schema_version: u32,
+                label: &'static str,
+                message: String,
+                color: &'static str,
+            }
+
+            // The percentage is rounded down so a badge never shows a threshold it is below
+            let percent = self.total().percent();
+            let badge = Badge {
+                schema_version: 1,
+                label: "doc coverage",
+                message: format!("{}%", percent.floor()),
+                color: colors.color(percent),
+            };
+
+            serde_json::to_string(&badge).expect("Not able to serialize the badge")
+        }
+        fn add_class(&mut self, class: &Class, outer: &str, counted: bool) {
+            let counted = counted && self.counts(&class.access);
+
+            self.found.kinds.classes.add(is_documented(&class.description));
+            let name = self.add_type(&class.package_name, outer, &class.name, &class.description, counted);
+            self.add_methods(&class.package_name, &name, &class.constructors, false, counted);
+            self.add_methods(&class.package_name, &name, &class.methods, false, counted);
+            self.add_inner_classes(&name, &class.inner_classes, counted);
+        }
+        fn add_interface(&mut self, inter: &Interface, outer: &str, counted: bool) {
+            let counted = counted && self.counts(&inter.access);
+
+            self.found.kinds.interfaces.add(is_documented(&inter.description));
+            let name = self.add_type(&inter.package_name, outer, &inter.name, &inter.description, counted);
+            self.add_methods(&inter.package_name, &name, &inter.methods, true, counted);
+            self.add_inner_classes(&name, &inter.inner_classes, counted);
+        }
+        fn add_enumeration(&mut self, enumeration: &Enumeration, outer: &str, counted: bool) {
+            let package = &enumeration.package_name;
+            let counted = counted && self.counts(&enumeration.access);
+
+            self.found.kinds.enumerations.add(is_documented(&enumeration.description));
+            let name = self.add_type(package, outer, &enumeration.name, &enumeration.description, counted);
+            self.add_methods(package, &name, &enumeration.constructors, false, counted);
+            self.add_methods(package, &name, &enumeration.methods, false, counted);
+            self.add_inner_classes(&name, &enumeration.inner_classes, counted);
+        }
+        fn add_annotation(&mut self, annotation: &Annotation, outer: &str, counted: bool) {
+            let package = &annotation.package_name;
+            let counted = counted && self.counts(&annotation.access);
+
+            self.found.kinds.annotations.add(is_documented(&annotation.description));
+            let name = self.add_type(package, outer, &annotation.name, &annotation.description, counted);
+            self.add_inner_classes(&name, &annotation.inner_classes, counted);
+        }
+        fn add_inner_classes(&mut self, outer: &str, inner_classes: &[ObjectType], counted: bool) {
+            for inner in inner_classes {
+                match inner {
+                    ObjectType::Class(class) => self.add_class(class, outer, counted),
+                    ObjectType::Interface(inter) => self.add_interface(inter, outer, counted),
+                    ObjectType::Enumeration(enumeration) => self.add_enumeration(enumeration, outer, counted),
+                    ObjectType::Annotation(annotation) => self.add_annotation(annotation, outer, counted),
+                }
+            }
+        }
+        /// Whether a type is counted. The members of types which are not counted
+        /// are not part of the public API either.
+        fn counts(&self, access: &str) -> bool {
+            self.count_private || access == "public"
+        }
+        /// Counts a type when it is `counted` and returns the name its members are counted under
+        fn add_type(&mut self, package: &str, outer: &str, name: &str, description: &str, counted: bool) -> String {
+            let name = if outer.is_empty() {
+                name.to_string()
+            } else {
+                format!("{}.{}", outer, name)
+            };
+            let documented = is_documented(description);
+
+            if !counted {
+                return name;
+            }
+            let package = self.package(package);
+
+            package.types.add(documented);
+            package.classes.entry(name.clone()).or_default().documented = documented;
+
+            name
+        }
+        /// Counts the methods and their parameters, every one is found but only the public
+        /// members of `counted` types are part of the coverage
+        fn add_methods(&mut self, package: &str, name: &str, methods: &[Method], interface: bool, counted: bool) {
+            for method in methods {
+                self.found.methods.add(is_documented(&method.description));
+
+                for param in &method.parameters {
+                    self.found.params.add(is_documented(&param.desc));
+                }
+            }
+
+            if !counted {
+                return;
+            }
+
+            let count_private = self.count_private;
+            let package = self.package(package);
+            let class = package.classes.entry(name.to_string()).or_default();
+
+            for method in methods {
+                if count_private || is_public(&method.privacy, interface) {
+                    let documented = is_documented(&method.description);
+                    package.methods.add(documented);
+                    class.methods.add(documented);
+
+                    for param in &method.parameters {
+                        let documented = is_documented(&param.desc);
+                        package.params.add(documented);
+                        class.params.add(documented);
+                    }
+                }
+            }
+        }
+        fn package(&mut self, name: &str) -> &mut PackageCoverage {
+            self.packages.entry(name.to_string()).or_default()
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    /// The coverage percentages below which the `--badge` is red and yellow, it is green
+    /// from `yellow` on
+    pub struct BadgeColors {
+        pub red: f64,
+        pub yellow: f64,
+    }
+
+    impl Default for BadgeColors {
+        fn default() -> BadgeColors {
+            BadgeColors { red: 50.0, yellow: 80.0 }
+        }
+    }
+
+    impl BadgeColors {
+        /// Reads a `--badge-colors` value such as `50,80`
+        pub fn parse(value: &str) -> Result<BadgeColors, String> {
+            let invalid = || format!("the badge colors `{}` are not of the form RED,YELLOW, e.g. 50,80", value);
+            let (red, yellow) = value.split_once(',').ok_or_else(invalid)?;
+            let red: f64 = red.trim().parse().map_err(|_| invalid())?;
+            let yellow: f64 = yellow.trim().parse().map_err(|_| invalid())?;
+
+            if !(0.0..=100.0).contains(&red) || !(red..=100.0).contains(&yellow) {
+                return Err(format!(
+                    "the badge colors `{}` must be percentages from 0 to 100 with RED at most YELLOW",
+                    value
+                ));
+            }
+
+            Ok(BadgeColors { red, yellow })
+        }
+        /// The shields.io color of a coverage percentage
+        pub fn color(&self, percent: f64) -> &'static str {
+            if percent < self.red {
+                "red"
+            } else if percent < self.yellow {
+                "yellow"
+            } else {
+                "green"
+            }
+        }
+    }
+
+    #[derive(Debug, Default, Serialize)]
+    /// The summary of a run which is printed when it finishes, and written to
+    /// `stats.json` with `--stats`
+    pub struct Stats {
+        /// The java files found in the inputs
+        pub files: usize,
+        /// The java files which could not be parsed
+        pub parse_failures: usize,
+        #[serde(flatten)]
+        pub kinds: KindCounts,
+        pub methods: Count,
+        pub params: Count,
+        /// The duration of the run in seconds
+        pub elapsed: f64,
+    }
+
+    impl Stats {
+        /// Summarizes a run from the coverage of the files it parsed. Every type and member
+        /// found is part of the summary, not only the ones the coverage report counts.
+        ///
+        /// # Arguments
+        ///
+        /// * `coverage` - The coverage of the parsed files, merged from every worker
+        /// * `files` - The number of java files found in the inputs
+        /// * `parse_failures` - The number of java files which could not be parsed
+        /// * `elapsed` - The duration of the run
+        pub fn new(coverage: &Coverage, files: usize, parse_failures: usize, elapsed: Duration) -> Stats {
+            Stats {
+                files,
+                parse_failures,
+                kinds: coverage.found.kinds,
+                methods: coverage.found.methods,
+                params: coverage.found.params,
+                elapsed: elapsed.as_secs_f64(),
+            }
+        }
+        /// The summary as a line with the files and a table of the documented items
+        pub fn summary(&self) -> String {
+            let row = |name: &str, count: Count| vec![name.to_string(), count.total.to_string(), count.documented.to_string()];
+            let rows = vec![
+                row("Classes", self.kinds.classes),
+                row("Interfaces", self.kinds.interfaces),
+                row("Enums", self.kinds.enumerations),
+                row("Annotations", self.kinds.annotations),
+                row("Methods", self.methods),
+                row("Parameters", self.params),
+            ];
+
+            format!(
+                "Scanned {} java files in {:.2}s, {} could not be parsed\n\n{}",
+                self.files,
+                self.elapsed,
+                self.parse_failures,
+                Markdown.table(&["Kind", "Found", "Documented"], &rows)
+            )
+        }
+        pub fn to_json(&self) -> String {
+            serde_json::to_string_pretty(self).expect("Not able to serialize the stats")
+        }
+    }
+
+    /// Writes a file through a temporary file next to it which is then renamed, so a
+    /// server reading the file while it is written again never reads half of it
+    pub fn write_atomic(path: &Path, contents: &str) -> Result<(), LojidocError> {
+        let name = path.file_name().map_or("badge".into(), |name| name.to_string_lossy());
+        let temp = path.with_file_name(format!(".{}.tmp", name));
+
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            fs::create_dir_all(parent).map_err(|err| LojidocError::io(parent, err))?;
+        }
+        fs::write(&temp, contents).map_err(|err| LojidocError::io(&temp, err))?;
+
+        fs::rename(&temp, path).map_err(|err| {
+            let _ = fs::remove_file(&temp);
+            LojidocError::io(path, err)
+        })
+    }
+
+    /// The name of a package, the default package has no name
+    fn package_name(name: &str) -> String {
+        if name.is_empty() {
+            "(default)".to_string()
+        } else {
+            name.to_string()
+        }
+    }
+
+    fn count_cell(count: Count) -> String {
+        format!("{}/{}", count.documented, count.total)
+    }
+
+    fn percent_cell(count: Count) -> String {
+        format!("{:.1}%", count.percent())
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,232 @@
+use coverage::coverage::*;
+use model::model::{Class, Method, ObjectType, Param, Project};
+
+fn method(privacy: &str, description: &str, param_desc: &str) -> Method {
+    let mut method = Method::new();
+    method.ch_method_name("find".to_string());
+    method.ch_privacy(privacy.to_string());
+    method.ch_description(description.to_string());
+    method.add_param(Param {
+        desc: param_desc.to_string(),
+        name: "id".to_string(),
+        var_type: "long".to_string(),
+        line_num: String::new(),
+    });
+
+    method
+}
+
+#[test]
+fn test_coverage() {
+    let mut service = Class::new();
+    service.name = "Service".to_string();
+    service.package_name = "com.foo".to_string();
+    service.access = "public".to_string();
+    service.description = "A service".to_string();
+    service.methods.push(method("public", "Finds", ""));
+    service.methods.push(method("public", "", "The id"));
+    service.methods.push(method("private", "", ""));
+
+    let mut hidden = Class::new();
+    hidden.name = "Hidden".to_string();
+    hidden.package_name = "com.bar".to_string();
+
+    let mut proj = Project::new();
+    proj.add_class(service);
+    proj.add_class(hidden);
+
+    let mut coverage = Coverage::new(false);
+    coverage.add_project(&proj);
+
+    let package = &coverage.packages["com.foo"];
+    assert_eq!(Count { documented: 1, total: 1 }, package.types);
+    assert_eq!(Count { documented: 1, total: 2 }, package.methods);
+    assert_eq!(Count { documented: 1, total: 2 }, package.params);
+    assert_eq!(60.0, coverage.total().percent());
+    assert!(!coverage.packages.contains_key("com.bar"));
+    assert_eq!(
+        "| Package | Types | Methods | Parameters | Coverage |\n\
+         | ------- | ----- | ------- | ---------- | -------- |\n\
+         | com.foo | 1/1   | 1/2     | 1/2        | 60.0%    |\n",
+        coverage.summary()
+    );
+
+    let mut all = Coverage::new(true);
+    all.add_project(&proj);
+
+    assert_eq!(Count { documented: 0, total: 1 }, all.packages["com.bar"].types);
+    assert_eq!(Count { documented: 1, total: 3 }, all.packages["com.foo"].methods);
+
+    coverage.merge(all);
+    assert_eq!(Count { documented: 2, total: 2 }, coverage.packages["com.foo"].types);
+    assert_eq!(100.0, Coverage::new(false).total().percent());
+}
+
+#[test]
+fn test_coverage_report() {
+    use serde_json::Value;
+
+    let mut inner = Class::new();
+    inner.name = "Builder".to_string();
+    inner.package_name = "com.foo".to_string();
+    inner.access = "public".to_string();
+    inner.methods.push(method("public", "Builds", "The id"));
+
+    let mut service = Class::new();
+    service.name = "Service".to_string();
+    service.package_name = "com.foo".to_string();
+    service.access = "public".to_string();
+    service.methods.push(method("public", "", ""));
+    service.inner_classes.push(ObjectType::Class(inner));
+
+    let mut proj = Project::new();
+    proj.add_class(service);
+
+    let mut coverage = Coverage::new(false);
+    coverage.add_project(&proj);
+
+    assert_eq!(
+        "# Documentation coverage\n\n\
+         2 of 6 types, methods and parameters are documented (33.3%).\n\n\
+         | Package | Types | Methods | Parameters | Coverage |\n\
+         | ------- | ----- | ------- | ---------- | -------- |\n\
+         | com.foo | 0/2   | 1/2     | 1/2        | 33.3%    |\n\
+         \n## com.foo\n\n\
+         | Type            | Javadoc | Methods | Parameters | Coverage |\n\
+         | --------------- | ------- | ------- | ---------- | -------- |\n\
+         | Service         | no      | 0/1     | 0/1        | 0.0%     |\n\
+         | Service.Builder | no      | 1/1     | 1/1        | 66.7%    |\n",
+        coverage.report()
+    );
+
+    let json: Value = serde_json::from_str(&coverage.to_json()).unwrap();
+    assert_eq!(6, json["total"]["total"]);
+    assert_eq!(1, json["packages"]["com.foo"]["classes"]["Service.Builder"]["params"]["documented"]);
+}
+
+#[test]
+fn test_stats() {
+    use serde_json::Value;
+    use std::time::Duration;
+
+    let project = || {
+        let mut service = Class::new();
+        service.name = "Service".to_string();
+        service.package_name = "com.foo".to_string();
+        service.access = "public".to_string();
+        service.description = "A service".to_string();
+        service.methods.push(method("public", "Finds", ""));
+        service.methods.push(method("public", "", "The id"));
+
+        let mut hidden = Class::new();
+        hidden.name = "Hidden".to_string();
+        hidden.package_name = "com.bar".to_string();
+
+        let mut proj = Project::new();
+        proj.add_class(service);
+        proj.add_class(hidden);
+        proj
+    };
+
+    // The workers count their files separately
+    let mut coverage = Coverage::new(false);
+    for _ in 0..2 {
+        let mut file_coverage = Coverage::new(false);
+        file_coverage.add_project(&project());
+        coverage.merge(file_coverage);
+    }
+    let stats = Stats::new(&coverage, 3, 1, Duration::from_millis(1500));
+
+    assert_eq!(
+        "Scanned 3 java files in 1.50s, 1 could not be parsed\n\n\
+         | Kind        | Found | Documented |\n\
+         | ----------- | ----- | ---------- |\n\
+         | Classes     | 4     | 2          |\n\
+         | Interfaces  | 0     | 0          |\n\
+         | Enums       | 0     | 0          |\n\
+         | Annotations | 0     | 0          |\n\
+         | Methods     | 4     | 2          |\n\
+         | Parameters  | 4     | 2          |\n",
+        stats.summary()
+    );
+
+    let json: Value = serde_json::from_str(&stats.to_json()).unwrap();
+    assert_eq!(1, json["parse_failures"]);
+    assert_eq!(4, json["classes"]["total"]);
+    assert_eq!(1.5, json["elapsed"]);
+}
+
+#[test]
+fn test_stats_package_private() {
+    use std::time::Duration;
+
+    let mut proj = Project::new();
+    for (name, access) in &[("Api", "public"), ("Helper", ""), ("Cache", ""), ("Pool", "")] {
+        let mut class = Class::new();
+        class.name = name.to_string();
+        class.package_name = "com.foo".to_string();
+        class.access = access.to_string();
+        class.methods.push(method("", "Runs", ""));
+        proj.add_class(class);
+    }
+
+    let mut coverage = Coverage::new(false);
+    coverage.add_project(&proj);
+    let stats = Stats::new(&coverage, 1, 0, Duration::from_secs(1));
+
+    // The stats report what was found while the coverage only counts the public API
+    assert_eq!(4, stats.kinds.classes.total);
+    assert_eq!(4, stats.methods.total);
+    assert_eq!(4, stats.methods.documented);
+    assert_eq!(1, coverage.packages["com.foo"].types.total);
+    assert_eq!(0, coverage.packages["com.foo"].methods.total);
+}
+
+#[test]
+fn test_badge() {
+    use std::env;
+    use std::fs;
+
+    let mut service = Class::new();
+    service.name = "Service".to_string();
+    service.package_name = "com.foo".to_string();
+    service.access = "public".to_string();
+    service.description = "A service".to_string();
+    service.methods.push(method("public", "Finds", ""));
+    service.methods.push(method("public", "", "The id"));
+
+    let mut proj = Project::new();
+    proj.add_class(service);
+    let mut coverage = Coverage::new(false);
+    coverage.add_project(&proj);
+
+    assert_eq!(
+        r#"{"schemaVersion":1,"label":"doc coverage","message":"60%","color":"yellow"}"#,
+        coverage.to_badge(BadgeColors::default())
+    );
+    assert_eq!(
+        r#"{"schemaVersion":1,"label":"doc coverage","message":"100%","color":"green"}"#,
+        Coverage::new(false).to_badge(BadgeColors::default())
+    );
+
+    let colors = BadgeColors::parse("70, 90").unwrap();
+    assert_eq!(BadgeColors { red: 70.0, yellow: 90.0 }, colors);
+    assert_eq!("red", colors.color(69.9));
+    assert_eq!("yellow", colors.color(70.0));
+    assert_eq!("green", colors.color(90.0));
+    assert!(BadgeColors::parse("80").is_err());
+    assert!(BadgeColors::parse("90,80").is_err());
+    assert!(BadgeColors::parse("50,120").is_err());
+
+    // The badge replaces the last one and leaves no temporary file
+    let dir = env::temp_dir().join(format!("lojidoc-badge-{}", std::process::id()));
+    let path = dir.join("badges").join("coverage.json");
+    write_atomic(&path, "{}").unwrap();
+    write_atomic(&path, &coverage.to_badge(colors)).unwrap();
+    let written = fs::read_to_string(&path).unwrap();
+    let files = fs::read_dir(dir.join("badges")).unwrap().count();
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert!(written.contains(r#""color":"red""#));
+    assert_eq!(1, files);
+}
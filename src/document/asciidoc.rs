@@ -0,0 +1,176 @@
+//! Renders the generated pages as AsciiDoc, e.g. for Antora
+
+use document::render::Renderer;
+use strings::strings::label;
+
+/// Renders pages as AsciiDoc
+pub struct Asciidoc;
+
+impl Renderer for Asciidoc {
+    fn extension(&self) -> &'static str {
+        "adoc"
+    }
+
+    fn heading(&self, level: usize, text: &str, anchor: &str, src: &str) -> String {
+        let mut doc = String::new();
+
+        if !anchor.is_empty() {
+            doc.push_str(format!("[[{}]]\n", anchor).as_str());
+        }
+        doc.push_str(format!("{} {}", "=".repeat(level), text).as_str());
+        if !src.is_empty() {
+            doc.push_str(format!(" {}", self.link("[src]", src)).as_str());
+        }
+        doc.push_str("\n\n");
+
+        doc
+    }
+
+    fn heading_ids(&self) -> bool {
+        true
+    }
+
+    fn notice(&self, label: &str, text: &str) -> String {
+        format!("*{}:* {}\n\n", label, self.inline(text))
+    }
+
+    fn field(&self, label: &str, value: &str) -> String {
+        format!("{}: {} +\n", label, self.inline(value))
+    }
+
+    fn description(&self, text: &str) -> String {
+        format!("{}:\n\n____\n{}\n____\n\n", label("description"), self.inline(text))
+    }
+
+    fn text(&self, text: &str) -> String {
+        format!("{}\n", self.inline(text))
+    }
+
+    fn list(&self, items: &[String]) -> String {
+        // Blank lines would end the list so each paragraph becomes a line break
+        items
+            .iter()
+            .map(|item| {
+                let item = self.inline(item);
+                let lines: Vec<&str> = item.lines().filter(|line| !line.trim().is_empty()).collect();
+
+                format!("* {}\n", lines.join(" +\n"))
+            }).collect()
+    }
+
+    fn table(&self, headers: &[&str], rows: &[Vec<String>]) -> String {
+        let mut doc = format!(
+            "[cols=\"{}*\", options=\"header\"]\n|===\n",
+            headers.len()
+        );
+
+        let header: Vec<String> = headers.iter().map(|header| format!("|{}", header)).collect();
+        doc.push_str(format!("{}\n", header.join(" ")).as_str());
+
+        // A `|` in a cell would start a new cell
+        for row in rows {
+            let cells: Vec<String> = row
+                .iter()
+                .map(|cell| format!("|{}", cell.replace("|", "\\|")))
+                .collect();
+            doc.push_str(format!("{}\n", cells.join(" ")).as_str());
+        }
+        doc.push_str("|===\n");
+
+        doc
+    }
+
+    fn details(&self, summary: &str, items: &[String]) -> String {
+        let mut doc = format!(".{}\n[%collapsible]\n====\n", summary);
+
+        for item in items {
+            doc.push_str(format!("* {}\n", item).as_str());
+        }
+        doc.push_str("====\n\n");
+
+        doc
+    }
+
+    fn grouped_details(&self, summary: &str, groups: &[(&str, Vec<String>)]) -> String {
+        let mut doc = format!(".{}\n[%collapsible]\n====\n", summary);
+
+        for (label, items) in groups {
+            doc.push_str(format!("* {}\n", label).as_str());
+            for item in items {
+                doc.push_str(format!("** {}\n", item).as_str());
+            }
+        }
+        doc.push_str("====\n\n");
+
+        doc
+    }
+
+    fn escape(&self, text: &str) -> String {
+        text.to_string()
+    }
+
+    fn inline(&self, text: &str) -> String {
+        // The backslashes the parser escapes markdown characters with are not AsciiDoc
+        let mut doc = String::new();
+        let mut chars = text.chars().peekable();
+
+        while let Some(ch) = chars.next() {
+            if ch != '\\' || !chars.peek().is_some_and(|next| next.is_ascii_punctuation()) {
+                doc.push(ch);
+            } else if let Some(next) = chars.next() {
+                doc.push(next);
+            }
+        }
+
+        doc
+    }
+
+    fn code(&self, text: &str) -> String {
+        // The passthrough keeps the text from being read as AsciiDoc markup
+        format!("`+{}+`", text)
+    }
+
+    fn code_block(&self, code: &str) -> String {
+        format!("[source,java]\n----\n{}\n----\n\n", code)
+    }
+
+    fn link(&self, text: &str, target: &str) -> String {
+        let text = text.replace(']', "\\]");
+
+        // Other pages are cross references so Antora can resolve them. Antora resolves
+        // them from the root of the pages directory instead of the current page.
+        if target.split('#').next().is_some_and(|page| page.ends_with(".adoc")) {
+            format!("xref:{}[{}]", target.trim_start_matches("../"), text)
+        } else {
+            format!("link:{}[{}]", target, text)
+        }
+    }
+
+    fn struck(&self, text: &str) -> String {
+        format!("[line-through]#{}#", text)
+    }
+
+    fn nest(&self, page: &str) -> String {
+        let mut doc = String::new();
+
+        for line in page.lines() {
+            // Only section titles are moved, not `====` block delimiters
+            if line.starts_with('=') && line.trim_start_matches('=').starts_with(' ') {
+                doc.push_str("==");
+            }
+
+            doc.push_str(line);
+            doc.push('\n');
+        }
+
+        doc
+    }
+
+    fn page(&self, _title: &str, body: &str) -> String {
+        body.to_string()
+    }
+
+    fn rule(&self) -> String {
+        "\n'''\n\n".to_string()
+    }
+}
@@ -0,0 +1,234 @@
+//! Renders the generated pages as standalone html pages
+
+use pulldown_cmark::html;
+use pulldown_cmark::Parser;
+use pulldown_cmark::OPTION_ENABLE_TABLES;
+
+use document::render::escape_doc_text;
+use document::render::Renderer;
+use strings::strings::label;
+
+/// The style sheet included in every page
+const STYLE: &str = "body { font-family: sans-serif; max-width: 60em; margin: 2em auto; padding: 0 1em; color: #222; }
+h1, h2, h3, h4, h5, h6 { color: #124; }
+a { color: #0b5394; }
+a.src { font-size: 0.7em; font-weight: normal; }
+table { border-collapse: collapse; margin: 1em 0; }
+th, td { border: 1px solid #ccc; padding: 0.3em 0.6em; text-align: left; vertical-align: top; }
+th { background: #f2f4f7; }
+code, pre { background: #f6f8fa; font-family: monospace; }
+blockquote { border-left: 3px solid #ccc; margin: 0.5em 0; padding-left: 1em; }
+.field { margin: 0.2em 0; }
+.notice { color: #a00; }
+";
+
+/// Renders pages as html
+pub struct Html;
+
+impl Html {
+    /// Converts markdown written by the parser to html
+    fn markdown(&self, text: &str) -> String {
+        let mut doc = String::new();
+        let text = escape_doc_text(text);
+        html::push_html(&mut doc, Parser::new_ext(&text, OPTION_ENABLE_TABLES));
+
+        doc
+    }
+}
+
+impl Renderer for Html {
+    fn extension(&self) -> &'static str {
+        "html"
+    }
+
+    fn heading(&self, level: usize, text: &str, anchor: &str, src: &str) -> String {
+        let id = if anchor.is_empty() {
+            String::new()
+        } else {
+            format!(" id=\"{}\"", self.escape(anchor))
+        };
+        let src = if src.is_empty() {
+            String::new()
+        } else {
+            format!(" <a class=\"src\" href=\"{}\">[src]</a>", self.escape(src))
+        };
+
+        format!(
+            "<h{}{}>{}{}</h{}>\n",
+            level,
+            id,
+            self.escape(text),
+            src,
+            level
+        )
+    }
+
+    fn heading_ids(&self) -> bool {
+        true
+    }
+
+    fn notice(&self, label: &str, text: &str) -> String {
+        format!(
+            "<p class=\"notice\"><strong>{}:</strong> {}</p>\n",
+            self.escape(label),
+            self.inline(text)
+        )
+    }
+
+    fn field(&self, label: &str, value: &str) -> String {
+        format!(
+            "<div class=\"field\"><strong>{}:</strong> {}</div>\n",
+            self.escape(label),
+            self.inline(value)
+        )
+    }
+
+    fn description(&self, text: &str) -> String {
+        format!(
+            "<div class=\"field\"><strong>{}:</strong></div>\n<blockquote>\n{}</blockquote>\n",
+            self.escape(&label("description")),
+            self.markdown(text)
+        )
+    }
+
+    fn text(&self, text: &str) -> String {
+        format!("<p>{}</p>\n", self.inline(text))
+    }
+
+    fn list(&self, items: &[String]) -> String {
+        let mut doc = String::from("<ul>\n");
+
+        for item in items {
+            doc.push_str(format!("<li>{}</li>\n", self.inline(item)).as_str());
+        }
+        doc.push_str("</ul>\n");
+
+        doc
+    }
+
+    fn table(&self, headers: &[&str], rows: &[Vec<String>]) -> String {
+        let mut doc = String::from("<table>\n<thead>\n<tr>");
+
+        for header in headers {
+            doc.push_str(format!("<th>{}</th>", self.escape(header)).as_str());
+        }
+        doc.push_str("</tr>\n</thead>\n<tbody>\n");
+
+        for row in rows {
+            doc.push_str("<tr>");
+            for cell in row {
+                doc.push_str(format!("<td>{}</td>", cell).as_str());
+            }
+            doc.push_str("</tr>\n");
+        }
+        doc.push_str("</tbody>\n</table>\n");
+
+        doc
+    }
+
+    fn details(&self, summary: &str, items: &[String]) -> String {
+        let mut doc = format!(
+            "<details>\n<summary>{}</summary>\n<ul>\n",
+            self.escape(summary)
+        );
+
+        for item in items {
+            doc.push_str(format!("<li>{}</li>\n", self.escape(item)).as_str());
+        }
+        doc.push_str("</ul>\n</details>\n");
+
+        doc
+    }
+
+    fn grouped_details(&self, summary: &str, groups: &[(&str, Vec<String>)]) -> String {
+        let mut doc = format!(
+            "<details>\n<summary>{}</summary>\n<ul>\n",
+            self.escape(summary)
+        );
+
+        for (label, items) in groups {
+            doc.push_str(format!("<li>{}\n<ul>\n", self.escape(label)).as_str());
+            for item in items {
+                doc.push_str(format!("<li>{}</li>\n", item).as_str());
+            }
+            doc.push_str("</ul>\n</li>\n");
+        }
+        doc.push_str("</ul>\n</details>\n");
+
+        doc
+    }
+
+    fn escape(&self, text: &str) -> String {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    }
+
+    fn inline(&self, text: &str) -> String {
+        let doc = self.markdown(text);
+        let doc = doc.trim_end();
+
+        // A single paragraph is shown without the paragraph tags
+        if doc.starts_with("<p>") && doc.ends_with("</p>") && doc.matches("<p>").count() == 1 {
+            doc[3..doc.len() - 4].to_string()
+        } else {
+            doc.to_string()
+        }
+    }
+
+    fn code(&self, text: &str) -> String {
+        // The text of inline tags is already escaped by `inline`, apart from
+        // type arguments which may be read as html tags
+        format!(
+            "<code>{}</code>",
+            text.replace('<', "&lt;").replace('>', "&gt;")
+        )
+    }
+
+    fn code_block(&self, code: &str) -> String {
+        format!(
+            "<pre><code class=\"language-java\">{}</code></pre>\n",
+            self.escape(code)
+        )
+    }
+
+    fn link(&self, text: &str, target: &str) -> String {
+        format!("<a href=\"{}\">{}</a>", target, text)
+    }
+
+    fn struck(&self, text: &str) -> String {
+        format!("<del>{}</del>", text)
+    }
+
+    fn nest(&self, page: &str) -> String {
+        let mut doc = page.to_string();
+
+        // The deepest headings are moved first so no heading is moved twice
+        for level in (1..5).rev() {
+            doc = doc
+                .replace(
+                    format!("<h{}", level).as_str(),
+                    format!("<h{}", level + 2).as_str(),
+                ).replace(
+                    format!("</h{}>", level).as_str(),
+                    format!("</h{}>", level + 2).as_str(),
+                );
+        }
+
+        doc
+    }
+
+    fn page(&self, title: &str, body: &str) -> String {
+        format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n{}</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+            self.escape(title),
+            STYLE,
+            body
+        )
+    }
+
+    fn rule(&self) -> String {
+        "<hr>\n".to_string()
+    }
+}
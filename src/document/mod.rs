@@ -1,546 +1,4199 @@
+pub mod asciidoc;
+pub mod html;
+pub mod render;
+pub mod rst;
+
 pub mod document {
     extern crate colored;
+    extern crate serde_json;
 
     use mdbook::MDBook;
 
+    use std::borrow::Cow;
+    use std::collections::BTreeSet;
+    use std::collections::HashMap;
+    use std::collections::HashSet;
+    use std::fmt;
     use std::fs;
     use std::fs::File;
-    use std::io::prelude::*;
+    use std::io;
+    use std::io::BufWriter;
+    use std::io::Write;
     use std::path::Path;
     use std::path::PathBuf;
+    use std::sync::Arc;
 
     use colored::*;
-    use model::contents::ApplicationDoc;
+    use model::model::ApiEntry;
+    use model::model::ApplicationDoc;
+    use model::model::Annotation;
     use model::model::Class;
+    use model::model::DiagramNode;
+    use model::model::Enumeration;
+    use model::model::EnumerationField;
+    use model::model::first_sentence;
     use model::model::Interface;
     use model::model::Member;
     use model::model::Method;
+    use model::model::Module;
+    use model::model::ObjectType;
+    use model::model::Package;
+    use model::model::PackageMember;
+    use model::model::Param;
     use model::model::Project;
+    use error::error::LojidocError;
+    use parse::parse::Charset;
+    use strings::strings::english_label;
+    use strings::strings::label;
+    use strings::strings::label_of;
+    use template::template::Templates;
+
+    use document::asciidoc::Asciidoc;
+    use document::html::Html;
+    use document::render::Markdown;
+    use document::render::Renderer;
+    use document::rst::Rst;
+
+    /// The format of the generated documentation
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub enum Format {
+        Markdown,
+        Html,
+        Asciidoc,
+        Rst,
+        Json,
+    }
+
+    impl Format {
+        /// Gets the name of the format used in the command line options and summaries
+        pub fn name(&self) -> &'static str {
+            match self {
+                Format::Markdown => "markdown",
+                Format::Html => "html",
+                Format::Asciidoc => "asciidoc",
+                Format::Rst => "rst",
+                Format::Json => "json",
+            }
+        }
+
+        /// Gets the extension of the files of a format
+        pub fn extension(&self) -> &'static str {
+            match self.renderer() {
+                Some(r) => r.extension(),
+                None => "json",
+            }
+        }
+
+        /// Gets the renderer of a page format, JSON is serialized without pages
+        pub fn renderer(&self) -> Option<&'static dyn Renderer> {
+            match self {
+                Format::Markdown => Some(&Markdown),
+                Format::Html => Some(&Html),
+                Format::Asciidoc => Some(&Asciidoc),
+                Format::Rst => Some(&Rst),
+                Format::Json => None,
+            }
+        }
+    }
+
+    /// The visibility of a type or member, from the most to the least visible
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    pub enum Visibility {
+        Public,
+        Protected,
+        Package,
+        Private,
+    }
+
+    impl Visibility {
+        /// Gets the visibility of an access modifier. Members of an interface or annotation
+        /// are public without a modifier, other members are package private.
+        pub fn of(access: &str, interface: bool) -> Visibility {
+            match access.trim() {
+                "public" => Visibility::Public,
+                "protected" => Visibility::Protected,
+                "private" => Visibility::Private,
+                _ if interface => Visibility::Public,
+                _ => Visibility::Package,
+            }
+        }
+        /// Gets a visibility by the name used for `--min-visibility`
+        pub fn from_name(name: &str) -> Option<Visibility> {
+            match name {
+                "public" => Some(Visibility::Public),
+                "protected" => Some(Visibility::Protected),
+                "package" => Some(Visibility::Package),
+                "private" => Some(Visibility::Private),
+                _ => None,
+            }
+        }
+        pub fn name(&self) -> &'static str {
+            match self {
+                Visibility::Public => "public",
+                Visibility::Protected => "protected",
+                Visibility::Package => "package",
+                Visibility::Private => "private",
+            }
+        }
+    }
+
+    /// What a run does with the files it writes which already exist in the destination
+    /// and were not written by lojidoc, e.g. hand-edited pages
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub enum OnConflict {
+        /// The files are written over
+        Overwrite,
+        /// The files are kept as they are and reported
+        Skip,
+        /// The run stops before anything is written
+        Error,
+    }
+
+    impl OnConflict {
+        /// Gets a policy by the name used for `--on-conflict`
+        pub fn from_name(name: &str) -> Option<OnConflict> {
+            match name {
+                "overwrite" => Some(OnConflict::Overwrite),
+                "skip" => Some(OnConflict::Skip),
+                "error" => Some(OnConflict::Error),
+                _ => None,
+            }
+        }
+        pub fn name(&self) -> &'static str {
+            match self {
+                OnConflict::Overwrite => "overwrite",
+                OnConflict::Skip => "skip",
+                OnConflict::Error => "error",
+            }
+        }
+    }
+
+    /// The order of the methods on the page of a type
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub enum MethodOrder {
+        /// The order the methods are declared in
+        Source,
+        /// By name, with overloads ordered by their number of parameters
+        Alpha,
+    }
+
+    impl MethodOrder {
+        /// Gets an order by the name used for `--sort-methods`
+        pub fn from_name(name: &str) -> Option<MethodOrder> {
+            match name {
+                "source" => Some(MethodOrder::Source),
+                "alpha" => Some(MethodOrder::Alpha),
+                _ => None,
+            }
+        }
+        pub fn name(&self) -> &'static str {
+            match self {
+                MethodOrder::Source => "source",
+                MethodOrder::Alpha => "alpha",
+            }
+        }
+    }
+
+    /// How the methods section of a type page is laid out
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub struct MethodLayout {
+        pub order: MethodOrder,
+        /// Whether the methods are listed in a section per visibility, the most
+        /// visible first
+        pub group_by_visibility: bool,
+        /// The number of lines up to which the source of a method is shown in its
+        /// section, for `--include-source`
+        pub include_source: Option<usize>,
+    }
+
+    impl Default for MethodLayout {
+        fn default() -> MethodLayout {
+            MethodLayout {
+                order: MethodOrder::Source,
+                group_by_visibility: false,
+                include_source: None,
+            }
+        }
+    }
+
+    /// The layout of the built-in pages of types
+    #[derive(Clone, Debug, Default, PartialEq)]
+    pub struct PageLayout {
+        pub methods: MethodLayout,
+        /// Whether the `java.*` and `javax.*` imports are left out of the dependencies
+        pub hide_jdk_imports: bool,
+        /// The packages of the types of the run. Their imports are listed as project
+        /// imports and linked to the pages of the types.
+        pub packages: BTreeSet<String>,
+        /// The start of the anchors of the methods of a nested type, see `method_anchor`
+        pub anchor_prefix: String,
+    }
+
+    impl PageLayout {
+        /// Gets the layout of the pages of a run
+        ///
+        /// # Arguments
+        ///
+        /// * `output` - The settings of the run
+        /// * `type_names` - The qualified names of the types of the run
+        pub fn new(output: &Output, type_names: &[String]) -> PageLayout {
+            PageLayout {
+                methods: output.methods,
+                hide_jdk_imports: output.hide_jdk_imports,
+                packages: type_names
+                    .iter()
+                    .map(|name| match name.rfind('.') {
+                        Some(i) => name[..i].to_string(),
+                        None => String::new(),
+                    }).collect(),
+                anchor_prefix: String::new(),
+            }
+        }
+    }
+
+    /// The YAML front matter which `--front-matter` writes before the heading of each
+    /// type page, for static site generators such as Hugo
+    #[derive(Clone, Debug, Default, PartialEq)]
+    pub struct FrontMatter {
+        /// The keys and values written after the title, description and package,
+        /// e.g. `layout: api`
+        pub extra: Vec<(String, String)>,
+        /// Whether the `id` and `sidebar_label` of Docusaurus are written before the title
+        pub docusaurus: bool,
+    }
+
+    impl FrontMatter {
+        /// Reads a `--front-matter-extra` value such as `layout=api`
+        pub fn parse_extra(value: &str) -> Result<(String, String), String> {
+            let (key, value) = value
+                .split_once('=')
+                .ok_or_else(|| format!("the front matter value `{}` is not of the form key=value", value))?;
+            let key = key.trim();
+
+            if key.is_empty() || !key.chars().all(|ch| ch.is_alphanumeric() || ch == '_' || ch == '-') {
+                return Err(format!("the front matter key `{}` is not valid", key));
+            }
+
+            Ok((key.to_string(), value.trim().to_string()))
+        }
+        /// Generates the front matter of the page of a type
+        ///
+        /// # Arguments
+        ///
+        /// * `title` - The name of the type
+        /// * `description` - The description of the type, of which the first sentence is used
+        /// * `package` - The package of the type, left out for the default package
+        pub fn gen_front_matter(&self, title: &str, description: &str, package: &str) -> String {
+            let mut doc = String::from("---\n");
+            let mut push = |key: &str, value: &str| {
+                doc.push_str(format!("{}: {}\n", key, yaml_string(value)).as_str());
+            };
+
+            // The id is the name of the page file, so the doc id of a type stays the same
+            // between runs and the links to it keep working
+            if self.docusaurus {
+                push("id", title);
+            }
+            push("title", title);
+            if self.docusaurus {
+                push("sidebar_label", title);
+            }
+            push("description", &plain_summary(description));
+            if !package.is_empty() {
+                push("package", package);
+            }
+            for (key, value) in self.extra.iter() {
+                push(key, value);
+            }
+            doc.push_str("---\n");
+
+            doc
+        }
+    }
+
+    /// Gets the summary sentence of a description as plain text, without the emphasis and
+    /// code spans the parser writes for the html of a javadoc. A `*` with whitespace on
+    /// both sides, such as in `2 * 3`, is not emphasis and is kept, as are the characters
+    /// the parser escapes outside of code spans, without their backslash.
+    fn plain_summary(description: &str) -> String {
+        let chars: Vec<char> = first_sentence(description).chars().collect();
+        let spaced = |j: Option<usize>| j.and_then(|j| chars.get(j)).is_none_or(|ch| ch.is_whitespace());
+        let mut summary = String::new();
+        let mut escaped = false;
+        let mut code = false;
+
+        for (i, &ch) in chars.iter().enumerate() {
+            if escaped || (code && ch != '`') {
+                summary.push(ch);
+                escaped = false;
+                continue;
+            }
+
+            let before = (0..i).rev().find(|&j| chars[j] != '*');
+            let after = (i + 1..chars.len()).find(|&j| chars[j] != '*');
+            escaped = ch == '\\' && chars.get(i + 1).is_some_and(|next| next.is_ascii_punctuation());
+            code ^= ch == '`';
+
+            if !escaped && ch != '`' && (ch != '*' || (spaced(before) && spaced(after))) {
+                summary.push(ch);
+            }
+        }
+
+        summary
+    }
+
+    /// Quotes a YAML string so colons, quotes and leading symbols are not read as syntax
+    fn yaml_string(value: &str) -> String {
+        let mut quoted = String::from("\"");
+
+        for ch in value.chars() {
+            match ch {
+                '"' => quoted.push_str("\\\""),
+                '\\' => quoted.push_str("\\\\"),
+                '\n' => quoted.push_str("\\n"),
+                '\t' => quoted.push_str("\\t"),
+                _ => quoted.push(ch),
+            }
+        }
+        quoted.push('"');
+
+        quoted
+    }
+
+    /// Settings for how the generated documentation is written
+    #[derive(Clone, Debug)]
+    pub struct Output {
+        pub format: Format,
+        /// Whether the pages are also added to the markdown book
+        pub book: bool,
+        /// Whether pages are written directly to the destination instead of package directories
+        pub flat: bool,
+        /// Whether every type is written to a single `API.md` or `api.json`
+        /// instead of a file per type
+        pub single_file: bool,
+        /// Whether the destination is the `src` directory of an mdBook which
+        /// gets a `SUMMARY.md` and package pages
+        pub mdbook: bool,
+        /// Whether the destination is in the docs of a Docusaurus site and gets a
+        /// `sidebar.json` with a category per package
+        pub docusaurus: bool,
+        /// The least visible types and members which are documented
+        pub min_visibility: Visibility,
+        /// The order and grouping of the methods of each type
+        pub methods: MethodLayout,
+        /// Whether the `java.*` and `javax.*` imports are left out of the dependencies
+        pub hide_jdk_imports: bool,
+        /// The front matter of the type pages, which have none when it is not set
+        pub front_matter: Option<FrontMatter>,
+        /// The `--template` directory the type pages are rendered with instead of the
+        /// built-in layout
+        pub templates: Option<Arc<Templates>>,
+        /// Whether the pages of types which the run did not write are removed from the
+        /// destination
+        pub clean: bool,
+        /// Whether each package gets a `diagram.md` page with a mermaid class diagram
+        pub diagrams: bool,
+        /// Whether the class diagrams list the public methods of the types
+        pub diagram_methods: bool,
+        /// The existing files which `--on-conflict skip` keeps, they are not written by
+        /// the run
+        pub kept: Arc<BTreeSet<PathBuf>>,
+        /// The encoding the java files are read with
+        pub charset: Charset,
+    }
+
+    /// Finds out whether a file is a java file from its `.java` extension
+    pub fn is_java_file(file: &Path) -> bool {
+        file.extension()
+            .is_some_and(|ext| ext.to_string_lossy().eq_ignore_ascii_case("java"))
+    }
+
+    /// Finds out whether a file is a `module-info.java` file, which documents its module
+    /// instead of declaring a type
+    pub fn is_module_info(file: &Path) -> bool {
+        file.file_stem().is_some_and(|stem| stem == "module-info")
+    }
+
+    /// Finds out whether a file is a `package-info.java` file, which documents its
+    /// package instead of declaring a type
+    pub fn is_package_info(file: &Path) -> bool {
+        file.file_stem().is_some_and(|stem| stem == "package-info")
+    }
+
+    /// Build output and version control directories which are excluded by default
+    pub const DEFAULT_EXCLUDES: &[&str] = &["**/.git", "**/target", "**/build", "**/out"];
+
+    /// The files which make a directory a Maven or Gradle module
+    pub const BUILD_FILES: &[&str] = &["pom.xml", "build.gradle", "build.gradle.kts"];
+
+    /// The directories of a module which hold the sources of its API
+    pub const MAIN_SOURCE_ROOTS: &[&str] = &["src/main/java"];
+
+    /// The directories of a module which hold the sources of its tests
+    pub const TEST_SOURCE_ROOTS: &[&str] = &["src/test/java"];
+
+    /// Glob patterns which choose the java files that are documented. The patterns are
+    /// matched against paths relative to the input directory, e.g. `**/test/**`.
+    #[derive(Clone, Debug, Default)]
+    pub struct PathFilter {
+        /// Files and directories which are skipped, directories are not traversed
+        pub exclude: Vec<String>,
+        /// When not empty only the files matching one of the patterns are documented
+        pub include: Vec<String>,
+        /// Whether the paths matching the `.gitignore` files of the project are skipped
+        pub gitignore: bool,
+        /// Whether symlinked directories are traversed. A directory which was already
+        /// traversed through another path is skipped, so symlink cycles end.
+        pub follow_symlinks: bool,
+        /// The directories of each Maven or Gradle module whose java files are
+        /// documented, relative to the module, e.g. `src/main/java`. Every java file is
+        /// documented when it is empty.
+        pub source_roots: Vec<String>,
+    }
+
+    /// A pattern of a `.gitignore` file
+    struct IgnoreRule {
+        pattern: String,
+        /// Whether the pattern starts with `!` and includes the paths again
+        negated: bool,
+        /// Whether the pattern ends with `/` and only matches directories
+        dir_only: bool,
+    }
+
+    /// The patterns of a `.gitignore` file in the input directory or one of its parents
+    struct Gitignore {
+        /// The directory of the file relative to the input directory, empty for parents
+        dir: String,
+        /// The input directory relative to the directory of the file, empty unless the
+        /// file is in a parent of the input directory
+        root: String,
+        rules: Vec<IgnoreRule>,
+    }
+
+    /// Reads the patterns of a `.gitignore` file, a missing file has no patterns
+    fn read_gitignore(path: &Path) -> Vec<IgnoreRule> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(_) => return Vec::new(),
+        };
+
+        contents
+            .lines()
+            .map(|line| line.trim_end())
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                let (negated, line) = match line.strip_prefix('!') {
+                    Some(line) => (true, line),
+                    None => (false, line),
+                };
+                let dir_only = line.ends_with('/');
+                let line = line.trim_end_matches('/');
+
+                // Patterns without a slash match at any depth below the file
+                let pattern = if line.contains('/') {
+                    line.trim_start_matches('/').to_string()
+                } else {
+                    format!("**/{}", line)
+                };
+
+                IgnoreRule {
+                    pattern,
+                    negated,
+                    dir_only,
+                }
+            }).collect()
+    }
+
+    /// Reads the `.gitignore` files in the parents of the input directory up to the root
+    /// of its git repository, the outermost file comes first
+    fn parent_gitignores(start_dir: &Path) -> Vec<Gitignore> {
+        let mut ignores = Vec::new();
+        let start_dir = match fs::canonicalize(start_dir) {
+            Ok(start_dir) => start_dir,
+            Err(_) => return ignores,
+        };
+
+        for dir in start_dir.ancestors().skip(1) {
+            let rules = read_gitignore(&dir.join(".gitignore"));
+
+            if !rules.is_empty() {
+                let root: Vec<String> = start_dir
+                    .strip_prefix(dir)
+                    .unwrap_or(&start_dir)
+                    .components()
+                    .map(|part| part.as_os_str().to_string_lossy().to_string())
+                    .collect();
+
+                ignores.push(Gitignore {
+                    dir: String::new(),
+                    root: root.join("/"),
+                    rules,
+                });
+            }
+
+            if dir.join(".git").exists() {
+                break;
+            }
+        }
+
+        ignores.reverse();
+        ignores
+    }
+
+    /// Checks whether a path relative to the input directory matches the `.gitignore`
+    /// files. The last matching pattern decides, so `!` patterns include paths again.
+    fn is_ignored(ignores: &[Gitignore], path: &str, is_dir: bool) -> bool {
+        let mut ignored = false;
+
+        for ignore in ignores {
+            let path = if !ignore.root.is_empty() {
+                format!("{}/{}", ignore.root, path)
+            } else if ignore.dir.is_empty() {
+                path.to_string()
+            } else {
+                match path.strip_prefix(format!("{}/", ignore.dir).as_str()) {
+                    Some(path) => path.to_string(),
+                    None => continue,
+                }
+            };
+
+            for rule in ignore.rules.iter() {
+                if (is_dir || !rule.dir_only) && glob_match(&rule.pattern, &path) {
+                    ignored = !rule.negated;
+                }
+            }
+        }
+
+        ignored
+    }
+
+    impl PathFilter {
+        /// Checks whether a path relative to the input directory is excluded
+        fn is_excluded(&self, path: &str) -> bool {
+            self.exclude.iter().any(|pattern| glob_match(pattern, path))
+        }
+
+        /// Checks whether a java file relative to the input directory is included
+        fn is_included(&self, path: &str) -> bool {
+            self.include.is_empty() || self.include.iter().any(|pattern| glob_match(pattern, path))
+        }
+    }
+
+    /// Matches a path against a glob pattern. `**` matches any number of directories,
+    /// `*` any part of a file or directory name and `?` a single character.
+    pub fn glob_match(pattern: &str, path: &str) -> bool {
+        let pattern: Vec<&str> = pattern.split('/').filter(|part| !part.is_empty()).collect();
+        let path: Vec<&str> = path.split('/').filter(|part| !part.is_empty()).collect();
+
+        match_segments(&pattern, &path)
+    }
+
+    /// Matches the directories and file name of a path against those of a glob pattern
+    fn match_segments(pattern: &[&str], path: &[&str]) -> bool {
+        match pattern.split_first() {
+            None => path.is_empty(),
+            Some((&"**", rest)) => (0..=path.len()).any(|i| match_segments(rest, &path[i..])),
+            Some((part, rest)) => match path.split_first() {
+                Some((name, path_rest)) => {
+                    match_name(part.as_bytes(), name.as_bytes()) && match_segments(rest, path_rest)
+                }
+                None => false,
+            },
+        }
+    }
+
+    /// Matches a file or directory name against a part of a glob pattern
+    fn match_name(pattern: &[u8], name: &[u8]) -> bool {
+        match pattern.split_first() {
+            None => name.is_empty(),
+            Some((b'*', rest)) => (0..=name.len()).any(|i| match_name(rest, &name[i..])),
+            Some((b'?', rest)) => !name.is_empty() && match_name(rest, &name[1..]),
+            Some((ch, rest)) => name.first() == Some(ch) && match_name(rest, &name[1..]),
+        }
+    }
+
+    /// Expands an input with wildcards, e.g. `plugins/*/src/main/java`, into the existing
+    /// paths it matches, sorted. The wildcards of `glob_match` are supported, so inputs
+    /// may be quoted or read from scripts on shells which do not expand them. An input
+    /// without wildcards, or which matches nothing, is kept as it is.
+    pub fn expand_input(input: &str) -> Vec<PathBuf> {
+        let is_pattern = |part: &str| part.contains('*') || part.contains('?');
+
+        if !input.split('/').any(is_pattern) {
+            return vec![PathBuf::from(input)];
+        }
+
+        let mut paths = vec![if input.starts_with('/') {
+            PathBuf::from("/")
+        } else {
+            PathBuf::new()
+        }];
+
+        for part in input.split('/').filter(|part| !part.is_empty()) {
+            let mut next = Vec::new();
+
+            for path in paths {
+                if !is_pattern(part) {
+                    next.push(path.join(part));
+                    continue;
+                }
+
+                // `**` matches the directory itself and every directory below it
+                let mut dirs = vec![path];
+                while let Some(dir) = dirs.pop() {
+                    let listed = if dir.as_os_str().is_empty() { Path::new(".") } else { &dir };
+                    let mut names: Vec<String> = match fs::read_dir(listed) {
+                        Ok(entries) => entries
+                            .filter_map(|entry| entry.ok())
+                            .map(|entry| entry.file_name().to_string_lossy().to_string())
+                            .collect(),
+                        Err(_) => continue,
+                    };
+                    names.sort();
+
+                    if part == "**" {
+                        next.push(dir.clone());
+                    }
+                    for name in names {
+                        let entry = dir.join(&name);
+
+                        if part == "**" {
+                            if entry.is_dir() {
+                                dirs.push(entry);
+                            }
+                        } else if match_name(part.as_bytes(), name.as_bytes()) {
+                            next.push(entry);
+                        }
+                    }
+                }
+            }
+            paths = next;
+        }
+
+        let mut paths: Vec<PathBuf> = paths.into_iter().filter(|path| path.exists()).collect();
+        paths.sort();
+        paths.dedup();
+
+        if paths.is_empty() {
+            vec![PathBuf::from(input)]
+        } else {
+            paths
+        }
+    }
+
+    /// Traverses the file structure to find all java files for parsing.
+    /// Paths which cannot be read are skipped so the rest of the tree is still
+    /// documented. Excluded directories are not traversed.
+    ///
+    /// Returns the java files which were found and an error for each path which could
+    /// not be read.
+    ///
+    /// # Arguments
+    ///
+    /// * `start_dir` - The directory to start looking for java files in, or a java file
+    /// * `filter` - The patterns of the files which are excluded or included
+    pub fn find_java_files(
+        start_dir: &Path,
+        filter: &PathFilter,
+    ) -> (Vec<PathBuf>, Vec<LojidocError>) {
+        let mut files: Vec<PathBuf> = Vec::new();
+        let mut failed: Vec<LojidocError> = Vec::new();
+
+        // A single java file is documented without walking a directory
+        if start_dir.is_file() {
+            if is_java_file(start_dir) {
+                files.push(start_dir.to_path_buf());
+            } else {
+                info!("Skipping {}, it is not a java file", start_dir.display());
+            }
+        } else {
+            let mut ignores = if filter.gitignore {
+                parent_gitignores(start_dir)
+            } else {
+                Vec::new()
+            };
+            let mut visited = HashSet::new();
+
+            if let Ok(dir) = fs::canonicalize(start_dir) {
+                visited.insert(dir);
+            }
+            collect_java_files(
+                start_dir,
+                start_dir,
+                filter,
+                &mut visited,
+                &mut ignores,
+                &mut files,
+                &mut failed,
+            );
+        }
+
+        (files, failed)
+    }
+
+    /// Finds out whether a directory has the build file of a Maven or Gradle module
+    pub fn is_build_module(dir: &Path) -> bool {
+        BUILD_FILES.iter().any(|name| dir.join(name).is_file())
+    }
+
+    /// Checks whether the java files of a directory are in a source root of the nearest
+    /// module which contains it, or in no module at all. Modules above the input
+    /// directory are not looked for.
+    fn in_source_root(root: &Path, dir: &Path, source_roots: &[String]) -> bool {
+        if source_roots.is_empty() {
+            return true;
+        }
+
+        let module = dir
+            .ancestors()
+            .take_while(|ancestor| ancestor.starts_with(root))
+            .find(|ancestor| is_build_module(ancestor));
+        let relative = match module {
+            Some(module) => relative_path(module, dir),
+            None => return true,
+        };
+
+        source_roots.iter().any(|source_root| {
+            let source_root = source_root.trim_matches('/');
+
+            source_root.is_empty()
+                || source_root == "."
+                || relative == source_root
+                || relative.starts_with(&format!("{}/", source_root))
+        })
+    }
+
+    /// Gets a path relative to the input directory with `/` separators like the patterns
+    fn relative_path(root: &Path, path: &Path) -> String {
+        let relative: Vec<String> = path
+            .strip_prefix(root)
+            .unwrap_or(path)
+            .components()
+            .map(|part| part.as_os_str().to_string_lossy().to_string())
+            .collect();
+
+        relative.join("/")
+    }
+
+    /// Adds the java files in `dir` and its subdirectories to `files`.
+    /// Every path which cannot be read is added to `failed` with its error.
+    /// `visited` holds the canonical paths of the traversed directories.
+    fn collect_java_files(
+        root: &Path,
+        dir: &Path,
+        filter: &PathFilter,
+        visited: &mut HashSet<PathBuf>,
+        ignores: &mut Vec<Gitignore>,
+        files: &mut Vec<PathBuf>,
+        failed: &mut Vec<LojidocError>,
+    ) {
+        let file_dir = match fs::read_dir(dir) {
+            Ok(file_dir) => file_dir,
+            Err(err) => {
+                failed.push(LojidocError::io(dir, err));
+                return;
+            }
+        };
+
+        // The patterns of a `.gitignore` file apply to its directory and subdirectories
+        let rules = if filter.gitignore {
+            read_gitignore(&dir.join(".gitignore"))
+        } else {
+            Vec::new()
+        };
+        let has_gitignore = !rules.is_empty();
+
+        if has_gitignore {
+            ignores.push(Gitignore {
+                dir: relative_path(root, dir),
+                root: String::new(),
+                rules,
+            });
+        }
+
+        // The test and generated sources of a module are skipped, its submodules are not
+        let in_source_root = in_source_root(root, dir, &filter.source_roots);
+        let mut entries = Vec::new();
+
+        for f in file_dir {
+            match f {
+                Ok(entry) => entries.push(entry.path()),
+                Err(err) => failed.push(LojidocError::io(dir, err)),
+            }
+        }
+        // The order of `read_dir` depends on the file system, sorting the entries keeps
+        // the order of the files and of the output the same on every platform
+        entries.sort();
+
+        // For every file or directory in the current directory find java files
+        for p in entries {
+            let relative = relative_path(root, &p);
+
+            if filter.is_excluded(&relative) {
+                verbose!("Skipping {}, it is excluded", p.display());
+            } else if is_ignored(ignores, &relative, p.is_dir()) {
+                verbose!("Skipping {}, it is ignored by .gitignore", p.display());
+            } else if p.is_dir() {
+                if is_symlink(&p) && !filter.follow_symlinks {
+                    verbose!("Skipping {}, it is a symlink", p.display());
+                } else if fs::canonicalize(&p).is_ok_and(|dir| !visited.insert(dir)) {
+                    verbose!("Skipping {}, it was already traversed", p.display());
+                } else {
+                    collect_java_files(root, &p, filter, visited, ignores, files, failed);
+                }
+            } else if p.is_file() && is_java_file(&p) {
+                if !in_source_root {
+                    verbose!("Skipping {}, it is not in a source root of its module", p.display());
+                } else if filter.is_included(&relative) {
+                    files.push(p);
+                } else {
+                    verbose!("Skipping {}, it is not included", p.display());
+                }
+            } else if let Err(err) = fs::metadata(&p) {
+                // Dangling symlinks are neither files nor directories
+                if is_symlink(&p) {
+                    error!("{} {}, the symlink is dangling", "Skipping".yellow(), p.display());
+                } else {
+                    failed.push(LojidocError::io(&p, err));
+                }
+            }
+        }
+
+        if has_gitignore {
+            ignores.pop();
+        }
+    }
+
+    fn is_symlink(path: &Path) -> bool {
+        fs::symlink_metadata(path).is_ok_and(|metadata| metadata.file_type().is_symlink())
+    }
+
+    fn warn_unreadable(path: &Path, err: &std::io::Error) {
+        error!("{} {} ({})", "Unable to read".yellow(), path.display(), err);
+    }
+
+    /// Traverses the generated directory to find all markdown pages.
+    /// Returns the path of each page relative to `gen_dir` without the `.md` extension,
+    /// e.g. `com/example/Client`, in sorted order.
+    ///
+    /// # Arguments
+    ///
+    /// * `gen_dir` - The directory the markdown files were generated in.
+    pub fn find_gen_files(gen_dir: &Path) -> Vec<String> {
+        let mut files: Vec<String> = Vec::new();
+
+        if gen_dir.is_dir() {
+            collect_gen_files(gen_dir, "", &mut files);
+        } else {
+            error!("Generated directory read error: {:?}", gen_dir);
+        }
+
+        files.sort();
+        files
+    }
+
+    /// Adds the markdown pages in `dir` and its subdirectories to `files`
+    fn collect_gen_files(dir: &Path, prefix: &str, files: &mut Vec<String>) {
+        let file_dir = match fs::read_dir(dir) {
+            Ok(file_dir) => file_dir,
+            Err(err) => {
+                warn_unreadable(dir, &err);
+                return;
+            }
+        };
+
+        for f in file_dir.filter_map(|f| f.ok()) {
+            let p = f.path();
+            let name = f.file_name().to_string_lossy().to_string();
+
+            if p.is_dir() {
+                collect_gen_files(&p, format!("{}{}/", prefix, name).as_str(), files);
+            } else if p.is_file() && p.extension().is_some_and(|ext| ext == "md") {
+                files.push(format!("{}{}", prefix, &name[..name.len() - 3]));
+            }
+        }
+    }
+
+    /// Generates the list of the annotations of a declaration shown under its heading.
+    /// `@Deprecated` is left out since the deprecation notice shows it.
+    pub fn gen_annotation_list(out: &mut impl Write, r: &dyn Renderer, annotations: &[String]) -> io::Result<()> {
+        let items: Vec<String> = annotations
+            .iter()
+            .filter(|annotation| !matches!(annotation.as_str(), "@Deprecated" | "@java.lang.Deprecated"))
+            .map(|annotation| r.code(annotation))
+            .collect();
+
+        if items.is_empty() {
+            return Ok(());
+        }
+
+        out.write_all(r.list(&items).as_bytes())?;
+        writeln!(out)?;
+
+        Ok(())
+    }
+
+    /// Generates the bold deprecation notice shown under a heading
+    ///
+    /// # Arguments
+    ///
+    /// * `out` - The writer the documentation is written to
+    /// * `r` - The renderer of the output format
+    /// * `deprecated` - The deprecation message, empty for a generic notice
+    pub fn gen_deprecation_docs(out: &mut impl Write, r: &dyn Renderer, deprecated: &Option<String>) -> io::Result<()> {
+        match deprecated {
+            Some(message) if !message.is_empty() => out.write_all(r.deprecation(message).as_bytes()),
+            Some(_) => out.write_all(r.deprecation(&label("deprecated_notice")).as_bytes()),
+            None => Ok(()),
+        }
+    }
+
+    /// Generates the inheritance line of a type, such as
+    /// `extends AbstractParser · implements Closeable`.
+    /// Each type is written as a `{@link}` tag so it links to its page when documented.
+    ///
+    /// # Arguments
+    ///
+    /// * `out` - The writer the documentation is written to
+    /// * `r` - The renderer of the output format
+    /// * `parent` - The superclass, empty when the type does not extend a class
+    /// * `interfaces` - The implemented interfaces
+    pub fn gen_inheritance_docs(
+        out: &mut impl Write,
+        r: &dyn Renderer,
+        parent: &str,
+        interfaces: &[String],
+    ) -> io::Result<()> {
+        let mut parts = Vec::new();
+
+        if !parent.is_empty() {
+            parts.push(format!("extends {}", inheritance_link(r, parent)));
+        }
+        if !interfaces.is_empty() {
+            let links: Vec<String> = interfaces.iter().map(|inter| inheritance_link(r, inter)).collect();
+            parts.push(format!("implements {}", links.join(", ")));
+        }
+
+        if parts.is_empty() {
+            Ok(())
+        } else {
+            out.write_all(r.field(&label("inheritance"), parts.join(" · ").as_str()).as_bytes())
+        }
+    }
+
+    /// Generates the list of the subtypes a sealed type permits, which link to their
+    /// pages when they are documented
+    pub fn gen_permits_docs(out: &mut impl Write, r: &dyn Renderer, permits: &[String]) -> io::Result<()> {
+        if permits.is_empty() {
+            return Ok(());
+        }
+
+        let items: Vec<String> = permits.iter().map(|name| inheritance_link(r, name)).collect();
+        out.write_all(r.heading(2, &label("permitted_subclasses"), "", "").as_bytes())?;
+        out.write_all(r.list(&items).as_bytes())?;
+        writeln!(out)?;
+
+        Ok(())
+    }
+
+    /// Links the raw type of `type_name` and escapes its generic arguments for the format
+    fn inheritance_link(r: &dyn Renderer, type_name: &str) -> String {
+        match type_name.find('<') {
+            Some(i) => format!("{{@link {}}}{}", &type_name[..i], r.escape(&type_name[i..])),
+            None => format!("{{@link {}}}", type_name),
+        }
+    }
+
+    /// Simple names of JDK types which are never linked, even when a type with the
+    /// same name is documented
+    const JDK_TYPES: &[&str] = &[
+        "Boolean", "Byte", "Character", "CharSequence", "Class", "Collection", "Comparable",
+        "Double", "Enum", "Exception", "Float", "Integer", "Iterable", "Iterator", "List",
+        "Long", "Map", "Number", "Object", "Optional", "RuntimeException", "Set", "Short",
+        "Stream", "String", "Throwable", "Void",
+    ];
+
+    /// Finds out whether a type name in a signature may link to the page of a documented type
+    fn is_linkable_type(name: &str) -> bool {
+        let primitive = matches!(
+            name,
+            "boolean" | "byte" | "char" | "double" | "float" | "int" | "long" | "short"
+                | "void" | "var" | "extends" | "super"
+        );
+
+        !primitive
+            && !name.starts_with("java.")
+            && !name.starts_with("javax.")
+            && !JDK_TYPES.contains(&name)
+    }
+
+    /// Writes a parameter, return or field type with each type name in it as a `{@type}`
+    /// tag, so `List<User>` links to the page of `User` when it is documented.
+    /// The rest of the type is escaped.
+    fn type_links(r: &dyn Renderer, type_name: &str) -> String {
+        let mut doc = String::new();
+        let mut rest = type_name;
+        let is_name_char = |ch: char| ch.is_alphanumeric() || ch == '_' || ch == '$' || ch == '.';
+
+        while !rest.is_empty() {
+            let end = rest.find(|ch: char| !is_name_char(ch)).unwrap_or(rest.len());
+
+            if end == 0 {
+                let len = rest.chars().next().map_or(1, |ch| ch.len_utf8());
+                doc.push_str(r.escape(&rest[..len]).as_str());
+                rest = &rest[len..];
+            } else {
+                // The dots of varargs such as `User...` are not part of the name
+                let name = rest[..end].trim_end_matches('.');
+                let end = if name.is_empty() { end } else { name.len() };
+
+                if !name.is_empty() && is_linkable_type(name) {
+                    doc.push_str(format!("{{@type {}}}", name).as_str());
+                } else {
+                    doc.push_str(r.escape(&rest[..end]).as_str());
+                }
+                rest = &rest[end..];
+            }
+        }
+
+        doc
+    }
+
+    /// Gets the package of an import from the naming convention of java, which starts
+    /// the names of types with an upper case letter, e.g. `java.util` for
+    /// `java.util.Map.Entry` and `java.util.*`
+    fn import_package(name: &str) -> String {
+        name.split('.')
+            .take_while(|part| !part.starts_with(|ch: char| ch.is_uppercase() || ch == '*'))
+            .collect::<Vec<&str>>()
+            .join(".")
+    }
+
+    /// Generates the collapsed list of the imports of a type, grouped into the imports of
+    /// the JDK, of the packages of the run and of third-party libraries. The imported
+    /// types of the run are linked to their pages.
+    fn gen_dependency_docs(
+        out: &mut impl Write,
+        r: &dyn Renderer,
+        dependencies: &[String],
+        layout: &PageLayout,
+    ) -> io::Result<()> {
+        let mut jdk = Vec::new();
+        let mut project = Vec::new();
+        let mut third_party = Vec::new();
+
+        for dependency in dependencies {
+            let (name, is_static) = match dependency.strip_prefix("static ") {
+                Some(name) => (name.trim(), true),
+                None => (dependency.as_str(), false),
+            };
+            let package = import_package(name);
+            let is_jdk = name.starts_with("java.") || name.starts_with("javax.");
+
+            if is_jdk && layout.hide_jdk_imports {
+                continue;
+            }
+
+            // The imported type of a static or nested import is linked without its members
+            let type_name = name
+                .split('.')
+                .take(package.split('.').count() + 1)
+                .collect::<Vec<&str>>()
+                .join(".");
+            let mut item = if layout.packages.contains(&package) && !type_name.ends_with('*') {
+                format!("{{@type {}}}{}", type_name, r.escape(&name[type_name.len()..]))
+            } else {
+                r.escape(name)
+            };
+
+            let labels: Vec<&str> = vec![("static", is_static), ("wildcard", name.ends_with('*'))]
+                .into_iter()
+                .filter(|(_, is_set)| *is_set)
+                .map(|(label, _)| label)
+                .collect();
+            if !labels.is_empty() {
+                item.push_str(format!(" ({})", labels.join(", ")).as_str());
+            }
+
+            if is_jdk {
+                jdk.push(item);
+            } else if layout.packages.contains(&package) {
+                project.push(item);
+            } else {
+                third_party.push(item);
+            }
+        }
+
+        let titles = [label("jdk"), label("project"), label("third_party")];
+        let groups: Vec<(&str, Vec<String>)> = titles
+            .iter()
+            .map(String::as_str)
+            .zip(vec![jdk, project, third_party])
+            .filter(|(_, items)| !items.is_empty())
+            .collect();
+        if groups.is_empty() {
+            return Ok(());
+        }
+
+        out.write_all(r.heading(2, &label("dependencies"), "", "").as_bytes())?;
+        out.write_all(r.grouped_details(&label("show_dependencies"), &groups).as_bytes())?;
+
+        Ok(())
+    }
+
+    /// Renders a table whose headers are the labels of `keys`
+    fn label_table(r: &dyn Renderer, keys: &[&str], rows: &[Vec<String>]) -> String {
+        let headers: Vec<String> = keys.iter().map(|key| label(key)).collect();
+        let headers: Vec<&str> = headers.iter().map(String::as_str).collect();
+
+        r.table(&headers, rows)
+    }
+
+    /// Joins the lines of a description so it fits in a table cell
+    fn table_cell(text: &str) -> String {
+        text.split_whitespace().collect::<Vec<&str>>().join(" ")
+    }
+
+    /// Gets the access of a type or member as it is shown, `package-private` when it has
+    /// no access modifier
+    fn access_label(access: &str) -> String {
+        if access.trim().is_empty() {
+            label("package_private")
+        } else {
+            access.trim().to_string()
+        }
+    }
+
+    /// Generates the documentation for a class
+    ///
+    /// # Arguments
+    ///
+    /// * `out` - The writer the documentation is written to
+    /// * `r` - The renderer of the output format
+    /// * `class` - The class struct containing the javadoc data
+    /// * `layout` - The layout of the page, for the imports of the type
+    pub fn gen_class_docs(out: &mut impl Write, r: &dyn Renderer, class: Class, layout: &PageLayout) -> io::Result<()> {
+        let kind = if class.record { "record" } else { "class" };
+
+        let heading = r.heading(
+            1,
+            label_of(kind, &class.name).as_str(),
+            "",
+            &type_source_link(&class.file_path, &class.line_num),
+        );
+        out.write_all(heading.as_bytes())?;
+
+        gen_annotation_list(out, r, &class.annotations)?;
+        gen_deprecation_docs(out, r, &class.deprecated)?;
+
+        if class.license != "" {
+            out.write_all(r.details(&label("show_license"), std::slice::from_ref(&class.license)).as_bytes())?;
+        }
+
+        out.write_all(r.field(&label("access"), &access_label(&class.access)).as_bytes())?;
+        if !class.modifiers.is_empty() {
+            out.write_all(r.field(&label("modifiers"), class.modifiers.join(" ").as_str()).as_bytes())?;
+        }
+        if class.description.as_str() != "" {
+            out.write_all(r.description(class.description.trim()).as_bytes())?;
+        }
+        if class.author != "" {
+            out.write_all(r.field(&label("author"), &class.author).as_bytes())?;
+        }
+        if !class.since.is_empty() {
+            out.write_all(r.field(&label("since"), &class.since).as_bytes())?;
+        }
+        if class.version != "" {
+            out.write_all(r.field(&label("version"), &class.version).as_bytes())?;
+        }
+        gen_inheritance_docs(out, r, &class.parent, &class.interfaces)?;
+
+        out.write_all(r.field(&label("package"), class.package_name.trim()).as_bytes())?;
+        writeln!(out)?;
+        gen_type_param_docs(out, r, &class.type_params)?;
+        gen_permits_docs(out, r, &class.permits)?;
+
+        if class.exceptions.len() > 0 {
+            for exception in class.exceptions {
+                let throws = format!("{}: {}", label_of("throws", &exception.exception_type), exception.desc);
+                out.write_all(r.text(&throws).as_bytes())?;
+                writeln!(out)?;
+            }
+            writeln!(out)?;
+        }
+
+        gen_dependency_docs(out, r, &class.dependencies, layout)?;
+
+        Ok(())
+    }
+
+    /// Generates the documentation for an interface
+    ///
+    /// # Arguments
+    ///
+    /// * `out` - The writer the documentation is written to
+    /// * `r` - The renderer of the output format
+    /// * `inter` - The interface struct containing the javadoc data
+    /// * `layout` - The layout of the page, for the imports of the type
+    pub fn gen_interface_docs(
+        out: &mut impl Write,
+        r: &dyn Renderer,
+        inter: Interface,
+        layout: &PageLayout,
+    ) -> io::Result<()> {
+        let heading = r.heading(
+            1,
+            label_of("interface", &inter.name).as_str(),
+            "",
+            &type_source_link(&inter.file_path, &inter.line_num),
+        );
+        out.write_all(heading.as_bytes())?;
+
+        gen_annotation_list(out, r, &inter.annotations)?;
+        gen_deprecation_docs(out, r, &inter.deprecated)?;
+
+        if inter.description.as_str() != "" {
+            out.write_all(r.description(inter.description.trim()).as_bytes())?;
+        }
+        if !inter.author.is_empty() {
+            out.write_all(r.field(&label("author"), &inter.author).as_bytes())?;
+        }
+        if !inter.since.is_empty() {
+            out.write_all(r.field(&label("since"), &inter.since).as_bytes())?;
+        }
+        if !inter.version.is_empty() {
+            out.write_all(r.field(&label("version"), &inter.version).as_bytes())?;
+        }
+        out.write_all(r.field(&label("access"), &access_label(&inter.access)).as_bytes())?;
+        out.write_all(r.field(&label("package"), inter.package_name.trim()).as_bytes())?;
+        writeln!(out)?;
+        gen_type_param_docs(out, r, &inter.type_params)?;
+        gen_permits_docs(out, r, &inter.permits)?;
+        gen_dependency_docs(out, r, &inter.dependencies, layout)?;
+
+        Ok(())
+    }
+
+    /// Generates the documentation for an annotation type
+    ///
+    /// # Arguments
+    ///
+    /// * `out` - The writer the documentation is written to
+    /// * `r` - The renderer of the output format
+    /// * `annotation` - The annotation struct containing the javadoc data
+    /// * `layout` - The layout of the page, for the imports of the type
+    pub fn gen_annotation_docs(
+        out: &mut impl Write,
+        r: &dyn Renderer,
+        annotation: Annotation,
+        layout: &PageLayout,
+    ) -> io::Result<()> {
+        let heading = r.heading(
+            1,
+            label_of("annotation", &annotation.name).as_str(),
+            "",
+            &type_source_link(&annotation.file_path, &annotation.line_num),
+        );
+        out.write_all(heading.as_bytes())?;
+
+        gen_annotation_list(out, r, &annotation.annotations)?;
+        gen_deprecation_docs(out, r, &annotation.deprecated)?;
+
+        out.write_all(r.field(&label("access"), &access_label(&annotation.access)).as_bytes())?;
+        if !annotation.description.is_empty() {
+            out.write_all(r.description(annotation.description.trim()).as_bytes())?;
+        }
+        if !annotation.author.is_empty() {
+            out.write_all(r.field(&label("author"), &annotation.author).as_bytes())?;
+        }
+        if !annotation.since.is_empty() {
+            out.write_all(r.field(&label("since"), &annotation.since).as_bytes())?;
+        }
+        if !annotation.version.is_empty() {
+            out.write_all(r.field(&label("version"), &annotation.version).as_bytes())?;
+        }
+        if !annotation.retention.is_empty() {
+            out.write_all(r.field(&label("retention"), &annotation.retention).as_bytes())?;
+        }
+        if !annotation.targets.is_empty() {
+            out.write_all(r.field(&label("target"), annotation.targets.join(", ").as_str()).as_bytes())?;
+        }
+
+        out.write_all(r.field(&label("package"), annotation.package_name.trim()).as_bytes())?;
+        writeln!(out)?;
+        gen_dependency_docs(out, r, &annotation.dependencies, layout)?;
+
+        Ok(())
+    }
+
+    /// Generates the documentation for an enum
+    ///
+    /// # Arguments
+    ///
+    /// * `out` - The writer the documentation is written to
+    /// * `r` - The renderer of the output format
+    /// * `enumeration` - The enumeration struct containing the javadoc data
+    /// * `layout` - The layout of the page, for the imports of the type
+    pub fn gen_enum_docs(
+        out: &mut impl Write,
+        r: &dyn Renderer,
+        enumeration: Enumeration,
+        layout: &PageLayout,
+    ) -> io::Result<()> {
+        let heading = r.heading(
+            1,
+            label_of("enum", &enumeration.name).as_str(),
+            "",
+            &type_source_link(&enumeration.file_path, &enumeration.line_num),
+        );
+        out.write_all(heading.as_bytes())?;
+
+        gen_annotation_list(out, r, &enumeration.annotations)?;
+        gen_deprecation_docs(out, r, &enumeration.deprecated)?;
+
+        out.write_all(r.field(&label("access"), &access_label(&enumeration.access)).as_bytes())?;
+        if !enumeration.modifiers.is_empty() {
+            out.write_all(r.field(&label("modifiers"), enumeration.modifiers.join(" ").as_str()).as_bytes())?;
+        }
+        if !enumeration.description.is_empty() {
+            out.write_all(r.description(enumeration.description.trim()).as_bytes())?;
+        }
+        if !enumeration.author.is_empty() {
+            out.write_all(r.field(&label("author"), &enumeration.author).as_bytes())?;
+        }
+        if !enumeration.since.is_empty() {
+            out.write_all(r.field(&label("since"), &enumeration.since).as_bytes())?;
+        }
+        if !enumeration.version.is_empty() {
+            out.write_all(r.field(&label("version"), &enumeration.version).as_bytes())?;
+        }
+
+        gen_inheritance_docs(out, r, "", &enumeration.interfaces)?;
+
+        out.write_all(r.field(&label("package"), enumeration.package_name.trim()).as_bytes())?;
+        writeln!(out)?;
+        gen_dependency_docs(out, r, &enumeration.dependencies, layout)?;
+
+        Ok(())
+    }
+
+    /// Generates the documentation for the constants of an enum
+    ///
+    /// # Arguments
+    ///
+    /// * `out` - The writer the documentation is written to
+    /// * `r` - The renderer of the output format
+    /// * `fields` - The vector of enum constants to be documented
+    pub fn gen_constant_docs(out: &mut impl Write, r: &dyn Renderer, fields: Vec<EnumerationField>) -> io::Result<()> {
+        if fields.is_empty() {
+            return out.write_all(r.heading(2, &label("no_constants"), "", "").as_bytes());
+        }
+
+        out.write_all(r.heading(2, &label("constants"), "", "").as_bytes())?;
+        let rows: Vec<Vec<String>> = fields
+            .iter()
+            .map(|field| {
+                let name = if !field.value.is_empty() {
+                    format!("{}({})", field.name, field.value)
+                } else {
+                    field.name.clone()
+                };
+
+                vec![r.escape(&name), r.inline(&table_cell(&field.desc))]
+            }).collect();
+
+        out.write_all(label_table(r, &["name", "description"], &rows).as_bytes())?;
+        writeln!(out)?;
+
+        Ok(())
+    }
+
+    /// Gets the name of a member with a `[src]` link to its line in the source file
+    fn src_name(r: &dyn Renderer, name: &str, path: &str, line_num: &str) -> String {
+        if !path.is_empty() {
+            format!(
+                "{} {}",
+                r.escape(name),
+                r.link("[src]", source_link(path, line_num).as_str())
+            )
+        } else {
+            r.escape(name)
+        }
+    }
+
+    /// Generates the documentation for the fields of a class.
+    /// Constants show their initial value in the table.
+    ///
+    /// # Arguments
+    ///
+    /// * `out` - The writer the documentation is written to
+    /// * `r` - The renderer of the output format
+    /// * `variables` - The vector of fields to be documented
+    /// * `path` - The path of the source file for `[src]` links
+    pub fn gen_var_docs(
+        out: &mut impl Write,
+        r: &dyn Renderer,
+        variables: Vec<Member>,
+        path: String,
+    ) -> io::Result<()> {
+        if variables.is_empty() {
+            return out.write_all(r.heading(2, &label("no_fields"), "", "").as_bytes());
+        }
+
+        out.write_all(r.heading(2, &label("fields"), "", "").as_bytes())?;
+        let rows: Vec<Vec<String>> = variables
+            .iter()
+            .map(|member| {
+                vec![
+                    src_name(r, &member.name, &path, &member.line_num),
+                    type_links(r, &member.var_type),
+                    r.escape(&access_label(&member.access)),
+                    r.escape(member.modifiers.join(" ").as_str()),
+                    r.escape(&member.value),
+                    r.inline(&table_cell(&member.desc)),
+                ]
+            }).collect();
+
+        out.write_all(
+            label_table(
+                r,
+                &["name", "type", "access", "modifiers", "value", "description"],
+                &rows,
+            ).as_bytes(),
+        )?;
+        writeln!(out)?;
+
+        Ok(())
+    }
+
+    /// Generates the table for the components of a record
+    ///
+    /// # Arguments
+    ///
+    /// * `out` - The writer the documentation is written to
+    /// * `r` - The renderer of the output format
+    /// * `components` - The components declared in the record header
+    pub fn gen_component_docs(out: &mut impl Write, r: &dyn Renderer, components: &[Param]) -> io::Result<()> {
+        if components.is_empty() {
+            return Ok(());
+        }
+
+        out.write_all(r.heading(2, &label("components"), "", "").as_bytes())?;
+        let rows: Vec<Vec<String>> = components
+            .iter()
+            .map(|component| {
+                vec![
+                    r.escape(&component.name),
+                    type_links(r, &component.var_type),
+                    r.inline(&table_cell(&component.desc)),
+                ]
+            }).collect();
+
+        out.write_all(label_table(r, &["name", "type", "description"], &rows).as_bytes())?;
+        writeln!(out)?;
+
+        Ok(())
+    }
+
+    /// Generates the documentation for the elements of an annotation type
+    ///
+    /// # Arguments
+    ///
+    /// * `out` - The writer the documentation is written to
+    /// * `r` - The renderer of the output format
+    /// * `elements` - The vector of elements to be documented
+    /// * `path` - The path of the source file for `[src]` links
+    pub fn gen_element_docs(
+        out: &mut impl Write,
+        r: &dyn Renderer,
+        elements: Vec<Member>,
+        path: String,
+    ) -> io::Result<()> {
+        if elements.is_empty() {
+            return out.write_all(r.heading(2, &label("no_elements"), "", "").as_bytes());
+        }
+
+        out.write_all(r.heading(2, &label("elements"), "", "").as_bytes())?;
+        let rows: Vec<Vec<String>> = elements
+            .iter()
+            .map(|member| {
+                vec![
+                    src_name(r, &member.name, &path, &member.line_num),
+                    type_links(r, &member.var_type),
+                    r.escape(&member.value),
+                    r.inline(&table_cell(&member.desc)),
+                ]
+            }).collect();
+
+        out.write_all(label_table(r, &["name", "type", "default", "description"], &rows).as_bytes())?;
+        writeln!(out)?;
+
+        Ok(())
+    }
+
+    /// Generates the table for the type parameters of a generic class or method.
+    /// Bounds are written as code so their angle brackets are not read as html.
+    ///
+    /// # Arguments
+    ///
+    /// * `out` - The writer the documentation is written to
+    /// * `r` - The renderer of the output format
+    /// * `type_params` - The type parameters with their bounds and descriptions
+    pub fn gen_type_param_docs(out: &mut impl Write, r: &dyn Renderer, type_params: &[Param]) -> io::Result<()> {
+        if type_params.is_empty() {
+            return Ok(());
+        }
+
+        out.write_all(r.text(&format!("{}:", label("type_parameters"))).as_bytes())?;
+        writeln!(out)?;
+
+        let rows: Vec<Vec<String>> = type_params
+            .iter()
+            .map(|type_param| {
+                let bound = if type_param.var_type.is_empty() {
+                    String::new()
+                } else {
+                    r.code(format!("extends {}", type_param.var_type).as_str())
+                };
+
+                vec![r.code(&type_param.name), bound, r.inline(&type_param.desc)]
+            }).collect();
+
+        out.write_all(label_table(r, &["name", "bound", "description"], &rows).as_bytes())?;
+        writeln!(out)?;
+
+        Ok(())
+    }
+
+    /// Gets the names which several methods of a section share
+    fn overloaded_names(methods: &[Method]) -> HashSet<String> {
+        let mut seen = HashSet::new();
+
+        methods
+            .iter()
+            .filter(|method| !seen.insert(method.name.as_str()))
+            .map(|method| method.name.clone())
+            .collect()
+    }
+
+    /// Gets the name of a type without its package and type arguments for the heading
+    /// of an overload, e.g. `Map[]` for `java.util.Map<String, List<Long>>[]`
+    pub fn simple_type_name(var_type: &str) -> String {
+        let mut erased = String::new();
+        let mut depth = 0;
+
+        for ch in var_type.chars() {
+            match ch {
+                '<' => depth += 1,
+                '>' => depth -= 1,
+                _ if depth == 0 => erased.push(ch),
+                _ => {}
+            }
+        }
+
+        let erased: String = erased.split_whitespace().collect();
+        let base = erased.trim_end_matches(['[', ']', '.']);
+        let simple = base.rsplit('.').next().unwrap_or(base);
+
+        format!("{}{}", simple, &erased[base.len()..])
+    }
+
+    /// Gets the name of a method with the simple names of its parameter types, e.g.
+    /// `of(String, int)`
+    pub fn overload_title(member: &Method) -> String {
+        let types: Vec<String> = member
+            .parameters
+            .iter()
+            .map(|param| simple_type_name(&param.var_type))
+            .collect();
+
+        format!("{}({})", member.name, types.join(", "))
+    }
+
+    /// Gets the anchor of a method, which is its lowercase name followed by the simple
+    /// names of its parameter types, e.g. `findbyid-long`. The methods of nested types
+    /// start with the names of the types, e.g. `builder.build`. The anchor only depends
+    /// on the signature of the method, so links from other documents keep working when
+    /// methods are added or moved. Methods whose anchors are the same, e.g. with
+    /// parameter types of the same simple name, get a number in the order of the page.
+    /// An anchor which is the slug of a section, e.g. `methods` of `methods()`, starts
+    /// with `method-` instead, since GitHub numbers the slugs of the sections of each
+    /// nested type, see `section_slugs`.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - The names of the nested types the method is declared in
+    /// * `member` - The method
+    /// * `anchors` - The anchors of the methods before it in the section
+    pub fn method_anchor(prefix: &str, member: &Method, anchors: &mut Vec<String>) -> String {
+        let mut anchor = format!("{}{}", prefix, member.name.to_lowercase());
+
+        for param in member.parameters.iter() {
+            let type_name = simple_type_name(&param.var_type)
+                .to_lowercase()
+                .replace("[]", "-array")
+                .replace("...", "-varargs");
+            anchor.push('-');
+            anchor.push_str(&type_name);
+        }
+        if section_slugs().contains(&anchor) {
+            anchor = format!("method-{}", anchor);
+        }
+
+        let overloads = anchors.iter().filter(|other| **other == anchor).count();
+        anchors.push(anchor.clone());
+
+        if overloads == 0 {
+            anchor
+        } else {
+            format!("{}-{}", anchor, overloads + 1)
+        }
+    }
+
+    /// The labels of the section headings of a type page
+    const SECTION_LABELS: [&str; 17] = [
+        "components",
+        "constants",
+        "constructors",
+        "dependencies",
+        "elements",
+        "fields",
+        "inner_classes",
+        "methods",
+        "no_constants",
+        "no_elements",
+        "no_fields",
+        "no_methods",
+        "package_methods",
+        "permitted_subclasses",
+        "private_methods",
+        "protected_methods",
+        "public_methods",
+    ];
+
+    /// Gets the slugs GitHub gives the section headings of a type page, e.g. `methods`
+    /// for `## Methods`, which the anchors of the methods must not be
+    pub fn section_slugs() -> Vec<String> {
+        SECTION_LABELS.iter().map(|key| heading_slug(&label(key))).collect()
+    }
+
+    /// Gets the start of the anchors of the methods of a nested type, e.g. `builder.`
+    /// for `Client.Builder`, empty for a top level type
+    fn anchor_prefix(owner: &str) -> String {
+        match owner.split_once('.') {
+            Some((_, nested)) => format!("{}.", nested.to_lowercase()),
+            None => String::new(),
+        }
+    }
+
+    /// Gets the heading of a method, which names the parameter types of overloads
+    fn method_title(r: &dyn Renderer, member: &Method, overloaded: bool) -> String {
+        let name = if overloaded {
+            r.escape(&overload_title(member))
+        } else {
+            member.name.clone()
+        };
+
+        // Default and static methods have a body in an interface, unlike its other methods
+        match member.modifiers.iter().find(|m| *m == "default" || *m == "static") {
+            Some(modifier) => format!("{} ({})", name, modifier),
+            None => name,
+        }
+    }
+
+    /// Reconstructs the declaration of a method or constructor on a single line, e.g.
+    /// `public static <T> List<T> find(long id) throws IOException`
+    pub fn method_signature(member: &Method) -> String {
+        let mut parts: Vec<String> = Vec::new();
+
+        if !member.privacy.is_empty() {
+            parts.push(member.privacy.clone());
+        }
+        parts.extend(member.modifiers.iter().cloned());
+
+        if !member.type_params.is_empty() {
+            let type_params: Vec<String> = member
+                .type_params
+                .iter()
+                .map(|type_param| {
+                    if type_param.var_type.is_empty() {
+                        type_param.name.clone()
+                    } else {
+                        format!("{} extends {}", type_param.name, type_param.var_type)
+                    }
+                }).collect();
+
+            parts.push(format!("<{}>", type_params.join(", ")));
+        }
+
+        // Constructors do not have a return type
+        if !member.return_type.is_empty() {
+            parts.push(member.return_type.clone());
+        }
+
+        let params: Vec<String> = member
+            .parameters
+            .iter()
+            .map(|param| format!("{} {}", param.var_type, param.name))
+            .collect();
+        parts.push(format!("{}({})", member.name, params.join(", ")));
+
+        if !member.throws.is_empty() {
+            parts.push(format!("throws {}", member.throws.join(", ")));
+        }
+
+        // Declarations split across lines in the source are shown on one line
+        parts
+            .join(" ")
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ")
+            .replace("( ", "(")
+            .replace(" )", ")")
+            .replace(" ,", ",")
+    }
+
+    /// Generates the documentation for a single method or constructor
+    ///
+    /// # Arguments
+    ///
+    /// * `out` - The writer the documentation is written to
+    /// * `r` - The renderer of the output format
+    /// * `member` - The method to be documented
+    /// * `path` - The file path of the method's source file
+    /// * `anchor` - The anchor of the method's heading
+    /// * `level` - The level of the method's heading
+    /// * `overloaded` - Whether the heading names the parameter types to tell the
+    ///   method apart from its overloads
+    fn gen_method_doc(
+        out: &mut impl Write,
+        r: &dyn Renderer,
+        member: Method,
+        path: &str,
+        anchor: &str,
+        level: usize,
+        overloaded: bool,
+        include_source: Option<usize>,
+    ) -> io::Result<()> {
+        let src = if !path.is_empty() {
+            source_link(path, &member.line_num)
+        } else {
+            String::new()
+        };
+
+        out.write_all(r.heading(level, &method_title(r, &member, overloaded), anchor, &src).as_bytes())?;
+
+        gen_annotation_list(out, r, &member.annotations)?;
+        out.write_all(r.code_block(&method_signature(&member)).as_bytes())?;
+        gen_deprecation_docs(out, r, &member.deprecated)?;
+
+        let mut fields = vec![format!("{}: {}", label("description"), member.description)];
+
+        fields.push(format!("{}: {}", label("access"), access_label(&member.privacy)));
+
+        if !member.modifiers.is_empty() {
+            fields.push(format!("{}: {}", label("modifiers"), member.modifiers.join(" ")));
+        }
+
+        // Constructors do not have a return type
+        if !member.return_type.is_empty() {
+            if member.return_desc.is_empty() {
+                fields.push(format!("{}: {}", label("return"), type_links(r, &member.return_type)));
+            } else {
+                fields.push(format!(
+                    "{}: {} — {}",
+                    label("returns"),
+                    type_links(r, &member.return_type),
+                    member.return_desc
+                ));
+            }
+        }
+        if !member.since.is_empty() {
+            fields.push(format!("{}: {}", label("since"), member.since));
+        }
+        out.write_all(r.list(&fields).as_bytes())?;
+        writeln!(out)?;
+        gen_type_param_docs(out, r, &member.type_params)?;
+
+        if !member.parameters.is_empty() {
+            let rows: Vec<Vec<String>> = member
+                .parameters
+                .iter()
+                .map(|param| {
+                    vec![
+                        r.escape(&param.name),
+                        type_links(r, &param.var_type),
+                        r.inline(&param.desc),
+                    ]
+                }).collect();
+
+            out.write_all(label_table(r, &["name", "type", "description"], &rows).as_bytes())?;
+        } else {
+            out.write_all(r.text(&label("no_parameters")).as_bytes())?;
+        }
+
+        if !member.exceptions.is_empty() {
+            let rows: Vec<Vec<String>> = member
+                .exceptions
+                .iter()
+                .map(|exception| {
+                    vec![
+                        type_links(r, &exception.exception_type),
+                        r.inline(&exception.desc),
+                    ]
+                }).collect();
+
+            writeln!(out)?;
+            out.write_all(r.text(&format!("{}:", label("exceptions"))).as_bytes())?;
+            writeln!(out)?;
+            out.write_all(label_table(r, &["type", "description"], &rows).as_bytes())?;
+        }
+
+        if let Some(max_lines) = include_source {
+            let lines = member.source.lines().count();
+
+            if lines > max_lines {
+                writeln!(out)?;
+                out.write_all(r.text(&label_of("source_omitted", &lines.to_string())).as_bytes())?;
+            } else if lines > 0 {
+                writeln!(out)?;
+                out.write_all(r.code_block(&member.source).as_bytes())?;
+            }
+        }
+
+        out.write_all(b"\n\n")?;
+
+        Ok(())
+    }
+
+    /// Generates the documentation for the constructors of a class.
+    /// Classes with only the implicit default constructor have no section.
+    ///
+    /// # Arguments
+    ///
+    /// * `out` - The writer the documentation is written to
+    /// * `r` - The renderer of the output format
+    /// * `constructors` - The vector of class constructors to be documented
+    /// * `path` - The file path of the class's source file
+    /// * `prefix` - The start of the anchors of the constructors, see `method_anchor`
+    pub fn gen_constructor_docs(
+        out: &mut impl Write,
+        r: &dyn Renderer,
+        constructors: Vec<Method>,
+        path: String,
+        prefix: &str,
+    ) -> io::Result<()> {
+        if constructors.is_empty() {
+            return Ok(());
+        }
+
+        out.write_all(r.heading(2, &label("constructors"), "", "").as_bytes())?;
+        let mut anchors = Vec::new();
+        let overloaded = constructors.len() > 1;
+
+        for constructor in constructors {
+            let anchor = method_anchor(prefix, &constructor, &mut anchors);
+            gen_method_doc(out, r, constructor, path.as_str(), &anchor, 3, overloaded, None)?;
+        }
+
+        Ok(())
+    }
+
+    /// Generates the documentation for the methods of a class
+    ///
+    /// # Arguments
+    ///
+    /// * `out` - The writer the documentation is written to
+    /// * `r` - The renderer of the output format
+    /// * `methods` - The vector of class methods to be documented
+    /// * `path` - The file path of the class's source file
+    /// * `layout` - The order and grouping of the methods
+    /// * `interface` - Whether the methods without a modifier are public
+    /// * `prefix` - The start of the anchors of the methods, see `method_anchor`
+    pub fn gen_method_docs(
+        out: &mut impl Write,
+        r: &dyn Renderer,
+        methods: Vec<Method>,
+        path: String,
+        layout: MethodLayout,
+        interface: bool,
+        prefix: &str,
+    ) -> io::Result<()> {
+        if methods.is_empty() {
+            return out.write_all(r.heading(2, &label("no_methods"), "", "").as_bytes());
+        }
+
+        out.write_all(r.heading(2, &label("methods"), "", "").as_bytes())?;
+        let mut anchors = Vec::new();
+        let overloads = overloaded_names(&methods);
+        let visibility = |method: &Method| Visibility::of(&method.privacy, interface);
+
+        let mut group = None;
+        for member in ordered_methods(methods, layout, interface) {
+            let level = if layout.group_by_visibility {
+                if group != Some(visibility(&member)) {
+                    group = Some(visibility(&member));
+                    out.write_all(r.heading(3, &visibility_title(visibility(&member)), "", "").as_bytes())?;
+                }
+                4
+            } else {
+                3
+            };
+            let overloaded = overloads.contains(&member.name);
+            let anchor = method_anchor(prefix, &member, &mut anchors);
+            gen_method_doc(out, r, member, path.as_str(), &anchor, level, overloaded, layout.include_source)?;
+        }
+
+        Ok(())
+    }
+
+    /// Orders the methods of a type as they are documented on its page. Methods without
+    /// a name are left out.
+    fn ordered_methods(mut methods: Vec<Method>, layout: MethodLayout, interface: bool) -> Vec<Method> {
+        methods.retain(|method| !method.name.is_empty());
+
+        // The sorts are stable so overloads keep their order in the source
+        if layout.order == MethodOrder::Alpha {
+            methods.sort_by(|a, b| {
+                (a.name.to_lowercase(), &a.name, a.parameters.len())
+                    .cmp(&(b.name.to_lowercase(), &b.name, b.parameters.len()))
+            });
+        }
+        if layout.group_by_visibility {
+            methods.sort_by_key(|method| Visibility::of(&method.privacy, interface));
+        }
+
+        methods
+    }
+
+    /// A public method on the page of a type with the text of its heading
+    struct PageMethod {
+        entry: ApiEntry,
+        heading: String,
+    }
+
+    /// Gets the public methods of a type and its nested types in the order of their
+    /// headings, with the anchors their headings are given
+    ///
+    /// # Arguments
+    ///
+    /// * `r` - The renderer of the output format
+    /// * `methods` - The methods of the type
+    /// * `inner_classes` - The types declared in the type
+    /// * `owner` - The name of the type, with the types it is nested in
+    /// * `interface` - Whether the methods without a modifier are public
+    /// * `layout` - The order and grouping of the methods
+    /// * `page_methods` - The methods found so far
+    fn public_methods(
+        r: &dyn Renderer,
+        methods: &[Method],
+        inner_classes: &[ObjectType],
+        owner: &str,
+        interface: bool,
+        layout: MethodLayout,
+        page_methods: &mut Vec<PageMethod>,
+    ) {
+        let overloads = overloaded_names(methods);
+        let prefix = anchor_prefix(owner);
+        let mut anchors = Vec::new();
+
+        for member in ordered_methods(methods.to_vec(), layout, interface) {
+            let overloaded = overloads.contains(&member.name);
+            let anchor = method_anchor(&prefix, &member, &mut anchors);
+
+            if Visibility::of(&member.privacy, interface) == Visibility::Public {
+                page_methods.push(PageMethod {
+                    heading: method_title(r, &member, overloaded),
+                    entry: ApiEntry {
+                        title: overload_title(&member),
+                        name: member.name,
+                        owner: owner.to_string(),
+                        anchor,
+                    },
+                });
+            }
+        }
+
+        for inner in inner_classes {
+            let (name, methods, inner_classes, interface): (&str, &[Method], &[ObjectType], bool) = match inner {
+                ObjectType::Class(class) => (&class.name, &class.methods, &class.inner_classes, false),
+                ObjectType::Interface(inter) => (&inter.name, &inter.methods, &inter.inner_classes, true),
+                ObjectType::Enumeration(enumeration) => {
+                    (&enumeration.name, &enumeration.methods, &enumeration.inner_classes, false)
+                }
+                ObjectType::Annotation(annotation) => (&annotation.name, &[], &annotation.inner_classes, true),
+            };
+            let owner = format!("{}.{}", owner, name);
+
+            public_methods(r, methods, inner_classes, &owner, interface, layout, page_methods);
+        }
+    }
+
+    /// Gets the public methods of the types of a java file by the name of their type
+    fn methods_by_type(r: &dyn Renderer, proj: &Project, layout: MethodLayout) -> HashMap<String, Vec<PageMethod>> {
+        let mut types = HashMap::new();
+        let mut add = |name: &str, methods: &[Method], inner_classes: &[ObjectType], interface: bool| {
+            let mut page_methods = Vec::new();
+            public_methods(r, methods, inner_classes, name, interface, layout, &mut page_methods);
+            types.insert(name.to_string(), page_methods);
+        };
+
+        for class in proj.classes.iter() {
+            add(&class.name, &class.methods, &class.inner_classes, false);
+        }
+        for inter in proj.interfaces.iter() {
+            add(&inter.name, &inter.methods, &inter.inner_classes, true);
+        }
+        for enumeration in proj.enumerations.iter() {
+            add(&enumeration.name, &enumeration.methods, &enumeration.inner_classes, false);
+        }
+        for annotation in proj.annotations.iter() {
+            add(&annotation.name, &[], &annotation.inner_classes, true);
+        }
+
+        types
+    }
+
+    /// Gets the index entries of the public methods of a page. Markdown headings are
+    /// linked by the slug GitHub generates from their text, which is numbered when
+    /// headings repeat, so the headings of the methods are looked up in the page in order.
+    /// A method whose heading is not found, e.g. with a `--template`, gets no anchor.
+    fn resolve_anchors(r: &dyn Renderer, doc: &str, page_methods: Vec<PageMethod>) -> Vec<ApiEntry> {
+        if r.heading_ids() {
+            return page_methods.into_iter().map(|method| method.entry).collect();
+        }
+
+        let headings = page_headings(doc);
+        let mut next = 0;
+
+        page_methods
+            .into_iter()
+            .map(|method| {
+                let found = headings[next..].iter().position(|heading| heading.text == method.heading);
+                let anchor = match found {
+                    Some(i) => {
+                        next += i + 1;
+                        headings[next - 1].anchor.clone()
+                    }
+                    None => String::new(),
+                };
+
+                ApiEntry { anchor, ..method.entry }
+            }).collect()
+    }
+
+    /// Gets the heading of the methods of a visibility when they are grouped
+    fn visibility_title(visibility: Visibility) -> String {
+        match visibility {
+            Visibility::Public => label("public_methods"),
+            Visibility::Protected => label("protected_methods"),
+            Visibility::Package => label("package_methods"),
+            Visibility::Private => label("private_methods"),
+        }
+    }
+
+    /// Generates the documentation for the inner classes of a type.
+    /// Each inner class is documented like a top level class with its headings
+    /// moved down two levels.
+    ///
+    /// # Arguments
+    ///
+    /// * `out` - The writer the documentation is written to
+    /// * `r` - The renderer of the output format
+    /// * `inner_classes` - The classes, interfaces and enums declared in the type
+    /// * `layout` - The layout of the pages of the inner classes
+    pub fn gen_inner_class_docs(
+        out: &mut impl Write,
+        r: &dyn Renderer,
+        inner_classes: Vec<ObjectType>,
+        layout: &PageLayout,
+    ) -> io::Result<()> {
+        if inner_classes.is_empty() {
+            return Ok(());
+        }
+
+        out.write_all(r.heading(2, &label("inner_classes"), "", "").as_bytes())?;
+
+        for inner in inner_classes {
+            let name = match &inner {
+                ObjectType::Class(class) => &class.name,
+                ObjectType::Interface(inter) => &inter.name,
+                ObjectType::Enumeration(enumeration) => &enumeration.name,
+                ObjectType::Annotation(annotation) => &annotation.name,
+            };
+            let layout = &PageLayout {
+                anchor_prefix: format!("{}{}.", layout.anchor_prefix, name.to_lowercase()),
+                ..layout.clone()
+            };
+            // The headings of the whole page are moved down
+            let page = written_string(|page| match inner {
+                ObjectType::Class(class) => gen_class_page(page, r, class, layout),
+                ObjectType::Interface(inter) => gen_interface_page(page, r, inter, layout),
+                ObjectType::Enumeration(enumeration) => gen_enum_page(page, r, enumeration, layout),
+                ObjectType::Annotation(annotation) => gen_annotation_page(page, r, annotation, layout),
+            });
+
+            out.write_all(r.nest(&page).as_bytes())?;
+        }
+
+        Ok(())
+    }
+
+    /// Gets the text a page generator such as `gen_class_page` writes, for the pages
+    /// which are changed as a whole before they are written
+    pub fn written_string<F>(write: F) -> String
+    where
+        F: FnOnce(&mut Vec<u8>) -> io::Result<()>,
+    {
+        let mut doc = Vec::new();
+        write(&mut doc).expect("Not able to write to memory");
+
+        String::from_utf8(doc).expect("The pages are written from strings")
+    }
+
+    /// Generates the complete page for a class
+    pub fn gen_class_page(out: &mut impl Write, r: &dyn Renderer, class: Class, layout: &PageLayout) -> io::Result<()> {
+        gen_class_docs(out, r, class.clone(), layout)?;
+        gen_component_docs(out, r, &class.components)?;
+        gen_var_docs(out, r, class.variables, class.file_path.clone())?;
+        let prefix = &layout.anchor_prefix;
+        gen_constructor_docs(out, r, class.constructors, class.file_path.clone(), prefix)?;
+        gen_method_docs(out, r, class.methods, class.file_path, layout.methods, false, prefix)?;
+        gen_inner_class_docs(out, r, class.inner_classes, layout)?;
+
+        Ok(())
+    }
+
+    /// Generates the markdown page of a class without writing it, for using the
+    /// documentation in another tool
+    pub fn generate_markdown_string(class: &Class) -> String {
+        written_string(|out| gen_class_page(out, &Markdown, class.clone(), &PageLayout::default()))
+    }
+
+    /// Generates the complete page for an interface
+    pub fn gen_interface_page(
+        out: &mut impl Write,
+        r: &dyn Renderer,
+        inter: Interface,
+        layout: &PageLayout,
+    ) -> io::Result<()> {
+        gen_interface_docs(out, r, inter.clone(), layout)?;
+        gen_var_docs(out, r, inter.variables, inter.file_path.clone())?;
+        let prefix = &layout.anchor_prefix;
+        gen_method_docs(out, r, inter.methods, inter.file_path, layout.methods, true, prefix)?;
+        gen_inner_class_docs(out, r, inter.inner_classes, layout)?;
+
+        Ok(())
+    }
+
+    /// Generates the complete page for an annotation type
+    pub fn gen_annotation_page(
+        out: &mut impl Write,
+        r: &dyn Renderer,
+        annotation: Annotation,
+        layout: &PageLayout,
+    ) -> io::Result<()> {
+        gen_annotation_docs(out, r, annotation.clone(), layout)?;
+        gen_element_docs(out, r, annotation.elements, annotation.file_path.clone())?;
+        if !annotation.variables.is_empty() {
+            gen_var_docs(out, r, annotation.variables, annotation.file_path)?;
+        }
+        gen_inner_class_docs(out, r, annotation.inner_classes, layout)?;
+
+        Ok(())
+    }
+
+    /// Generates the complete page for an enum
+    pub fn gen_enum_page(
+        out: &mut impl Write,
+        r: &dyn Renderer,
+        enumeration: Enumeration,
+        layout: &PageLayout,
+    ) -> io::Result<()> {
+        gen_enum_docs(out, r, enumeration.clone(), layout)?;
+        gen_constant_docs(out, r, enumeration.fields)?;
+        gen_var_docs(out, r, enumeration.variables, enumeration.file_path.clone())?;
+        gen_constructor_docs(
+            out,
+            r,
+            enumeration.constructors,
+            enumeration.file_path.clone(),
+            &layout.anchor_prefix,
+        )?;
+        gen_method_docs(
+            out,
+            r,
+            enumeration.methods,
+            enumeration.file_path,
+            layout.methods,
+            false,
+            &layout.anchor_prefix,
+        )?;
+        gen_inner_class_docs(out, r, enumeration.inner_classes, layout)?;
+
+        Ok(())
+    }
+
+    /// Finds the end of an inline javadoc tag such as `{@code x}`, allowing balanced
+    /// braces inside the tag. Returns `None` when the braces are unbalanced.
+    fn find_tag_end(tag: &str) -> Option<usize> {
+        let mut depth = 0;
+
+        for (i, ch) in tag.char_indices() {
+            match ch {
+                '{' => depth += 1,
+                '}' => {
+                    depth -= 1;
+
+                    if depth == 0 {
+                        return Some(i);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        None
+    }
+
+    /// Gets the path of the page of a type relative to the destination directory.
+    /// Pages are placed in directories named after their package unless `flat` is set.
+    ///
+    /// # Arguments
+    ///
+    /// * `package` - The package of the type, e.g. `com.example`
+    /// * `name` - The name of the type
+    /// * `flat` - Whether all pages are placed directly in the destination directory
+    pub fn page_path(package: &str, name: &str, flat: bool) -> String {
+        type_path(package, name, flat, "md")
+    }
+
+    /// Gets the path of the output file of a type with the given extension
+    pub fn type_path(package: &str, name: &str, flat: bool, extension: &str) -> String {
+        if flat || package.is_empty() {
+            format!("{}.{}", name, extension)
+        } else {
+            format!("{}/{}.{}", package.replace('.', "/"), name, extension)
+        }
+    }
+
+    /// Finds the page a `{@link}` reference points to and returns its path relative to
+    /// the page of `package`. Types in the same package are preferred over types with
+    /// the same name in other packages.
+    fn resolve_link(
+        r: &dyn Renderer,
+        type_name: &str,
+        type_names: &[String],
+        package: &str,
+    ) -> Option<String> {
+        let simple_name = type_name.rsplit('.').next().unwrap_or(type_name);
+        let split_name = |name: &str| match name.rfind('.') {
+            Some(i) => (name[..i].to_string(), name[i + 1..].to_string()),
+            None => (String::new(), name.to_string()),
+        };
+
+        let candidates: Vec<(String, String)> = type_names
+            .iter()
+            .filter(|name| !type_name.contains('.') || *name == type_name)
+            .map(|name| split_name(name))
+            .filter(|(_, name)| name == simple_name)
+            .collect();
+
+        let (target_package, target_name) = candidates
+            .iter()
+            .find(|(target_package, _)| target_package == package)
+            .or_else(|| candidates.first())?;
+
+        let depth = if package.is_empty() {
+            0
+        } else {
+            package.split('.').count()
+        };
+
+        Some(format!(
+            "{}{}",
+            "../".repeat(depth),
+            type_path(target_package, target_name, false, r.extension())
+        ))
+    }
+
+    /// Converts the contents of a `{@link}` tag such as `Type#member(long) label`.
+    /// Types with a page in `type_names` are linked to, other references become code spans.
+    fn convert_link(r: &dyn Renderer, content: &str, type_names: &[String], package: &str) -> String {
+        // The reference ends at the first space outside of the member's parameters
+        let mut depth = 0;
+        let mut split = content.len();
+
+        for (i, ch) in content.char_indices() {
+            match ch {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                ' ' if depth == 0 => {
+                    split = i;
+                    break;
+                }
+                _ => {}
+            }
+        }
+
+        let reference = &content[..split];
+        let label = content[split..].trim();
+        let (type_name, member) = match reference.find('#') {
+            Some(i) => (&reference[..i], &reference[i + 1..]),
+            None => (reference, ""),
+        };
+        let simple_name = type_name.rsplit('.').next().unwrap_or(type_name);
+
+        let text = if !label.is_empty() {
+            label.to_string()
+        } else if simple_name.is_empty() {
+            r.code(member)
+        } else if member.is_empty() {
+            r.code(simple_name)
+        } else {
+            r.code(format!("{}.{}", simple_name, member).as_str())
+        };
+
+        let target = if simple_name.is_empty() {
+            None
+        } else {
+            resolve_link(r, type_name, type_names, package)
+        };
+
+        if let Some(target) = target {
+            r.link(&text, &target)
+        } else if !label.is_empty() {
+            r.code(label)
+        } else {
+            text
+        }
+    }
+
+    /// Converts javadoc inline tags to the output format. `{@code x}` and `{@literal x}`
+    /// become code spans and `{@link}` tags become links or code spans. The `{@type}`
+    /// tags of signatures become links or plain names. Tags with unbalanced braces and
+    /// unknown tags are left as they are. The tables are laid out again afterwards.
+    ///
+    /// # Arguments
+    ///
+    /// * `r` - The renderer of the output format
+    /// * `text` - The documentation text containing inline tags
+    /// * `type_names` - The qualified names of the types with a page in this run
+    /// * `package` - The package of the page the text is on, used for relative links
+    pub fn convert_inline_tags(
+        r: &dyn Renderer,
+        text: &str,
+        type_names: &[String],
+        package: &str,
+    ) -> String {
+        let mut result = String::new();
+        let mut rest = text;
+
+        while let Some(start) = rest.find("{@") {
+            result.push_str(&rest[..start]);
+            let tag = &rest[start..];
+
+            match find_tag_end(tag) {
+                Some(end) => {
+                    let body = &tag[2..end];
+                    let (name, content) = match body.find(char::is_whitespace) {
+                        Some(i) => (&body[..i], body[i..].trim()),
+                        None => (body, ""),
+                    };
+
+                    match name {
+                        "code" | "literal" => result.push_str(r.code(content).as_str()),
+                        "link" | "linkplain" => {
+                            result.push_str(convert_link(r, content, type_names, package).as_str())
+                        }
+                        "type" => match resolve_link(r, content, type_names, package) {
+                            Some(target) => result.push_str(r.link(&r.escape(content), &target).as_str()),
+                            None => result.push_str(r.type_name(content).as_str()),
+                        },
+                        _ => result.push_str(&tag[..end + 1]),
+                    }
+
+                    rest = &tag[end + 1..];
+                }
+                None => {
+                    result.push_str("{@");
+                    rest = &tag[2..];
+                }
+            }
+        }
+
+        result.push_str(rest);
+
+        r.align_tables(&result)
+    }
+
+    /// Gets the qualified names of the types which get a page from the names of their
+    /// java files and the package statement in each file
+    ///
+    /// # Arguments
+    ///
+    /// * `file_paths` - The paths of the java files being documented
+    pub fn get_type_names(file_paths: &[PathBuf]) -> Vec<String> {
+        file_paths
+            .iter()
+            .filter_map(|path| {
+                let name = path.file_stem()?.to_string_lossy().to_string();
+
+                match read_package(path) {
+                    Some(package) => Some(format!("{}.{}", package, name)),
+                    None => Some(name),
+                }
+            })
+            .collect()
+    }
+
+    /// Reads the package statement of a java file without parsing the whole file
+    fn read_package(path: &Path) -> Option<String> {
+        let bytes = fs::read(path).ok()?;
+        let contents = String::from_utf8_lossy(&bytes);
+
+        contents
+            .lines()
+            .map(|line| line.trim_start_matches('\u{feff}').trim())
+            .find(|line| line.starts_with("package "))
+            .map(|line| line["package ".len()..].trim_end_matches(';').trim().to_string())
+    }
+
+    /// Gets the qualified name of a type, e.g. `com.example.Client`
+    fn qualified_name(package: &str, name: &str) -> String {
+        if package.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}.{}", package, name)
+        }
+    }
+
+    /// Checks that the pages can be written to a destination before a run writes them, so
+    /// a destination which is a file or can not be written to fails the run at once. The
+    /// directory is created when it does not exist, and a probe file is written to it
+    /// and removed again.
+    pub fn prepare_destination(dest: &str) -> Result<(), LojidocError> {
+        let path = Path::new(dest);
+
+        if let Some(file) = path.ancestors().find(|ancestor| ancestor.is_file()) {
+            return Err(LojidocError::InvalidArgs(if file == path {
+                format!("the destination {} is a file, not a directory", dest)
+            } else {
+                format!("the destination {} can not be created since {} is a file", dest, file.display())
+            }));
+        }
+        fs::create_dir_all(path).map_err(|err| LojidocError::io(path, err))?;
+
+        let probe = path.join(format!(".lojidoc-probe-{}", std::process::id()));
+        fs::write(&probe, "").map_err(|err| LojidocError::io(&probe, err))?;
+        fs::remove_file(&probe).map_err(|err| LojidocError::io(&probe, err))
+    }
+
+    /// Writes a page to the destination directory and to the markdown book.
+    /// Missing package directories are created.
+    fn write_page(dest: &str, kept: &BTreeSet<PathBuf>, book: bool, path: &str, doc: &str) -> Result<(), LojidocError> {
+        write_file(dest, kept, book, path, |out| out.write_all(doc.as_bytes()))
+    }
+
+    /// Writes a file through a buffer to the destination, and to the book with `book`.
+    /// `write` is called for each of them, its errors are reported with the path of the
+    /// file which failed. The files in `kept` are left as they are.
+    fn write_file<F>(dest: &str, kept: &BTreeSet<PathBuf>, book: bool, path: &str, write: F) -> Result<(), LojidocError>
+    where
+        F: Fn(&mut BufWriter<File>) -> io::Result<()>,
+    {
+        let mut roots = vec![dest];
+        if book {
+            roots.push("./markdown-book/src");
+        }
+        let mut written = false;
+
+        for root in roots {
+            let file_path = Path::new(root).join(path);
+
+            if kept.contains(&file_path) {
+                verbose!("{} was kept", file_path.display());
+                continue;
+            }
+            if let Some(parent) = file_path.parent() {
+                fs::create_dir_all(parent).map_err(|err| LojidocError::io(parent, err))?;
+            }
+
+            let mut out = File::create(&file_path)
+                .map(BufWriter::new)
+                .map_err(|err| LojidocError::io(&file_path, err))?;
+            write(&mut out)
+                .and_then(|_| out.flush())
+                .map_err(|err| LojidocError::io(&file_path, err))?;
+            written = true;
+        }
+
+        if written {
+            verbose!("{} was created", path);
+        }
+        Ok(())
+    }
+
+    /// Gets the layout of the page of a type. The anchors of the methods of the types
+    /// which share the single page start with the names of the types, like those of
+    /// nested types, so the same method of two types has two anchors.
+    fn type_layout<'a>(layout: &'a PageLayout, name: &str, shared: bool) -> Cow<'a, PageLayout> {
+        if shared {
+            Cow::Owned(PageLayout {
+                anchor_prefix: format!("{}{}.", layout.anchor_prefix, name.to_lowercase()),
+                ..layout.clone()
+            })
+        } else {
+            Cow::Borrowed(layout)
+        }
+    }
+
+    /// Generates the page of every type in a project, through the template of its kind
+    /// when there is one. Returns the package, name and description of each type with
+    /// its page.
+    ///
+    /// # Arguments
+    ///
+    /// * `r` - The renderer of the output format
+    /// * `proj` - The types
+    /// * `templates` - The templates of the run, if any
+    /// * `layout` - The layout of the pages
+    /// * `shared` - Whether the types share the single page
+    fn gen_type_pages(
+        r: &dyn Renderer,
+        proj: Project,
+        templates: Option<&Templates>,
+        layout: &PageLayout,
+        shared: bool,
+    ) -> Result<Vec<(String, String, String, String)>, LojidocError> {
+        let mut pages: Vec<(String, String, String, String)> = Vec::new();
+        // A kind of type without a template keeps the built-in layout
+        let template = |kind: &str| templates.filter(|templates| templates.has(kind));
+
+        for class in proj.classes {
+            let package = class.package_name.clone();
+            let (name, description) = (class.name.clone(), class.description.clone());
+            let page = match template("class") {
+                Some(templates) => templates.render("class", &class)?,
+                None => written_string(|out| gen_class_page(out, r, class, &type_layout(layout, &name, shared))),
+            };
+            pages.push((package, name, description, page));
+        }
+        for inter in proj.interfaces {
+            let package = inter.package_name.clone();
+            let (name, description) = (inter.name.clone(), inter.description.clone());
+            let page = match template("interface") {
+                Some(templates) => templates.render("interface", &inter)?,
+                None => written_string(|out| gen_interface_page(out, r, inter, &type_layout(layout, &name, shared))),
+            };
+            pages.push((package, name, description, page));
+        }
+        for enumeration in proj.enumerations {
+            let package = enumeration.package_name.clone();
+            let (name, description) = (enumeration.name.clone(), enumeration.description.clone());
+            let page = match template("enum") {
+                Some(templates) => templates.render("enum", &enumeration)?,
+                None => written_string(|out| gen_enum_page(out, r, enumeration, &type_layout(layout, &name, shared))),
+            };
+            pages.push((package, name, description, page));
+        }
+        for annotation in proj.annotations {
+            let package = annotation.package_name.clone();
+            let (name, description) = (annotation.name.clone(), annotation.description.clone());
+            let page = match template("annotation") {
+                Some(templates) => templates.render("annotation", &annotation)?,
+                None => {
+                    written_string(|out| gen_annotation_page(out, r, annotation, &type_layout(layout, &name, shared)))
+                }
+            };
+            pages.push((package, name, description, page));
+        }
+
+        Ok(pages)
+    }
+
+    /// Gets the qualified names of the top level types of a project
+    fn project_type_names(proj: &Project) -> Vec<String> {
+        let mut type_names = Vec::new();
+
+        type_names.extend(
+            proj.classes
+                .iter()
+                .map(|class| qualified_name(&class.package_name, &class.name)),
+        );
+        type_names.extend(
+            proj.interfaces
+                .iter()
+                .map(|inter| qualified_name(&inter.package_name, &inter.name)),
+        );
+        type_names.extend(
+            proj.enumerations
+                .iter()
+                .map(|enumeration| qualified_name(&enumeration.package_name, &enumeration.name)),
+        );
+        type_names.extend(
+            proj.annotations
+                .iter()
+                .map(|annotation| qualified_name(&annotation.package_name, &annotation.name)),
+        );
+
+        type_names
+    }
+
+    /// Generates the pages of the types of a java file
+    ///
+    /// # Arguments
+    ///
+    /// * `proj` - The parsed types of a java file
+    /// * `dest` - The destination directory of the pages
+    /// * `type_names` - The qualified names of the types with a page in this run for `{@link}` tags
+    /// * `output` - The layout of the pages, with the front matter and templates if any
+    /// * `r` - The renderer of the output format
+    ///
+    /// Returns the documented types by package for the index page, or the error of the
+    /// first page which could not be written
+    pub fn generate_pages(
+        proj: Project,
+        dest: &str,
+        type_names: &[String],
+        output: &Output,
+        r: &dyn Renderer,
+    ) -> Result<ApplicationDoc, LojidocError> {
+        let mut app_doc = ApplicationDoc::new();
+
+        for (page, member) in render_members(proj, type_names, output, r)? {
+            write_page(dest, &output.kept, output.book, &page.path, &page.doc)?;
+            app_doc.add_package_member(page.package, member);
+        }
+
+        Ok(app_doc)
+    }
+
+    /// Renders the pages of the types of a java file like `render_pages`, each with the
+    /// entry of its type in the index
+    pub fn render_members(
+        proj: Project,
+        type_names: &[String],
+        output: &Output,
+        r: &dyn Renderer,
+    ) -> Result<Vec<(TypePage, PackageMember)>, LojidocError> {
+        let mut nodes = diagram_nodes(&proj);
+        let mut methods = methods_by_type(r, &proj, output.methods);
+        let deprecated = deprecated_types(&proj);
+        let mut members = Vec::new();
+
+        for page in render_pages(proj, type_names, output, r)? {
+            let page_methods = methods.remove(&page.name).unwrap_or_default();
+            let member = PackageMember {
+                name: page.name.clone(),
+                path: page.path.clone(),
+                summary: first_sentence(&page.description),
+                deprecated: deprecated.contains(&page.name),
+                diagram: nodes.remove(&page.name).unwrap_or_default(),
+                methods: resolve_anchors(r, &page.doc, page_methods),
+            };
+
+            members.push((page, member));
+        }
+
+        Ok(members)
+    }
+
+    /// Gets the names of the deprecated types of a java file
+    fn deprecated_types(proj: &Project) -> Vec<String> {
+        let mut names = Vec::new();
+
+        names.extend(proj.classes.iter().filter(|class| class.deprecated.is_some()).map(|class| class.name.clone()));
+        names.extend(proj.interfaces.iter().filter(|inter| inter.deprecated.is_some()).map(|inter| inter.name.clone()));
+        names.extend(
+            proj.enumerations
+                .iter()
+                .filter(|enumeration| enumeration.deprecated.is_some())
+                .map(|enumeration| enumeration.name.clone()),
+        );
+        names.extend(
+            proj.annotations
+                .iter()
+                .filter(|annotation| annotation.deprecated.is_some())
+                .map(|annotation| annotation.name.clone()),
+        );
+
+        names
+    }
+
+    /// The page of a type with the path it is written to
+    pub struct TypePage {
+        pub package: String,
+        pub name: String,
+        pub description: String,
+        /// The path of the page relative to the destination
+        pub path: String,
+        /// The complete page with its front matter
+        pub doc: String,
+    }
+
+    /// Renders the pages of the types of a java file without writing them, with their
+    /// links and front matter
+    ///
+    /// # Arguments
+    ///
+    /// * `proj` - The parsed types of a java file
+    /// * `type_names` - The qualified names of the types with a page in this run for `{@link}` tags
+    /// * `output` - The layout of the pages, with the front matter and templates if any
+    /// * `r` - The renderer of the output format
+    pub fn render_pages(
+        proj: Project,
+        type_names: &[String],
+        output: &Output,
+        r: &dyn Renderer,
+    ) -> Result<Vec<TypePage>, LojidocError> {
+        let flat = output.flat;
+        let mut pages = Vec::new();
+        let mut type_names = type_names.to_vec();
+        type_names.extend(project_type_names(&proj));
+        let layout = PageLayout::new(output, &type_names);
+
+        // Without package directories every page links to the others by name
+        if flat {
+            type_names = type_names
+                .iter()
+                .map(|name| name.rsplit('.').next().unwrap_or(name).to_string())
+                .collect();
+        }
+
+        for (package_name, name, description, page) in gen_type_pages(r, proj, output.templates.as_deref(), &layout, false)? {
+            // A page without a name would be written to a file named only by its extension
+            if name.is_empty() {
+                continue;
+            }
+            let path = type_path(&package_name, &name, flat, r.extension());
+            let link_package = if flat { "" } else { package_name.as_str() };
+
+            let mut body = convert_inline_tags(r, &page, &type_names, link_package);
+            // The headings of markdown pages only have the anchors GitHub gives them
+            if !r.heading_ids() && output.templates.is_none() {
+                body = add_contents(&body);
+            }
+            let mut doc = r.page(&name, &body);
+            if let Some(front_matter) = output.front_matter.as_ref() {
+                doc.insert_str(0, &front_matter.gen_front_matter(&name, &description, &package_name));
+            }
+
+            pages.push(TypePage {
+                package: package_name,
+                name,
+                description,
+                path,
+                doc,
+            });
+        }
+
+        Ok(pages)
+    }
+
+    /// Generates the index page which lists every documented type by package.
+    /// Packages and the types in them are sorted by name.
+    ///
+    /// # Arguments
+    ///
+    /// * `r` - The renderer of the output format
+    /// * `app_doc` - The documented types of every file in the run
+    pub fn gen_index_page(r: &dyn Renderer, app_doc: &ApplicationDoc) -> String {
+        let mut doc = r.heading(1, &label("index"), "", "");
+        let mut packages = app_doc.packages.clone();
+
+        packages.sort_by(|a, b| a.name.cmp(&b.name));
+
+        for package in packages.iter() {
+            let mut members = package.members.clone();
+            members.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.path.cmp(&b.path)));
+
+            doc.push_str(r.heading(2, &package_title(&package.name), "", "").as_str());
+            doc.push_str(gen_package_description(r, package).as_str());
+            if !members.is_empty() {
+                doc.push_str(gen_member_table(r, &members, false).as_str());
+            }
+            doc.push('\n');
+        }
+
+        convert_inline_tags(r, &doc, &[], "")
+    }
+
+    /// Generates the description of a package from its `package-info.java` file
+    fn gen_package_description(r: &dyn Renderer, package: &Package) -> String {
+        if package.description.trim().is_empty() {
+            String::new()
+        } else {
+            r.description(package.description.trim())
+        }
+    }
+
+    /// Gets the title of a package in the index, the default package has no name
+    fn package_title(package: &str) -> String {
+        if package.is_empty() {
+            label("default_package")
+        } else {
+            package.to_string()
+        }
+    }
+
+    /// Generates the table of the types in a package with a link to their page and
+    /// their summary. With `relative` the links are relative to the package directory.
+    fn gen_member_table(r: &dyn Renderer, members: &[PackageMember], relative: bool) -> String {
+        let rows: Vec<Vec<String>> = members
+            .iter()
+            .map(|member| {
+                let path = if relative {
+                    member.path.rsplit('/').next().unwrap_or(&member.path)
+                } else {
+                    member.path.as_str()
+                };
+
+                let link = r.link(&r.escape(&member.name), path);
+                let link = if member.deprecated { r.struck(&link) } else { link };
+
+                vec![
+                    link,
+                    r.inline(&member.summary),
+                ]
+            }).collect();
+
+        label_table(r, &["type", "summary"], &rows)
+    }
+
+    /// Writes the index page to the destination directory, e.g. `index.md`
+    ///
+    /// # Arguments
+    ///
+    /// * `r` - The renderer of the output format
+    /// * `app_doc` - The documented types of every file in the run
+    /// * `dest` - The destination directory of the pages
+    /// * `kept` - The existing files which are not written, see `Output::kept`
+    pub fn generate_index(
+        r: &dyn Renderer,
+        app_doc: &ApplicationDoc,
+        dest: &str,
+        kept: &BTreeSet<PathBuf>,
+    ) -> Result<(), LojidocError> {
+        let path = format!("index.{}", r.extension());
+        let doc = r.page(&label("index"), &gen_index_page(r, app_doc));
+
+        write_page(dest, kept, false, &path, &doc)
+    }
+
+    /// Gets the path of the API index, e.g. `api-index.md`
+    pub fn api_index_path(extension: &str) -> String {
+        format!("api-index.{}", extension)
+    }
+
+    /// Generates the page listing the public methods of every documented type by name,
+    /// e.g. `withTimeout(Duration) — Client`, each linked to its heading
+    ///
+    /// # Arguments
+    ///
+    /// * `r` - The renderer of the output format
+    /// * `app_doc` - The documented types of every file in the run
+    pub fn gen_api_index_page(r: &dyn Renderer, app_doc: &ApplicationDoc) -> String {
+        let mut entries: Vec<(&PackageMember, &ApiEntry)> = app_doc
+            .packages
+            .iter()
+            .flat_map(|package| package.members.iter())
+            .flat_map(|member| member.methods.iter().map(move |entry| (member, entry)))
+            .collect();
+        entries.sort_by(|(a_member, a), (b_member, b)| {
+            (a.name.to_lowercase(), &a.name, &a.title, &a.owner, &a_member.path)
+                .cmp(&(b.name.to_lowercase(), &b.name, &b.title, &b.owner, &b_member.path))
+        });
+
+        let mut doc = r.heading(1, &label("api_index"), "", "");
+        if entries.is_empty() {
+            doc.push_str(r.text(&label("no_public_methods")).as_str());
+            return doc;
+        }
+
+        let items: Vec<String> = entries
+            .iter()
+            .map(|(member, entry)| {
+                let target = if entry.anchor.is_empty() {
+                    member.path.clone()
+                } else {
+                    format!("{}#{}", member.path, entry.anchor)
+                };
+
+                format!("{} — {}", r.link(&r.escape(&entry.title), &target), r.escape(&entry.owner))
+            }).collect();
+        doc.push_str(r.list(&items).as_str());
+
+        doc
+    }
+
+    /// Writes the API index of the public methods to `api-index.md`
+    ///
+    /// # Arguments
+    ///
+    /// * `r` - The renderer of the output format
+    /// * `app_doc` - The documented types of every file in the run
+    /// * `dest` - The destination directory of the pages
+    /// * `kept` - The existing files which are not written, see `Output::kept`
+    pub fn generate_api_index(
+        r: &dyn Renderer,
+        app_doc: &ApplicationDoc,
+        dest: &str,
+        kept: &BTreeSet<PathBuf>,
+    ) -> Result<(), LojidocError> {
+        let doc = r.page(&label("api_index"), &gen_api_index_page(r, app_doc));
+
+        write_page(dest, kept, false, &api_index_path(r.extension()), &doc)
+    }
+
+    /// Generates the page of a module with its description and a table for each kind of
+    /// directive it declares
+    pub fn gen_module_page(r: &dyn Renderer, module: &Module) -> String {
+        let mut doc = r.heading(1, &label_of("module", &module.name), "", "");
+        let names = |names: &[String]| {
+            names.iter().map(|name| r.code(name)).collect::<Vec<String>>().join(", ")
+        };
+
+        if module.open {
+            doc.push_str(r.field(&label("modifiers"), "open").as_str());
+        }
+        if !module.description.trim().is_empty() {
+            doc.push_str(r.description(module.description.trim()).as_str());
+        }
+
+        let sections = [
+            ("requires", ["module_column", "modifiers"]),
+            ("exports", ["package_column", "to"]),
+            ("opens", ["package_column", "to"]),
+            ("uses", ["service", ""]),
+            ("provides", ["service", "with"]),
+        ];
+        for (kind, headers) in sections.iter() {
+            let rows: Vec<Vec<String>> = module
+                .directives_of(kind)
+                .map(|directive| {
+                    let targets = if *kind == "requires" {
+                        directive.modifiers.join(" ")
+                    } else {
+                        names(&directive.targets)
+                    };
+                    let mut row = vec![r.code(&directive.name)];
+
+                    if !headers[1].is_empty() {
+                        row.push(targets);
+                    }
+                    row
+                }).collect();
+
+            if !rows.is_empty() {
+                let headers: Vec<&str> = headers.iter().cloned().filter(|header| !header.is_empty()).collect();
+
+                doc.push_str(r.heading(2, &label(kind), "", "").as_str());
+                doc.push_str(label_table(r, &headers, &rows).as_str());
+                doc.push('\n');
+            }
+        }
+
+        convert_inline_tags(r, &doc, &[], "")
+    }
+
+    /// Writes the page of the module of a `module-info.java` file to the destination
+    /// directory, e.g. `module.md`
+    ///
+    /// # Arguments
+    ///
+    /// * `r` - The renderer of the output format
+    /// * `module` - The module declared in the file
+    /// * `dest` - The destination directory of the pages
+    /// * `kept` - The existing files which are not written, see `Output::kept`
+    pub fn generate_module_page(
+        r: &dyn Renderer,
+        module: &Module,
+        dest: &str,
+        kept: &BTreeSet<PathBuf>,
+    ) -> Result<(), LojidocError> {
+        let doc = r.page(&label_of("module", &module.name), &gen_module_page(r, module));
+
+        write_page(dest, kept, false, &module_page_path(r.extension()), &doc)
+    }
+
+    /// Gets the path of the page of the module of the run
+    pub fn module_page_path(extension: &str) -> String {
+        format!("module.{}", extension)
+    }
+
+    /// Gets the path of the index page of a package with a `package-info.java` file
+    pub fn package_index_path(package: &str, extension: &str) -> String {
+        type_path(package, "index", false, extension)
+    }
+
+    /// Gets the path of the page listing the types of a package, named like the
+    /// `package-summary.html` pages of javadoc
+    pub fn package_page_path(package: &str) -> String {
+        type_path(package, "package-summary", false, "md")
+    }
+
+    /// Generates the `SUMMARY.md` of an mdBook. Every package is a chapter with its
+    /// types nested under it, both sorted by name. The index is the introduction.
+    ///
+    /// # Arguments
+    ///
+    /// * `app_doc` - The documented types of every file in the run
+    pub fn gen_summary(app_doc: &ApplicationDoc) -> String {
+        let mut doc = format!("# Summary\n\n[{}](index.md)\n\n", label("index"));
+        let mut packages = app_doc.packages.clone();
+
+        packages.sort_by(|a, b| a.name.cmp(&b.name));
+
+        for package in packages.iter() {
+            let mut members = package.members.clone();
+            members.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.path.cmp(&b.path)));
+
+            doc.push_str(
+                format!(
+                    "- [{}]({})\n",
+                    package_title(&package.name),
+                    package_page_path(&package.name)
+                ).as_str(),
+            );
+
+            for member in members.iter() {
+                // Deprecated types are struck through like in the markdown book
+                if member.deprecated {
+                    doc.push_str(format!("    - [~~{}~~]({})\n", member.name, member.path).as_str());
+                } else {
+                    doc.push_str(format!("    - [{}]({})\n", member.name, member.path).as_str());
+                }
+            }
+        }
+
+        doc
+    }
+
+    /// Finds the directory of the doc ids of the pages in `dest`, which is relative to the
+    /// `docs` directory of the Docusaurus site `dest` is in, e.g. `api` for `website/docs/api`.
+    /// The ids have no directory when no site is found.
+    fn docusaurus_prefix(dest: &str) -> String {
+        let dest = match fs::canonicalize(dest) {
+            Ok(dest) => dest,
+            Err(_) => return String::new(),
+        };
+        let is_site = |dir: &Path| {
+            ["js", "ts", "mjs"]
+                .iter()
+                .any(|ext| dir.join(format!("docusaurus.config.{}", ext)).is_file())
+        };
+
+        for dir in dest.ancestors() {
+            if dir.file_name().is_some_and(|name| name == "docs") && dir.parent().is_some_and(is_site) {
+                let prefix = dest.strip_prefix(dir).unwrap_or(Path::new(""));
+                return prefix
+                    .components()
+                    .map(|component| component.as_os_str().to_string_lossy().into_owned())
+                    .collect::<Vec<String>>()
+                    .join("/");
+            }
+        }
+
+        String::new()
+    }
+
+    /// Gets the Docusaurus doc id of a page, which is its path without the extension
+    fn doc_id(prefix: &str, path: &str) -> String {
+        let path = path.strip_suffix(".md").unwrap_or(path);
+
+        if prefix.is_empty() {
+            path.to_string()
+        } else {
+            format!("{}/{}", prefix, path)
+        }
+    }
+
+    /// Generates the `sidebar.json` of a Docusaurus site, which lists the index pages and
+    /// a category of every package with its types, both sorted by name. The list is the
+    /// items of a sidebar, e.g. `api: require('./docs/api/sidebar.json')` in `sidebars.js`.
+    ///
+    /// # Arguments
+    ///
+    /// * `app_doc` - The documented types of every file in the run
+    /// * `prefix` - The directory of the doc ids relative to the `docs` directory
+    pub fn gen_sidebar(app_doc: &ApplicationDoc, prefix: &str) -> String {
+        let mut items = vec![
+            serde_json::Value::from(doc_id(prefix, "index")),
+            serde_json::Value::from(doc_id(prefix, &api_index_path("md"))),
+        ];
+        let mut packages = app_doc.packages.clone();
+
+        packages.sort_by(|a, b| a.name.cmp(&b.name));
+
+        for package in packages.iter() {
+            let mut members = package.members.clone();
+            members.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.path.cmp(&b.path)));
+
+            let mut category = serde_json::Map::new();
+            category.insert("type".to_string(), "category".into());
+            category.insert("label".to_string(), package_title(&package.name).into());
+            category.insert(
+                "items".to_string(),
+                members.iter().map(|member| doc_id(prefix, &member.path)).collect(),
+            );
+            items.push(category.into());
+        }
+
+        let mut doc = serde_json::to_string_pretty(&items).expect("Not able to serialize the sidebar");
+        doc.push('\n');
+
+        doc
+    }
+
+    /// Generates the page of a package with its description and the types in it, which
+    /// is the chapter page of the package in an mdBook
+    fn gen_package_page(r: &dyn Renderer, package: &Package) -> String {
+        let mut members = package.members.clone();
+        members.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.path.cmp(&b.path)));
+
+        let title = if package.name.is_empty() {
+            package_title(&package.name)
+        } else {
+            label_of("package_title", &package.name)
+        };
+        let mut doc = r.heading(1, &title, "", "");
+        doc.push_str(gen_package_description(r, package).as_str());
+        if !members.is_empty() {
+            doc.push_str(gen_member_table(r, &members, true).as_str());
+        }
+
+        convert_inline_tags(r, &doc, &[], "")
+    }
+
+    /// Writes the `index` page of every package with a `package-info.java` file to its
+    /// package directory, e.g. `com/example/index.md`
+    ///
+    /// # Arguments
+    ///
+    /// * `r` - The renderer of the output format
+    /// * `app_doc` - The documented types of every file in the run
+    /// * `dest` - The destination directory of the pages
+    /// * `kept` - The existing files which are not written, see `Output::kept`
+    pub fn generate_package_indexes(
+        r: &dyn Renderer,
+        app_doc: &ApplicationDoc,
+        dest: &str,
+        kept: &BTreeSet<PathBuf>,
+    ) -> Result<(), LojidocError> {
+        let documented = app_doc
+            .packages
+            .iter()
+            .filter(|package| !package.name.is_empty() && !package.description.trim().is_empty());
+
+        for package in documented {
+            let path = package_index_path(&package.name, r.extension());
+            let doc = r.page(&label_of("package_title", &package.name), &gen_package_page(r, package));
+
+            write_page(dest, kept, false, &path, &doc)?;
+        }
+
+        Ok(())
+    }
+
+    /// The most types a class diagram shows, the diagrams of larger packages are not
+    /// readable
+    pub const DIAGRAM_LIMIT: usize = 40;
+
+    /// Gets the types of a java file by name as they are shown in the class diagram of
+    /// their package
+    pub fn diagram_nodes(proj: &Project) -> HashMap<String, DiagramNode> {
+        let public_methods = |methods: &[Method]| -> Vec<String> {
+            methods
+                .iter()
+                .filter(|method| method.privacy == "public")
+                .map(|method| method.name.clone())
+                .collect()
+        };
+        let mut nodes = HashMap::new();
+
+        for class in proj.classes.iter() {
+            let node = DiagramNode {
+                kind: "class".to_string(),
+                extends: Some(class.parent.clone()).filter(|parent| !parent.is_empty()).into_iter().collect(),
+                implements: class.interfaces.clone(),
+                methods: public_methods(&class.methods),
+            };
+            nodes.insert(class.name.clone(), node);
+        }
+        for inter in proj.interfaces.iter() {
+            let node = DiagramNode {
+                kind: "interface".to_string(),
+                extends: inter.extends.clone(),
+                implements: Vec::new(),
+                methods: public_methods(&inter.methods),
+            };
+            nodes.insert(inter.name.clone(), node);
+        }
+        for enumeration in proj.enumerations.iter() {
+            let node = DiagramNode {
+                kind: "enum".to_string(),
+                extends: Vec::new(),
+                implements: enumeration.interfaces.clone(),
+                methods: public_methods(&enumeration.methods),
+            };
+            nodes.insert(enumeration.name.clone(), node);
+        }
+        for annotation in proj.annotations.iter() {
+            let node = DiagramNode {
+                kind: "annotation".to_string(),
+                ..DiagramNode::default()
+            };
+            nodes.insert(annotation.name.clone(), node);
+        }
+
+        nodes
+    }
+
+    /// Gets the mermaid identifier of a type, which has no package and type arguments,
+    /// e.g. `Map` for `java.util.Map<K, V>`
+    fn mermaid_id(type_name: &str) -> String {
+        let type_name = type_name.split('<').next().unwrap_or(type_name).trim();
+        let name = type_name.rsplit('.').next().unwrap_or(type_name);
+
+        name.chars()
+            .map(|ch| if ch.is_alphanumeric() || ch == '_' { ch } else { '_' })
+            .collect()
+    }
+
+    /// Generates the mermaid class diagram of the types of a package with the classes
+    /// they extend and the interfaces they implement. Supertypes of other packages are
+    /// shown without members.
+    ///
+    /// # Arguments
+    ///
+    /// * `package` - The package with its documented types
+    /// * `methods` - Whether the public methods of the types are listed
+    pub fn gen_class_diagram(package: &Package, methods: bool) -> String {
+        let mut members = package.members.clone();
+        members.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let title = label_of("class_diagram", &package_title(&package.name));
+        let mut doc = format!("# {}\n\n```mermaid\nclassDiagram\n", title);
+        let mut edges = Vec::new();
+
+        for member in members.iter() {
+            let id = mermaid_id(&member.name);
+            let node = &member.diagram;
+            let mut body = Vec::new();
+
+            match node.kind.as_str() {
+                "interface" => body.push("<<interface>>".to_string()),
+                "enum" => body.push("<<enumeration>>".to_string()),
+                "annotation" => body.push("<<annotation>>".to_string()),
+                _ => {}
+            }
+            if methods {
+                // Overloads are listed once
+                let mut names = node.methods.clone();
+                names.sort();
+                names.dedup();
+                body.extend(names.iter().map(|name| format!("+{}()", name)));
+            }
+
+            if body.is_empty() {
+                doc.push_str(&format!("    class {}\n", id));
+            } else {
+                doc.push_str(&format!("    class {} {{\n", id));
+                for line in body {
+                    doc.push_str(&format!("        {}\n", line));
+                }
+                doc.push_str("    }\n");
+            }
+
+            edges.extend(node.extends.iter().map(|parent| format!("    {} <|-- {}\n", mermaid_id(parent), id)));
+            edges.extend(node.implements.iter().map(|inter| format!("    {} <|.. {}\n", mermaid_id(inter), id)));
+        }
+        for edge in edges {
+            doc.push_str(&edge);
+        }
+        doc.push_str("```\n");
 
-    /// Finds out whether a file is a java file
-    fn is_java_file(file: &str) -> bool {
-        let line_vec: Vec<&str> = file.split(".").collect::<Vec<&str>>();
-        let l_index = line_vec.len() - 1;
+        doc
+    }
 
-        if line_vec[l_index].contains("java") {
-            true
-        } else {
-            false
-        }
+    /// Gets the path of the class diagram of a package
+    pub fn diagram_path(package: &str) -> String {
+        type_path(package, "diagram", false, "md")
     }
 
-    /// Traverses the file structure to find all java files for parsing.
+    /// Writes the class diagram of every package to its package directory, e.g.
+    /// `com/example/diagram.md`. A package with more than `DIAGRAM_LIMIT` types gets
+    /// no diagram.
     ///
     /// # Arguments
     ///
-    /// * `start_dir` - The directory to start looking for java files in.
-    pub fn find_java_files(start_dir: &Path) -> Vec<PathBuf> {
-        let mut files: Vec<PathBuf> = Vec::new();
+    /// * `app_doc` - The documented types of every file in the run
+    /// * `dest` - The destination directory of the pages
+    /// * `kept` - The existing files which are not written, see `Output::kept`
+    /// * `methods` - Whether the public methods of the types are listed
+    pub fn generate_diagrams(
+        app_doc: &ApplicationDoc,
+        dest: &str,
+        kept: &BTreeSet<PathBuf>,
+        methods: bool,
+    ) -> Result<(), LojidocError> {
+        for package in app_doc.packages.iter().filter(|package| !package.members.is_empty()) {
+            if package.members.len() > DIAGRAM_LIMIT {
+                info!(
+                    "{} the class diagram of {}, it has {} types, more than {}",
+                    "Skipping".yellow(),
+                    package_title(&package.name),
+                    package.members.len(),
+                    DIAGRAM_LIMIT
+                );
+                continue;
+            }
 
-        // If the start directory is a single file return only that path
-        if start_dir.is_file() {
-            files.push(start_dir.to_path_buf());
-            return files;
+            write_page(dest, kept, false, &diagram_path(&package.name), &gen_class_diagram(package, methods))?;
         }
 
-        let file_dir = fs::read_dir(start_dir);
+        Ok(())
+    }
 
-        if !file_dir.is_ok() {
-            println!("Incorrect file path");
-            return files;
+    /// Writes the `SUMMARY.md` and the package pages of an mdBook whose `src` directory
+    /// is `dest`. The summary is generated from scratch so removed types leave the
+    /// navigation. A minimal `book.toml` is added to the book when it has none.
+    ///
+    /// # Arguments
+    ///
+    /// * `app_doc` - The documented types of every file in the run
+    /// * `dest` - The destination directory of the pages
+    /// * `kept` - The existing files which are not written, see `Output::kept`
+    pub fn generate_mdbook(app_doc: &ApplicationDoc, dest: &str, kept: &BTreeSet<PathBuf>) -> Result<(), LojidocError> {
+        for package in app_doc.packages.iter() {
+            write_page(dest, kept, false, &package_page_path(&package.name), &gen_package_page(&Markdown, package))?;
         }
+        write_page(dest, kept, false, "SUMMARY.md", &gen_summary(app_doc))?;
 
-        // For every file or directory in the current directory find java files
-        for f in file_dir.unwrap() {
-            let p = f.unwrap().path();
+        let src = fs::canonicalize(dest).map_err(|err| LojidocError::io(Path::new(dest), err))?;
+        let root = src.parent().unwrap_or(&src);
+        let config = root.join("book.toml");
 
-            if p.is_dir() {
-                let path = p.as_path();
-                let new_files = find_java_files(path);
+        if !config.exists() {
+            let src_name = src.file_name().map_or("src".into(), |name| name.to_string_lossy());
+            let doc = format!("[book]\ntitle = \"{}\"\nsrc = \"{}\"\n", label("api_documentation"), src_name);
 
-                for n_file in new_files {
-                    files.push(n_file.clone());
-                }
-            } else if p.is_file() {
-                if is_java_file(p.as_path().file_name().unwrap().to_str().unwrap()) {
-                    files.push(p.clone());
-                }
-            }
+            fs::write(&config, doc).map_err(|err| LojidocError::io(&config, err))?;
+            verbose!("{} was created", config.display());
         }
 
-        files.clone()
+        Ok(())
     }
 
-    /// Traverses the file structure to find all java files for parsing.
+    /// Writes the `sidebar.json` of the pages in `dest`, which is generated from scratch
+    /// so removed types leave the navigation
     ///
     /// # Arguments
     ///
-    /// * `start_dir` - The directory to start looking for java files in.
-    pub fn find_gen_files(gen_dir: &Path) -> Vec<String> {
-        let mut files: Vec<String> = Vec::new();
-        let file_dir = fs::read_dir(gen_dir);
-
-        if file_dir.is_ok() {
-            for f in file_dir.unwrap() {
-                if f.is_ok() {
-                    let p = f.unwrap().path();
+    /// * `app_doc` - The documented types of every file in the run
+    /// * `dest` - The destination directory of the pages
+    /// * `kept` - The existing files which are not written, see `Output::kept`
+    pub fn generate_sidebar(app_doc: &ApplicationDoc, dest: &str, kept: &BTreeSet<PathBuf>) -> Result<(), LojidocError> {
+        write_page(dest, kept, false, "sidebar.json", &gen_sidebar(app_doc, &docusaurus_prefix(dest)))
+    }
 
-                    if p.is_file() {
-                        let file = p.as_path().file_name().unwrap().to_str().unwrap();
-                        let line_vec: Vec<&str> = file.split(".").collect::<Vec<&str>>();
-                        let l_index = line_vec.len() - 1;
+    /// Gets the level of a markdown heading, `None` when the line is not a heading
+    fn heading_level(line: &str) -> Option<usize> {
+        let text = line.trim_start_matches('#');
+        let level = line.len() - text.len();
 
-                        if line_vec[l_index].contains("md") {
-                            files.push(line_vec[0].to_string());
-                        }
-                    }
-                }
-            }
+        if (1..=6).contains(&level) && (text.is_empty() || text.starts_with(' ')) {
+            Some(level)
         } else {
-            println!("Generated directory read error: {:?}", gen_dir);
+            None
         }
+    }
 
-        files
+    /// Gets the anchor GitHub generates for the text of a markdown heading.
+    /// Links keep only their text and punctuation other than `-` and `_` is removed.
+    pub fn heading_slug(heading: &str) -> String {
+        let mut text = String::new();
+        let mut rest = heading;
+
+        // Drops the targets of links such as `[[src]](path)`
+        while let Some(i) = rest.find("](") {
+            text.push_str(&rest[..i + 1]);
+            rest = match rest[i..].find(')') {
+                Some(end) => &rest[i + end + 1..],
+                None => "",
+            };
+        }
+        text.push_str(rest);
+
+        text.trim()
+            .to_lowercase()
+            .chars()
+            .filter(|ch| ch.is_alphanumeric() || *ch == '-' || *ch == '_' || *ch == ' ')
+            .map(|ch| if ch == ' ' { '-' } else { ch })
+            .collect()
     }
 
-    /// Generates the markdown documentation for a class
-    ///
-    /// # Arguments
-    ///
-    /// * `class` - The class struct containing the javadoc data
-    pub fn gen_class_docs(class: Class) -> String {
-        let mut doc = String::new();
+    /// The anchors GitHub gives the headings of a markdown page. A heading whose slug
+    /// was taken gets the first free number, e.g. `run-1` for the second `run`.
+    #[derive(Default)]
+    pub struct HeadingSlugs {
+        /// The numbers last given to each slug
+        counts: HashMap<String, usize>,
+    }
 
-        if class.file_path != "" {
-            doc.push_str(
-                format!("# Class {} [[src]]({})  \n\n", class.name, class.file_path).as_str(),
-            );
-        } else {
-            doc.push_str(format!("# Class {}\n\n", class.name).as_str());
+    impl HeadingSlugs {
+        pub fn new() -> HeadingSlugs {
+            HeadingSlugs::default()
         }
+        /// Gets the anchor of the next heading of the page
+        pub fn slug(&mut self, heading: &str) -> String {
+            let original = heading_slug(heading);
+            let mut slug = original.clone();
 
-        if class.license != "" {
-            doc.push_str("<details>  \n");
-            doc.push_str("  <summary>  \n");
-            doc.push_str("    Show license  \n\n");
-            doc.push_str("  </summary>  \n");
+            while self.counts.contains_key(&slug) {
+                let count = self.counts.entry(original.clone()).or_insert(0);
+                *count += 1;
+                slug = format!("{}-{}", original, count);
+            }
+            self.counts.insert(slug.clone(), 0);
 
-            doc.push_str("  <ul>  \n");
-            doc.push_str(class.license.as_str());
-            doc.push_str("  </ul>  \n");
-            doc.push_str("</details>  \n\n");
-            doc.push_str("<br/>");
+            slug
         }
+    }
 
-        doc.push_str(format!("Access: {}  \n", class.access.trim()).as_str());
-        if class.description.as_str() != "" {
-            doc.push_str(format!("Description:  \n > {}  \n\n", class.description.trim()).as_str());
+    /// A heading of a markdown page with the anchors it is linked by
+    pub struct PageHeading {
+        pub level: usize,
+        /// The text of the heading without its source link
+        pub text: String,
+        /// The anchor written before the heading, or the slug when it has none
+        pub anchor: String,
+        /// The anchor GitHub gives the heading
+        pub slug: String,
+    }
+
+    /// Gets the headings of a markdown page in order, leaving out the lines of code blocks
+    pub fn page_headings(doc: &str) -> Vec<PageHeading> {
+        let mut slugs = HeadingSlugs::new();
+        let mut headings = Vec::new();
+        let mut code = false;
+        let mut anchor = None;
+
+        for line in doc.lines() {
+            if line.starts_with("```") {
+                code = !code;
+            }
+            if code {
+                continue;
+            }
+            if let Some(level) = heading_level(line) {
+                let heading = line[level..].trim();
+                let text = heading.find(" [[src]]").map_or(heading, |i| &heading[..i]);
+                let slug = slugs.slug(heading);
+
+                headings.push(PageHeading {
+                    level,
+                    text: text.to_string(),
+                    anchor: anchor.take().unwrap_or_else(|| slug.clone()),
+                    slug,
+                });
+            } else if let Some(id) = line.strip_prefix("<a id=\"").and_then(|rest| rest.strip_suffix("\"></a>")) {
+                anchor = Some(id.to_string());
+            } else if !line.trim().is_empty() {
+                anchor = None;
+            }
         }
-        if class.author != "" {
-            doc.push_str(format!("Author: {}  \n", class.author).as_str());
+
+        headings
+    }
+
+    /// Generates the table of contents of a markdown type page, which links to its
+    /// sections and to the constructors, methods and nested types in them. Pages
+    /// without sections have none.
+    pub fn gen_contents(doc: &str) -> String {
+        let mut contents = String::new();
+        let mut section = String::new();
+        let empty_sections = [
+            label("no_constants"),
+            label("no_fields"),
+            label("no_elements"),
+            label("no_methods"),
+        ];
+
+        for heading in page_headings(doc) {
+            let depth = match heading.level {
+                2 => {
+                    section = heading.text.clone();
+                    0
+                }
+                3 => 1,
+                // The methods grouped by visibility are a level further down
+                4 if section == label("methods") => 2,
+                _ => continue,
+            };
+            // The sections without entries, e.g. `No fields in this class`
+            let empty = section.starts_with("No ") || empty_sections.contains(&section);
+            if section == label("dependencies") || empty {
+                continue;
+            }
+
+            contents.push_str(&"  ".repeat(depth));
+            contents.push_str(format!("- [{}](#{})\n", heading.text, heading.anchor).as_str());
         }
-        if class.version != "" {
-            doc.push_str(format!("Since version: {}  \n", class.version).as_str());
+
+        if contents.is_empty() {
+            contents
+        } else {
+            format!("{}:  \n\n{}\n", label("contents"), contents)
         }
-        if class.parent != "" {
-            doc.push_str(format!("Parent class: {}  \n", class.parent).as_str());
+    }
+
+    /// Adds the table of contents to a markdown type page, after the description and
+    /// the other details of the type before its first section
+    fn add_contents(doc: &str) -> String {
+        let contents = gen_contents(doc);
+        let start = doc
+            .match_indices("\n## ")
+            .map(|(i, _)| i + 1)
+            .next()
+            .unwrap_or(doc.len());
+
+        format!("{}{}{}", &doc[..start], contents, &doc[start..])
+    }
+
+    /// Generates the pages of the types of the single page, sorted by package and name
+    /// with their inline tags converted. Returns the package, name, description and page
+    /// of each type.
+    fn gen_single_page_types(proj: Project, layout: &PageLayout) -> Vec<(String, String, String, String)> {
+        let r = &Markdown;
+        // Templates are not used for the single page, whose headings are moved below
+        // the heading of each type
+        let mut pages = gen_type_pages(r, proj, None, layout, true).unwrap_or_default();
+        pages.sort_by(|a, b| (&a.0, &a.1).cmp(&(&b.0, &b.1)));
+
+        for page in pages.iter_mut() {
+            page.3 = convert_inline_tags(r, &page.3, &[], "");
         }
 
-        if class.interfaces.len() > 0 {
-            doc.push_str("Interfaces:  \n");
+        pages
+    }
 
-            for inter in class.interfaces {
-                doc.push_str(format!("- {}  \n", inter).as_str());
+    /// Writes the single page of the types to `out`, with a table of contents at the top.
+    /// The headings inside each type are moved down a level below the type's heading.
+    fn write_single_page_types(out: &mut impl Write, pages: &[(String, String, String, String)]) -> io::Result<()> {
+        let r = &Markdown;
+        // The headings of the page moved down a level, except for the heading of the type
+        let moved = |line: &str| {
+            if line.starts_with("##") {
+                format!("#{}", line)
+            } else {
+                line.to_string()
             }
-            doc.push_str("\n");
-        }
+        };
 
-        doc.push_str(format!("package: {}  \n\n", class.package_name.trim()).as_str());
+        // GitHub numbers the anchors of headings with the same text
+        let mut slugs = HeadingSlugs::new();
+        slugs.slug(&label("api"));
+        slugs.slug(&label("contents"));
 
-        if class.exceptions.len() > 0 {
-            for exception in class.exceptions {
-                doc.push_str(
-                    format!(
-                        "Throws {}: {}  \n\n",
-                        exception.exception_type, exception.desc
-                    ).as_str(),
-                );
+        let mut contents = String::new();
+        let mut package: Option<&str> = None;
+
+        for (package_name, _, description, page) in pages.iter() {
+            let mut summary = Some(first_sentence(description));
+
+            if package != Some(package_name.as_str()) {
+                contents.push_str(format!("- {}\n", package_title(package_name)).as_str());
+                package = Some(package_name.as_str());
             }
-            doc.push_str("\n");
-        }
 
-        doc.push_str("## Dependencies\n\n");
-        doc.push_str("<details>  \n");
-        doc.push_str("  <summary>  \n");
-        doc.push_str("    Show dependencies  \n");
-        doc.push_str("  </summary>  \n");
+            for line in page.lines().map(moved) {
+                if let Some(level) = heading_level(&line) {
+                    let heading = line[level..].trim();
+                    let slug = slugs.slug(heading);
 
-        doc.push_str("  <ul>  \n");
-        for dep in class.dependencies {
-            doc.push_str(format!("<li>{}</li>\n", dep).as_str());
+                    if level == 1 {
+                        let title = match heading.find(" [[src]]") {
+                            Some(i) => &heading[..i],
+                            None => heading,
+                        };
+                        // Only the type of the page is summarized, not its nested types
+                        match summary.take().filter(|summary| !summary.is_empty()) {
+                            Some(summary) => contents.push_str(
+                                format!("  - [{}](#{}) - {}\n", title, slug, r.inline(&summary)).as_str(),
+                            ),
+                            None => contents.push_str(format!("  - [{}](#{})\n", title, slug).as_str()),
+                        }
+                    }
+                }
+            }
         }
-        doc.push_str("  </ul>  \n");
-        doc.push_str("</details>  \n\n");
 
-        doc
+        // The contents come first, so the pages are written once they are all listed
+        write!(out, "# {}\n\n## {}\n\n{}\n", label("api"), label("contents"), contents)?;
+        for (_, _, _, page) in pages.iter() {
+            for line in page.lines().map(moved) {
+                writeln!(out, "{}", line)?;
+            }
+        }
+
+        Ok(())
     }
 
-    /// Generates the markdown documentation for an interface
+    /// Writes a single markdown page with every type in a project to `out`. The types are
+    /// sorted by package and name and listed in a table of contents at the top.
     ///
     /// # Arguments
     ///
-    /// * `inter` - The interface struct containing the javadoc data
-    pub fn gen_interface_docs(inter: Interface) -> String {
-        let mut doc = String::new();
+    /// * `out` - Where the page is written, such as a file or stdout
+    /// * `proj` - The parsed types of every java file
+    /// * `layout` - The layout of the page of each type
+    pub fn write_single_page(out: &mut impl Write, proj: Project, layout: &PageLayout) -> io::Result<()> {
+        write_single_page_types(out, &gen_single_page_types(proj, layout))
+    }
 
-        if inter.file_path != "" {
-            doc.push_str(
-                format!(
-                    "# Interface {} [[src]]({})  \n\n",
-                    inter.name, inter.file_path
-                ).as_str(),
-            );
-        } else {
-            doc.push_str(format!("# Interface {}\n\n", inter.name).as_str());
+    /// Writes every type in a project to `API.md` in the destination directory
+    pub fn generate_single_page(
+        proj: Project,
+        dest: &str,
+        output: &Output,
+    ) -> Result<(), LojidocError> {
+        let layout = PageLayout::new(output, &project_type_names(&proj));
+        let pages = gen_single_page_types(proj, &layout);
+
+        write_file(dest, &output.kept, output.book, "API.md", |out| write_single_page_types(out, &pages))
+    }
+
+    /// Writes pages one after another, separated by the horizontal rule of the format
+    ///
+    /// # Arguments
+    ///
+    /// * `out` - Where the pages are written, such as stdout
+    /// * `r` - The renderer of the output format
+    /// * `pages` - The complete pages
+    pub fn write_pages(out: &mut impl Write, r: &dyn Renderer, pages: &[String]) -> io::Result<()> {
+        for (i, page) in pages.iter().enumerate() {
+            if i > 0 {
+                out.write_all(r.rule().as_bytes())?;
+            }
+            out.write_all(page.as_bytes())?;
         }
 
-        if inter.description.as_str() != "" {
-            doc.push_str(format!("description: {}  \n", inter.description.trim()).as_str());
+        out.flush()
+    }
+
+    /// Serializes a type with its members to pretty printed JSON
+    pub fn gen_type_json(object: &ObjectType) -> String {
+        serde_json::to_string_pretty(object).expect("Not able to serialize the type")
+    }
+
+    /// Serializes every type in a project to pretty printed JSON.
+    /// The types are sorted first so the output does not depend on the order
+    /// the files were parsed in.
+    pub fn gen_api_json(proj: &mut Project) -> String {
+        proj.sort();
+
+        serde_json::to_string_pretty(proj).expect("Not able to serialize the project")
+    }
+
+    /// Writes a JSON file for each type in a project
+    ///
+    /// # Arguments
+    ///
+    /// * `proj` - The parsed types of a java file
+    /// * `dest` - The destination directory of the JSON files
+    /// * `kept` - The existing files which are not written, see `Output::kept`
+    /// * `flat` - Whether files are written directly to `dest` instead of package directories
+    pub fn generate_json(proj: Project, dest: &str, kept: &BTreeSet<PathBuf>, flat: bool) -> Result<(), LojidocError> {
+        let mut objects: Vec<(String, ObjectType)> = Vec::new();
+
+        for class in proj.classes {
+            let path = type_path(&class.package_name, &class.name, flat, "json");
+            objects.push((path, ObjectType::Class(class)));
+        }
+        for inter in proj.interfaces {
+            let path = type_path(&inter.package_name, &inter.name, flat, "json");
+            objects.push((path, ObjectType::Interface(inter)));
+        }
+        for enumeration in proj.enumerations {
+            let path = type_path(&enumeration.package_name, &enumeration.name, flat, "json");
+            objects.push((path, ObjectType::Enumeration(enumeration)));
+        }
+        for annotation in proj.annotations {
+            let path = type_path(&annotation.package_name, &annotation.name, flat, "json");
+            objects.push((path, ObjectType::Annotation(annotation)));
         }
-        doc.push_str(format!("privacy: {}  \n", inter.access.trim()).as_str());
-        doc.push_str(format!("package: {}  \n\n", inter.package_name.trim()).as_str());
-        doc.push_str("## Dependencies\n\n");
-        doc.push_str("<details>  \n");
-        doc.push_str("  <summary>  \n");
-        doc.push_str("    Show dependencies  \n");
-        doc.push_str("  </summary>  \n");
 
-        doc.push_str("  <ul>  \n");
-        for dep in inter.dependencies {
-            doc.push_str(format!("    <li>{}</li>\n", dep).as_str());
+        for (path, object) in objects {
+            write_page(dest, kept, false, &path, &gen_type_json(&object))?;
         }
-        doc.push_str("  </ul>  \n");
-        doc.push_str("</details>  \n\n");
 
-        doc
+        Ok(())
     }
 
-    /// Generates the markdown documentation for the member variables of a class
+    /// Gets the path of every file the documentation of a project is written to, relative
+    /// to the destination, with the qualified name of the type written to it. Files which
+    /// do not belong to a single type, such as the index, have no type name.
     ///
     /// # Arguments
     ///
-    /// * `variables` - The vector of class methods to be documented
-    pub fn gen_var_docs(variables: Vec<Member>, path: String) -> String {
-        let mut doc = String::new();
-
-        if variables.len() > 0 {
-            doc.push_str("## Member Variables\n\n");
-        } else {
-            doc.push_str("## No member variables in this class\n\n");
+    /// * `proj` - The parsed types of every file in the run
+    /// * `output` - The format and layout of the documentation
+    pub fn output_paths(proj: &Project, output: &Output) -> Vec<(String, String)> {
+        let mut paths = Vec::new();
 
-            return doc;
+        if output.single_file {
+            let name = if output.format == Format::Json { "api.json" } else { "API.md" };
+            paths.push((name.to_string(), String::new()));
+            return paths;
         }
 
-        for member in variables {
-            if path != "" {
-                let mut file_path = path.clone();
-                file_path.push_str(format!("#L{}", member.line_num).as_str());
-                doc.push_str(
-                    format!(
-                        "#### {} {} [[src]]({})\n\n",
-                        member.var_type, member.name, file_path
-                    ).as_str(),
-                );
-            } else {
-                doc.push_str(format!("#### {} {}\n\n", member.var_type, member.name).as_str());
-            }
+        let types = proj
+            .classes
+            .iter()
+            .map(|class| (&class.package_name, &class.name))
+            .chain(proj.interfaces.iter().map(|inter| (&inter.package_name, &inter.name)))
+            .chain(
+                proj.enumerations
+                    .iter()
+                    .map(|enumeration| (&enumeration.package_name, &enumeration.name)),
+            ).chain(
+                proj.annotations
+                    .iter()
+                    .map(|annotation| (&annotation.package_name, &annotation.name)),
+            );
+        let mut packages: Vec<&String> = Vec::new();
 
-            if member.desc != "" {
-                doc.push_str(format!("+ Description: {}  \n", member.desc).as_str());
+        for (package, name) in types {
+            paths.push((
+                type_path(package, name, output.flat, output.format.extension()),
+                qualified_name(package, name),
+            ));
+
+            if !packages.contains(&package) {
+                packages.push(package);
             }
+        }
 
-            if member.access == "" {
-                doc.push_str("+ Access: package-private  \n");
-            } else {
-                doc.push_str(format!("+ Access: {}  \n", member.access).as_str());
+        if let Some(r) = output.format.renderer() {
+            paths.push((format!("index.{}", r.extension()), String::new()));
+            paths.push((api_index_path(r.extension()), String::new()));
+        }
+        if output.mdbook {
+            packages.sort();
+            for package in packages.iter() {
+                paths.push((package_page_path(package), String::new()));
+            }
+            paths.push(("SUMMARY.md".to_string(), String::new()));
+        }
+        if output.docusaurus {
+            paths.push(("sidebar.json".to_string(), String::new()));
+        }
+        if output.diagrams {
+            packages.sort();
+            for package in packages.iter() {
+                paths.push((diagram_path(package), String::new()));
             }
+        }
 
-            if member.modifiers.len() > 0 {
-                doc.push_str("+ Modifiers: ");
+        paths
+    }
 
-                for mem in member.modifiers {
-                    doc.push_str(format!("{} ", mem).as_str())
-                }
+    /// The labels of the headings which start the pages of types
+    const TYPE_PAGE_KINDS: &[&str] = &["class", "record", "interface", "enum", "annotation"];
 
-                doc.push_str("\n");
+    /// Finds out whether the contents of a file are a page or json file of a type written
+    /// by lojidoc. Pages start with the heading of the type, e.g. `# Class Foo` in the
+    /// labels of the run or in English, and json files with the kind of the type.
+    pub fn is_type_page(contents: &str, format: Format) -> bool {
+        let r = match format.renderer() {
+            Some(r) => r,
+            None => {
+                let kind = serde_json::from_str::<serde_json::Value>(contents)
+                    .ok()
+                    .filter(|value| value.get("package_name").is_some())
+                    .and_then(|value| value.get("kind").and_then(|kind| kind.as_str().map(str::to_string)));
+
+                return kind.is_some_and(|kind| {
+                    ["class", "interface", "enumeration", "annotation"].contains(&kind.as_str())
+                });
             }
+        };
+
+        starts_with_title(contents, r, TYPE_PAGE_KINDS)
+    }
+
+    /// Finds out whether a page starts with a title of one of the labels of `keys`, in the
+    /// labels of the run or in English
+    fn starts_with_title(contents: &str, r: &dyn Renderer, keys: &[&str]) -> bool {
+        // The front matter and the head of html pages come before the heading
+        let heading = r.heading(1, "\u{0}", "", "");
+        let marker = heading.split('\u{0}').next().unwrap_or_default();
+        // The title of a reStructuredText page is the line after its overline
+        let marker = marker.rsplit('\n').next().unwrap_or_default();
 
-            doc.push_str("\n");
+        let prefixes: Vec<String> = keys
+            .iter()
+            .flat_map(|key| vec![label_of(key, ""), english_label(key).replacen("{}", "", 1)])
+            .filter(|prefix| !prefix.is_empty())
+            .collect();
+
+        contents
+            .lines()
+            .find(|line| line.starts_with(marker) && !line.chars().all(|ch| ch == '='))
+            .is_some_and(|line| prefixes.iter().any(|prefix| line[marker.len()..].starts_with(prefix.as_str())))
+    }
+
+    /// The labels of the titles of the pages which lojidoc writes beside the pages of types
+    const GENERATED_PAGE_TITLES: &[&str] = &[
+        "index",
+        "api_index",
+        "api",
+        "module",
+        "package_title",
+        "default_package",
+        "class_diagram",
+        "summary",
+    ];
+
+    /// Finds out whether an existing file was written by an earlier run, so it is not in
+    /// conflict with the file a run writes to its path. Pages start with the title of a
+    /// type or of the other pages, such as the index, and json files are the types, the
+    /// `api.json` or the `sidebar.json` lojidoc writes.
+    pub fn is_generated_file(path: &Path, contents: &str, format: Format) -> bool {
+        if path.extension().is_some_and(|ext| ext == "json") {
+            return match serde_json::from_str::<serde_json::Value>(contents) {
+                Ok(serde_json::Value::Array(_)) => path.file_name().is_some_and(|name| name == "sidebar.json"),
+                Ok(value) => value.get("classes").is_some() || is_type_page(contents, Format::Json),
+                Err(_) => false,
+            };
         }
 
-        doc
+        // The summary of an mdBook and the class diagrams are always markdown
+        let r = format.renderer().unwrap_or(&Markdown);
+        is_type_page(contents, format) || starts_with_title(contents, r, GENERATED_PAGE_TITLES)
     }
 
-    /// Generates the markdown documentation for the methods of a class
+    /// Finds the pages of types in a destination which a run did not write, such as the
+    /// pages of renamed and deleted types. Only the files with the extension of the format
+    /// which look like the page of a type are found, so hand-written files are kept.
     ///
     /// # Arguments
     ///
-    /// * `methods` - The vector of class methods to be documented
-    pub fn gen_method_docs(methods: Vec<Method>, path: String) -> String {
-        let mut doc = String::new();
+    /// * `dest` - The destination directory
+    /// * `written` - The files written by the run, relative to the destination
+    /// * `format` - The format of the run
+    pub fn stale_pages(dest: &str, written: &HashSet<String>, format: Format) -> Vec<PathBuf> {
+        let written: HashSet<PathBuf> = written.iter().map(|path| Path::new(dest).join(path)).collect();
+        let mut dirs = vec![PathBuf::from(dest)];
+        let mut stale = Vec::new();
 
-        if methods.len() > 0 {
-            doc.push_str("## Methods\n\n");
-        } else {
-            doc.push_str("## No methods in this class\n\n");
+        while let Some(dir) = dirs.pop() {
+            let entries = match fs::read_dir(&dir) {
+                Ok(entries) => entries,
+                Err(_) => continue,
+            };
 
-            return doc;
-        }
+            for entry in entries.filter_map(|entry| entry.ok()) {
+                let path = entry.path();
+                // Symlinks are not followed out of the destination
+                let file_type = match entry.file_type() {
+                    Ok(file_type) => file_type,
+                    Err(_) => continue,
+                };
 
-        for member in methods {
-            if member.name != String::from("") {
-                if path != "" {
-                    let mut file_path = path.clone();
-                    file_path.push_str(format!("#L{}", member.line_num).as_str());
-                    doc.push_str(
-                        format!("### {} [[src]]({})\n\n", member.name, file_path).as_str(),
-                    );
-                } else {
-                    doc.push_str(format!("### {}\n\n", member.name).as_str());
+                if file_type.is_dir() {
+                    dirs.push(path);
+                } else if file_type.is_file()
+                    && path.extension().is_some_and(|ext| ext == format.extension())
+                    && !written.contains(&path)
+                    && fs::read_to_string(&path).is_ok_and(|contents| is_type_page(&contents, format))
+                {
+                    stale.push(path);
                 }
+            }
+        }
+        stale.sort();
 
-                doc.push_str(format!("+ Description: {}  \n", member.description).as_str());
+        stale
+    }
 
-                if member.privacy == "" {
-                    doc.push_str("+ Access: package-private  \n");
-                } else {
-                    doc.push_str(format!("+ Access: {}  \n", member.privacy).as_str());
-                }
+    /// Writes every type in a project to `api.json` in the destination directory, unless
+    /// it is one of the `kept` files
+    pub fn generate_api_json(proj: &mut Project, dest: &str, kept: &BTreeSet<PathBuf>) -> Result<(), LojidocError> {
+        write_page(dest, kept, false, "api.json", &gen_api_json(proj))
+    }
 
-                if member.modifiers.len() > 0 {
-                    doc.push_str("+ Modifiers: ");
+    /// Whether a javadoc text documents anything, shared by the lint mode
+    pub fn is_documented(text: &str) -> bool {
+        !text.trim().is_empty()
+    }
 
-                    for mem in member.modifiers {
-                        doc.push_str(format!("{} ", mem).as_str())
-                    }
+    /// Whether a member is part of the public API. The members of an interface are
+    /// public without a modifier.
+    pub fn is_public(privacy: &str, interface: bool) -> bool {
+        privacy == "public" || (interface && privacy.is_empty())
+    }
 
-                    doc.push_str("\n");
-                }
+    /// Removes the types and members of a project which are less visible than `min`.
+    /// Types which are left without any member are removed too, so they are not listed
+    /// in the index either.
+    pub fn filter_visibility(proj: &mut Project, min: Visibility) {
+        proj.classes.retain_mut(|class| filter_class(class, false, min));
+        proj.interfaces.retain_mut(|inter| filter_interface(inter, false, min));
+        proj.enumerations
+            .retain_mut(|enumeration| filter_enumeration(enumeration, false, min));
+        proj.annotations
+            .retain_mut(|annotation| filter_annotation(annotation, false, min));
+    }
 
-                for exception in member.exceptions {
-                    doc.push_str(
-                        format!(
-                            "+ Throws {}: {}  \n",
-                            exception.exception_type, exception.desc
-                        ).as_str(),
-                    );
-                }
-                doc.push_str(format!("+ return: {}  \n\n", member.return_type).as_str());
+    /// Whether a type or member is at least as visible as `min`
+    fn is_visible(access: &str, interface: bool, min: Visibility) -> bool {
+        Visibility::of(access, interface) <= min
+    }
 
-                if member.parameters.len() > 0 {
-                    doc.push_str("| Name | Type | Description |  \n");
-                    doc.push_str("| ----- | ----- | ----- |  \n");
-                } else {
-                    doc.push_str("This method has no parameters.  \n");
-                }
+    fn filter_methods(methods: &mut Vec<Method>, interface: bool, min: Visibility) {
+        methods.retain(|method| is_visible(&method.privacy, interface, min));
+    }
 
-                for param in member.parameters {
-                    doc.push_str(
-                        format!(
-                            "| {} | {} | {} |  \n",
-                            param.name, param.var_type, param.desc
-                        ).as_str(),
-                    );
-                }
+    fn filter_variables(variables: &mut Vec<Member>, interface: bool, min: Visibility) {
+        variables.retain(|var| is_visible(&var.access, interface, min));
+    }
 
-                doc.push_str("\n\n");
-            }
+    /// Filters the types nested in a type, `interface` is whether the outer type is an
+    /// interface or annotation which makes them public without a modifier
+    fn filter_inner_classes(inner_classes: &mut Vec<ObjectType>, interface: bool, min: Visibility) {
+        inner_classes.retain_mut(|inner| match inner {
+            ObjectType::Class(class) => filter_class(class, interface, min),
+            ObjectType::Interface(inter) => filter_interface(inter, interface, min),
+            ObjectType::Enumeration(enumeration) => filter_enumeration(enumeration, interface, min),
+            ObjectType::Annotation(annotation) => filter_annotation(annotation, interface, min),
+        });
+    }
+
+    /// Filters the members of a class and returns whether the class is kept
+    fn filter_class(class: &mut Class, in_interface: bool, min: Visibility) -> bool {
+        if !is_visible(&class.access, in_interface, min) {
+            return false;
         }
 
-        doc
+        let before = class.constructors.len()
+            + class.methods.len()
+            + class.variables.len()
+            + class.inner_classes.len();
+        filter_methods(&mut class.constructors, false, min);
+        filter_methods(&mut class.methods, false, min);
+        filter_variables(&mut class.variables, false, min);
+        filter_inner_classes(&mut class.inner_classes, false, min);
+        let after = class.constructors.len()
+            + class.methods.len()
+            + class.variables.len()
+            + class.inner_classes.len();
+
+        before == 0 || after > 0
     }
 
-    /// Generates a markdown file for a java file
-    /// Uses a Class struct to write the markdown
-    ///
-    /// # Arguments
-    ///
-    /// * `class` - The class struct containing the java documentation data
-    /// * `dest` - The file path where the markdown file will be saved
-    /// * `context` - The project context e.g. `github.com/user/repo`
-    pub fn generate_markdown(proj: Project, dest: &str, book: bool) {
-        let mut app_doc = ApplicationDoc::new();
+    fn filter_interface(inter: &mut Interface, in_interface: bool, min: Visibility) -> bool {
+        if !is_visible(&inter.access, in_interface, min) {
+            return false;
+        }
 
-        for mut class in proj.classes {
-            let name = format!("{}/{}.{}", dest, class.name, "md");
-            let mut file = File::create(name).unwrap();
+        let before = inter.methods.len() + inter.variables.len() + inter.inner_classes.len();
+        filter_methods(&mut inter.methods, true, min);
+        filter_variables(&mut inter.variables, true, min);
+        filter_inner_classes(&mut inter.inner_classes, true, min);
+        let after = inter.methods.len() + inter.variables.len() + inter.inner_classes.len();
 
-            let mut doc = gen_class_docs(class.clone());
-            doc.push_str(gen_var_docs(class.variables, class.file_path.clone()).as_str());
-            doc.push_str(gen_method_docs(class.methods, class.file_path).as_str());
-            file.write(doc.as_str().as_bytes())
-                .expect("Not able to write to file");
+        before == 0 || after > 0
+    }
 
-            if book {
-                let name = format!("./markdown-book/src/{}.{}", class.name, "md");
-                let mut file = File::create(name).unwrap();
+    /// Filters the members of an enum. The constants are always public, so an enum
+    /// with constants is always kept.
+    fn filter_enumeration(enumeration: &mut Enumeration, in_interface: bool, min: Visibility) -> bool {
+        if !is_visible(&enumeration.access, in_interface, min) {
+            return false;
+        }
 
-                file.write(doc.as_str().as_bytes())
-                    .expect("Not able to write to file");
-            }
+        let before = enumeration.fields.len()
+            + enumeration.constructors.len()
+            + enumeration.methods.len()
+            + enumeration.variables.len()
+            + enumeration.inner_classes.len();
+        filter_methods(&mut enumeration.constructors, false, min);
+        filter_methods(&mut enumeration.methods, false, min);
+        filter_variables(&mut enumeration.variables, false, min);
+        filter_inner_classes(&mut enumeration.inner_classes, false, min);
+        let after = enumeration.fields.len()
+            + enumeration.constructors.len()
+            + enumeration.methods.len()
+            + enumeration.variables.len()
+            + enumeration.inner_classes.len();
 
-            app_doc.add_package_class(class.package_name, class.name.clone());
+        before == 0 || after > 0
+    }
 
-            println!("{}.{} was created", class.name, "md");
+    /// Filters the constants and nested types of an annotation. Its elements are public.
+    fn filter_annotation(annotation: &mut Annotation, in_interface: bool, min: Visibility) -> bool {
+        if !is_visible(&annotation.access, in_interface, min) {
+            return false;
         }
 
-        for mut inter in proj.interfaces {
-            let name = format!("{}/{}.{}", dest, inter.name, "md");
-            let mut file = File::create(name).unwrap();
+        let before = annotation.elements.len()
+            + annotation.variables.len()
+            + annotation.inner_classes.len();
+        filter_variables(&mut annotation.variables, true, min);
+        filter_inner_classes(&mut annotation.inner_classes, true, min);
+        let after = annotation.elements.len()
+            + annotation.variables.len()
+            + annotation.inner_classes.len();
 
-            let mut doc = gen_interface_docs(inter.clone());
-            doc.push_str(gen_var_docs(inter.variables, inter.file_path.clone()).as_str());
-            doc.push_str(gen_method_docs(inter.methods, inter.file_path).as_str());
-            file.write(doc.as_str().as_bytes())
-                .expect("Not able to write to file");
+        before == 0 || after > 0
+    }
 
-            println!("{}.{} was created", inter.name, "md");
-        }
+    /// Formats a lint finding as `path:line: message` so editors can jump to it
+    fn lint_finding(path: &str, line: &str, message: String) -> String {
+        format!("{}:{}: {}", path, line, message)
     }
 
-    pub fn lint_method(method: &Method) -> String {
-        let mut method_errs = String::new();
+    /// Lints the description and parameter descriptions of a public method or constructor
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the java file declaring the method
+    /// * `kind` - Either `method` or `constructor`
+    /// * `method` - The method to lint
+    pub fn lint_method(path: &str, kind: &str, method: &Method) -> Vec<String> {
+        let mut findings = Vec::new();
 
-        if method.description == "" {
-            method_errs.push_str(
-                "\tMissing description for method "
-                    .yellow()
-                    .to_string()
-                    .as_str(),
-            );
-            method_errs.push_str(format!("{} (Line: {})\n", method.name, method.line_num).as_str());
-        }
-        if method.return_type == "" {
-            method_errs.push_str(
-                "\tMissing return type for method "
-                    .yellow()
-                    .to_string()
-                    .as_str(),
-            );
-            method_errs.push_str(format!("{} (Line: {})\n", method.name, method.line_num).as_str());
+        if !is_documented(&method.description) {
+            findings.push(lint_finding(
+                path,
+                &method.line_num,
+                format!("public {} {} has no description", kind, method.name),
+            ));
         }
 
-        for p in method.parameters.clone() {
-            if p.desc == "" {
-                method_errs.push_str(
-                    "\tJavadoc parameter not found "
-                        .yellow()
-                        .to_string()
-                        .as_str(),
-                );
-                method_errs.push_str(
+        for param in &method.parameters {
+            if !is_documented(&param.desc) {
+                findings.push(lint_finding(
+                    path,
+                    &param.line_num,
                     format!(
-                        "{} in method: {} (Line: {})\n",
-                        p.name, method.name, method.line_num
-                    ).as_str(),
-                );
+                        "parameter {} of {} {} has no description",
+                        param.name, kind, method.name
+                    ),
+                ));
             }
         }
+        for name in &method.unmatched_params {
+            findings.push(lint_finding(
+                path,
+                &method.line_num,
+                format!("@param {} does not match a parameter of {} {}", name, kind, method.name),
+            ));
+        }
 
-        method_errs
+        findings
     }
-    pub fn lint_var(var: &Member) -> String {
-        let mut errs = String::new();
 
-        if var.desc == "" {
-            errs.push_str(
-                "\tMissing description for member variable "
-                    .yellow()
-                    .to_string()
-                    .as_str(),
-            );
-            errs.push_str(format!("{} (Line: {})\n", var.name, var.line_num).as_str());
-        }
-        if var.var_type == "" {
-            errs.push_str(
-                "\tMissing return type for member variable "
-                    .yellow()
-                    .to_string()
-                    .as_str(),
-            );
-            errs.push_str(format!("{} (Line: {})\n", var.name, var.line_num).as_str());
+    /// Lints a public type and its public constructors, methods and nested types
+    fn lint_object(object: ObjectType, findings: &mut Vec<String>) {
+        let (kind, access, name, description, path, line, constructors, methods, inner_classes) =
+            match object {
+                ObjectType::Class(class) => (
+                    "class",
+                    class.access,
+                    class.name,
+                    class.description,
+                    class.file_path,
+                    class.line_num,
+                    class.constructors,
+                    class.methods,
+                    class.inner_classes,
+                ),
+                ObjectType::Interface(inter) => (
+                    "interface",
+                    inter.access,
+                    inter.name,
+                    inter.description,
+                    inter.file_path,
+                    inter.line_num,
+                    Vec::new(),
+                    inter.methods,
+                    inter.inner_classes,
+                ),
+                ObjectType::Enumeration(enumeration) => (
+                    "enum",
+                    enumeration.access,
+                    enumeration.name,
+                    enumeration.description,
+                    enumeration.file_path,
+                    enumeration.line_num,
+                    enumeration.constructors,
+                    enumeration.methods,
+                    enumeration.inner_classes,
+                ),
+                ObjectType::Annotation(annotation) => (
+                    "annotation",
+                    annotation.access,
+                    annotation.name,
+                    annotation.description,
+                    annotation.file_path,
+                    annotation.line_num,
+                    Vec::new(),
+                    Vec::new(),
+                    annotation.inner_classes,
+                ),
+            };
+
+        if access != "public" {
+            return;
         }
 
-        errs
+        if !is_documented(&description) {
+            findings.push(lint_finding(
+                &path,
+                &line,
+                format!("public {} {} has no javadoc", kind, name),
+            ));
+        }
+
+        let interface = kind == "interface";
+
+        for constructor in constructors.iter().filter(|c| is_public(&c.privacy, interface)) {
+            findings.extend(lint_method(&path, "constructor", constructor));
+        }
+        for method in methods.iter().filter(|m| is_public(&m.privacy, interface)) {
+            findings.extend(lint_method(&path, "method", method));
+        }
+        for inner in inner_classes {
+            lint_object(inner, findings);
+        }
     }
 
-    /// Lints the java project's javadoc comments and prints the errors
+    /// Lints the public API of a java project for missing javadocs
     ///
     /// # Arguments
     ///
     /// * `proj` - The project to lint
-    pub fn lint_project(proj: Project) -> String {
-        let mut jdoc_errs = String::new();
-
-        for mut class in proj.classes {
-            let mut temp_err = String::new();
-
-            for v in class.variables {
-                temp_err.push_str(lint_var(&v).as_str());
-            }
-            for m in class.methods {
-                temp_err.push_str(lint_method(&m).as_str());
-            }
-
-            if temp_err != "" {
-                jdoc_errs.push_str(
-                    "Javadoc errors for class "
-                        .green()
-                        .bold()
-                        .to_string()
-                        .as_str(),
-                );
-                jdoc_errs.push_str(
-                    format!(
-                        "{}\nFile: {}\n",
-                        class.name,
-                        class.file_path.as_str().blue().to_string()
-                    ).as_str(),
-                );
-                jdoc_errs.push_str(format!("{}\n", temp_err).as_str());
-            }
-        }
-
-        for mut inter in proj.interfaces {
-            let mut temp_err = String::new();
-
-            for v in inter.variables {
-                temp_err.push_str(lint_var(&v).as_str());
-            }
-            for m in inter.methods {
-                temp_err.push_str(lint_method(&m).as_str());
-            }
+    ///
+    /// Returns one `path:line: message` finding for each missing description
+    pub fn lint_project(proj: Project) -> Vec<String> {
+        let mut findings = Vec::new();
+        let objects = proj
+            .classes
+            .into_iter()
+            .map(ObjectType::Class)
+            .chain(proj.interfaces.into_iter().map(ObjectType::Interface))
+            .chain(proj.enumerations.into_iter().map(ObjectType::Enumeration))
+            .chain(proj.annotations.into_iter().map(ObjectType::Annotation));
 
-            if temp_err != "" {
-                jdoc_errs.push_str(
-                    "Javadoc errors for interface "
-                        .green()
-                        .bold()
-                        .to_string()
-                        .as_str(),
-                );
-                jdoc_errs.push_str(
-                    format!(
-                        "{}\nFile: {}\n",
-                        inter.name,
-                        inter.file_path.as_str().blue().to_string()
-                    ).as_str(),
-                );
-                jdoc_errs.push_str(format!("{}\n", temp_err).as_str());
-            }
+        for object in objects {
+            lint_object(object, &mut findings);
         }
 
-        jdoc_errs
+        findings
     }
 
     /// Determines whether a file path contains a git or mercurial file
@@ -575,17 +4228,14 @@ pub mod document {
                 line_p.push_str(format!("{}/", line_vec[j]).as_str());
             }
 
-            let file_dir = fs::read_dir(line_p);
-
-            if file_dir.is_ok() {
-                for f in file_dir.unwrap() {
-                    let p = f.unwrap().path();
+            if let Ok(file_dir) = fs::read_dir(line_p) {
+                // Entries which can not be read are not the repository directory
+                for f in file_dir.flatten() {
+                    let p = f.path();
 
                     if p.is_dir() {
-                        let p_str = p.as_path().to_str().unwrap();
-                        if is_repo_dir(&p_str) {
-                            let res_str = p.parent().unwrap().as_os_str().to_str().unwrap();
-                            res = res_str.to_string().clone();
+                        if is_repo_dir(&p.to_string_lossy()) {
+                            res = p.parent().map_or_else(String::new, |parent| parent.to_string_lossy().to_string());
                             break;
                         }
                     }
@@ -596,21 +4246,105 @@ pub mod document {
         res
     }
 
+    /// The placeholder of a `--source-url` template for the path of a java file
+    pub const PATH_PLACEHOLDER: &str = "{path}";
+
+    /// The placeholder of a `--source-url` template for the line of a declaration
+    pub const LINE_PLACEHOLDER: &str = "{line}";
+
+    /// Where the source links of the pages point to
+    #[derive(Clone, Debug, Default)]
+    pub struct SourceLinks {
+        /// The url of the repository, which the path of a file in the repository is
+        /// appended to
+        pub context: String,
+        /// A `--source-url` template such as
+        /// `https://github.com/org/repo/blob/main/{path}#L{line}`, which is used instead
+        /// of the context when it is set
+        pub template: String,
+        /// The input directories and java files the paths of the template are relative to
+        pub inputs: Vec<PathBuf>,
+    }
+
+    impl SourceLinks {
+        /// Whether the pages link to the source files
+        pub fn is_empty(&self) -> bool {
+            self.context.is_empty() && self.template.is_empty()
+        }
+        /// Gets the link to a java file, which is empty when the pages do not link to the
+        /// source files. The `{line}` of a template is kept so the link to each declaration
+        /// can fill it in.
+        pub fn file_url(&self, file: &Path) -> String {
+            if self.is_empty() {
+                return String::new();
+            }
+            if self.template.is_empty() {
+                return resolve_context(file, &self.context);
+            }
+
+            self.template.replace(PATH_PLACEHOLDER, &input_relative_path(file, &self.inputs))
+        }
+    }
+
+    impl fmt::Display for SourceLinks {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            if self.template.is_empty() {
+                write!(f, "{}", self.context)
+            } else {
+                write!(f, "{}", self.template)
+            }
+        }
+    }
+
+    /// Gets the path of a java file relative to the input it was found in, with `/`
+    /// separators. The path of a java file given as an input is its file name.
+    pub fn input_relative_path(file: &Path, inputs: &[PathBuf]) -> String {
+        let relative = inputs
+            .iter()
+            .filter(|input| input.as_path() != file)
+            .find_map(|input| file.strip_prefix(input).ok())
+            .or_else(|| file.file_name().map(Path::new))
+            .unwrap_or(file);
+
+        relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// Gets the link to the line of a member in its source file. The `{line}` of a
+    /// `--source-url` template is filled in, other links get a `#L` anchor.
+    pub fn source_link(file_url: &str, line: &str) -> String {
+        if file_url.contains(LINE_PLACEHOLDER) {
+            file_url.replace(LINE_PLACEHOLDER, line)
+        } else {
+            format!("{}#L{}", file_url, line)
+        }
+    }
+
+    /// Gets the link to the source file of a type, which points to the line of its
+    /// declaration when a `--source-url` template has a `{line}`
+    pub fn type_source_link(file_url: &str, line: &str) -> String {
+        file_url.replace(LINE_PLACEHOLDER, line)
+    }
+
     /// Combines the repo url with java file path to provide a link in the docs
     ///
     /// # Arguments
     ///
     /// * `paths` - The java file path
     /// * `context` - Url of the git or mercurial repository
-    pub fn resolve_context(path: &PathBuf, context: &String) -> String {
-        let p = path.to_str().unwrap();
+    pub fn resolve_context(path: &Path, context: &str) -> String {
+        // A path which is not valid UTF-8 is linked with its invalid parts replaced
+        let p = path.to_string_lossy();
         let line_vec: Vec<&str> = p.split("/").collect::<Vec<&str>>();
         let mut part = line_vec[0].to_string();
         part.push_str("/");
 
         let repo_root = find_repo_home(p.to_string());
         let line_vec: Vec<&str> = p.split(repo_root.as_str()).collect::<Vec<&str>>();
-        let mut new_context = context.clone();
+        let mut new_context = context.to_string();
         new_context.push_str(line_vec.join("").as_str());
 
         new_context
@@ -622,29 +4356,47 @@ pub mod document {
     /// # Arguments
     ///
     /// * `gen_dir` - The directory containing the generated documentation
-    pub fn gen_md_book(gen_dir: String) {
-        let name = "./markdown-book/src/SUMMARY.md";
-        let res_file = File::create(name);
-        let files = find_gen_files(&PathBuf::from(gen_dir.as_str()));
+    /// * `app_doc` - The documented types of every file in the run, used to find the
+    ///   deprecated types
+    pub fn gen_md_book(gen_dir: &str, app_doc: &ApplicationDoc) -> Result<(), LojidocError> {
+        let name = Path::new("./markdown-book/src/SUMMARY.md");
+        let mut files = find_gen_files(&PathBuf::from(gen_dir));
+        files.retain(|f| f != "index");
+        let deprecated: Vec<&str> = app_doc
+            .packages
+            .iter()
+            .flat_map(|package| package.members.iter())
+            .filter(|member| member.deprecated)
+            .map(|member| member.path.as_str())
+            .collect();
         let mut doc = String::new();
 
-        if res_file.is_ok() {
-            let mut file = res_file.unwrap();
+        for f in files {
+            let file_path = format!("./{}.md", f.clone());
+            let type_name = f.rsplit('/').next().unwrap_or(&f);
 
-            for f in files {
-                let file_path = format!("./{}.md", f.clone());
-                doc.push_str(format!("- [{}]({})  \n", f, file_path).as_str());
+            // Deprecated types are struck through in the index
+            if deprecated.contains(&format!("{}.md", f).as_str()) {
+                doc.push_str(format!("- [~~{}~~]({})  \n", type_name, file_path).as_str());
+            } else {
+                doc.push_str(format!("- [{}]({})  \n", type_name, file_path).as_str());
             }
+        }
 
-            file.write(doc.as_str().as_bytes())
-                .expect("Not able to write to file");
+        fs::write(name, doc).map_err(|err| LojidocError::io(name, err))?;
 
-            let md = MDBook::load("./markdown-book").expect("Unable to load the book");
-            md.build().expect("Building failed");
+        // The errors of mdbook are not io errors, they are reported for the book directory
+        let book_error = |err: mdbook::errors::Error| {
+            let err = io::Error::other(err.to_string());
+            LojidocError::io(Path::new("./markdown-book"), err)
+        };
+        let md = MDBook::load("./markdown-book").map_err(book_error)?;
+        md.build().map_err(book_error)?;
 
-            println!("Generated the markdown book");
-        } else {
-            println!("Error creating file: {:?}", res_file);
-        }
+        verbose!("Generated the markdown book");
+        Ok(())
     }
 }
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,494 @@
+//! The output formats of the generated pages. The generator in `document` walks
+//! the parsed types and builds every section through a `Renderer`, so the
+//! formats share the page structure and only differ in markup.
+
+use grammar::grammar::get_html_tags;
+use strings::strings::label;
+
+/// Builds the markup of the sections of a page
+pub trait Renderer {
+    /// The extension of the generated pages without the dot, e.g. `md`
+    fn extension(&self) -> &'static str;
+
+    /// A heading of `level`, starting at 1 for the title of a page.
+    /// `anchor` is the id used to link to the heading and `src` the source link, both may be empty.
+    fn heading(&self, level: usize, text: &str, anchor: &str, src: &str) -> String;
+
+    /// Whether headings are only linked by the anchor they are given. Markdown headings
+    /// are also linked by the slug GitHub generates from their text.
+    fn heading_ids(&self) -> bool;
+
+    /// A bold notice such as the deprecation message of a type
+    fn notice(&self, label: &str, text: &str) -> String;
+
+    /// The deprecation notice of a type or member
+    fn deprecation(&self, text: &str) -> String {
+        self.notice(&label("deprecated"), text)
+    }
+
+    /// A labeled line such as `Access: public`
+    fn field(&self, label: &str, value: &str) -> String;
+
+    /// The description of a type, which may have several paragraphs
+    fn description(&self, text: &str) -> String;
+
+    /// A line of plain text
+    fn text(&self, text: &str) -> String;
+
+    /// A bullet list
+    fn list(&self, items: &[String]) -> String;
+
+    /// Lays out the tables of a page again once the inline tags in their cells are
+    /// converted, which changes the width of the cells
+    fn align_tables(&self, doc: &str) -> String {
+        doc.to_string()
+    }
+
+    /// A table with a header row. The cells are already rendered with
+    /// `escape`, `inline`, `code` or `link`.
+    fn table(&self, headers: &[&str], rows: &[Vec<String>]) -> String;
+
+    /// A collapsed list which is shown when `summary` is clicked
+    fn details(&self, summary: &str, items: &[String]) -> String;
+
+    /// A collapsed list of labeled groups which is shown when `summary` is clicked.
+    /// The items are already rendered with `escape`, `code` or `link`.
+    fn grouped_details(&self, summary: &str, groups: &[(&str, Vec<String>)]) -> String;
+
+    /// Escapes a name or type so it is shown as it is
+    fn escape(&self, text: &str) -> String;
+
+    /// Renders description text. The parser writes its html as markdown and keeps its
+    /// characters as they are, so they are escaped here for the format.
+    fn inline(&self, text: &str) -> String;
+
+    /// A code span
+    fn code(&self, text: &str) -> String;
+
+    /// The name of a type in a signature which has no page to link to
+    fn type_name(&self, name: &str) -> String {
+        self.escape(name)
+    }
+
+    /// A block of java code such as the signature of a method
+    fn code_block(&self, code: &str) -> String;
+
+    /// A link to `target`
+    fn link(&self, text: &str, target: &str) -> String;
+
+    /// The name of a deprecated type in a listing. It is shown as it is in formats
+    /// without strikethrough.
+    fn struck(&self, text: &str) -> String {
+        text.to_string()
+    }
+
+    /// Moves the headings of a page down two levels so it can be placed in another page
+    fn nest(&self, page: &str) -> String;
+
+    /// Wraps the body of a page into a complete document
+    fn page(&self, title: &str, body: &str) -> String;
+
+    /// A horizontal rule which separates pages printed one after another
+    fn rule(&self) -> String;
+}
+
+/// Continues each line after the first line of a text with `prefix`
+/// so multi line text stays inside a quote or list item
+fn continue_lines(text: &str, prefix: &str) -> String {
+    text.lines()
+        .collect::<Vec<&str>>()
+        .join(format!("\n{}", prefix).as_str())
+}
+
+/// Escapes the description text of the parser for markdown. The parser writes the
+/// markdown of the html of a javadoc, such as `**bold**`, code spans and fenced code
+/// blocks, which is kept like the inline tags, and escapes the emphasis, code span and
+/// link characters of its text. A `#`, `>` or `* ` starting a line would start a
+/// heading, quote or list, an `_` at the edge of a word emphasis, and a `<` which does
+/// not start an html tag or a link, such as in `List<User>`, an html tag. Escaped
+/// characters are left as they are, so text is only escaped once.
+pub fn escape_doc_text(text: &str) -> String {
+    let mut doc = String::new();
+    let mut fenced = false;
+
+    for (i, line) in text.split('\n').enumerate() {
+        if i > 0 {
+            doc.push('\n');
+        }
+
+        if line.trim_start().starts_with("```") {
+            fenced = !fenced;
+            doc.push_str(line);
+        } else if fenced {
+            doc.push_str(line);
+        } else {
+            escape_doc_line(&mut doc, line);
+        }
+    }
+
+    doc
+}
+
+/// Escapes a line of description text which is not in a fenced code block
+fn escape_doc_line(doc: &mut String, line: &str) {
+    let text = line.trim_start();
+    doc.push_str(&line[..line.len() - text.len()]);
+
+    if text.starts_with('#') || text.starts_with('>') || text.starts_with("* ") {
+        doc.push('\\');
+    }
+
+    let mut prev: Option<char> = None;
+    let mut rest = text;
+
+    while let Some(ch) = rest.chars().next() {
+        let len = match ch {
+            '\\' => rest[1..].chars().next().map_or(1, |next| 1 + next.len_utf8()),
+            '`' => {
+                let ticks = rest.len() - rest.trim_start_matches('`').len();
+                let fence = &rest[..ticks];
+
+                rest[ticks..].find(fence).map_or(ticks, |end| end + 2 * ticks)
+            }
+            '{' if rest.starts_with("{@") => inline_tag_len(rest).unwrap_or(1),
+            '<' => {
+                if !starts_html(&rest[1..]) {
+                    doc.push('\\');
+                }
+                1
+            }
+            '_' => {
+                let next = rest[1..].chars().next();
+                let in_word = prev.is_some_and(char::is_alphanumeric) && next.is_some_and(char::is_alphanumeric);
+
+                if !in_word {
+                    doc.push('\\');
+                }
+                1
+            }
+            _ => ch.len_utf8(),
+        };
+
+        doc.push_str(&rest[..len]);
+        prev = rest[..len].chars().last();
+        rest = &rest[len..];
+    }
+}
+
+/// Gets the length of the inline tag such as `{@code x}` at the start of `text`,
+/// `None` when its braces are not balanced
+fn inline_tag_len(text: &str) -> Option<usize> {
+    let mut depth = 0;
+
+    for (i, ch) in text.char_indices() {
+        match ch {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+
+                if depth == 0 {
+                    return Some(i + 1);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Whether the text after a `<` is an html tag, an html comment or a link such as
+/// `<https://example.com>`
+fn starts_html(text: &str) -> bool {
+    let name: String = text
+        .trim_start_matches('/')
+        .chars()
+        .take_while(|ch| ch.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_lowercase();
+    let after = &text.trim_start_matches('/')[name.len()..];
+    let tag_end = after.starts_with(|ch: char| ch.is_whitespace() || ch == '>' || ch == '/');
+
+    (get_html_tags().contains(&name.as_str()) && tag_end)
+        || text.starts_with("!--")
+        || text.starts_with("http://")
+        || text.starts_with("https://")
+        || text.starts_with("mailto:")
+}
+
+/// Puts the text of a table cell on a single line so it does not end the table.
+/// Wrapped lines are joined and paragraphs are separated by line breaks.
+/// Pipes which are not escaped yet are escaped so they do not start a new cell.
+fn markdown_cell(cell: &str) -> String {
+    let mut paragraphs: Vec<Vec<&str>> = vec![Vec::new()];
+
+    for line in cell.lines().map(|line| line.trim()) {
+        if !line.is_empty() {
+            paragraphs.last_mut().unwrap().push(line);
+        } else if !paragraphs.last().unwrap().is_empty() {
+            paragraphs.push(Vec::new());
+        }
+    }
+
+    let text = paragraphs
+        .iter()
+        .filter(|lines| !lines.is_empty())
+        .map(|lines| lines.join(" "))
+        .collect::<Vec<String>>()
+        .join("<br><br>");
+
+    let mut doc = String::new();
+    let mut escaped = false;
+
+    for ch in text.chars() {
+        if ch == '|' && !escaped {
+            doc.push('\\');
+        }
+
+        escaped = ch == '\\' && !escaped;
+        doc.push(ch);
+    }
+
+    doc
+}
+
+/// Gets the width of a table cell. The `{@type}` tags of signatures are measured by
+/// the name in them, which is what is left of them when the type is not documented.
+fn cell_width(cell: &str) -> usize {
+    cell.chars().count() - cell.matches("{@type ").count() * "{@type }".len()
+}
+
+/// Writes the rows of a markdown table with the columns padded to the widest cell,
+/// so the raw markdown is readable
+fn pad_table(headers: &[String], rows: &[Vec<String>]) -> String {
+    let mut widths: Vec<usize> = headers.iter().map(|header| cell_width(header).max(3)).collect();
+    for row in rows.iter() {
+        for (i, cell) in row.iter().enumerate().take(widths.len()) {
+            widths[i] = widths[i].max(cell_width(cell));
+        }
+    }
+
+    let line = |cells: &[String]| {
+        let cells: Vec<String> = widths
+            .iter()
+            .enumerate()
+            .map(|(i, width)| {
+                let cell = cells.get(i).map_or("", |cell| cell.as_str());
+                format!("{}{}", cell, " ".repeat(width - cell_width(cell)))
+            }).collect();
+
+        format!("| {} |\n", cells.join(" | "))
+    };
+
+    let mut doc = line(headers);
+    let separator: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+    doc.push_str(line(&separator).as_str());
+
+    for row in rows.iter() {
+        doc.push_str(line(row).as_str());
+    }
+
+    doc
+}
+
+/// Splits a row of a markdown table into its cells without their padding. Escaped
+/// pipes are part of a cell.
+fn table_cells(line: &str) -> Vec<String> {
+    line[2..line.len() - 2]
+        .split(" | ")
+        .map(|cell| cell.trim_end().to_string())
+        .collect()
+}
+
+/// Renders pages as markdown
+pub struct Markdown;
+
+impl Renderer for Markdown {
+    fn extension(&self) -> &'static str {
+        "md"
+    }
+
+    fn heading(&self, level: usize, text: &str, anchor: &str, src: &str) -> String {
+        let mut doc = String::new();
+
+        // GitHub and mdBook keep the ids of html anchors
+        if !anchor.is_empty() {
+            doc.push_str(format!("<a id=\"{}\"></a>\n\n", anchor).as_str());
+        }
+        if src.is_empty() {
+            doc.push_str(format!("{} {}\n\n", "#".repeat(level), text).as_str());
+        } else {
+            doc.push_str(format!("{} {} [[src]]({})\n\n", "#".repeat(level), text, src).as_str());
+        }
+
+        doc
+    }
+
+    fn heading_ids(&self) -> bool {
+        false
+    }
+
+    fn notice(&self, label: &str, text: &str) -> String {
+        format!("**{}:** {}  \n\n", label, self.inline(text))
+    }
+
+    fn field(&self, label: &str, value: &str) -> String {
+        format!("{}: {}  \n", label, self.inline(value))
+    }
+
+    fn description(&self, text: &str) -> String {
+        format!("{}:  \n > {}  \n\n", label("description"), continue_lines(&self.inline(text), " > "))
+    }
+
+    fn text(&self, text: &str) -> String {
+        format!("{}  \n", self.inline(text))
+    }
+
+    fn list(&self, items: &[String]) -> String {
+        items
+            .iter()
+            .map(|item| format!("+ {}  \n", continue_lines(&self.inline(item), "  ")))
+            .collect()
+    }
+
+    fn table(&self, headers: &[&str], rows: &[Vec<String>]) -> String {
+        let headers: Vec<String> = headers.iter().map(|header| markdown_cell(header)).collect();
+        let rows: Vec<Vec<String>> = rows
+            .iter()
+            .map(|row| row.iter().map(|cell| markdown_cell(cell)).collect())
+            .collect();
+
+        pad_table(&headers, &rows)
+    }
+
+    fn align_tables(&self, doc: &str) -> String {
+        let lines: Vec<&str> = doc.split('\n').collect();
+        let is_row = |line: &str| line.len() >= 4 && line.starts_with("| ") && line.ends_with(" |");
+        let mut aligned: Vec<String> = Vec::new();
+        let mut in_code = false;
+        let mut i = 0;
+
+        while i < lines.len() {
+            if lines[i].starts_with("```") {
+                in_code = !in_code;
+            }
+
+            let end = (i..lines.len()).find(|&j| !is_row(lines[j])).unwrap_or(lines.len());
+            let is_table = !in_code
+                && end > i + 1
+                && table_cells(lines[i + 1]).iter().all(|cell| !cell.is_empty() && cell.chars().all(|ch| ch == '-'));
+
+            if !is_table {
+                aligned.push(lines[i].to_string());
+                i += 1;
+                continue;
+            }
+
+            let headers = table_cells(lines[i]);
+            let rows: Vec<Vec<String>> = lines[i + 2..end].iter().map(|line| table_cells(line)).collect();
+            let table = pad_table(&headers, &rows);
+
+            aligned.extend(table.trim_end_matches('\n').split('\n').map(|line| line.to_string()));
+            i = end;
+        }
+
+        aligned.join("\n")
+    }
+
+    fn details(&self, summary: &str, items: &[String]) -> String {
+        let mut doc = String::from("<details>  \n");
+        doc.push_str("  <summary>  \n");
+        doc.push_str(format!("    {}  \n", summary).as_str());
+        doc.push_str("  </summary>  \n");
+
+        doc.push_str("  <ul>  \n");
+        for item in items {
+            doc.push_str(format!("    <li>{}</li>\n", item).as_str());
+        }
+        doc.push_str("  </ul>  \n");
+        doc.push_str("</details>  \n\n");
+
+        doc
+    }
+
+    fn grouped_details(&self, summary: &str, groups: &[(&str, Vec<String>)]) -> String {
+        let mut doc = String::from("<details>  \n");
+        doc.push_str("  <summary>  \n");
+        doc.push_str(format!("    {}  \n", summary).as_str());
+        doc.push_str("  </summary>  \n\n");
+
+        // The list is written in markdown after a blank line so the links of its items
+        // are rendered inside the html block
+        for (label, items) in groups {
+            doc.push_str(format!("- {}\n", label).as_str());
+            for item in items {
+                doc.push_str(format!("  - {}\n", item).as_str());
+            }
+        }
+        doc.push_str("\n</details>  \n\n");
+
+        doc
+    }
+
+    fn escape(&self, text: &str) -> String {
+        // Names such as `max_value`, type arguments such as `List<Foo>` and arrays such
+        // as `int[]` would be read as emphasis, html tags and links
+        let mut doc = String::new();
+
+        for ch in text.chars() {
+            if "\\`*_|<>[]".contains(ch) {
+                doc.push('\\');
+            }
+            doc.push(ch);
+        }
+
+        doc
+    }
+
+    fn inline(&self, text: &str) -> String {
+        escape_doc_text(text)
+    }
+
+    fn code(&self, text: &str) -> String {
+        if text.contains('`') {
+            format!("`` {} ``", text)
+        } else {
+            format!("`{}`", text)
+        }
+    }
+
+    fn code_block(&self, code: &str) -> String {
+        format!("```java\n{}\n```\n\n", code)
+    }
+
+    fn link(&self, text: &str, target: &str) -> String {
+        format!("[{}]({})", text, target)
+    }
+
+    fn struck(&self, text: &str) -> String {
+        format!("~~{}~~", text)
+    }
+
+    fn nest(&self, page: &str) -> String {
+        let mut doc = String::new();
+
+        for line in page.lines() {
+            if line.starts_with('#') {
+                doc.push_str("##");
+            }
+
+            doc.push_str(line);
+            doc.push('\n');
+        }
+
+        doc
+    }
+
+    fn page(&self, _title: &str, body: &str) -> String {
+        body.to_string()
+    }
+
+    fn rule(&self) -> String {
+        "\n---\n\n".to_string()
+    }
+}
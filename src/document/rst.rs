@@ -0,0 +1,374 @@
+//! Renders the generated pages as reStructuredText, e.g. for Sphinx
+
+use document::render::Renderer;
+use strings::strings::label;
+
+/// The characters which underline the section titles of each level from the second,
+/// the title of a page is also overlined with `=`
+const ADORNMENTS: &[char] = &['=', '-', '~', '^', '"', '\'', '+', '`', '#', ':', '.', '_', '*'];
+
+/// Renders pages as reStructuredText
+pub struct Rst;
+
+/// Gets the id which docutils gives an explicit target, e.g. `inner-find-long` for
+/// `inner.find-long`. Non-alphanumeric characters become hyphens and the id starts
+/// with a letter.
+pub fn rst_id(name: &str) -> String {
+    let mut id = String::new();
+
+    for ch in name.to_lowercase().chars() {
+        if ch.is_alphanumeric() {
+            id.push(ch);
+        } else if !id.is_empty() && !id.ends_with('-') {
+            id.push('-');
+        }
+    }
+
+    id.trim_start_matches(|ch: char| ch.is_ascii_digit() || ch == '-')
+        .trim_end_matches('-')
+        .to_string()
+}
+
+/// Gets the level of a section title, the title being the line at `i`
+fn title_level(lines: &[&str], i: usize) -> Option<usize> {
+    let is_adornment = |line: &str, ch: char| !line.is_empty() && line.chars().all(|c| c == ch);
+    let title = lines[i];
+
+    if title.is_empty() || title.starts_with(' ') {
+        return None;
+    }
+    let under = lines.get(i + 1)?;
+    let ch = under.chars().next()?;
+    let over = if i > 0 { lines[i - 1] } else { "" };
+
+    if ch == '=' && is_adornment(over, '=') && is_adornment(under, '=') {
+        Some(1)
+    } else if is_adornment(under, ch) && !is_adornment(title, ch) {
+        ADORNMENTS.iter().position(|c| *c == ch).map(|level| level + 2)
+    } else {
+        None
+    }
+}
+
+/// Writes a section title with the adornment of its level
+fn title(level: usize, text: &str) -> String {
+    let width = text.chars().count();
+
+    if level <= 1 {
+        let line = "=".repeat(width);
+        format!("{}\n{}\n{}\n", line, text, line)
+    } else {
+        let ch = ADORNMENTS[(level - 2).min(ADORNMENTS.len() - 1)];
+        format!("{}\n{}\n", text, ch.to_string().repeat(width))
+    }
+}
+
+/// Indents each line of a text which is not empty
+fn indent(text: &str, prefix: &str) -> String {
+    text.lines()
+        .map(|line| if line.trim().is_empty() { String::new() } else { format!("{}{}", prefix, line) })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Splits the version off a deprecation message which starts with it, e.g. `since 2.0,
+/// use find`. Sphinx needs a version for the `deprecated` directive, which javadoc
+/// does not have otherwise.
+fn deprecated_version(text: &str) -> (Option<&str>, &str) {
+    if !text.get(..6).is_some_and(|start| start.eq_ignore_ascii_case("since ")) {
+        return (None, text);
+    }
+
+    let rest = text[6..].trim_start();
+    let end = rest
+        .find(|ch: char| ch.is_whitespace() || ch == ',' || ch == ';' || ch == ':')
+        .unwrap_or(rest.len());
+    let message = rest[end..].trim_start_matches(|ch: char| ch.is_whitespace() || ch == ',' || ch == ';' || ch == ':');
+
+    (Some(rest[..end].trim_end_matches('.')), message)
+}
+
+/// Finds the first backtick of a text which is not escaped
+fn find_backtick(text: &str) -> Option<usize> {
+    let mut escaped = false;
+
+    for (i, ch) in text.char_indices() {
+        if ch == '`' && !escaped {
+            return Some(i);
+        }
+        escaped = ch == '\\' && !escaped;
+    }
+
+    None
+}
+
+/// Converts the markdown code spans of a line to literals. The literals, roles and
+/// hyperlinks of reStructuredText in it are kept as they are.
+fn code_spans(line: &str) -> String {
+    let mut doc = String::new();
+    let mut rest = line;
+
+    while let Some(i) = find_backtick(rest) {
+        doc.push_str(&rest[..i]);
+        let tail = &rest[i..];
+        let run = tail.len() - tail.trim_start_matches('`').len();
+        let markup = run > 1 || rest[..i].ends_with(':');
+        let end = match tail[run..].find(&"`".repeat(run)) {
+            Some(j) => run + j + run,
+            None => {
+                doc.push_str(tail);
+                return doc;
+            }
+        };
+        let after = &tail[end..];
+        let underscores = after.len() - after.trim_start_matches('_').len();
+
+        if markup || underscores > 0 {
+            doc.push_str(&tail[..end + underscores]);
+            rest = &tail[end + underscores..];
+        } else {
+            doc.push_str(format!("``{}``", &tail[1..end - 1]).as_str());
+            rest = after;
+        }
+    }
+    doc.push_str(rest);
+
+    doc
+}
+
+impl Renderer for Rst {
+    fn extension(&self) -> &'static str {
+        "rst"
+    }
+
+    fn heading(&self, level: usize, text: &str, anchor: &str, src: &str) -> String {
+        let mut doc = String::new();
+
+        if !anchor.is_empty() {
+            doc.push_str(format!(".. _{}:\n\n", anchor).as_str());
+        }
+        if src.is_empty() {
+            doc.push_str(title(level, text).as_str());
+        } else {
+            doc.push_str(title(level, &format!("{} {}", text, self.link("[src]", src))).as_str());
+        }
+        doc.push('\n');
+
+        doc
+    }
+
+    fn heading_ids(&self) -> bool {
+        true
+    }
+
+    fn notice(&self, label: &str, text: &str) -> String {
+        format!("**{}:** {}\n\n", label, text)
+    }
+
+    fn deprecation(&self, text: &str) -> String {
+        let (version, text) = deprecated_version(text);
+        // Sphinx needs a version for the `deprecated` directive, without one the deprecation
+        // is an admonition, or a warning when there is no text for the admonition
+        let directive = match (version, text.is_empty()) {
+            (Some(version), _) => format!(".. deprecated:: {}", version),
+            (None, true) => format!(".. warning:: {}", label("deprecated")),
+            (None, false) => format!(".. admonition:: {}", label("deprecated")),
+        };
+
+        if text.is_empty() {
+            format!("{}\n\n", directive)
+        } else {
+            format!("{}\n\n{}\n\n", directive, indent(&self.inline(text), "   "))
+        }
+    }
+
+    // Each block ends with a blank line, which reStructuredText needs between blocks
+    fn field(&self, label: &str, value: &str) -> String {
+        format!("{}: {}\n\n", label, value)
+    }
+
+    fn description(&self, text: &str) -> String {
+        format!("{}:\n\n{}\n\n", label("description"), indent(&self.inline(text), "   "))
+    }
+
+    fn text(&self, text: &str) -> String {
+        format!("{}\n\n", text)
+    }
+
+    fn list(&self, items: &[String]) -> String {
+        let mut doc: String = items
+            .iter()
+            .map(|item| {
+                let item = indent(&self.inline(item), "  ");
+
+                // An item with blocks such as code ends with a blank line
+                if item.contains('\n') {
+                    format!("* {}\n\n", item.trim_start())
+                } else {
+                    format!("* {}\n", item.trim_start())
+                }
+            }).collect();
+        doc.push('\n');
+
+        doc
+    }
+
+    fn table(&self, headers: &[&str], rows: &[Vec<String>]) -> String {
+        let mut doc = String::from(".. list-table::\n   :header-rows: 1\n\n");
+        let header: Vec<String> = headers.iter().map(|header| header.to_string()).collect();
+
+        for row in Some(&header).into_iter().chain(rows.iter()) {
+            for (i, cell) in row.iter().enumerate() {
+                let bullet = if i == 0 { "   * -" } else { "     -" };
+                let cell = indent(cell.trim(), "       ");
+
+                if cell.is_empty() {
+                    doc.push_str(format!("{}\n", bullet).as_str());
+                } else {
+                    doc.push_str(format!("{} {}\n", bullet, cell.trim_start()).as_str());
+                }
+            }
+        }
+        doc.push('\n');
+
+        doc
+    }
+
+    fn details(&self, summary: &str, items: &[String]) -> String {
+        let mut doc = format!(".. rubric:: {}\n\n", summary);
+
+        for item in items {
+            doc.push_str(format!("* {}\n", item).as_str());
+        }
+        doc.push('\n');
+
+        doc
+    }
+
+    fn grouped_details(&self, summary: &str, groups: &[(&str, Vec<String>)]) -> String {
+        let mut doc = format!(".. rubric:: {}\n\n", summary);
+
+        // The nested lists are separated from the items by blank lines
+        for (label, items) in groups {
+            doc.push_str(format!("* {}\n\n", label).as_str());
+            for item in items {
+                doc.push_str(format!("  * {}\n", item).as_str());
+            }
+            doc.push('\n');
+        }
+
+        doc
+    }
+
+    fn escape(&self, text: &str) -> String {
+        // Type arguments such as `List<Foo>` and arrays such as `int[]` would end the
+        // roles of the type names next to them
+        let mut doc = String::new();
+
+        for ch in text.chars() {
+            if "\\`*_|<>[]".contains(ch) {
+                doc.push('\\');
+            }
+            doc.push(ch);
+        }
+
+        doc
+    }
+
+    fn inline(&self, text: &str) -> String {
+        // The markdown of the parser is mostly valid reStructuredText, apart from its code
+        // spans and fenced code blocks
+        let mut doc = String::new();
+        let mut lines = text.lines();
+
+        while let Some(line) = lines.next() {
+            if line.trim_start().starts_with("```") {
+                let code: Vec<&str> = lines.by_ref().take_while(|line| !line.trim_start().starts_with("```")).collect();
+
+                doc.push_str(format!("\n.. code-block:: java\n\n{}\n\n", indent(&code.join("\n"), "   ")).as_str());
+                continue;
+            }
+
+            doc.push_str(code_spans(line).as_str());
+            doc.push('\n');
+        }
+
+        doc.trim_end().to_string()
+    }
+
+    fn code(&self, text: &str) -> String {
+        if text.trim().is_empty() {
+            String::new()
+        } else {
+            format!("``{}``", text.trim())
+        }
+    }
+
+    fn code_block(&self, code: &str) -> String {
+        format!(".. code-block:: java\n\n{}\n\n", indent(code, "   "))
+    }
+
+    fn type_name(&self, name: &str) -> String {
+        format!(":java:ref:`{}`", name)
+    }
+
+    fn link(&self, text: &str, target: &str) -> String {
+        // Hyperlinks can not hold other inline markup
+        let text = text
+            .strip_prefix("``")
+            .and_then(|text| text.strip_suffix("``"))
+            .unwrap_or(text);
+        let (page, anchor) = match target.find('#') {
+            Some(i) => (&target[..i], Some(&target[i + 1..])),
+            None => (target, None),
+        };
+
+        // Sphinx writes the pages as html and gives the targets the ids of docutils
+        let mut target = match page.strip_suffix(".rst") {
+            Some(page) => format!("{}.html", page),
+            None => page.to_string(),
+        };
+        if let Some(anchor) = anchor {
+            let internal = page.is_empty() || page.ends_with(".rst");
+            target.push('#');
+            target.push_str(if internal { rst_id(anchor) } else { anchor.to_string() }.as_str());
+        }
+
+        format!("`{} <{}>`__", text, target)
+    }
+
+    fn nest(&self, page: &str) -> String {
+        let lines: Vec<&str> = page.lines().collect();
+        let mut doc = String::new();
+        let mut i = 0;
+
+        while i < lines.len() {
+            match title_level(&lines, i) {
+                Some(level) => {
+                    // The overline of a page title was already written as a line
+                    if level == 1 {
+                        let overline = doc.trim_end_matches('\n').rfind('\n').map_or(0, |i| i + 1);
+                        doc.truncate(overline);
+                    }
+                    doc.push_str(title(level + 2, lines[i]).as_str());
+                    i += 2;
+                }
+                None => {
+                    doc.push_str(lines[i]);
+                    doc.push('\n');
+                    i += 1;
+                }
+            }
+        }
+
+        doc
+    }
+
+    fn page(&self, _title: &str, body: &str) -> String {
+        body.to_string()
+    }
+
+    fn rule(&self) -> String {
+        "\n\n----\n\n".to_string()
+    }
+}
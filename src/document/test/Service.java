@@ -0,0 +1,33 @@
+package com.example;
+
+import java.util.List;
+
+/**
+ * Finds and stores {@link Item items}.
+ *
+ * @author Jo
+ * @since 1.2
+ * @param <T> the item type
+ */
+public class Service<T extends Item> {
+    /** The maximum number of items */
+    public static final int MAX = 10;
+
+    /**
+     * Creates a service.
+     *
+     * @param name the name of the service
+     */
+    public Service(String name) {}
+
+    /**
+     * Finds an item.
+     *
+     * @param id the id of the item
+     * @return the items found
+     * @throws IllegalStateException when the service is closed
+     */
+    public List<T> find(long id) throws IllegalStateException {
+        return null;
+    }
+}
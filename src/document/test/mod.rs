@@ -0,0 +1,1985 @@
+use document::document::*;
+use document::render::Markdown;
+use model::model::first_sentence;
+use parse::parse::Charset;
+
+#[test]
+fn test_inline_code_tags() {
+    let names: Vec<String> = Vec::new();
+
+    assert_eq!(
+        "Returns `null` when `a<b`",
+        convert_inline_tags(&Markdown, "Returns {@code null} when {@literal a<b}", &names, "")
+    );
+    assert_eq!(
+        "Builds a `Map<K, V> {x}`",
+        convert_inline_tags(&Markdown, "Builds a {@code Map<K, V> {x}}", &names, "")
+    );
+}
+
+#[test]
+fn test_inline_link_tags() {
+    let names = vec!["UserService".to_string()];
+
+    assert_eq!(
+        "See [`UserService.findById(long)`](UserService.md)",
+        convert_inline_tags(&Markdown, "See {@link UserService#findById(long)}", &names, "")
+    );
+    assert_eq!(
+        "See [the finder](UserService.md)",
+        convert_inline_tags(&Markdown, "See {@link UserService#find(long, int) the finder}", &names, "")
+    );
+    assert_eq!(
+        "See `Map.get(Object)` and `other`",
+        convert_inline_tags(&Markdown, "See {@link java.util.Map#get(Object)} and {@link #other}", &names, "")
+    );
+}
+
+#[test]
+fn test_unbalanced_inline_tag() {
+    let names: Vec<String> = Vec::new();
+
+    assert_eq!(
+        "Broken {@code foo and `bar`",
+        convert_inline_tags(&Markdown, "Broken {@code foo and {@code bar}", &names, "")
+    );
+}
+
+#[test]
+fn test_deprecation_notice() {
+    let notice = |deprecated: Option<&str>| {
+        written_string(|out| gen_deprecation_docs(out, &Markdown, &deprecated.map(str::to_string)))
+    };
+
+    assert_eq!("**Deprecated:** use Foo instead  \n\n", notice(Some("use Foo instead")));
+    assert!(notice(Some("")).starts_with("**Deprecated:** "));
+    assert_eq!("", notice(None));
+}
+
+#[test]
+fn test_annotation_list() {
+    let annotations = vec![
+        "@RestController".to_string(),
+        "@Deprecated".to_string(),
+        "@RequestMapping(\"/items\")".to_string(),
+    ];
+
+    let list = |annotations: &[String]| written_string(|out| gen_annotation_list(out, &Markdown, annotations));
+
+    assert_eq!("+ `@RestController`  \n+ `@RequestMapping(\"/items\")`  \n\n", list(&annotations));
+    assert_eq!("", list(&["@java.lang.Deprecated".to_string()]));
+}
+
+#[test]
+fn test_permitted_subclasses() {
+    let names = vec!["p.Circle".to_string()];
+    let permits = vec!["Circle".to_string(), "Square".to_string()];
+
+    let doc = written_string(|out| gen_permits_docs(out, &Markdown, &permits));
+
+    assert_eq!(
+        "## Permitted subclasses\n\n+ [`Circle`](../p/Circle.md)  \n+ `Square`  \n\n",
+        convert_inline_tags(&Markdown, &doc, &names, "p")
+    );
+    assert_eq!("", written_string(|out| gen_permits_docs(out, &Markdown, &[])));
+}
+
+#[test]
+fn test_inheritance_line() {
+    use document::asciidoc::Asciidoc;
+    use document::html::Html;
+    use document::render::Renderer;
+    use document::rst::Rst;
+
+    let names = vec!["AbstractParser".to_string()];
+    let interfaces = vec!["Closeable".to_string(), "Comparable<Money>".to_string()];
+
+    let line = |r: &dyn Renderer, parent: &str, interfaces: &[String]| {
+        written_string(|out| gen_inheritance_docs(out, r, parent, interfaces))
+    };
+
+    assert_eq!(
+        "Inheritance: extends [`AbstractParser`](AbstractParser.md) · implements `Closeable`, `Comparable`\\<Money\\>  \n",
+        convert_inline_tags(&Markdown, &line(&Markdown, "AbstractParser", &interfaces), &names, "")
+    );
+    // The type arguments are escaped for each format
+    assert!(line(&Html, "", &interfaces).contains("{@link Comparable}&lt;Money&gt;"));
+    assert!(line(&Rst, "", &interfaces).contains("{@link Comparable}\\<Money\\>"));
+    assert!(line(&Asciidoc, "", &interfaces).contains("{@link Comparable}<Money>"));
+    assert_eq!("", line(&Markdown, "", &[]));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_find_java_files_skips_unreadable_paths() {
+    use std::env;
+    use std::fs;
+    use std::os::unix::fs::symlink;
+
+    let root = env::temp_dir().join(format!("lojidoc-find-{}", std::process::id()));
+    let nested = root.join("nested");
+
+    fs::create_dir_all(&nested).unwrap();
+    fs::write(root.join("Main.java"), "class Main {}").unwrap();
+    fs::write(nested.join("Util.java"), "class Util {}").unwrap();
+    fs::write(nested.join("notes.txt"), "").unwrap();
+    symlink(root.join("missing"), root.join("Broken.java")).unwrap();
+
+    let (mut files, failed) = find_java_files(&root, &PathFilter::default());
+    files.sort();
+
+    fs::remove_dir_all(&root).unwrap();
+
+    assert_eq!(vec![root.join("Main.java"), nested.join("Util.java")], files);
+    // The dangling symlink is skipped with a warning
+    assert!(failed.is_empty());
+}
+
+#[test]
+fn test_find_java_files_with_symlinks() {
+    use std::env;
+    use std::fs;
+    use std::os::unix::fs::symlink;
+
+    let root = env::temp_dir().join(format!("lojidoc-symlinks-{}", std::process::id()));
+    let shared = root.join("shared");
+    let app = root.join("app");
+
+    fs::create_dir_all(&shared).unwrap();
+    fs::create_dir_all(&app).unwrap();
+    fs::write(shared.join("Util.java"), "class Util {}").unwrap();
+    fs::write(app.join("Main.java"), "class Main {}").unwrap();
+    symlink(&shared, app.join("shared")).unwrap();
+    // A symlink back up the tree
+    symlink(&app, app.join("cycle")).unwrap();
+
+    let (files, failed) = find_java_files(&app, &PathFilter::default());
+    let filter = PathFilter {
+        follow_symlinks: true,
+        ..PathFilter::default()
+    };
+    let (followed, followed_failed) = find_java_files(&app, &filter);
+
+    fs::remove_dir_all(&root).unwrap();
+
+    assert_eq!(vec![app.join("Main.java")], files);
+    assert!(failed.is_empty());
+    assert_eq!(vec![app.join("Main.java"), app.join("shared").join("Util.java")], followed);
+    assert!(followed_failed.is_empty());
+}
+
+#[test]
+fn test_is_java_file() {
+    use std::path::Path;
+
+    assert!(is_java_file(Path::new("Foo.java")));
+    assert!(is_java_file(Path::new("src/Foo.JAVA")));
+    assert!(!is_java_file(Path::new("Foo.java.bak")));
+    assert!(!is_java_file(Path::new("Foo.javascript~")));
+    assert!(!is_java_file(Path::new("build.gradle.javabackup")));
+    assert!(!is_java_file(Path::new("src/main/java/")));
+}
+
+#[test]
+fn test_find_java_files_in_java_directory() {
+    use std::env;
+    use std::fs;
+
+    let root = env::temp_dir().join(format!("lojidoc-java-dir-{}", std::process::id()));
+    let java = root.join("java");
+
+    fs::create_dir_all(&java).unwrap();
+    fs::write(java.join("Foo.java"), "class Foo {}").unwrap();
+    fs::write(java.join("Foo.java.orig"), "class Foo {}").unwrap();
+    fs::write(java.join("package-info.java"), "package com.example;").unwrap();
+    fs::write(java.join("module-info.java"), "module com.example {}").unwrap();
+
+    let (mut files, failed) = find_java_files(&root, &PathFilter::default());
+    files.sort();
+
+    fs::remove_dir_all(&root).unwrap();
+
+    assert_eq!(
+        vec![java.join("Foo.java"), java.join("module-info.java"), java.join("package-info.java")],
+        files
+    );
+    assert!(is_module_info(&files[1]));
+    assert!(!is_module_info(&files[0]));
+    assert!(failed.is_empty());
+}
+
+#[test]
+fn test_package_links() {
+    let names = vec![
+        "com.foo.Client".to_string(),
+        "com.bar.Client".to_string(),
+        "com.bar.Util".to_string(),
+    ];
+
+    assert_eq!("com/foo/Client.md", page_path("com.foo", "Client", false));
+    assert_eq!("Client.md", page_path("com.foo", "Client", true));
+    assert_eq!(
+        "[`Client`](../../com/bar/Client.md) and [`Util`](../../com/bar/Util.md)",
+        convert_inline_tags(&Markdown, "{@link Client} and {@link Util}", &names, "com.bar")
+    );
+    assert_eq!(
+        "[`Client`](../../com/foo/Client.md)",
+        convert_inline_tags(&Markdown, "{@link Client}", &names, "com.foo")
+    );
+    assert_eq!(
+        "[`Client`](../../com/bar/Client.md)",
+        convert_inline_tags(&Markdown, "{@link com.bar.Client}", &names, "com.foo")
+    );
+}
+
+#[test]
+fn test_index_page() {
+    use model::model::ApplicationDoc;
+    use model::model::DiagramNode;
+    use model::model::PackageMember;
+
+    let member = |name: &str, path: &str, summary: &str| PackageMember {
+        name: name.to_string(),
+        path: path.to_string(),
+        summary: summary.to_string(),
+        deprecated: false,
+        diagram: DiagramNode::default(),
+        methods: Vec::new(),
+    };
+    let mut app_doc = ApplicationDoc::new();
+    app_doc.add_package_member("com.foo".to_string(), member("Zeta", "com/foo/Zeta.md", "Last."));
+    app_doc.add_package_member("com.bar".to_string(), member("Util", "com/bar/Util.md", "Helps"));
+    app_doc.add_package_member("com.foo".to_string(), member("Alpha", "com/foo/Alpha.md", ""));
+    app_doc.add_package_doc("com.bar".to_string(), "Utilities.".to_string());
+    app_doc.add_package_doc("com.empty".to_string(), "Nothing yet.".to_string());
+
+    assert_eq!("Does things.", first_sentence("Does things. More\ndetail here."));
+    assert_eq!(
+        "# Index\n\n## com.bar\n\nDescription:  \n > Utilities.  \n\n\
+         | Type                    | Summary |\n\
+         | ----------------------- | ------- |\n| [Util](com/bar/Util.md) | Helps   |\n\n\
+         ## com.empty\n\nDescription:  \n > Nothing yet.  \n\n\n\
+         ## com.foo\n\n| Type                      | Summary |\n\
+         | ------------------------- | ------- |\n| [Alpha](com/foo/Alpha.md) |         |\n\
+         | [Zeta](com/foo/Zeta.md)   | Last.   |\n\n",
+        gen_index_page(&Markdown, &app_doc)
+    );
+}
+
+#[test]
+fn test_api_json() {
+    use model::model::{Class, Method, ObjectType, Param, Project};
+    use serde_json::Value;
+
+    let mut method = Method::new();
+    method.ch_method_name("find".to_string());
+    method.ch_privacy("public".to_string());
+    method.add_param(Param {
+        desc: "The id".to_string(),
+        name: "id".to_string(),
+        var_type: "long".to_string(),
+        line_num: String::new(),
+    });
+
+    let mut service = Class::new();
+    service.name = "Service".to_string();
+    service.package_name = "com.foo".to_string();
+    service.methods.push(method);
+
+    let mut client = Class::new();
+    client.name = "Client".to_string();
+    client.package_name = "com.foo".to_string();
+
+    let mut proj = Project::new();
+    proj.add_class(service);
+    proj.add_class(client);
+
+    let api: Value = serde_json::from_str(&gen_api_json(&mut proj)).unwrap();
+    assert_eq!("Client", api["classes"][0]["name"]);
+    assert_eq!("Service", api["classes"][1]["name"]);
+    assert_eq!("com.foo", api["classes"][1]["package_name"]);
+    assert_eq!("id", api["classes"][1]["methods"][0]["parameters"][0]["name"]);
+    assert_eq!("The id", api["classes"][1]["methods"][0]["parameters"][0]["desc"]);
+
+    let class: Value =
+        serde_json::from_str(&gen_type_json(&ObjectType::Class(Class::new()))).unwrap();
+    assert_eq!("class", class["kind"]);
+}
+
+#[test]
+fn test_html_page() {
+    use document::html::Html;
+    use model::model::{Class, Method, Param};
+
+    let mut method = Method::new();
+    method.ch_method_name("find".to_string());
+    method.ch_description("Finds a {@link Client}".to_string());
+    method.add_param(Param {
+        desc: "The <b>id</b>".to_string(),
+        name: "id".to_string(),
+        var_type: "List<Long>".to_string(),
+        line_num: String::new(),
+    });
+
+    let mut class = Class::new();
+    class.name = "Service".to_string();
+    class.package_name = "com.foo".to_string();
+    class.methods.push(method.clone());
+    class.methods.push(method);
+
+    let names = vec!["com.foo.Client".to_string()];
+    let layout = PageLayout::default();
+    let page = written_string(|out| gen_class_page(out, &Html, class, &layout));
+    let page = convert_inline_tags(&Html, &page, &names, "com.foo");
+
+    assert!(page.starts_with("<h1>Class Service</h1>\n"));
+    assert!(page.contains("<h3 id=\"find-list\">find(List)</h3>"));
+    assert!(page.contains("<h3 id=\"find-list-2\">find(List)</h3>"));
+    assert!(page.contains("<li>Description: Finds a <a href=\"../../com/foo/Client.html\"><code>Client</code></a></li>"));
+    assert!(page.contains("<tr><td>id</td><td>List&lt;Long&gt;</td><td>The <b>id</b></td></tr>"));
+}
+
+#[test]
+fn test_asciidoc_page() {
+    use document::asciidoc::Asciidoc;
+    use model::model::ObjectType;
+    use parse::parse::parse_file;
+    use std::path::Path;
+
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/document/test/Service.java");
+    let class = match parse_file(&fixture, Charset::Auto, false).unwrap().remove(0) {
+        ObjectType::Class(class) => class,
+        _ => panic!("Expected a class"),
+    };
+    let names = vec!["com.example.Item".to_string()];
+
+    let page = written_string(|out| gen_class_page(out, &Asciidoc, class, &PageLayout::default()));
+
+    assert_eq!(include_str!("Service.adoc"), convert_inline_tags(&Asciidoc, &page, &names, "com.example"));
+}
+
+#[test]
+fn test_rst_page() {
+    use document::rst::Rst;
+    use model::model::ObjectType;
+    use parse::parse::parse_file;
+    use std::path::Path;
+
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/document/test/Service.java");
+    let class = match parse_file(&fixture, Charset::Auto, false).unwrap().remove(0) {
+        ObjectType::Class(class) => class,
+        _ => panic!("Expected a class"),
+    };
+    let names = vec!["com.example.Item".to_string()];
+    let page = written_string(|out| gen_class_page(out, &Rst, class, &PageLayout::default()));
+    let page = convert_inline_tags(&Rst, &page, &names, "com.example");
+
+    assert_eq!(include_str!("Service.rst"), page);
+}
+
+#[test]
+fn test_rst_markup() {
+    use document::render::Renderer;
+    use document::rst::{rst_id, Rst};
+
+    assert_eq!("inner-find-long", rst_id("inner.find-long"));
+    assert_eq!("Map\\<K, V\\> int\\[\\]", Rst.escape("Map<K, V> int[]"));
+    assert_eq!(
+        "Runs ``a_b`` and ``c``\n\n.. code-block:: java\n\n   int a = 1;",
+        Rst.inline("Runs `a_b` and ``c``\n```java\nint a = 1;\n```")
+    );
+    assert_eq!("`Item <Item.html#find-long>`__", Rst.link("``Item``", "Item.rst#find-long"));
+    assert_eq!("`site <https://example.com/#Top>`__", Rst.link("site", "https://example.com/#Top"));
+    assert_eq!(".. deprecated:: 2.0\n\n   use find\n\n", Rst.deprecation("since 2.0, use find"));
+    assert_eq!(".. admonition:: Deprecated\n\n   use find\n\n", Rst.deprecation("use find"));
+    assert_eq!(".. warning:: Deprecated\n\n", Rst.deprecation(""));
+    assert_eq!(
+        "==========\nClass Main\n==========\n\nFields\n======\n\n",
+        Rst.heading(1, "Class Main", "", "").to_string() + &Rst.heading(2, "Fields", "", "")
+    );
+    assert_eq!(
+        "Text\n\nClass Inner\n-----------\n\nFields\n~~~~~~\n\n",
+        Rst.nest("Text\n\n===========\nClass Inner\n===========\n\nFields\n======\n\n")
+    );
+    assert!(is_type_page("===========\nClass Inner\n===========\n", Format::Rst));
+    assert!(!is_type_page("=====\nIndex\n=====\n", Format::Rst));
+}
+
+#[test]
+fn test_single_page() {
+    use links::links::duplicate_anchors;
+    use model::model::{Class, Method, Project};
+
+    assert_eq!("class-service-src", heading_slug("Class Service [[src]](Service.java)"));
+    assert_eq!("method-findlistlong", heading_slug("Method find(List<Long>)"));
+
+    let class = |name: &str, package: &str| {
+        let mut class = Class::new();
+        class.name = name.to_string();
+        class.package_name = package.to_string();
+        let mut run = Method::new();
+        run.ch_method_name("run".to_string());
+        class.methods.push(run);
+        class
+    };
+    let mut proj = Project::new();
+    proj.add_class(class("Zeta", "com.foo"));
+    proj.add_class(class("Util", "com.bar"));
+    proj.add_class(class("Alpha", "com.foo"));
+
+    let mut page = Vec::new();
+    write_single_page(&mut page, proj, &PageLayout::default()).unwrap();
+    let page = String::from_utf8(page).unwrap();
+    assert!(page.starts_with(
+        "# API\n\n## Contents\n\n- com.bar\n  - [Class Util](#class-util)\n\
+         - com.foo\n  - [Class Alpha](#class-alpha)\n  - [Class Zeta](#class-zeta)\n\n# Class Util\n"
+    ));
+    assert!(page.find("# Class Alpha").unwrap() < page.find("# Class Zeta").unwrap());
+    assert!(page.contains("\n### No fields in this class\n"));
+    assert!(!page.contains("\n## No fields in this class\n"));
+    // The methods of the types have anchors of their own
+    assert!(page.contains("<a id=\"alpha.run\"></a>"));
+    assert!(page.contains("<a id=\"zeta.run\"></a>"));
+    assert!(duplicate_anchors(&page, Format::Markdown).is_empty());
+}
+
+#[test]
+fn test_write_pages() {
+    let pages = vec!["# Class A\n".to_string(), "# Class B\n".to_string()];
+    let mut out = Vec::new();
+    write_pages(&mut out, &Markdown, &pages).unwrap();
+
+    assert_eq!("# Class A\n\n---\n\n# Class B\n", String::from_utf8(out).unwrap());
+
+    // A writer which is full reports the error instead of writing part of the pages
+    let mut buffer = [0u8; 12];
+    assert!(write_pages(&mut &mut buffer[..], &Markdown, &pages).is_err());
+}
+
+#[test]
+fn test_class_diagram() {
+    use model::model::ApplicationDoc;
+    use model::model::ObjectType;
+    use model::model::PackageMember;
+    use model::model::Project;
+    use parse::parse::parse_str;
+
+    let sources = [
+        "package shapes;\npublic interface Shape extends Comparable<Shape> { double area(); }",
+        "package shapes;\npublic abstract class Base<T> implements Shape {\n\
+         public double area() { return 0; }\n public double area(int scale) { return 0; }\n\
+         private void reset() {}\n}",
+        "package shapes;\npublic class Circle extends Base<Double> implements java.io.Serializable {}",
+        "package shapes;\npublic enum Kind implements Shape { ROUND; public double area() { return 1; } }",
+    ];
+    let mut app_doc = ApplicationDoc::new();
+
+    for source in sources.iter() {
+        let mut project = Project::new();
+        for object in parse_str(source, None).unwrap() {
+            match object {
+                ObjectType::Class(class) => project.add_class(class),
+                ObjectType::Interface(inter) => project.add_interface(inter),
+                ObjectType::Enumeration(enumeration) => project.add_enumeration(enumeration),
+                ObjectType::Annotation(annotation) => project.add_annotation(annotation),
+            }
+        }
+
+        for (name, node) in diagram_nodes(&project) {
+            let member = PackageMember {
+                path: page_path("shapes", &name, false),
+                name,
+                summary: String::new(),
+                deprecated: false,
+                diagram: node,
+                methods: Vec::new(),
+            };
+            app_doc.add_package_member("shapes".to_string(), member);
+        }
+    }
+
+    assert_eq!(
+        "# Class diagram of shapes\n\n```mermaid\nclassDiagram\n\
+         \x20   class Base\n\
+         \x20   class Circle\n\
+         \x20   class Kind {\n        <<enumeration>>\n    }\n\
+         \x20   class Shape {\n        <<interface>>\n    }\n\
+         \x20   Shape <|.. Base\n\
+         \x20   Base <|-- Circle\n\
+         \x20   Serializable <|.. Circle\n\
+         \x20   Shape <|.. Kind\n\
+         \x20   Comparable <|-- Shape\n\
+         ```\n",
+        gen_class_diagram(&app_doc.packages[0], false)
+    );
+    assert!(gen_class_diagram(&app_doc.packages[0], true)
+        .contains("    class Base {\n        +area()\n    }\n"));
+}
+
+#[test]
+fn test_large_packages_get_no_diagram() {
+    use model::model::ApplicationDoc;
+    use model::model::DiagramNode;
+    use model::model::PackageMember;
+    use std::env;
+    use std::fs;
+
+    let dest = env::temp_dir().join(format!("lojidoc-diagrams-{}", std::process::id()));
+    let mut app_doc = ApplicationDoc::new();
+    for i in 0..=DIAGRAM_LIMIT {
+        let member = PackageMember {
+            name: format!("Type{}", i),
+            path: format!("big/Type{}.md", i),
+            summary: String::new(),
+            deprecated: false,
+            diagram: DiagramNode::default(),
+            methods: Vec::new(),
+        };
+        app_doc.add_package_member("big".to_string(), member.clone());
+        if i < 2 {
+            app_doc.add_package_member("small".to_string(), member);
+        }
+    }
+
+    generate_diagrams(&app_doc, dest.to_str().unwrap(), &Default::default(), false).unwrap();
+    let (big, small) = (dest.join("big/diagram.md").exists(), dest.join("small/diagram.md").exists());
+    fs::remove_dir_all(&dest).unwrap();
+
+    assert!(!big);
+    assert!(small);
+}
+
+#[test]
+fn test_mdbook_summary() {
+    use model::model::ApplicationDoc;
+    use model::model::DiagramNode;
+    use model::model::PackageMember;
+
+    let member = |name: &str, package: &str| PackageMember {
+        name: name.to_string(),
+        path: page_path(package, name, false),
+        summary: String::new(),
+        deprecated: false,
+        diagram: DiagramNode::default(),
+        methods: Vec::new(),
+    };
+    let mut app_doc = ApplicationDoc::new();
+    app_doc.add_package_member("com.foo".to_string(), member("Zeta", "com.foo"));
+    app_doc.add_package_member("com.bar".to_string(), member("Util", "com.bar"));
+    app_doc.add_package_member("com.foo".to_string(), member("Alpha", "com.foo"));
+    app_doc.add_package_member(String::new(), member("Main", ""));
+
+    assert_eq!("com/foo/package-summary.md", package_page_path("com.foo"));
+    assert_eq!(
+        "# Summary\n\n[Index](index.md)\n\n\
+         - [Default package](package-summary.md)\n    - [Main](Main.md)\n\
+         - [com.bar](com/bar/package-summary.md)\n    - [Util](com/bar/Util.md)\n\
+         - [com.foo](com/foo/package-summary.md)\n    - [Alpha](com/foo/Alpha.md)\n\
+         \x20   - [Zeta](com/foo/Zeta.md)\n",
+        gen_summary(&app_doc)
+    );
+}
+
+#[test]
+fn test_docusaurus_sidebar() {
+    use model::model::ApplicationDoc;
+    use model::model::DiagramNode;
+    use model::model::PackageMember;
+    use std::env;
+    use std::fs;
+
+    let member = |name: &str, package: &str| PackageMember {
+        name: name.to_string(),
+        path: page_path(package, name, false),
+        summary: String::new(),
+        deprecated: false,
+        diagram: DiagramNode::default(),
+        methods: Vec::new(),
+    };
+    let mut app_doc = ApplicationDoc::new();
+    app_doc.add_package_member("com.foo".to_string(), member("Zeta", "com.foo"));
+    app_doc.add_package_member("com.foo".to_string(), member("Alpha", "com.foo"));
+    app_doc.add_package_member(String::new(), member("Main", ""));
+
+    let sidebar = "[\n  \"api/index\",\n  \"api/api-index\",\n  {\n    \"items\": [\n      \"api/Main\"\n    ],\n    \
+                   \"label\": \"Default package\",\n    \"type\": \"category\"\n  },\n  {\n    \"items\": [\n      \
+                   \"api/com/foo/Alpha\",\n      \"api/com/foo/Zeta\"\n    ],\n    \"label\": \"com.foo\",\n    \
+                   \"type\": \"category\"\n  }\n]\n";
+    assert_eq!(sidebar, gen_sidebar(&app_doc, "api"));
+    assert!(gen_sidebar(&app_doc, "").contains("\"com/foo/Alpha\""));
+
+    // The ids are relative to the docs directory of the site the pages are written to
+    let site = env::temp_dir().join(format!("lojidoc-docusaurus-{}", std::process::id()));
+    let dest = site.join("docs").join("api");
+    fs::create_dir_all(&dest).unwrap();
+    fs::write(site.join("docusaurus.config.js"), "module.exports = {};\n").unwrap();
+    generate_sidebar(&app_doc, dest.to_str().unwrap(), &Default::default()).unwrap();
+    let written = fs::read_to_string(dest.join("sidebar.json")).unwrap();
+    fs::remove_dir_all(&site).unwrap();
+
+    assert_eq!(sidebar, written);
+}
+
+#[test]
+fn test_signature_type_links() {
+    use document::html::Html;
+    use model::model::{Class, Method, Param};
+
+    let mut method = Method::new();
+    method.ch_method_name("find".to_string());
+    method.ch_return_type("Map<String, List<Client>>".to_string());
+    method.add_param(Param {
+        desc: String::new(),
+        name: "id".to_string(),
+        var_type: "long".to_string(),
+        line_num: String::new(),
+    });
+    method.add_param(Param {
+        desc: String::new(),
+        name: "other".to_string(),
+        var_type: "com.bar.Client[]".to_string(),
+        line_num: String::new(),
+    });
+    method.add_param(Param {
+        desc: String::new(),
+        name: "rest".to_string(),
+        var_type: "Client...".to_string(),
+        line_num: String::new(),
+    });
+
+    let mut class = Class::new();
+    class.name = "Service".to_string();
+    class.package_name = "com.foo".to_string();
+    class.methods.push(method);
+
+    let names = vec![
+        "com.bar.Client".to_string(),
+        "com.foo.Client".to_string(),
+        "com.foo.String".to_string(),
+    ];
+    let layout = PageLayout::default();
+    let page = written_string(|out| gen_class_page(out, &Markdown, class.clone(), &layout));
+    let page = convert_inline_tags(&Markdown, &page, &names, "com.foo");
+
+    assert!(page.contains("+ return: Map\\<String, List\\<[Client](../../com/foo/Client.md)\\>\\>  \n"));
+    assert!(page.contains(
+        "| Name  | Type                                          | Description |\n\
+         | ----- | --------------------------------------------- | ----------- |\n\
+         | id    | long                                          |             |\n\
+         | other | [com.bar.Client](../../com/bar/Client.md)\\[\\] |             |\n\
+         | rest  | [Client](../../com/foo/Client.md)...          |             |\n"
+    ));
+
+    let page = written_string(|out| gen_class_page(out, &Html, class, &layout));
+    let page = convert_inline_tags(&Html, &page, &names, "com.foo");
+    assert!(page.contains("return: Map&lt;String, List&lt;<a href=\"../../com/foo/Client.html\">Client</a>&gt;&gt;"));
+}
+
+#[test]
+fn test_markdown_table() {
+    use document::render::Renderer;
+
+    let rows = vec![
+        vec![Markdown.escape("Map<String, List<Foo>>"), "Splits a | b".to_string()],
+        vec!["x".to_string(), "First line\nsecond line\n\nNext \\| paragraph".to_string()],
+    ];
+
+    assert_eq!(
+        "| Type                       | Description                                     |\n\
+         | -------------------------- | ----------------------------------------------- |\n\
+         | Map\\<String, List\\<Foo\\>\\> | Splits a \\| b                                   |\n\
+         | x                          | First line second line<br><br>Next \\| paragraph |\n",
+        Markdown.table(&["Type", "Description"], &rows)
+    );
+}
+
+#[test]
+fn test_escaped_names() {
+    use document::asciidoc::Asciidoc;
+    use document::render::Renderer;
+
+    assert_eq!("max\\_value \\*", Markdown.escape("max_value *"));
+    assert_eq!("Map\\<K, V\\>", Markdown.escape("Map<K, V>"));
+    assert_eq!("int\\[\\]\\[\\]", Markdown.escape("int[][]"));
+    assert_eq!("max_value \\ `a_b` <T>", Asciidoc.inline("max_value \\ `a_b` <T>"));
+}
+
+#[test]
+fn test_escaped_doc_text() {
+    use document::asciidoc::Asciidoc;
+    use document::render::Renderer;
+    use parse::parse::html_to_markdown;
+
+    assert_eq!(
+        "Clamps max_value to \\_MIN and 2 * 3 \\< 4 for a List\\<User>",
+        Markdown.inline("Clamps max_value to _MIN and 2 * 3 < 4 for a List<User>")
+    );
+    assert_eq!("\\# TODO fix #12\n\\* not a list", Markdown.inline("# TODO fix #12\n* not a list"));
+    // The emphasis, code spans and links of the javadoc text are escaped by the parser,
+    // the markdown it writes for the html and the inline tags are kept
+    let doc = html_to_markdown("Pass *args or `name` as [0](1) to <b>run</b> the <code>a*b</code> {@code [c]}");
+    assert_eq!(
+        "Pass \\*args or \\`name\\` as \\[0](1) to **run** the `a*b` {@code [c]}",
+        Markdown.inline(&doc)
+    );
+    assert_eq!("Pass *args or `name` as [0](1) to **run** the `a*b` {@code [c]}", Asciidoc.inline(&doc));
+    assert_eq!(
+        "**Bold** `a_b<T>` {@code _c<d>} <b>kept</b>\n```java\nList<T> _x;\n```",
+        Markdown.inline("**Bold** `a_b<T>` {@code _c<d>} <b>kept</b>\n```java\nList<T> _x;\n```")
+    );
+    // The escaped names of signatures are not escaped again
+    assert_eq!(Markdown.escape("Map<K, _V>"), Markdown.inline(&Markdown.escape("Map<K, _V>")));
+}
+
+#[test]
+fn test_method_signature() {
+    use model::model::{Method, Param};
+
+    let mut method = Method::new();
+    method.ch_method_name("find".to_string());
+    method.ch_privacy("public".to_string());
+    method.add_modifier("static".to_string());
+    method.ch_type_params(vec![Param {
+        desc: String::new(),
+        name: "T".to_string(),
+        var_type: "Comparable<T>".to_string(),
+        line_num: String::new(),
+    }]);
+    method.ch_return_type("Map<String,\n        List<T>>".to_string());
+    method.add_param(Param {
+        desc: String::new(),
+        name: "keys".to_string(),
+        var_type: "String...".to_string(),
+        line_num: String::new(),
+    });
+    method.add_throws("IOException".to_string());
+
+    assert_eq!(
+        "public static <T extends Comparable<T>> Map<String, List<T>> find(String... keys) throws IOException",
+        method_signature(&method)
+    );
+
+    let mut constructor = Method::new();
+    constructor.ch_method_name("Service".to_string());
+
+    assert_eq!("Service()", method_signature(&constructor));
+}
+
+#[test]
+fn test_glob_match() {
+    assert!(glob_match("**/test/**", "src/test"));
+    assert!(glob_match("**/test/**", "test/Foo.java"));
+    assert!(glob_match("**/test/**", "a/b/test/c/Foo.java"));
+    assert!(!glob_match("**/test/**", "src/testing/Foo.java"));
+    assert!(glob_match("**/*.java", "Foo.java"));
+    assert!(glob_match("src/*/Foo?.java", "src/main/Foo1.java"));
+    assert!(!glob_match("src/*/Foo?.java", "src/main/java/Foo1.java"));
+    assert!(glob_match("target", "target"));
+    assert!(!glob_match("target", "src/target"));
+}
+
+#[test]
+fn test_expand_input() {
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+
+    let root = env::temp_dir().join(format!("lojidoc-expand-{}", std::process::id()));
+    for dir in &["core/src/main/java", "plugins/b/src/main/java", "plugins/a/src/main/java", "plugins/c/src"] {
+        fs::create_dir_all(root.join(dir)).unwrap();
+    }
+    let root_str = root.to_str().unwrap();
+
+    let plugins = expand_input(&format!("{}/plugins/*/src/main/java", root_str));
+    let nested = expand_input(&format!("{}/**/main/java", root_str));
+    let missing = expand_input(&format!("{}/missing/*", root_str));
+    let plain = expand_input("src/main/java");
+
+    fs::remove_dir_all(&root).unwrap();
+
+    assert_eq!(
+        vec![root.join("plugins/a/src/main/java"), root.join("plugins/b/src/main/java")],
+        plugins
+    );
+    assert_eq!(
+        vec![
+            root.join("core/src/main/java"),
+            root.join("plugins/a/src/main/java"),
+            root.join("plugins/b/src/main/java"),
+        ],
+        nested
+    );
+    assert_eq!(vec![PathBuf::from(format!("{}/missing/*", root_str))], missing);
+    assert_eq!(vec![PathBuf::from("src/main/java")], plain);
+}
+
+#[test]
+fn test_find_java_files_with_filter() {
+    use std::env;
+    use std::fs;
+
+    let root = env::temp_dir().join(format!("lojidoc-filter-{}", std::process::id()));
+    let main = root.join("src/main/com/api");
+    let test = root.join("src/test");
+
+    fs::create_dir_all(&main).unwrap();
+    fs::create_dir_all(&test).unwrap();
+    fs::write(main.join("Client.java"), "class Client {}").unwrap();
+    fs::write(root.join("src/main/Main.java"), "class Main {}").unwrap();
+    fs::write(test.join("Broken.java"), "class {").unwrap();
+
+    let filter = PathFilter {
+        exclude: vec!["**/test/**".to_string()],
+        include: Vec::new(),
+        gitignore: false,
+        follow_symlinks: false,
+        source_roots: Vec::new(),
+    };
+    let (mut files, failed) = find_java_files(&root, &filter);
+    files.sort();
+
+    assert_eq!(vec![root.join("src/main/Main.java"), main.join("Client.java")], files);
+    assert!(failed.is_empty());
+
+    let filter = PathFilter {
+        exclude: Vec::new(),
+        include: vec!["**/api/*.java".to_string()],
+        gitignore: false,
+        follow_symlinks: false,
+        source_roots: Vec::new(),
+    };
+    let (files, _) = find_java_files(&root, &filter);
+
+    fs::remove_dir_all(&root).unwrap();
+
+    assert_eq!(vec![main.join("Client.java")], files);
+}
+
+#[test]
+fn test_find_java_files_with_gitignore() {
+    use std::env;
+    use std::fs;
+
+    let root = env::temp_dir().join(format!("lojidoc-gitignore-{}", std::process::id()));
+    let src = root.join("src");
+
+    fs::create_dir_all(root.join(".git")).unwrap();
+    fs::create_dir_all(src.join("generated")).unwrap();
+    fs::create_dir_all(src.join("target")).unwrap();
+    fs::create_dir_all(src.join("api")).unwrap();
+    fs::write(root.join(".gitignore"), "# build output\ngenerated/\n*.gen.java\n").unwrap();
+    fs::write(src.join("api/.gitignore"), "!Keep.gen.java\n").unwrap();
+    fs::write(src.join("Main.java"), "class Main {}").unwrap();
+    fs::write(src.join("Stub.gen.java"), "class Stub {}").unwrap();
+    fs::write(src.join("api/Keep.gen.java"), "class Keep {}").unwrap();
+    fs::write(src.join("generated/Proto.java"), "class Proto {}").unwrap();
+    fs::write(src.join("target/Out.java"), "class Out {}").unwrap();
+
+    let filter = PathFilter {
+        exclude: DEFAULT_EXCLUDES.iter().map(|pattern| pattern.to_string()).collect(),
+        include: Vec::new(),
+        gitignore: true,
+        follow_symlinks: false,
+        source_roots: Vec::new(),
+    };
+    let (mut files, _) = find_java_files(&src, &filter);
+    files.sort();
+
+    let (all, _) = find_java_files(&src, &PathFilter::default());
+
+    fs::remove_dir_all(&root).unwrap();
+
+    assert_eq!(vec![src.join("Main.java"), src.join("api/Keep.gen.java")], files);
+    assert_eq!(5, all.len());
+}
+
+#[test]
+fn test_find_java_files_in_build_modules() {
+    use std::env;
+    use std::fs;
+
+    let root = env::temp_dir().join(format!("lojidoc-modules-{}", std::process::id()));
+    let api = root.join("api/src/main/java/com/api");
+    let tests = root.join("api/src/test/java/com/api");
+    let app = root.join("apps/app/src/main/java");
+
+    fs::create_dir_all(&api).unwrap();
+    fs::create_dir_all(&tests).unwrap();
+    fs::create_dir_all(&app).unwrap();
+    fs::create_dir_all(root.join("tools")).unwrap();
+    fs::write(root.join("pom.xml"), "<project/>").unwrap();
+    fs::write(root.join("api/pom.xml"), "<project/>").unwrap();
+    fs::write(root.join("apps/app/build.gradle.kts"), "").unwrap();
+    fs::write(api.join("Client.java"), "class Client {}").unwrap();
+    fs::write(tests.join("ClientTest.java"), "class ClientTest {}").unwrap();
+    fs::write(app.join("Main.java"), "class Main {}").unwrap();
+    fs::write(root.join("tools/Gen.java"), "class Gen {}").unwrap();
+
+    let mut filter = PathFilter {
+        source_roots: MAIN_SOURCE_ROOTS.iter().map(|root| root.to_string()).collect(),
+        ..PathFilter::default()
+    };
+    let (mut files, failed) = find_java_files(&root, &filter);
+    files.sort();
+
+    filter.source_roots.push(TEST_SOURCE_ROOTS[0].to_string());
+    let (mut with_tests, _) = find_java_files(&root, &filter);
+    with_tests.sort();
+
+    // The source roots of the modules above the input directory do not apply
+    let (in_tests, _) = find_java_files(&root.join("api/src/test/java"), &filter);
+    let (all, _) = find_java_files(&root, &PathFilter::default());
+
+    fs::remove_dir_all(&root).unwrap();
+
+    assert_eq!(vec![api.join("Client.java"), app.join("Main.java")], files);
+    assert!(failed.is_empty());
+    assert_eq!(
+        vec![api.join("Client.java"), tests.join("ClientTest.java"), app.join("Main.java")],
+        with_tests
+    );
+    assert_eq!(vec![tests.join("ClientTest.java")], in_tests);
+    assert_eq!(4, all.len());
+}
+
+#[test]
+fn test_find_java_files_for_a_file() {
+    use std::env;
+    use std::fs;
+
+    let root = env::temp_dir().join(format!("lojidoc-file-{}", std::process::id()));
+
+    fs::create_dir_all(&root).unwrap();
+    fs::write(root.join("Foo.java"), "class Foo {}").unwrap();
+    fs::write(root.join("notes.txt"), "").unwrap();
+
+    let (files, failed) = find_java_files(&root.join("Foo.java"), &PathFilter::default());
+    let (others, _) = find_java_files(&root.join("notes.txt"), &PathFilter::default());
+    let (missing, missing_failed) =
+        find_java_files(&root.join("Missing.java"), &PathFilter::default());
+
+    fs::remove_dir_all(&root).unwrap();
+
+    assert_eq!(vec![root.join("Foo.java")], files);
+    assert!(failed.is_empty());
+    assert!(others.is_empty());
+    assert!(missing.is_empty());
+    assert_eq!(
+        vec![Some(root.join("Missing.java").as_path())],
+        missing_failed.iter().map(|err| err.path()).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_lint_project() {
+    use model::model::{ObjectType, Project};
+    use parse::parse::parse_file;
+    use std::env;
+    use std::fs;
+
+    let root = env::temp_dir().join(format!("lojidoc-lint-{}", std::process::id()));
+    fs::create_dir_all(&root).unwrap();
+    fs::write(
+        root.join("Foo.java"),
+        "package p;\n\npublic class Foo {\n    /**\n     * Adds.\n     * @param a The first\n     * @param b\n     * @param c The removed parameter\n     */\n    public int add(int a, int b) {\n        return a + b;\n    }\n\n    private void hidden(int x) {\n    }\n\n    /** Runs. */\n    public interface Task {\n        void run();\n    }\n}\n",
+    ).unwrap();
+
+    let mut proj = Project::new();
+    for object in parse_file(&root.join("Foo.java"), Charset::Auto, true).unwrap() {
+        if let ObjectType::Class(mut class) = object {
+            class.ch_file_path("Foo.java".to_string());
+            proj.add_class(class);
+        }
+    }
+    fs::remove_dir_all(&root).unwrap();
+
+    assert_eq!(
+        vec![
+            "Foo.java:3: public class Foo has no javadoc",
+            "Foo.java:10: parameter b of method add has no description",
+            "Foo.java:10: @param c does not match a parameter of method add",
+            "Foo.java:19: public method run has no description",
+        ],
+        lint_project(proj)
+    );
+    assert!(!is_documented(" "));
+    assert!(is_documented("The first"));
+}
+
+#[test]
+fn test_output_paths() {
+    use model::model::{Class, Project};
+
+    let mut proj = Project::new();
+    for package in &["com.foo", "com.bar"] {
+        let mut class = Class::new();
+        class.name = "Client".to_string();
+        class.package_name = package.to_string();
+        proj.add_class(class);
+    }
+
+    let mut output = Output {
+        format: Format::Html,
+        book: false,
+        flat: false,
+        single_file: false,
+        mdbook: false,
+        docusaurus: false,
+        min_visibility: Visibility::Private,
+        methods: MethodLayout::default(),
+        hide_jdk_imports: false,
+        front_matter: None,
+        templates: None,
+        clean: false,
+        diagrams: false,
+        diagram_methods: false,
+        kept: Default::default(),
+        charset: Charset::Auto,
+    };
+    let path = |path: &str, name: &str| (path.to_string(), name.to_string());
+
+    assert_eq!(
+        vec![
+            path("com/foo/Client.html", "com.foo.Client"),
+            path("com/bar/Client.html", "com.bar.Client"),
+            path("index.html", ""),
+            path("api-index.html", ""),
+        ],
+        output_paths(&proj, &output)
+    );
+
+    output.format = Format::Json;
+    output.flat = true;
+    assert_eq!(
+        vec![path("Client.json", "com.foo.Client"), path("Client.json", "com.bar.Client")],
+        output_paths(&proj, &output)
+    );
+
+    output.single_file = true;
+    assert_eq!(vec![path("api.json", "")], output_paths(&proj, &output));
+}
+
+#[test]
+fn test_filter_visibility() {
+    use model::model::{Class, Method, Object, Project};
+
+    let method = |name: &str, privacy: &str| {
+        let mut method = Method::new();
+        method.ch_method_name(name.to_string());
+        method.ch_privacy(privacy.to_string());
+        method
+    };
+    let class = |name: &str, access: &str, methods: Vec<Method>| {
+        let mut class = Class::new();
+        class.name = name.to_string();
+        class.access = access.to_string();
+        class.methods = methods;
+        class
+    };
+
+    let project = || {
+        let mut proj = Project::new();
+        proj.add_class(class(
+            "Client",
+            "public",
+            vec![method("run", "public"), method("check", "protected"), method("load", "")],
+        ));
+        proj.add_class(class("Helper", "", vec![method("help", "public")]));
+        proj.add_class(class("Internal", "public", vec![method("reset", "private")]));
+        proj.add_class(class("Marker", "public", Vec::new()));
+
+        let mut inter = Object::new();
+        inter.ch_name("Service".to_string());
+        inter.ch_access("public".to_string());
+        inter.add_method(method("find", ""));
+        proj.add_interface(inter.to_interface());
+        proj
+    };
+
+    let names = |proj: &Project| -> Vec<String> {
+        proj.classes.iter().map(|class| class.name.clone()).collect()
+    };
+
+    let mut all = project();
+    filter_visibility(&mut all, Visibility::Private);
+    assert_eq!(vec!["Client", "Helper", "Internal", "Marker"], names(&all));
+
+    let mut package = project();
+    filter_visibility(&mut package, Visibility::Package);
+    assert_eq!(vec!["Client", "Helper", "Marker"], names(&package));
+    assert_eq!(3, package.classes[0].methods.len());
+
+    let mut proj = project();
+    filter_visibility(&mut proj, Visibility::Public);
+    assert_eq!(vec!["Client", "Marker"], names(&proj));
+    assert_eq!(1, proj.classes[0].methods.len());
+    assert_eq!(1, proj.interfaces[0].methods.len());
+}
+
+#[test]
+fn test_interface_default_methods() {
+    use model::model::{ObjectType, Project};
+    use parse::parse::parse_str;
+
+    let source = "public interface Client {
+                      /**
+                       * The policy of failed requests
+                       *
+                       * @return The policy
+                       */
+                      default RetryPolicy retryPolicy() {
+                          if (retries() > 0) { return new RetryPolicy(retries()) { }; }
+                          return RetryPolicy.NONE;
+                      }
+
+                      /** Creates a client */
+                      static Client create(String url) {
+                          return new HttpClient(url);
+                      }
+
+                      /** Counts the retries */
+                      private int retries() {
+                          return 3;
+                      }
+
+                      /** Sends a request */
+                      void send(String body);
+                  }";
+    let inter = match parse_str(source, None).unwrap().remove(0) {
+        ObjectType::Interface(inter) => inter,
+        _ => panic!("Expected an interface"),
+    };
+
+    assert_eq!(
+        vec!["retryPolicy", "create", "retries", "send"],
+        inter.methods.iter().map(|m| m.name.as_str()).collect::<Vec<_>>()
+    );
+    assert_eq!("private", inter.methods[2].privacy);
+    assert_eq!("Sends a request", inter.methods[3].description);
+
+    let page = written_string(|out| gen_interface_page(out, &Markdown, inter.clone(), &PageLayout::default()));
+    assert!(page.contains("### retryPolicy (default)\n"));
+    assert!(page.contains("### create (static)\n"));
+    assert!(page.contains("### send\n"));
+
+    let mut proj = Project::new();
+    proj.add_interface(inter);
+    filter_visibility(&mut proj, Visibility::Public);
+    assert_eq!(3, proj.interfaces[0].methods.len());
+}
+
+#[test]
+fn test_method_layout() {
+    use model::model::ObjectType;
+    use parse::parse::parse_str;
+
+    let source = "public class Client {
+                      private void reset() { }
+                      public void send(String body, int retries) { }
+                      protected void close() { }
+                      public void send(String body) { }
+                      public void Connect() { }
+                  }";
+    let class = match parse_str(source, None).unwrap().remove(0) {
+        ObjectType::Class(class) => class,
+        _ => panic!("Expected a class"),
+    };
+    let headings = |page: &str| -> Vec<String> {
+        page.lines()
+            .filter(|line| line.starts_with("### ") || line.starts_with("#### "))
+            .map(|line| line.to_string())
+            .collect()
+    };
+
+    let page = written_string(|out| gen_class_page(out, &Markdown, class.clone(), &PageLayout::default()));
+    assert_eq!(
+        vec!["### reset", "### send(String, int)", "### close", "### send(String)", "### Connect"],
+        headings(&page)
+    );
+
+    let alpha = PageLayout {
+        methods: MethodLayout {
+            order: MethodOrder::Alpha,
+            group_by_visibility: false,
+            include_source: None,
+        },
+        ..PageLayout::default()
+    };
+    let page = written_string(|out| gen_class_page(out, &Markdown, class.clone(), &alpha));
+    assert_eq!(
+        vec!["### close", "### Connect", "### reset", "### send(String)", "### send(String, int)"],
+        headings(&page)
+    );
+
+    let grouped = PageLayout {
+        methods: MethodLayout {
+            order: MethodOrder::Alpha,
+            group_by_visibility: true,
+            include_source: None,
+        },
+        ..PageLayout::default()
+    };
+    let page = written_string(|out| gen_class_page(out, &Markdown, class, &grouped));
+    assert_eq!(
+        vec![
+            "### Public methods",
+            "#### Connect",
+            "#### send(String)",
+            "#### send(String, int)",
+            "### Protected methods",
+            "#### close",
+            "### Private methods",
+            "#### reset",
+        ],
+        headings(&page)
+    );
+    assert_eq!(Some(MethodOrder::Alpha), MethodOrder::from_name("alpha"));
+    assert_eq!(None, MethodOrder::from_name("size"));
+}
+
+#[test]
+fn test_include_source() {
+    use model::model::ObjectType;
+    use parse::parse::parse_str;
+
+    let source = "public class Counter {
+    int count;
+
+    public int next() {
+        return ++count;
+    }
+
+    public void reset() {
+        count = 0;
+        count += 0;
+    }
+
+    public abstract void close();
+}";
+    let class = match parse_str(source, None).unwrap().remove(0) {
+        ObjectType::Class(class) => class,
+        _ => panic!("Expected a class"),
+    };
+    let layout = PageLayout {
+        methods: MethodLayout {
+            include_source: Some(3),
+            ..MethodLayout::default()
+        },
+        ..PageLayout::default()
+    };
+
+    let page = written_string(|out| gen_class_page(out, &Markdown, class.clone(), &layout));
+    assert!(page.contains("```java\npublic int next() {\n    return ++count;\n}\n```"));
+    assert!(page.contains("Source omitted (4 lines)"));
+    assert_eq!(4, page.matches("```java").count());
+
+    let page = written_string(|out| gen_class_page(out, &Markdown, class, &PageLayout::default()));
+    assert!(!page.contains("return ++count;"));
+    assert!(!page.contains("Source omitted"));
+}
+
+#[test]
+fn test_overload_headings() {
+    use document::html::Html;
+    use model::model::ObjectType;
+    use parse::parse::parse_str;
+
+    let source = "public class Range {
+                      public Range() { }
+                      public Range(int end) { }
+                      public static Range of(String text, int radix) { return null; }
+                      public static Range of(java.util.Map<String, List<Long>> bounds) { return null; }
+                      public static Range of(int... ends) { return null; }
+                      public static Range of(int[] ends, boolean open) { return null; }
+                      public int size() { return 0; }
+                  }";
+    let class = match parse_str(source, None).unwrap().remove(0) {
+        ObjectType::Class(class) => class,
+        _ => panic!("Expected a class"),
+    };
+
+    let page = written_string(|out| gen_class_page(out, &Markdown, class.clone(), &PageLayout::default()));
+    assert!(page.contains("### Range()\n"));
+    assert!(page.contains("### Range(int)\n"));
+    assert!(page.contains("### of(String, int) (static)\n"));
+    assert!(page.contains("### of(Map) (static)\n"));
+    assert!(page.contains("### of(int...) (static)\n"));
+    assert!(page.contains("### of(int\\[\\], boolean) (static)\n"));
+    assert!(page.contains("### size\n"));
+    assert!(page.contains("<a id=\"of-string-int\"></a>\n\n### of(String, int) (static)\n"));
+
+    let page = written_string(|out| gen_class_page(out, &Html, class, &PageLayout::default()));
+    assert!(page.contains("<h3 id=\"range\">Range()</h3>"));
+    assert!(page.contains("<h3 id=\"range-int\">Range(int)</h3>"));
+    assert!(page.contains("<h3 id=\"of-string-int\">of(String, int) (static)</h3>"));
+    assert!(page.contains("<h3 id=\"of-map\">of(Map) (static)</h3>"));
+    assert!(page.contains("<h3 id=\"of-int-varargs\">of(int...) (static)</h3>"));
+    assert!(page.contains("<h3 id=\"of-int-array-boolean\">of(int[], boolean) (static)</h3>"));
+    assert!(page.contains("<h3 id=\"size\">size</h3>"));
+
+    assert_eq!("Map[]", simple_type_name("java.util.Map<String, List<Long>>[]"));
+    assert_eq!("Entry", simple_type_name("Map.Entry<K, V>"));
+}
+
+#[test]
+fn test_dependency_groups() {
+    use document::html::Html;
+    use model::model::Class;
+
+    let mut class = Class::new();
+    class.name = "Service".to_string();
+    class.package_name = "com.foo".to_string();
+    class.dependencies = vec![
+        "java.util.List".to_string(),
+        "static java.util.Collections.sort".to_string(),
+        "com.bar.Client".to_string(),
+        "com.bar.*".to_string(),
+        "static com.bar.Client.connect".to_string(),
+        "org.slf4j.Logger".to_string(),
+    ];
+    let names = vec!["com.bar.Client".to_string()];
+    let mut layout = PageLayout::default();
+    layout.packages.insert("com.bar".to_string());
+
+    let page = written_string(|out| gen_class_page(out, &Markdown, class.clone(), &layout));
+    let page = convert_inline_tags(&Markdown, &page, &names, "com.foo");
+    assert!(page.contains(
+        "- JDK
+  - java.util.List
+  - java.util.Collections.sort (static)
+- Project
+  - [com.bar.Client](../../com/bar/Client.md)
+  - com.bar.\\* (wildcard)
+  - [com.bar.Client](../../com/bar/Client.md).connect (static)
+- Third-party
+  - org.slf4j.Logger
+"
+    ));
+
+    layout.hide_jdk_imports = true;
+    let page = written_string(|out| gen_class_page(out, &Html, class.clone(), &layout));
+    let page = convert_inline_tags(&Html, &page, &names, "com.foo");
+    assert!(page.contains("<li>Project\n<ul>\n<li><a href=\"../../com/bar/Client.html\">com.bar.Client</a></li>\n"));
+    assert!(!page.contains("java.util"));
+
+    class.dependencies.truncate(2);
+    assert!(!written_string(|out| gen_class_page(out, &Markdown, class, &layout)).contains("Dependencies"));
+}
+
+#[test]
+fn test_stale_pages() {
+    use std::collections::HashSet;
+    use std::env;
+    use std::fs;
+
+    let root = env::temp_dir().join(format!("lojidoc-clean-{}", std::process::id()));
+    let dest = root.to_str().unwrap();
+
+    fs::create_dir_all(root.join("com/foo")).unwrap();
+    fs::write(root.join("com/foo/Client.md"), "# Class Client [[src]](Client.java)\n").unwrap();
+    fs::write(root.join("com/foo/Server.md"), "---\ntitle: \"Server\"\n---\n# Interface Server\n").unwrap();
+    fs::write(root.join("com/foo/Old.html"), "<body>\n<h1>Enum Old</h1>\n").unwrap();
+    fs::write(root.join("index.md"), "# Index\n").unwrap();
+    fs::write(root.join("notes.md"), "# Classes of the project\n").unwrap();
+
+    let written: HashSet<String> = vec!["com/foo/Client.md".to_string()].into_iter().collect();
+    let markdown = stale_pages(dest, &written, Format::Markdown);
+    let html = stale_pages(dest, &HashSet::new(), Format::Html);
+
+    fs::remove_dir_all(&root).unwrap();
+
+    assert_eq!(vec![root.join("com/foo/Server.md")], markdown);
+    assert_eq!(vec![root.join("com/foo/Old.html")], html);
+    assert!(is_type_page("= Annotation Marker\n", Format::Asciidoc));
+    assert!(is_type_page("{\"kind\": \"class\", \"package_name\": \"com.foo\"}", Format::Json));
+    assert!(!is_type_page("{\"kind\": \"class\"}", Format::Json));
+}
+
+#[test]
+fn test_generated_files() {
+    use std::path::Path;
+
+    let md = |path: &str, contents: &str| is_generated_file(Path::new(path), contents, Format::Markdown);
+
+    // The pages of earlier runs are not in conflict with the pages of a run
+    assert!(md("com/foo/Client.md", "---\nid: \"Client\"\n---\n# Class Client\n"));
+    assert!(md("index.md", "# Index\n\n## com.foo\n"));
+    assert!(md("api-index.md", "# API Index\n"));
+    assert!(md("SUMMARY.md", "# Summary\n\n[Index](index.md)\n"));
+    assert!(md("com/foo/diagram.md", "# Class diagram of com.foo\n"));
+    assert!(md("sidebar.json", "[\"index\"]"));
+    assert!(is_generated_file(Path::new("index.html"), "<html>\n<body>\n<h1>Index</h1>\n", Format::Html));
+    assert!(is_generated_file(Path::new("api.json"), "{\"classes\": []}", Format::Json));
+
+    // Hand-written files are in conflict
+    assert!(!md("index.md", "My notes\n"));
+    assert!(!md("com/foo/Client.md", "# Client\n\nHow to use the client.\n"));
+    assert!(!md("sidebar.json", "{\"docs\": []}"));
+    assert!(!md("api.json", "not json"));
+}
+
+#[test]
+fn test_kept_files() {
+    use model::model::{ApplicationDoc, Class, Project};
+    use std::collections::BTreeSet;
+    use std::env;
+    use std::fs;
+    use std::sync::Arc;
+
+    let root = env::temp_dir().join(format!("lojidoc-kept-{}", std::process::id()));
+    let dest = root.to_str().unwrap();
+    fs::create_dir_all(&root).unwrap();
+    fs::write(root.join("Client.md"), "How to use the client\n").unwrap();
+    fs::write(root.join("index.md"), "My notes\n").unwrap();
+
+    let mut proj = Project::new();
+    for name in &["Client", "Server"] {
+        let mut class = Class::new();
+        class.name = name.to_string();
+        class.package_name = "com.foo".to_string();
+        proj.add_class(class);
+    }
+    let kept: BTreeSet<_> = vec![root.join("Client.md"), root.join("index.md")].into_iter().collect();
+    let output = Output {
+        format: Format::Markdown,
+        book: false,
+        flat: true,
+        single_file: false,
+        mdbook: false,
+        docusaurus: false,
+        min_visibility: Visibility::Private,
+        methods: MethodLayout::default(),
+        hide_jdk_imports: false,
+        front_matter: None,
+        templates: None,
+        clean: false,
+        diagrams: false,
+        diagram_methods: false,
+        kept: Arc::new(kept.clone()),
+        charset: Charset::Auto,
+    };
+
+    let app_doc = generate_pages(proj, dest, &[], &output, &Markdown).unwrap();
+    generate_index(&Markdown, &app_doc, dest, &kept).unwrap();
+    let client = fs::read_to_string(root.join("Client.md")).unwrap();
+    let server = fs::read_to_string(root.join("Server.md")).unwrap();
+    let index = fs::read_to_string(root.join("index.md")).unwrap();
+
+    // Another run without kept files writes them
+    generate_index(&Markdown, &ApplicationDoc::new(), dest, &BTreeSet::new()).unwrap();
+    let rewritten = fs::read_to_string(root.join("index.md")).unwrap();
+    fs::remove_dir_all(&root).unwrap();
+
+    assert_eq!("How to use the client\n", client);
+    assert!(server.starts_with("# Class Server"));
+    assert_eq!("My notes\n", index);
+    assert!(rewritten.starts_with("# Index"));
+}
+
+#[test]
+fn test_render_pages() {
+    use model::model::{Class, Project};
+
+    let mut proj = Project::new();
+    for name in &["Client", "Server"] {
+        let mut class = Class::new();
+        class.name = name.to_string();
+        class.package_name = "com.foo".to_string();
+        class.description = format!("The {}. Connects.", name.to_lowercase());
+        proj.add_class(class);
+    }
+    let output = Output {
+        format: Format::Markdown,
+        book: false,
+        flat: true,
+        single_file: false,
+        mdbook: false,
+        docusaurus: false,
+        min_visibility: Visibility::Private,
+        methods: MethodLayout::default(),
+        hide_jdk_imports: false,
+        front_matter: Some(FrontMatter::default()),
+        templates: None,
+        clean: false,
+        diagrams: false,
+        diagram_methods: false,
+        kept: Default::default(),
+        charset: Charset::Auto,
+    };
+
+    let pages = render_pages(proj, &[], &output, &Markdown).unwrap();
+    assert_eq!(
+        vec!["Client.md", "Server.md"],
+        pages.iter().map(|page| page.path.as_str()).collect::<Vec<_>>()
+    );
+    assert!(pages[1]
+        .doc
+        .starts_with("---\ntitle: \"Server\"\ndescription: \"The server.\"\npackage: \"com.foo\"\n---\n# Class Server\n"));
+    assert_eq!("com.foo", pages[1].package);
+}
+
+#[test]
+fn test_api_index() {
+    use document::html::Html;
+    use document::render::Renderer;
+    use model::model::ApplicationDoc;
+    use model::model::ObjectType;
+    use model::model::Project;
+    use parse::parse::parse_str;
+
+    let source = "package net;
+public class Client {
+    public Client timeout(int seconds) { return this; }
+    public Client timeout(java.time.Duration duration) { return this; }
+    private void reset() {}
+    public void close() {}
+    public static class Builder {
+        public Client build() { return null; }
+        public void close() {}
+    }
+}
+";
+    let project = || {
+        let mut project = Project::new();
+        for object in parse_str(source, None).unwrap() {
+            if let ObjectType::Class(class) = object {
+                project.add_class(class);
+            }
+        }
+        project
+    };
+    let mut output = Output {
+        format: Format::Markdown,
+        book: false,
+        flat: false,
+        single_file: false,
+        mdbook: false,
+        docusaurus: false,
+        min_visibility: Visibility::Private,
+        methods: MethodLayout::default(),
+        hide_jdk_imports: false,
+        front_matter: None,
+        templates: None,
+        clean: false,
+        diagrams: false,
+        diagram_methods: false,
+        kept: Default::default(),
+        charset: Charset::Auto,
+    };
+    let index = |output: &Output, r: &dyn Renderer| {
+        let mut app_doc = ApplicationDoc::new();
+        for (page, member) in render_members(project(), &[], output, r).unwrap() {
+            app_doc.add_package_member(page.package, member);
+        }
+        gen_api_index_page(r, &app_doc)
+    };
+
+    // The anchors of the methods of nested types start with the name of the type
+    assert_eq!(
+        "# API Index\n\n\
+         + [build()](net/Client.md#builder.build) — Client.Builder  \n\
+         + [close()](net/Client.md#close) — Client  \n\
+         + [close()](net/Client.md#builder.close) — Client.Builder  \n\
+         + [timeout(Duration)](net/Client.md#timeout-duration) — Client  \n\
+         + [timeout(int)](net/Client.md#timeout-int) — Client  \n",
+        index(&output, &Markdown)
+    );
+
+    output.format = Format::Html;
+    let html = index(&output, &Html);
+    assert!(html.contains("<li><a href=\"net/Client.html#timeout-duration\">timeout(Duration)</a> — Client</li>"));
+    assert!(!html.contains("reset"));
+}
+
+#[test]
+fn test_deprecated_members() {
+    use document::html::Html;
+    use model::model::ApplicationDoc;
+    use model::model::ObjectType;
+    use model::model::Project;
+    use parse::parse::parse_str;
+
+    let source = "package net;
+/**
+ * Sends requests.
+ * @deprecated Use {@link Client} instead
+ */
+@Deprecated
+public class Sender {}
+/** Sends requests. */
+public class Client {}
+";
+    let mut project = Project::new();
+    for object in parse_str(source, None).unwrap() {
+        if let ObjectType::Class(class) = object {
+            project.add_class(class);
+        }
+    }
+    // The front matter comes before the deprecation notice of the pages
+    let output = Output {
+        format: Format::Markdown,
+        book: false,
+        flat: false,
+        single_file: false,
+        mdbook: true,
+        docusaurus: false,
+        min_visibility: Visibility::Private,
+        methods: MethodLayout::default(),
+        hide_jdk_imports: false,
+        front_matter: Some(FrontMatter::default()),
+        templates: None,
+        clean: false,
+        diagrams: false,
+        diagram_methods: false,
+        kept: Default::default(),
+        charset: Charset::Auto,
+    };
+    let mut app_doc = ApplicationDoc::new();
+    for (page, member) in render_members(project, &[], &output, &Markdown).unwrap() {
+        app_doc.add_package_member(page.package, member);
+    }
+
+    assert!(gen_summary(&app_doc).contains("    - [Client](net/Client.md)\n    - [~~Sender~~](net/Sender.md)\n"));
+    assert_eq!(
+        "# Index\n\n## net\n\n\
+         | Type                        | Summary         |\n\
+         | --------------------------- | --------------- |\n\
+         | [Client](net/Client.md)     | Sends requests. |\n\
+         | ~~[Sender](net/Sender.md)~~ | Sends requests. |\n\n",
+        gen_index_page(&Markdown, &app_doc)
+    );
+    assert!(gen_index_page(&Html, &app_doc).contains("<del><a href=\"net/Sender.md\">Sender</a></del>"));
+}
+
+#[test]
+fn test_module_page() {
+    use model::model::Module;
+    use model::model::ModuleDirective;
+
+    let directive = |kind: &str, modifiers: &[&str], name: &str, targets: &[&str]| ModuleDirective {
+        kind: kind.to_string(),
+        modifiers: modifiers.iter().map(|word| word.to_string()).collect(),
+        name: name.to_string(),
+        targets: targets.iter().map(|word| word.to_string()).collect(),
+    };
+    let module = Module {
+        name: "com.example".to_string(),
+        description: "The example module".to_string(),
+        open: false,
+        directives: vec![
+            directive("requires", &["transitive"], "java.sql", &[]),
+            directive("exports", &[], "com.example.api", &[]),
+            directive("exports", &[], "com.example.spi", &["com.example.plugins", "com.example.test"]),
+            directive("provides", &[], "com.example.spi.Plugin", &["com.example.Default"]),
+        ],
+    };
+
+    assert_eq!("module.md", module_page_path("md"));
+    assert_eq!(
+        "# Module com.example\n\n\
+         Description:  \n > The example module  \n\n\
+         ## Requires\n\n\
+         | Module     | Modifiers  |\n\
+         | ---------- | ---------- |\n\
+         | `java.sql` | transitive |\n\n\
+         ## Exports\n\n\
+         | Package           | To                                        |\n\
+         | ----------------- | ----------------------------------------- |\n\
+         | `com.example.api` |                                           |\n\
+         | `com.example.spi` | `com.example.plugins`, `com.example.test` |\n\n\
+         ## Provides\n\n\
+         | Service                  | With                  |\n\
+         | ------------------------ | --------------------- |\n\
+         | `com.example.spi.Plugin` | `com.example.Default` |\n\n",
+        gen_module_page(&Markdown, &module)
+    );
+}
+
+#[test]
+fn test_source_links() {
+    use std::path::Path;
+    use std::path::PathBuf;
+
+    let links = SourceLinks {
+        context: "https://github.com/org/repo".to_string(),
+        template: "https://github.com/org/repo/blob/main/{path}#L{line}".to_string(),
+        inputs: vec![PathBuf::from("src/main/java"), PathBuf::from("tools/Tool.java")],
+    };
+    let file_url = links.file_url(Path::new("src/main/java/com/example/Foo.java"));
+
+    assert_eq!("https://github.com/org/repo/blob/main/com/example/Foo.java#L{line}", file_url);
+    assert_eq!("https://github.com/org/repo/blob/main/com/example/Foo.java#L12", source_link(&file_url, "12"));
+    assert_eq!("https://github.com/org/repo/blob/main/com/example/Foo.java#L3", type_source_link(&file_url, "3"));
+    assert_eq!(
+        "https://github.com/org/repo/blob/main/Tool.java#L{line}",
+        links.file_url(Path::new("tools/Tool.java"))
+    );
+
+    // Links without a line placeholder get an anchor for members only
+    assert_eq!("https://host/Foo.java#L12", source_link("https://host/Foo.java", "12"));
+    assert_eq!("https://host/Foo.java", type_source_link("https://host/Foo.java", "3"));
+    assert!(SourceLinks::default().is_empty());
+}
+
+#[test]
+fn test_package_private_type_access() {
+    use model::model::ObjectType;
+    use parse::parse::parse_str;
+
+    let source = "package com.foo;
+
+                  class Helper {
+                      enum Mode { FAST }
+                  }
+
+                  enum Kind { ROUND }
+
+                  interface Shapes {
+                      class Circle {
+                      }
+                  }";
+    let mut types = parse_str(source, None).unwrap();
+    let shapes = match types.remove(2) {
+        ObjectType::Interface(inter) => inter,
+        _ => panic!("Expected an interface"),
+    };
+    let kind = match types.remove(1) {
+        ObjectType::Enumeration(enumeration) => enumeration,
+        _ => panic!("Expected an enum"),
+    };
+    let helper = match types.remove(0) {
+        ObjectType::Class(class) => class,
+        _ => panic!("Expected a class"),
+    };
+
+    let helper_page = written_string(|out| gen_class_page(out, &Markdown, helper, &PageLayout::default()));
+    let kind_page = written_string(|out| gen_enum_page(out, &Markdown, kind, &PageLayout::default()));
+    let shapes_page = written_string(|out| gen_interface_page(out, &Markdown, shapes, &PageLayout::default()));
+
+    assert_eq!(2, helper_page.matches("Access: package-private").count(), "{}", helper_page);
+    assert!(kind_page.contains("Access: package-private"));
+    // The interface is package private and its nested class public
+    assert!(shapes_page.contains("Access: package-private"));
+    assert!(shapes_page.contains("Access: public"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_source_link_of_a_file_name_which_is_not_utf8() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+    use std::path::Path;
+
+    let file = Path::new("/lojidoc-missing").join(OsStr::from_bytes(b"Caf\xe9.java"));
+    let links = SourceLinks {
+        context: "https://github.com/org/repo".to_string(),
+        ..SourceLinks::default()
+    };
+
+    assert!(links.file_url(&file).ends_with("Caf\u{FFFD}.java"));
+    assert!(links.file_url(&file).starts_with("https://github.com/org/repo"));
+}
+
+/// The description of a parsed javadoc with characters markdown escapes
+fn front_matter_description() -> String {
+    use model::model::ObjectType;
+    use parse::parse::parse_source;
+
+    let source = "/**\n * Joins <i>max_value</i> paths of *.java files with <code>C:\\</code> when 2 * 2 < 5. Rest\n */\npublic class Paths {}\n";
+
+    match parse_source(source, None).objects.remove(0) {
+        ObjectType::Class(class) => class.description,
+        _ => panic!("Expected a class"),
+    }
+}
+
+#[test]
+fn test_front_matter() {
+    let front_matter = FrontMatter {
+        extra: vec![FrontMatter::parse_extra("layout=api").unwrap()],
+        docusaurus: false,
+    };
+    let docusaurus = FrontMatter {
+        extra: Vec::new(),
+        docusaurus: true,
+    };
+
+    assert_eq!(
+        "---\ntitle: \"Client\"\ndescription: \"Sends \\\"requests\\\": GET and POST.\"\n\
+         package: \"com.foo\"\nlayout: \"api\"\n---\n",
+        front_matter.gen_front_matter("Client", "Sends \"requests\": GET\n and POST. Retries once.", "com.foo")
+    );
+    assert_eq!(
+        "---\ntitle: \"Main\"\ndescription: \"\"\n---\n",
+        FrontMatter::default().gen_front_matter("Main", "", "")
+    );
+    assert_eq!(
+        "---\nid: \"Client\"\ntitle: \"Client\"\nsidebar_label: \"Client\"\ndescription: \"Sends requests.\"\n\
+         package: \"com.foo\"\n---\n",
+        docusaurus.gen_front_matter("Client", "Sends requests.", "com.foo")
+    );
+    assert_eq!(
+        "---\ntitle: \"Paths\"\ndescription: \"Joins max_value paths of *.java files with C:\\\\ when 2 * 2 < 5.\"\n---\n",
+        FrontMatter::default().gen_front_matter("Paths", &front_matter_description(), "")
+    );
+    assert_eq!(Ok(("weight".to_string(), "a=b".to_string())), FrontMatter::parse_extra("weight=a=b"));
+    assert!(FrontMatter::parse_extra("layout").is_err());
+    assert!(FrontMatter::parse_extra("lay out=api").is_err());
+}
+
+#[test]
+fn test_heading_slugs() {
+    assert_eq!("ofstring-int", heading_slug("of(String, int)"));
+    assert_eq!("ofstring-int-src", heading_slug("of(String, int) [[src]](Calc.java#L14)"));
+    assert_eq!("getlistt", heading_slug("get(List\\<T\\>)"));
+    assert_eq!("to_string", heading_slug("to_string"));
+    assert_eq!("ofint", heading_slug("of(int...)"));
+    assert_eq!("class-outerinner", heading_slug("Class Outer.Inner"));
+
+    let mut slugs = HeadingSlugs::new();
+    assert_eq!("run", slugs.slug("run"));
+    assert_eq!("run-1", slugs.slug("run"));
+    assert_eq!("run-1-1", slugs.slug("run-1"));
+    assert_eq!("run-2", slugs.slug("Run"));
+}
+
+#[test]
+fn test_contents() {
+    let doc = "# Class Calc [[src]](Calc.java)
+
+package: p  
+
+## Dependencies
+
+- JDK
+
+## Fields
+
+## Constructors
+
+### Calc() [[src]](Calc.java#L3)
+
+```java
+# not a heading
+```
+
+## Methods
+
+### Public methods
+
+#### run [[src]](Calc.java#L5)
+
+### Private methods
+
+#### run(int) [[src]](Calc.java#L6)
+
+## Inner Classes
+
+### Class Inner
+
+#### Methods
+
+##### run
+
+## No record components
+";
+    assert_eq!(
+        "Contents:  \n\n\
+         - [Fields](#fields)\n\
+         - [Constructors](#constructors)\n  \
+         - [Calc()](#calc-src)\n\
+         - [Methods](#methods)\n  \
+         - [Public methods](#public-methods)\n    \
+         - [run](#run-src)\n  \
+         - [Private methods](#private-methods)\n    \
+         - [run(int)](#runint-src)\n\
+         - [Inner Classes](#inner-classes)\n  \
+         - [Class Inner](#class-inner)\n\n",
+        gen_contents(doc)
+    );
+    assert_eq!("", gen_contents("# Class Empty\n\npackage: p  \n"));
+}
+
+#[test]
+fn test_stable_method_anchors() {
+    use document::html::Html;
+    use model::model::ObjectType;
+    use parse::parse::parse_str;
+
+    let page = |source: &str| {
+        let class = match parse_str(source, None).unwrap().remove(0) {
+            ObjectType::Class(class) => class,
+            _ => panic!("Expected a class"),
+        };
+        written_string(|out| gen_class_page(out, &Html, class, &PageLayout::default()))
+    };
+
+    let before = page("public class Repo {
+                           public Item findById(long id) { return null; }
+                           public static class Query { public Query where(String clause) { return this; } }
+                       }");
+    let after = page("public class Repo {
+                          public void close() { }
+                          public Item findById(String id) { return null; }
+                          public Item findById(long id) { return null; }
+                          public static class Query { public Query where(String clause) { return this; } }
+                      }");
+
+    for anchor in &["<h3 id=\"findbyid-long\">", "<h5 id=\"query.where-string\">"] {
+        assert!(before.contains(anchor), "{} is not in {}", anchor, before);
+        assert!(after.contains(anchor), "{} is not in {}", anchor, after);
+    }
+}
+
+#[test]
+fn test_method_anchors_of_section_names() {
+    use links::links::duplicate_anchors;
+    use model::model::ObjectType;
+    use parse::parse::parse_str;
+
+    let source = "public class Repo {
+                      public void methods() { }
+                      public int fields() { return 0; }
+                      public void run() { }
+                      public static class Query { public void methods() { } }
+                  }";
+    let class = match parse_str(source, None).unwrap().remove(0) {
+        ObjectType::Class(class) => class,
+        _ => panic!("Expected a class"),
+    };
+    let page = written_string(|out| gen_class_page(out, &Markdown, class, &PageLayout::default()));
+
+    assert!(page.contains("<a id=\"method-methods\"></a>"), "{}", page);
+    assert!(page.contains("<a id=\"method-fields\"></a>"));
+    assert!(page.contains("<a id=\"run\"></a>"));
+    assert!(page.contains("<a id=\"query.methods\"></a>"));
+    assert!(duplicate_anchors(&page, Format::Markdown).is_empty());
+}
+
+#[test]
+fn test_prepare_destination() {
+    use error::error::LojidocError;
+    use std::env;
+    use std::fs;
+
+    let root = env::temp_dir().join(format!("lojidoc-dest-{}", std::process::id()));
+    let nested = root.join("docs/api");
+    let file = root.join("notes.md");
+
+    prepare_destination(nested.to_str().unwrap()).unwrap();
+    fs::write(&file, "# Notes\n").unwrap();
+    let created = nested.is_dir() && fs::read_dir(&nested).unwrap().count() == 0;
+    let is_file = prepare_destination(file.to_str().unwrap());
+    let in_file = prepare_destination(file.join("api").to_str().unwrap());
+    fs::remove_dir_all(&root).unwrap();
+
+    // The probe file is removed again
+    assert!(created);
+    match is_file {
+        Err(LojidocError::InvalidArgs(message)) => {
+            assert_eq!(format!("the destination {} is a file, not a directory", file.display()), message)
+        }
+        result => panic!("Expected invalid arguments, got {:?}", result),
+    }
+    assert!(in_file.unwrap_err().to_string().ends_with(&format!("since {} is a file", file.display())));
+}
@@ -0,0 +1,171 @@
+pub mod error {
+    //! Module with the errors which stop a file or a run from being documented
+
+    use std::error;
+    use std::fmt;
+    use std::io;
+    use std::path::Path;
+    use std::path::PathBuf;
+
+    use config::config::ConfigError;
+
+    /// The exit code of a run in which files could not be read, parsed or written
+    pub const EXIT_GENERATION: i32 = 1;
+
+    /// The exit code of a run with invalid arguments or configuration
+    pub const EXIT_INVALID_ARGS: i32 = 2;
+
+    /// The exit code of a run which found no java files in its inputs, which usually
+    /// means the paths are wrong
+    pub const EXIT_NO_INPUTS: i32 = 3;
+
+    /// Errors which stop a file or a run from being documented
+    #[derive(Debug)]
+    pub enum LojidocError {
+        /// A file or directory could not be read or written
+        Io { path: PathBuf, err: io::Error },
+        /// A java file could not be parsed. The line is 0 when the parser failed
+        /// without knowing where.
+        Parse {
+            file: PathBuf,
+            line: usize,
+            message: String,
+        },
+        /// A `--template` file could not be compiled or rendered
+        Template {
+            template: PathBuf,
+            line: usize,
+            message: String,
+        },
+        /// A java file declares no type, e.g. it is empty or only has a package
+        /// statement and imports
+        NoTypes { file: PathBuf },
+        /// A file the run writes already exists and was not written by lojidoc,
+        /// found with `--on-conflict error`
+        Conflict { path: PathBuf },
+        /// The command line arguments or the configuration file are not valid
+        InvalidArgs(String),
+        /// A link of a generated page leads to a page or anchor which does not exist,
+        /// found by `--check-links`
+        BrokenLink { page: PathBuf, target: String },
+        /// An anchor is given to more than one place of a generated page, found by
+        /// `--check-links`
+        DuplicateAnchor { page: PathBuf, anchor: String },
+    }
+
+    impl LojidocError {
+        pub fn io(path: &Path, err: io::Error) -> LojidocError {
+            LojidocError::Io {
+                path: path.to_path_buf(),
+                err,
+            }
+        }
+        /// The path of the file the error happened in, if it has one
+        pub fn path(&self) -> Option<&Path> {
+            match self {
+                LojidocError::Io { path, .. } => Some(path),
+                LojidocError::Parse { file, .. } => Some(file),
+                LojidocError::Template { template, .. } => Some(template),
+                LojidocError::NoTypes { file } => Some(file),
+                LojidocError::Conflict { path } => Some(path),
+                LojidocError::InvalidArgs(_) => None,
+                LojidocError::BrokenLink { page, .. } => Some(page),
+                LojidocError::DuplicateAnchor { page, .. } => Some(page),
+            }
+        }
+        /// The exit code of a run which stopped with the error
+        pub fn exit_code(&self) -> i32 {
+            match self {
+                LojidocError::InvalidArgs(_) => EXIT_INVALID_ARGS,
+                _ => EXIT_GENERATION,
+            }
+        }
+    }
+
+    impl fmt::Display for LojidocError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                LojidocError::Io { path, err } => write!(f, "{}: {}", path.display(), err),
+                LojidocError::Parse { file, line: 0, message } => write!(f, "{}: {}", file.display(), message),
+                LojidocError::Parse {
+                    file,
+                    line,
+                    message,
+                } => write!(f, "{}:{}: {}", file.display(), line, message),
+                LojidocError::Template {
+                    template,
+                    line,
+                    message,
+                } => write!(f, "{}:{}: {}", template.display(), line, message),
+                LojidocError::NoTypes { file } => write!(f, "{}: no classes found", file.display()),
+                LojidocError::Conflict { path } => {
+                    write!(f, "{}: the file already exists and was not written by lojidoc", path.display())
+                }
+                LojidocError::InvalidArgs(message) => write!(f, "{}", message),
+                LojidocError::BrokenLink { page, target } => {
+                    write!(f, "{}: the link to {} does not resolve", page.display(), target)
+                }
+                LojidocError::DuplicateAnchor { page, anchor } => {
+                    write!(f, "{}: the anchor #{} is used more than once", page.display(), anchor)
+                }
+            }
+        }
+    }
+
+    impl error::Error for LojidocError {}
+
+    /// A problem in a java file which does not stop it from being documented, but may
+    /// make its documentation wrong, such as a `@param` tag without a parameter
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Warning {
+        pub file: PathBuf,
+        pub line: usize,
+        pub message: String,
+    }
+
+    impl fmt::Display for Warning {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            // A warning about the whole file has no line
+            match self.line {
+                0 => write!(f, "{}: {}", self.file.display(), self.message),
+                line => write!(f, "{}:{}: {}", self.file.display(), line, self.message),
+            }
+        }
+    }
+
+    /// Lists warnings grouped by file, each file sorted by line, e.g.
+    ///
+    /// ```text
+    /// src/Client.java
+    ///   12: @param id does not match a parameter of find
+    /// ```
+    pub fn warning_report(warnings: &[Warning]) -> String {
+        let mut sorted: Vec<&Warning> = warnings.iter().collect();
+        sorted.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+
+        let mut report = String::new();
+        let mut file = None;
+
+        for warning in sorted {
+            if file != Some(&warning.file) {
+                report.push_str(format!("{}\n", warning.file.display()).as_str());
+                file = Some(&warning.file);
+            }
+            match warning.line {
+                0 => report.push_str(format!("  {}\n", warning.message).as_str()),
+                line => report.push_str(format!("  {}: {}\n", line, warning.message).as_str()),
+            }
+        }
+
+        report
+    }
+
+    impl From<ConfigError> for LojidocError {
+        fn from(err: ConfigError) -> LojidocError {
+            LojidocError::InvalidArgs(err.to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test;
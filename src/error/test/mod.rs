@@ -0,0 +1,56 @@
+use error::error::*;
+use std::io;
+use std::path::Path;
+
+#[test]
+fn test_error_messages() {
+    let not_found = io::Error::new(io::ErrorKind::NotFound, "not found");
+    let io_error = LojidocError::io(Path::new("src/Client.java"), not_found);
+    let parse_error = LojidocError::Parse {
+        file: Path::new("src/Client.java").to_path_buf(),
+        line: 12,
+        message: "the comment is not closed".to_string(),
+    };
+    let args_error = LojidocError::InvalidArgs("--mdbook needs markdown pages".to_string());
+    let parser_failed = LojidocError::Parse {
+        file: Path::new("src/Client.java").to_path_buf(),
+        line: 0,
+        message: "the parser failed".to_string(),
+    };
+
+    assert_eq!("src/Client.java: not found", io_error.to_string());
+    assert_eq!("src/Client.java:12: the comment is not closed", parse_error.to_string());
+    assert_eq!("src/Client.java: the parser failed", parser_failed.to_string());
+    assert_eq!(Some(Path::new("src/Client.java")), io_error.path());
+    assert_eq!(None, args_error.path());
+    assert_eq!(EXIT_GENERATION, io_error.exit_code());
+    assert_eq!(EXIT_GENERATION, parse_error.exit_code());
+    assert_eq!(EXIT_INVALID_ARGS, args_error.exit_code());
+}
+
+#[test]
+fn test_warning_report() {
+    let warning = |file: &str, line: usize, message: &str| Warning {
+        file: Path::new(file).to_path_buf(),
+        line,
+        message: message.to_string(),
+    };
+    let warnings = vec![
+        warning("src/Server.java", 3, "the comment is not closed"),
+        warning("src/Client.java", 20, "@param b does not match a parameter of find"),
+        warning("src/Client.java", 8, "the javadoc is not followed by a declaration"),
+        warning("src/Client.java", 0, "the file is not valid UTF-8, it is read as ISO-8859-1"),
+    ];
+
+    assert_eq!("src/Server.java:3: the comment is not closed", warnings[0].to_string());
+    assert_eq!("src/Client.java: the file is not valid UTF-8, it is read as ISO-8859-1", warnings[3].to_string());
+    assert_eq!(
+        "src/Client.java\n\
+         \x20 the file is not valid UTF-8, it is read as ISO-8859-1\n\
+         \x20 8: the javadoc is not followed by a declaration\n\
+         \x20 20: @param b does not match a parameter of find\n\
+         src/Server.java\n\
+         \x20 3: the comment is not closed\n",
+        warning_report(&warnings)
+    );
+}
@@ -0,0 +1,98 @@
+pub mod git {
+    //! Module that asks git which java files of an input directory changed since a ref,
+    //! for `--changed-since`
+
+    use std::path::Path;
+    use std::path::PathBuf;
+    use std::process::Command;
+
+    use document::document::is_java_file;
+    use error::error::LojidocError;
+
+    /// The java files of a directory which changed between a ref and `HEAD`
+    #[derive(Debug, Default, PartialEq)]
+    pub struct Changes {
+        /// The files which were added or modified, and the new paths of renamed files
+        pub changed: Vec<PathBuf>,
+        /// The files which were deleted, and the old paths of renamed files
+        pub deleted: Vec<PathBuf>,
+    }
+
+    /// Runs git in a directory and returns its output
+    fn run_git(dir: &Path, args: &[&str]) -> Result<Vec<u8>, LojidocError> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(args)
+            .output()
+            .map_err(|err| LojidocError::InvalidArgs(format!("--changed-since needs git: {}", err)))?;
+
+        if output.status.success() {
+            Ok(output.stdout)
+        } else {
+            let message = String::from_utf8_lossy(&output.stderr);
+
+            Err(LojidocError::InvalidArgs(format!(
+                "git failed in {}: {}",
+                dir.display(),
+                message.trim()
+            )))
+        }
+    }
+
+    /// Finds the java files of a directory which changed between a ref and `HEAD`, i.e.
+    /// in the commits of `HEAD` since it branched off the ref
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - The input directory, the paths of the changes start with it
+    /// * `git_ref` - The branch, tag or commit the changes are compared with
+    pub fn changed_since(dir: &Path, git_ref: &str) -> Result<Changes, LojidocError> {
+        if run_git(dir, &["rev-parse", "--is-inside-work-tree"]).is_err() {
+            return Err(LojidocError::InvalidArgs(format!(
+                "{} is not in a git repository, --changed-since needs one",
+                dir.display()
+            )));
+        }
+
+        let range = format!("{}...HEAD", git_ref);
+        let output = run_git(dir, &["diff", "--name-status", "--no-renames", "--relative", "-z", &range])?;
+        let output = String::from_utf8_lossy(&output);
+        let mut fields = output.split('\0').filter(|field| !field.is_empty());
+        let mut changes = Changes::default();
+
+        // The output is the status and the path of each file separated by NUL
+        while let (Some(status), Some(path)) = (fields.next(), fields.next()) {
+            let path = dir.join(path);
+
+            if !is_java_file(&path) {
+                continue;
+            }
+            if status.starts_with('D') {
+                changes.deleted.push(path);
+            } else {
+                changes.changed.push(path);
+            }
+        }
+
+        Ok(changes)
+    }
+
+    /// Reads a file as it was at a ref, `None` when it did not exist
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - The input directory the changes were found in
+    /// * `git_ref` - The branch, tag or commit
+    /// * `path` - The path of the file, starting with `dir`
+    pub fn read_at(dir: &Path, git_ref: &str, path: &Path) -> Option<String> {
+        let relative = path.strip_prefix(dir).ok()?;
+        let spec = format!("{}:./{}", git_ref, relative.to_string_lossy().replace('\\', "/"));
+        let contents = run_git(dir, &["show", &spec]).ok()?;
+
+        Some(String::from_utf8_lossy(&contents).into_owned())
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,68 @@
+use git::git::*;
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn git(dir: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+        .args(args)
+        .output()
+        .unwrap()
+        .status;
+
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+#[test]
+fn test_changed_since() {
+    let root = env::temp_dir().join(format!("lojidoc-git-{}", std::process::id()));
+    let src = root.join("src");
+
+    fs::create_dir_all(&src).unwrap();
+    fs::write(src.join("Kept.java"), "class Kept {}").unwrap();
+    fs::write(src.join("Edited.java"), "class Edited {}").unwrap();
+    fs::write(src.join("Removed.java"), "package p;\nclass Removed {}").unwrap();
+    fs::write(root.join("README.md"), "").unwrap();
+    git(&root, &["init", "-q"]);
+    git(&root, &["add", "-A"]);
+    git(&root, &["commit", "-q", "-m", "base"]);
+    git(&root, &["tag", "base"]);
+
+    fs::write(src.join("Edited.java"), "class Edited { int a; }").unwrap();
+    fs::write(src.join("Added.java"), "class Added {}").unwrap();
+    fs::write(root.join("README.md"), "changed").unwrap();
+    fs::remove_file(src.join("Removed.java")).unwrap();
+    git(&root, &["add", "-A"]);
+    git(&root, &["commit", "-q", "-m", "change"]);
+
+    let changes = changed_since(&src, "base").unwrap();
+    let removed = read_at(&src, "base", &src.join("Removed.java"));
+    let missing = read_at(&src, "base", &src.join("Added.java"));
+    let unknown_ref = changed_since(&src, "no-such-ref");
+
+    fs::remove_dir_all(&root).unwrap();
+
+    assert_eq!(vec![src.join("Added.java"), src.join("Edited.java")], changes.changed);
+    assert_eq!(vec![src.join("Removed.java")], changes.deleted);
+    assert_eq!(Some("package p;\nclass Removed {}".to_string()), removed);
+    assert_eq!(None, missing);
+    assert!(unknown_ref.is_err());
+}
+
+#[test]
+fn test_changed_since_outside_a_repository() {
+    let root = env::temp_dir().join(format!("lojidoc-no-git-{}", std::process::id()));
+    fs::create_dir_all(&root).unwrap();
+
+    let result = changed_since(&root, "main");
+    fs::remove_dir_all(&root).unwrap();
+
+    // The temporary directory may be inside a repository on some machines
+    if let Err(err) = result {
+        assert!(err.to_string().contains("is not in a git repository"));
+    }
+}
@@ -89,6 +89,59 @@ pub mod grammar {
         ]
     }
 
+    /// Gets the names of the html elements recognized in javadoc comments.
+    /// Single letter names which are often used for type parameters are left out.
+    pub fn get_html_tags<'a>() -> Vec<&'a str> {
+        vec![
+            "a",
+            "abbr",
+            "b",
+            "big",
+            "blockquote",
+            "br",
+            "caption",
+            "center",
+            "cite",
+            "code",
+            "dd",
+            "dfn",
+            "div",
+            "dl",
+            "dt",
+            "em",
+            "font",
+            "h1",
+            "h2",
+            "h3",
+            "h4",
+            "h5",
+            "h6",
+            "hr",
+            "i",
+            "img",
+            "kbd",
+            "li",
+            "ol",
+            "p",
+            "pre",
+            "small",
+            "span",
+            "strike",
+            "strong",
+            "sub",
+            "sup",
+            "table",
+            "tbody",
+            "td",
+            "th",
+            "thead",
+            "tr",
+            "tt",
+            "ul",
+            "var",
+        ]
+    }
+
     #[derive(Debug, Clone)]
     pub enum Stream {
         Import,
@@ -96,6 +149,9 @@ pub mod grammar {
         Exception,
         Implement,
         Parent,
+        Permits,
+        Parameters,
+        Default,
         Object(String),
         Access(String),
         Modifier(String),
@@ -138,6 +194,8 @@ pub mod grammar {
         pub class: bool,
         pub interface: bool,
         pub enum_ob: bool,
+        pub annotation: bool,
+        pub record: bool,
         pub doc: bool,
         pub comment: bool,
         pub doc_ready: bool,
@@ -149,6 +207,8 @@ pub mod grammar {
                 class: false,
                 interface: false,
                 enum_ob: false,
+                annotation: false,
+                record: false,
                 doc: false,
                 comment: false,
                 doc_ready: false,
@@ -163,5 +223,11 @@ pub mod grammar {
         pub fn ch_interface(&mut self, value: bool) {
             self.interface = value;
         }
+        pub fn ch_annotation(&mut self, value: bool) {
+            self.annotation = value;
+        }
+        pub fn ch_record(&mut self, value: bool) {
+            self.record = value;
+        }
     }
 }
@@ -0,0 +1,252 @@
+pub mod incremental {
+    //! Module that keeps the manifest of `--incremental` runs, which records the java
+    //! files documented in the destination and the files written for them
+
+    use std::collections::BTreeMap;
+    use std::fs;
+    use std::path::Path;
+    use std::path::PathBuf;
+
+    use serde_json;
+
+    use error::error::LojidocError;
+    use error::error::Warning;
+    use model::model::ApplicationDoc;
+    use model::model::PackageMember;
+    use model::model::Project;
+
+    /// The name of the manifest in the destination directory
+    pub const MANIFEST_FILE: &str = ".lojidoc-manifest.json";
+
+    /// The version of the manifest. It is raised when the model or the pages change
+    /// so the documentation of an older version is generated again.
+    pub const MANIFEST_VERSION: u32 = 9;
+
+    #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+    /// A java file of an earlier run
+    pub struct ManifestEntry {
+        /// The hash of the contents of the file, empty when it could not be parsed
+        pub hash: String,
+        /// The files written for the types of the file, relative to the destination
+        pub outputs: Vec<String>,
+        /// The types of the file listed in the index with their package
+        pub members: Vec<(String, PackageMember)>,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    /// The java files documented in a destination by their path
+    pub struct Manifest {
+        pub version: u32,
+        /// The options the documentation was generated with, such as the format
+        pub settings: String,
+        pub files: BTreeMap<String, ManifestEntry>,
+    }
+
+    impl Manifest {
+        pub fn new(settings: &str) -> Manifest {
+            Manifest {
+                version: MANIFEST_VERSION,
+                settings: settings.to_string(),
+                files: BTreeMap::new(),
+            }
+        }
+    }
+
+    /// Reads the manifest of the last run in a destination. `None` is returned when
+    /// there is no manifest or it was written by another version or with other settings.
+    pub fn read_manifest(dest: &str, settings: &str) -> Option<Manifest> {
+        let text = fs::read_to_string(Path::new(dest).join(MANIFEST_FILE)).ok()?;
+        let manifest: Manifest = serde_json::from_str(&text).ok()?;
+
+        if manifest.version == MANIFEST_VERSION && manifest.settings == settings {
+            Some(manifest)
+        } else {
+            None
+        }
+    }
+
+    pub fn write_manifest(dest: &str, manifest: &Manifest) -> Result<(), LojidocError> {
+        let text = serde_json::to_string_pretty(manifest).expect("Not able to serialize the manifest");
+        let path = Path::new(dest).join(MANIFEST_FILE);
+
+        fs::write(&path, text).map_err(|err| LojidocError::io(&path, err))
+    }
+
+    /// Hashes the contents of a file with 64 bit FNV-1a, which does not change between
+    /// builds like the hasher of the standard library may
+    pub fn hash_contents(bytes: &[u8]) -> String {
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+
+        for byte in bytes {
+            hash ^= u64::from(*byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+
+        format!("{:016x}", hash)
+    }
+
+    /// The java files of an incremental run which are documented again
+    pub struct Plan {
+        /// The files which are new, changed or could not be parsed in the last run
+        pub changed: Vec<PathBuf>,
+        /// The entries of the files which are up to date
+        pub unchanged: BTreeMap<String, ManifestEntry>,
+        /// The files written for the changed and deleted java files in the last run,
+        /// which are removed when no file of this run writes them
+        pub previous_outputs: Vec<String>,
+        /// The unchanged files which are not in the manifest, whose outputs and types
+        /// are found without documenting them again
+        pub unrecorded: Vec<PathBuf>,
+    }
+
+    /// Compares the java files of a run with the manifest of the last run. Every file is
+    /// documented again when there is no manifest or a file was added or deleted, since
+    /// the links to the types of other files change with them.
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest` - The manifest of the last run
+    /// * `files` - The java files of this run with the hash of their contents
+    pub fn plan(manifest: Option<Manifest>, files: &[(PathBuf, String)]) -> Plan {
+        let mut plan = Plan {
+            changed: Vec::new(),
+            unchanged: BTreeMap::new(),
+            previous_outputs: Vec::new(),
+            unrecorded: Vec::new(),
+        };
+        let mut previous = match manifest {
+            Some(manifest) => manifest.files,
+            None => {
+                plan.changed = files.iter().map(|(path, _)| path.clone()).collect();
+                return plan;
+            }
+        };
+        let same_files = previous.len() == files.len()
+            && files
+                .iter()
+                .all(|(path, _)| previous.contains_key(&path.display().to_string()));
+
+        for (path, hash) in files {
+            let key = path.display().to_string();
+
+            match previous.remove(&key) {
+                Some(ref entry) if same_files && !hash.is_empty() && entry.hash == *hash => {
+                    plan.unchanged.insert(key, entry.clone());
+                }
+                Some(entry) => {
+                    plan.previous_outputs.extend(entry.outputs);
+                    plan.changed.push(path.clone());
+                }
+                None => plan.changed.push(path.clone()),
+            }
+        }
+        // The files left are the deleted java files
+        for (_, entry) in previous {
+            plan.previous_outputs.extend(entry.outputs);
+        }
+
+        plan
+    }
+
+    /// Plans a run which documents the java files changed in git. The other files are up
+    /// to date, they keep their entry of the last run or are unrecorded when the last run
+    /// did not write one. Unlike `plan` an added or deleted file does not document the
+    /// other files again, so the pages which were not changed stay untouched.
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest` - The manifest of the last run
+    /// * `files` - The java files of this run with the hash of their contents
+    /// * `changed` - The java files which changed in git
+    pub fn plan_changed(manifest: Option<Manifest>, files: &[(PathBuf, String)], changed: &[PathBuf]) -> Plan {
+        let mut plan = Plan {
+            changed: Vec::new(),
+            unchanged: BTreeMap::new(),
+            previous_outputs: Vec::new(),
+            unrecorded: Vec::new(),
+        };
+        let mut previous = manifest.map(|manifest| manifest.files).unwrap_or_default();
+
+        for (path, hash) in files {
+            let key = path.display().to_string();
+
+            match previous.remove(&key) {
+                // The files which could not be parsed have no hash
+                Some(ref entry) if !changed.contains(path) && !entry.hash.is_empty() => {
+                    plan.unchanged.insert(key, entry.clone());
+                }
+                Some(entry) => {
+                    plan.previous_outputs.extend(entry.outputs);
+                    plan.changed.push(path.clone());
+                }
+                None if changed.contains(path) => plan.changed.push(path.clone()),
+                None => {
+                    let entry = ManifestEntry {
+                        hash: hash.clone(),
+                        ..ManifestEntry::default()
+                    };
+                    plan.unchanged.insert(key, entry);
+                    plan.unrecorded.push(path.clone());
+                }
+            }
+        }
+        for (_, entry) in previous {
+            plan.previous_outputs.extend(entry.outputs);
+        }
+
+        plan
+    }
+
+    /// A java file documented in a run with the files written for it
+    pub struct BuiltFile {
+        pub path: PathBuf,
+        pub outputs: Vec<String>,
+        pub members: Vec<(String, PackageMember)>,
+    }
+
+    /// The state shared by the files of a run
+    pub struct Build {
+        /// The qualified names of the types of every java file of the run, including
+        /// the unchanged files of an incremental run, for `{@link}` tags
+        pub type_names: Vec<String>,
+        /// The types of the unchanged files, which are listed in the index too
+        pub previous: ApplicationDoc,
+        /// The files documented in the run
+        pub files: Vec<BuiltFile>,
+        /// The types of the unchanged files, which the documented files may inherit
+        /// method documentation from
+        pub supertypes: Vec<Project>,
+        /// The parse warnings of the documented files
+        pub warnings: Vec<Warning>,
+    }
+
+    impl Build {
+        pub fn new(type_names: Vec<String>) -> Build {
+            Build {
+                type_names,
+                previous: ApplicationDoc::new(),
+                files: Vec::new(),
+                supertypes: Vec::new(),
+                warnings: Vec::new(),
+            }
+        }
+    }
+
+    /// Finds the types of an index which are written to one of `outputs`
+    pub fn members_of(app_doc: &ApplicationDoc, outputs: &[String]) -> Vec<(String, PackageMember)> {
+        let mut members = Vec::new();
+
+        for package in app_doc.packages.iter() {
+            for member in package.members.iter() {
+                if outputs.contains(&member.path) {
+                    members.push((package.name.clone(), member.clone()));
+                }
+            }
+        }
+
+        members
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,120 @@
+use incremental::incremental::*;
+use model::model::{ApplicationDoc, DiagramNode, PackageMember};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+fn entry(hash: &str, output: &str) -> ManifestEntry {
+    ManifestEntry {
+        hash: hash.to_string(),
+        outputs: vec![output.to_string()],
+        members: Vec::new(),
+    }
+}
+
+#[test]
+fn test_hash_contents() {
+    assert_eq!("cbf29ce484222325", hash_contents(b""));
+    assert_eq!(hash_contents(b"class A {}"), hash_contents(b"class A {}"));
+    assert_ne!(hash_contents(b"class A {}"), hash_contents(b"class B {}"));
+}
+
+#[test]
+fn test_manifest_round_trip() {
+    let dest = env::temp_dir().join(format!("lojidoc-manifest-{}", std::process::id()));
+    fs::create_dir_all(&dest).unwrap();
+    let dest_str = dest.to_str().unwrap();
+
+    let mut manifest = Manifest::new("format=markdown");
+    manifest.files.insert("src/A.java".to_string(), entry("1", "A.md"));
+    write_manifest(dest_str, &manifest).unwrap();
+
+    let read = read_manifest(dest_str, "format=markdown");
+    let other_settings = read_manifest(dest_str, "format=html");
+    fs::remove_dir_all(&dest).unwrap();
+
+    assert_eq!(Some(manifest), read);
+    assert_eq!(None, other_settings);
+}
+
+#[test]
+fn test_plan() {
+    let a = PathBuf::from("src/A.java");
+    let b = PathBuf::from("src/B.java");
+    let mut manifest = Manifest::new("");
+    manifest.files.insert("src/A.java".to_string(), entry("1", "A.md"));
+    manifest.files.insert("src/B.java".to_string(), entry("2", "B.md"));
+
+    let changed = plan(
+        Some(manifest),
+        &[(a.clone(), "1".to_string()), (b.clone(), "3".to_string())],
+    );
+
+    assert_eq!(vec![b.clone()], changed.changed);
+    assert_eq!(vec!["src/A.java"], changed.unchanged.keys().collect::<Vec<_>>());
+    assert_eq!(vec!["B.md".to_string()], changed.previous_outputs);
+
+    let mut manifest = Manifest::new("");
+    manifest.files.insert("src/A.java".to_string(), entry("1", "A.md"));
+    manifest.files.insert("src/C.java".to_string(), entry("4", "C.md"));
+
+    // A deleted file documents every file again and its page is removed
+    let deleted = plan(Some(manifest), &[(a.clone(), "1".to_string())]);
+
+    assert_eq!(vec![a.clone()], deleted.changed);
+    assert!(deleted.unchanged.is_empty());
+    assert_eq!(vec!["A.md".to_string(), "C.md".to_string()], deleted.previous_outputs);
+
+    let files = [(a.clone(), String::new()), (b.clone(), String::new())];
+    assert_eq!(vec![a, b], plan(None, &files).changed);
+}
+
+#[test]
+fn test_plan_changed() {
+    let a = PathBuf::from("src/A.java");
+    let b = PathBuf::from("src/B.java");
+    let c = PathBuf::from("src/C.java");
+    let mut manifest = Manifest::new("");
+    manifest.files.insert("src/A.java".to_string(), entry("1", "A.md"));
+    manifest.files.insert("src/B.java".to_string(), entry("2", "B.md"));
+    manifest.files.insert("src/D.java".to_string(), entry("4", "D.md"));
+
+    let files = [
+        (a.clone(), "1".to_string()),
+        (b.clone(), "5".to_string()),
+        (c.clone(), "3".to_string()),
+    ];
+    let changed = plan_changed(Some(manifest), &files, std::slice::from_ref(&b));
+
+    assert_eq!(vec![b.clone()], changed.changed);
+    assert_eq!(vec!["src/A.java", "src/C.java"], changed.unchanged.keys().collect::<Vec<_>>());
+    assert_eq!(vec![c.clone()], changed.unrecorded);
+    assert_eq!("3", changed.unchanged["src/C.java"].hash);
+    // The page of the deleted file is removed
+    assert_eq!(vec!["B.md".to_string(), "D.md".to_string()], changed.previous_outputs);
+
+    let without_manifest = plan_changed(None, &files, std::slice::from_ref(&c));
+
+    assert_eq!(vec![c], without_manifest.changed);
+    assert_eq!(vec![a, b], without_manifest.unrecorded);
+}
+
+#[test]
+fn test_members_of() {
+    let member = |name: &str| PackageMember {
+        name: name.to_string(),
+        path: format!("com/foo/{}.md", name),
+        summary: String::new(),
+        deprecated: false,
+        diagram: DiagramNode::default(),
+        methods: Vec::new(),
+    };
+    let mut app_doc = ApplicationDoc::new();
+    app_doc.add_package_member("com.foo".to_string(), member("A"));
+    app_doc.add_package_member("com.foo".to_string(), member("B"));
+
+    assert_eq!(
+        vec![("com.foo".to_string(), member("B"))],
+        members_of(&app_doc, &["com/foo/B.md".to_string()])
+    );
+}
@@ -0,0 +1,356 @@
+pub mod inherit {
+    //! Module that copies the documentation of overridden methods to the methods which
+    //! are documented with `{@inheritDoc}` or not documented at all
+
+    use std::collections::HashMap;
+    use std::collections::HashSet;
+    use std::slice;
+
+    use model::model::Method;
+    use model::model::ObjectType;
+    use model::model::Project;
+
+    /// The javadoc tag which is replaced by the documentation of the overridden method
+    pub const INHERIT_DOC: &str = "{@inheritDoc}";
+
+    /// The methods of `Object` which classes override
+    const OBJECT_METHODS: &[&str] = &["clone", "equals", "finalize", "hashCode", "toString"];
+
+    /// A type of the run which other types may inherit documentation from
+    struct Supertype {
+        package: String,
+        /// The extended class and the implemented or extended interfaces as they are written
+        supertypes: Vec<String>,
+        methods: Vec<Method>,
+    }
+
+    /// The types of a run by their simple name, nested types included
+    pub struct TypeIndex {
+        types: HashMap<String, Vec<Supertype>>,
+    }
+
+    impl TypeIndex {
+        pub fn new(projects: &[Project]) -> TypeIndex {
+            let mut index = TypeIndex {
+                types: HashMap::new(),
+            };
+
+            for project in projects {
+                index.add_project(project);
+            }
+
+            index
+        }
+        pub fn add_project(&mut self, project: &Project) {
+            for class in project.classes.iter() {
+                let supertypes = class_supertypes(&class.parent, &class.interfaces);
+                self.add(&class.name, &class.package_name, supertypes, &class.methods);
+                self.add_inner(&class.inner_classes);
+            }
+            for inter in project.interfaces.iter() {
+                self.add(&inter.name, &inter.package_name, inter.extends.clone(), &inter.methods);
+                self.add_inner(&inter.inner_classes);
+            }
+            for enumeration in project.enumerations.iter() {
+                let interfaces = enumeration.interfaces.clone();
+                self.add(&enumeration.name, &enumeration.package_name, interfaces, &enumeration.methods);
+                self.add_inner(&enumeration.inner_classes);
+            }
+            for annotation in project.annotations.iter() {
+                self.add_inner(&annotation.inner_classes);
+            }
+        }
+        fn add_inner(&mut self, inner_classes: &[ObjectType]) {
+            for inner in inner_classes {
+                match inner {
+                    ObjectType::Class(class) => {
+                        let supertypes = class_supertypes(&class.parent, &class.interfaces);
+                        self.add(&class.name, &class.package_name, supertypes, &class.methods);
+                        self.add_inner(&class.inner_classes);
+                    }
+                    ObjectType::Interface(inter) => {
+                        self.add(&inter.name, &inter.package_name, inter.extends.clone(), &inter.methods);
+                        self.add_inner(&inter.inner_classes);
+                    }
+                    ObjectType::Enumeration(enumeration) => {
+                        let interfaces = enumeration.interfaces.clone();
+                        self.add(&enumeration.name, &enumeration.package_name, interfaces, &enumeration.methods);
+                        self.add_inner(&enumeration.inner_classes);
+                    }
+                    ObjectType::Annotation(annotation) => self.add_inner(&annotation.inner_classes),
+                }
+            }
+        }
+        fn add(&mut self, name: &str, package: &str, supertypes: Vec<String>, methods: &[Method]) {
+            self.types.entry(name.to_string()).or_default().push(Supertype {
+                package: package.to_string(),
+                supertypes,
+                methods: methods.to_vec(),
+            });
+        }
+        /// Finds a supertype by the name it is written with, preferring a type of the
+        /// same package when several types have the same simple name
+        fn find(&self, name: &str, package: &str) -> Option<&Supertype> {
+            let types = self.types.get(simple_name(name))?;
+
+            types
+                .iter()
+                .find(|supertype| supertype.package == package)
+                .or_else(|| types.first())
+        }
+        /// Finds the nearest documented method which a method overrides. The extended
+        /// class is searched before the interfaces, and each supertype before its own
+        /// supertypes.
+        ///
+        /// Returns the documented method, or the supertypes which are not part of the run
+        fn overridden(
+            &self,
+            supertypes: &[String],
+            package: &str,
+            method: &Method,
+            visited: &mut HashSet<String>,
+        ) -> Result<Method, Vec<String>> {
+            let mut missing = Vec::new();
+
+            for name in supertypes {
+                if !visited.insert(simple_name(name).to_string()) {
+                    continue;
+                }
+
+                let supertype = match self.find(name, package) {
+                    Some(supertype) => supertype,
+                    None => {
+                        missing.push(simple_name(name).to_string());
+                        continue;
+                    }
+                };
+
+                if let Some(candidate) = overloads(&supertype.methods, method) {
+                    if !is_inherited(&candidate.description) {
+                        return Ok(candidate.clone());
+                    }
+                }
+
+                match self.overridden(&supertype.supertypes, &supertype.package, method, visited) {
+                    Ok(found) => return Ok(found),
+                    Err(names) => missing.extend(names),
+                }
+            }
+
+            Err(missing)
+        }
+    }
+
+    /// Resolves the documentation of the methods of every type in a run which are
+    /// documented with `{@inheritDoc}` or not documented, from the methods they override.
+    /// A method whose supertypes are not part of the run names them in its description
+    /// instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `projects` - The parsed java files of the run
+    /// * `supertypes` - Other types which may be inherited from but are not documented,
+    ///   such as the unchanged files of an incremental run
+    pub fn inherit_docs(projects: &mut [Project], supertypes: &[Project]) {
+        let mut index = TypeIndex::new(projects);
+        for project in supertypes {
+            index.add_project(project);
+        }
+
+        for project in projects.iter_mut() {
+            for class in project.classes.iter_mut() {
+                let parents = class_supertypes(&class.parent, &class.interfaces);
+                inherit_methods(&index, &class.package_name, &parents, &mut class.methods);
+                inherit_inner(&index, &mut class.inner_classes);
+            }
+            for inter in project.interfaces.iter_mut() {
+                inherit_methods(&index, &inter.package_name, &inter.extends, &mut inter.methods);
+                inherit_inner(&index, &mut inter.inner_classes);
+            }
+            for enumeration in project.enumerations.iter_mut() {
+                let package = &enumeration.package_name;
+                inherit_methods(&index, package, &enumeration.interfaces, &mut enumeration.methods);
+                inherit_inner(&index, &mut enumeration.inner_classes);
+            }
+            for annotation in project.annotations.iter_mut() {
+                inherit_inner(&index, &mut annotation.inner_classes);
+            }
+        }
+    }
+
+    /// Gets the simple names of the supertypes of the types in a project, nested types
+    /// included
+    pub fn supertype_names(project: &Project) -> HashSet<String> {
+        let index = TypeIndex::new(slice::from_ref(project));
+
+        index
+            .types
+            .values()
+            .flatten()
+            .flat_map(|supertype| supertype.supertypes.iter())
+            .map(|name| simple_name(name).to_string())
+            .collect()
+    }
+
+    fn inherit_inner(index: &TypeIndex, inner_classes: &mut [ObjectType]) {
+        for inner in inner_classes.iter_mut() {
+            match inner {
+                ObjectType::Class(class) => {
+                    let parents = class_supertypes(&class.parent, &class.interfaces);
+                    inherit_methods(index, &class.package_name, &parents, &mut class.methods);
+                    inherit_inner(index, &mut class.inner_classes);
+                }
+                ObjectType::Interface(inter) => {
+                    inherit_methods(index, &inter.package_name, &inter.extends, &mut inter.methods);
+                    inherit_inner(index, &mut inter.inner_classes);
+                }
+                ObjectType::Enumeration(enumeration) => {
+                    let package = &enumeration.package_name;
+                    inherit_methods(index, package, &enumeration.interfaces, &mut enumeration.methods);
+                    inherit_inner(index, &mut enumeration.inner_classes);
+                }
+                ObjectType::Annotation(annotation) => inherit_inner(index, &mut annotation.inner_classes),
+            }
+        }
+    }
+
+    fn inherit_methods(index: &TypeIndex, package: &str, supertypes: &[String], methods: &mut [Method]) {
+        for method in methods.iter_mut() {
+            let tagged = method.return_desc.contains(INHERIT_DOC)
+                || method.parameters.iter().any(|param| param.desc.contains(INHERIT_DOC));
+
+            // Static and private methods do not override anything
+            if !(is_inherited(&method.description) || tagged)
+                || method.modifiers.iter().any(|modifier| modifier == "static")
+                || method.privacy == "private"
+            {
+                continue;
+            }
+
+            match index.overridden(supertypes, package, method, &mut HashSet::new()) {
+                Ok(overridden) => inherit_method(method, &overridden),
+                Err(mut missing) => {
+                    if OBJECT_METHODS.contains(&method.name.as_str()) {
+                        missing = vec!["Object".to_string()];
+                    }
+
+                    let note = if missing.is_empty() {
+                        String::new()
+                    } else {
+                        format!("Inherited from {} (not documented here)", missing.join(", "))
+                    };
+                    // An undocumented method names its supertypes when it is marked as overriding
+                    if method.description.trim().is_empty() && is_override(method) {
+                        method.description = note;
+                    } else {
+                        method.description = method.description.replace(INHERIT_DOC, &note).trim().to_string();
+                    }
+                    method.return_desc = method.return_desc.replace(INHERIT_DOC, "").trim().to_string();
+                    for param in method.parameters.iter_mut() {
+                        param.desc = param.desc.replace(INHERIT_DOC, "").trim().to_string();
+                    }
+                }
+            }
+        }
+    }
+
+    /// Copies the description, parameter, return and exception documentation of an
+    /// overridden method which the method does not document itself
+    fn inherit_method(method: &mut Method, overridden: &Method) {
+        method.description = inherit_text(&method.description, &overridden.description);
+        method.return_desc = inherit_text(&method.return_desc, &overridden.return_desc);
+
+        // Parameters are inherited by position since the names may differ
+        for (param, overridden) in method.parameters.iter_mut().zip(overridden.parameters.iter()) {
+            param.desc = inherit_text(&param.desc, &overridden.desc);
+        }
+
+        for exception in method.exceptions.iter_mut() {
+            let declared = simple_name(&exception.exception_type);
+
+            if let Some(overridden) = overridden
+                .exceptions
+                .iter()
+                .find(|overridden| simple_name(&overridden.exception_type) == declared)
+            {
+                exception.desc = inherit_text(&exception.desc, &overridden.desc);
+            }
+        }
+        if method.exceptions.is_empty() {
+            method.exceptions = overridden.exceptions.clone();
+        }
+    }
+
+    /// Gets the text of a method with `{@inheritDoc}` replaced by the overridden text, or
+    /// the overridden text when the method has none
+    fn inherit_text(text: &str, overridden: &str) -> String {
+        if text.trim().is_empty() {
+            overridden.to_string()
+        } else {
+            text.replace(INHERIT_DOC, overridden).trim().to_string()
+        }
+    }
+
+    /// Whether a method has the `@Override` annotation
+    fn is_override(method: &Method) -> bool {
+        method
+            .annotations
+            .iter()
+            .any(|annotation| annotation == "@Override" || annotation == "@java.lang.Override")
+    }
+
+    /// Whether a text is inherited from the overridden method
+    fn is_inherited(text: &str) -> bool {
+        text.trim().is_empty() || text.contains(INHERIT_DOC)
+    }
+
+    /// Finds the method a method overrides among the methods of a supertype. Methods
+    /// with the same name and number of parameters match, the one with the same
+    /// parameter types is preferred since type parameters may be replaced.
+    fn overloads<'a>(methods: &'a [Method], method: &Method) -> Option<&'a Method> {
+        let param_types = |method: &Method| -> Vec<String> {
+            method
+                .parameters
+                .iter()
+                .map(|param| simple_name(&param.var_type).to_string())
+                .collect()
+        };
+        let candidates: Vec<&Method> = methods
+            .iter()
+            .filter(|candidate| {
+                candidate.name == method.name && candidate.parameters.len() == method.parameters.len()
+            }).collect();
+
+        candidates
+            .iter()
+            .find(|candidate| param_types(candidate) == param_types(method))
+            .or_else(|| candidates.first())
+            .cloned()
+    }
+
+    /// Gets the extended class and the implemented interfaces of a class
+    fn class_supertypes(parent: &str, interfaces: &[String]) -> Vec<String> {
+        let mut supertypes = Vec::new();
+
+        if !parent.is_empty() {
+            supertypes.push(parent.to_string());
+        }
+        supertypes.extend(interfaces.iter().cloned());
+
+        supertypes
+    }
+
+    /// Gets the name of a type without its package and type arguments, e.g. `Map` for
+    /// `java.util.Map<K, V>`
+    fn simple_name(type_name: &str) -> &str {
+        let type_name = match type_name.find('<') {
+            Some(i) => &type_name[..i],
+            None => type_name,
+        };
+
+        type_name.trim().rsplit('.').next().unwrap_or(type_name)
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,124 @@
+use inherit::inherit::*;
+use model::model::{ObjectType, Project};
+use parse::parse::parse_str;
+
+fn project(source: &str) -> Project {
+    let mut project = Project::new();
+
+    for object in parse_str(source, None).unwrap() {
+        match object {
+            ObjectType::Class(class) => project.add_class(class),
+            ObjectType::Interface(inter) => project.add_interface(inter),
+            ObjectType::Enumeration(enumeration) => project.add_enumeration(enumeration),
+            ObjectType::Annotation(annotation) => project.add_annotation(annotation),
+        }
+    }
+
+    project
+}
+
+const TASK: &str = "package p;
+
+public interface Task extends Named {
+    /**
+     * Runs the task
+     *
+     * @param times How often it runs
+     * @return Whether it ran
+     * @throws IllegalStateException when it is running
+     */
+    boolean run(int times);
+}
+";
+
+const NAMED: &str = "package p;
+
+public interface Named {
+    /**
+     * Gets the name
+     *
+     * @return The name
+     */
+    String name();
+}
+";
+
+#[test]
+fn test_inherit_docs() {
+    let job = "package p;
+
+public class Job implements Task, Runnable {
+    /** {@inheritDoc} Jobs run once. */
+    @Override
+    public boolean run(int count) {
+        return true;
+    }
+
+    public String name() {
+        return null;
+    }
+
+    /**
+     * {@inheritDoc}
+     */
+    public void run() {
+    }
+
+    /** {@inheritDoc} */
+    public String toString() {
+        return null;
+    }
+
+    public void stop() {
+    }
+
+    @Override
+    public void close() {
+    }
+}
+";
+    let mut projects = vec![project(job)];
+    inherit_docs(&mut projects, &[project(TASK), project(NAMED)]);
+
+    let methods = &projects[0].classes[0].methods;
+    assert_eq!("Runs the task Jobs run once.", methods[0].description);
+    assert_eq!("Whether it ran", methods[0].return_desc);
+    assert_eq!("How often it runs", methods[0].parameters[0].desc);
+    assert_eq!("IllegalStateException", methods[0].exceptions[0].exception_type);
+    assert_eq!("when it is running", methods[0].exceptions[0].desc);
+    assert_eq!("Gets the name", methods[1].description);
+    assert_eq!("The name", methods[1].return_desc);
+    assert_eq!("Inherited from Runnable (not documented here)", methods[2].description);
+    assert_eq!("Inherited from Object (not documented here)", methods[3].description);
+    assert_eq!("", methods[4].description);
+    assert_eq!("Inherited from Runnable (not documented here)", methods[5].description);
+}
+
+#[test]
+fn test_inherit_docs_through_classes() {
+    let base = "package p;
+
+public abstract class Base implements Task {
+    /** {@inheritDoc} */
+    public abstract boolean run(int times);
+}
+";
+    let child = "package p;
+
+public class Child extends Base {
+    /**
+     * @param n {@inheritDoc}
+     */
+    public boolean run(int n) {
+        return true;
+    }
+}
+";
+    let mut projects = vec![project(base), project(child), project(TASK), project(NAMED)];
+    inherit_docs(&mut projects, &[]);
+
+    assert_eq!("Runs the task", projects[0].classes[0].methods[0].description);
+    let method = &projects[1].classes[0].methods[0];
+    assert_eq!("Runs the task", method.description);
+    assert_eq!("How often it runs", method.parameters[0].desc);
+}
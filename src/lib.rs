@@ -0,0 +1,82 @@
+//! Lojidoc parses the javadoc comments of java files and generates markdown, html,
+//! asciidoc or json documentation from them.
+//!
+//! The parser can be used by other tools without the command line interface, which is
+//! left out when the crate is built with `--no-default-features`.
+//!
+//! ```
+//! use lojidoc::ObjectType;
+//!
+//! let source = "package com.example;
+//!
+//! /** A client of the service */
+//! public class Client {
+//!     /**
+//!      * Finds a user
+//!      *
+//!      * @param id The id of the user
+//!      */
+//!     public String find(long id) {
+//!         return null;
+//!     }
+//! }
+//! ";
+//!
+//! match &lojidoc::parse_str(source, None).unwrap()[0] {
+//!     ObjectType::Class(class) => {
+//!         assert_eq!("Client", class.name);
+//!         assert_eq!("find", class.methods[0].name);
+//!         assert_eq!("The id of the user", class.methods[0].parameters[0].desc);
+//!
+//!         let page = lojidoc::generate_markdown_string(class);
+//!         assert!(page.starts_with("# Class Client"));
+//!     }
+//!     _ => unreachable!(),
+//! }
+//! ```
+
+extern crate colored;
+extern crate handlebars;
+extern crate mdbook;
+#[cfg(feature = "cli")]
+extern crate notify;
+extern crate pulldown_cmark;
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+extern crate serde_json;
+#[cfg(feature = "cli")]
+extern crate threadpool;
+extern crate toml;
+
+#[macro_use]
+pub mod log;
+
+pub mod config;
+pub mod coverage;
+pub mod document;
+pub mod error;
+pub mod git;
+pub mod grammar;
+pub mod incremental;
+pub mod inherit;
+pub mod links;
+pub mod model;
+pub mod parse;
+#[cfg(feature = "cli")]
+pub mod pipeline;
+pub mod strings;
+pub mod template;
+
+pub use document::document::find_java_files;
+pub use document::document::generate_markdown_string;
+pub use document::document::PathFilter;
+pub use error::error::LojidocError;
+pub use error::error::Warning;
+pub use model::model::Class;
+pub use model::model::Method;
+pub use model::model::ObjectType;
+pub use model::model::Param;
+pub use parse::parse::parse_file;
+pub use parse::parse::parse_source;
+pub use parse::parse::parse_str;
@@ -0,0 +1,283 @@
+pub mod links {
+    //! Module that checks the links between the generated pages after a run, for
+    //! `--check-links`
+
+    use std::collections::HashMap;
+    use std::collections::HashSet;
+    use std::fs;
+    use std::path::Component;
+    use std::path::Path;
+    use std::path::PathBuf;
+
+    use document::document::page_headings;
+    use document::document::Format;
+    use document::rst::rst_id;
+    use error::error::LojidocError;
+
+    /// Gets the links of a page to the pages of its format, and to the anchors of those
+    /// pages or its own. Links to java files and websites are left out.
+    ///
+    /// # Arguments
+    ///
+    /// * `doc` - The page
+    /// * `format` - The format of the page
+    pub fn page_links(doc: &str, format: Format) -> Vec<String> {
+        let extension = format!(".{}", format.extension());
+        let targets = match format {
+            Format::Markdown => markdown_targets(doc),
+            Format::Html => attribute_values(doc, "href=\""),
+            Format::Asciidoc => {
+                let mut targets = delimited(doc, "xref:", '[');
+                targets.extend(delimited(doc, "link:", '['));
+                targets
+            }
+            // Sphinx links the pages by the html files it writes for them
+            Format::Rst => rst_targets(doc)
+                .into_iter()
+                .map(|target| match target.find(".html") {
+                    Some(i) if target[i + 5..].is_empty() || target[i + 5..].starts_with('#') => {
+                        format!("{}.rst{}", &target[..i], &target[i + 5..])
+                    }
+                    _ => target,
+                }).collect(),
+            Format::Json => Vec::new(),
+        };
+
+        targets
+            .into_iter()
+            .map(|target| target.replace("&amp;", "&"))
+            .filter(|target| {
+                let path = target.split('#').next().unwrap_or("");
+                !target.contains("://") && (path.is_empty() || path.ends_with(&extension))
+            }).collect()
+    }
+
+    /// Gets the anchors which a page can be linked to with
+    ///
+    /// # Arguments
+    ///
+    /// * `doc` - The page
+    /// * `format` - The format of the page
+    pub fn page_anchors(doc: &str, format: Format) -> HashSet<String> {
+        match format {
+            Format::Markdown => page_headings(doc)
+                .into_iter()
+                .flat_map(|heading| vec![heading.anchor, heading.slug])
+                .collect(),
+            Format::Html => attribute_values(doc, "id=\"").into_iter().collect(),
+            Format::Asciidoc => delimited(doc, "[[", ']').into_iter().collect(),
+            Format::Rst => doc
+                .lines()
+                .filter_map(|line| line.strip_prefix(".. _")?.strip_suffix(':'))
+                .map(rst_id)
+                .collect(),
+            Format::Json => HashSet::new(),
+        }
+    }
+
+    /// Gets the anchors which are given to more than one place of a page, in the order
+    /// they are first repeated. The anchor written before a markdown heading may be the
+    /// slug of that heading, but not of another one.
+    ///
+    /// # Arguments
+    ///
+    /// * `doc` - The page
+    /// * `format` - The format of the page
+    pub fn duplicate_anchors(doc: &str, format: Format) -> Vec<String> {
+        let ids = match format {
+            Format::Markdown => page_headings(doc)
+                .into_iter()
+                .flat_map(|heading| {
+                    if heading.anchor == heading.slug {
+                        vec![heading.slug]
+                    } else {
+                        vec![heading.anchor, heading.slug]
+                    }
+                }).collect(),
+            Format::Html => attribute_values(doc, "id=\""),
+            Format::Asciidoc => delimited(doc, "[[", ']'),
+            Format::Rst => doc
+                .lines()
+                .filter_map(|line| line.strip_prefix(".. _")?.strip_suffix(':'))
+                .map(rst_id)
+                .collect(),
+            Format::Json => Vec::new(),
+        };
+        let mut seen = HashSet::new();
+        let mut duplicates = Vec::new();
+
+        for id in ids {
+            if !seen.insert(id.clone()) && !duplicates.contains(&id) {
+                duplicates.push(id);
+            }
+        }
+
+        duplicates
+    }
+
+    /// Checks that every link between the pages of a run resolves, i.e. that the page
+    /// exists in the destination and has the anchor of the link, and that no anchor of
+    /// a page is given twice
+    ///
+    /// Returns an error for each link which does not resolve and each repeated anchor
+    ///
+    /// # Arguments
+    ///
+    /// * `dest` - The destination directory
+    /// * `format` - The format of the pages
+    pub fn check_links(dest: &Path, format: Format) -> Vec<LojidocError> {
+        let mut pages = HashMap::new();
+        let mut errors = Vec::new();
+
+        if let Err(err) = read_pages(dest, dest, format, &mut pages) {
+            return vec![err];
+        }
+
+        let anchors: HashMap<&PathBuf, HashSet<String>> = pages
+            .iter()
+            .map(|(path, doc)| (path, page_anchors(doc, format)))
+            .collect();
+        let mut paths: Vec<&PathBuf> = pages.keys().collect();
+        paths.sort();
+
+        for path in paths {
+            for anchor in duplicate_anchors(&pages[path], format) {
+                errors.push(LojidocError::DuplicateAnchor {
+                    page: dest.join(path),
+                    anchor,
+                });
+            }
+            for target in page_links(&pages[path], format) {
+                let (page, anchor) = match target.find('#') {
+                    Some(i) => (&target[..i], &target[i + 1..]),
+                    None => (target.as_str(), ""),
+                };
+                let page = if page.is_empty() {
+                    path.clone()
+                } else if format == Format::Asciidoc {
+                    // Antora resolves cross references from the root of the pages
+                    normalize(Path::new(page))
+                } else {
+                    normalize(&path.parent().unwrap_or_else(|| Path::new("")).join(page))
+                };
+                let resolves = anchors
+                    .get(&page)
+                    .is_some_and(|ids| anchor.is_empty() || ids.contains(anchor));
+
+                if !resolves {
+                    errors.push(LojidocError::BrokenLink {
+                        page: dest.join(path),
+                        target: target.clone(),
+                    });
+                }
+            }
+        }
+
+        errors
+    }
+
+    /// Reads the pages of a format in a directory and its subdirectories by their path
+    /// relative to the destination
+    fn read_pages(
+        dest: &Path,
+        dir: &Path,
+        format: Format,
+        pages: &mut HashMap<PathBuf, String>,
+    ) -> Result<(), LojidocError> {
+        let entries = fs::read_dir(dir).map_err(|err| LojidocError::io(dir, err))?;
+
+        for entry in entries {
+            let path = entry.map_err(|err| LojidocError::io(dir, err))?.path();
+
+            if path.is_dir() {
+                read_pages(dest, &path, format, pages)?;
+            } else if path.extension().is_some_and(|ext| ext == format.extension()) {
+                let doc = fs::read_to_string(&path).map_err(|err| LojidocError::io(&path, err))?;
+                let relative = path.strip_prefix(dest).unwrap_or(&path).to_path_buf();
+                pages.insert(relative, doc);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Removes the `.` and `..` components of a relative path
+    fn normalize(path: &Path) -> PathBuf {
+        let mut normal = PathBuf::new();
+
+        for component in path.components() {
+            match component {
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    if !normal.pop() {
+                        normal.push("..");
+                    }
+                }
+                other => normal.push(other),
+            }
+        }
+
+        normal
+    }
+
+    /// Gets the targets of the links of a markdown page, leaving out code blocks
+    fn markdown_targets(doc: &str) -> Vec<String> {
+        let mut targets = Vec::new();
+        let mut code = false;
+
+        for line in doc.lines() {
+            if line.starts_with("```") {
+                code = !code;
+            }
+            if !code {
+                targets.extend(delimited(line, "](", ')'));
+            }
+        }
+
+        targets
+    }
+
+    /// Gets the targets of the hyperlinks of a reStructuredText page, e.g. `Foo.html`
+    /// of `` `Foo <Foo.html>`__ ``
+    fn rst_targets(doc: &str) -> Vec<String> {
+        let mut targets = Vec::new();
+
+        for line in doc.lines() {
+            let mut rest = line;
+
+            while let Some(end) = rest.find(">`__") {
+                if let Some(start) = rest[..end].rfind('<') {
+                    targets.push(rest[start + 1..end].to_string());
+                }
+                rest = &rest[end + 4..];
+            }
+        }
+
+        targets
+    }
+
+    /// Gets the values of an html attribute, where `start` is its name with `="`
+    fn attribute_values(doc: &str, start: &str) -> Vec<String> {
+        delimited(doc, start, '"')
+    }
+
+    /// Gets the text after each `start` up to `end`
+    fn delimited(doc: &str, start: &str, end: char) -> Vec<String> {
+        let mut values = Vec::new();
+        let mut rest = doc;
+
+        while let Some(i) = rest.find(start) {
+            rest = &rest[i + start.len()..];
+
+            if let Some(len) = rest.find(end) {
+                values.push(rest[..len].to_string());
+                rest = &rest[len..];
+            }
+        }
+
+        values
+    }
+}
+
+#[cfg(test)]
+mod test;
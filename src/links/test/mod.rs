@@ -0,0 +1,82 @@
+use document::document::Format;
+use links::links::*;
+use std::env;
+use std::fs;
+
+#[test]
+fn test_page_links() {
+    let doc = "# Class Calc [[src]](../src/Calc.java)
+
+- [Methods](#methods)
+- [Item](../p/Item.md#of-string) and [site](https://example.com/a.md)
+
+```java
+String link = \"[x](code.md)\";
+```
+";
+    assert_eq!(vec!["#methods", "../p/Item.md#of-string"], page_links(doc, Format::Markdown));
+
+    let html = "<a class=\"src\" href=\"Calc.java\">[src]</a> <a href=\"Item.html#run\">run</a>";
+    assert_eq!(vec!["Item.html#run"], page_links(html, Format::Html));
+
+    let adoc = "xref:p/Item.adoc#run[run] link:Calc.java[\\[src\\]]";
+    assert_eq!(vec!["p/Item.adoc#run"], page_links(adoc, Format::Asciidoc));
+
+    let rst = "run `[src] <Calc.java#L3>`__\n\n* `run <../p/Item.html#inner-run>`__ `site <https://example.com/>`__\n";
+    assert_eq!(vec!["../p/Item.rst#inner-run"], page_links(rst, Format::Rst));
+}
+
+#[test]
+fn test_page_anchors() {
+    let doc = "# Class Calc\n\n<a id=\"of-string\"></a>\n\n### of(String) [[src]](Calc.java#L3)\n";
+    let anchors = page_anchors(doc, Format::Markdown);
+
+    assert!(anchors.contains("class-calc"));
+    assert!(anchors.contains("of-string"));
+    assert!(anchors.contains("ofstring-src"));
+    assert!(page_anchors("<h3 id=\"run\">run</h3>", Format::Html).contains("run"));
+    assert!(page_anchors("[[run]]\n=== run\n", Format::Asciidoc).contains("run"));
+    assert!(page_anchors(".. _inner.run:\n\nrun\n---\n", Format::Rst).contains("inner-run"));
+}
+
+#[test]
+fn test_duplicate_anchors() {
+    // The anchor of a heading may be its own slug
+    let doc = "# Class Calc\n\n## Methods\n\n<a id=\"run\"></a>\n\n### run\n\n<a id=\"methods\"></a>\n\n### methods\n";
+
+    assert_eq!(vec!["methods"], duplicate_anchors(doc, Format::Markdown));
+    assert!(duplicate_anchors("# Class Calc\n\n<a id=\"run\"></a>\n\n### run\n", Format::Markdown).is_empty());
+    assert_eq!(
+        vec!["run"],
+        duplicate_anchors("<h3 id=\"run\">run</h3><h3 id=\"run\">run</h3><h3 id=\"run\">run</h3>", Format::Html)
+    );
+    assert_eq!(vec!["run"], duplicate_anchors("[[run]]\n=== run\n[[run]]\n", Format::Asciidoc));
+    assert!(duplicate_anchors(".. _run:\n\n.. _stop:\n", Format::Rst).is_empty());
+}
+
+#[test]
+fn test_check_links() {
+    let dest = env::temp_dir().join(format!("lojidoc-links-{}", std::process::id()));
+    fs::create_dir_all(dest.join("p")).unwrap();
+    fs::write(dest.join("index.md"), "# Index\n\n[Calc](p/Calc.md) [Gone](p/Gone.md)\n").unwrap();
+    fs::write(
+        dest.join("p/Calc.md"),
+        "# Class Calc\n\n[run](#run) [stop](#stop) [index](../index.md#index)\n\n<a id=\"run\"></a>\n\n### run\n",
+    ).unwrap();
+    fs::write(dest.join("p/Item.md"), "# Class Item\n\n## Fields\n\n<a id=\"fields\"></a>\n\n### fields\n").unwrap();
+
+    let errors: Vec<String> = check_links(&dest, Format::Markdown)
+        .iter()
+        .map(|err| err.to_string())
+        .collect();
+    fs::remove_dir_all(&dest).unwrap();
+
+    assert_eq!(
+        vec![
+            format!("{}: the link to p/Gone.md does not resolve", dest.join("index.md").display()),
+            format!("{}: the link to #stop does not resolve", dest.join("p/Calc.md").display()),
+            format!("{}: the anchor #fields is used more than once", dest.join("p/Item.md").display()),
+        ],
+        errors
+    );
+}
@@ -0,0 +1,224 @@
+/// Prints an error, which is shown at every verbosity
+#[macro_export]
+macro_rules! error {
+    ($($arg:tt)*) => {
+        $crate::log::log::write($crate::log::log::Verbosity::Quiet, &format!($($arg)*))
+    };
+}
+
+/// Prints a message which is shown unless `-q` is used
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)*) => {
+        $crate::log::log::write($crate::log::log::Verbosity::Normal, &format!($($arg)*))
+    };
+}
+
+/// Prints per-file progress and parse warnings, shown with `-v`
+#[macro_export]
+macro_rules! verbose {
+    ($($arg:tt)*) => {
+        $crate::log::log::write($crate::log::log::Verbosity::Verbose, &format!($($arg)*))
+    };
+}
+
+/// Prints debugging output such as the parsed model, shown with `-vv`
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        $crate::log::log::write($crate::log::log::Verbosity::Debug, &format!($($arg)*))
+    };
+}
+
+pub mod log {
+    //! Module that prints messages depending on the verbosity set on the command line
+
+    use std::io;
+    use std::io::IsTerminal;
+    use std::io::Write;
+    use std::path::Path;
+    use std::sync::atomic::AtomicBool;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+    use std::sync::Mutex;
+    use std::time::Duration;
+    use std::time::Instant;
+
+    /// How much is printed, from errors only to the parsed model
+    #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+    pub enum Verbosity {
+        Quiet,
+        Normal,
+        Verbose,
+        Debug,
+    }
+
+    static VERBOSITY: AtomicUsize = AtomicUsize::new(Verbosity::Normal as usize);
+
+    /// Whether the messages are written to stderr since stdout is the documentation
+    static TO_STDERR: AtomicBool = AtomicBool::new(false);
+
+    /// The progress bar shown on the last line of the terminal, if there is one
+    static BAR: Mutex<Option<String>> = Mutex::new(None);
+
+    /// Sets the verbosity for every thread
+    pub fn set_verbosity(verbosity: Verbosity) {
+        VERBOSITY.store(verbosity as usize, Ordering::SeqCst);
+    }
+
+    /// Writes the messages of every thread to stderr, for `--stdout`. No progress bar is
+    /// shown then.
+    pub fn log_to_stderr() {
+        TO_STDERR.store(true, Ordering::SeqCst);
+    }
+
+    pub fn verbosity() -> Verbosity {
+        match VERBOSITY.load(Ordering::SeqCst) {
+            0 => Verbosity::Quiet,
+            1 => Verbosity::Normal,
+            2 => Verbosity::Verbose,
+            _ => Verbosity::Debug,
+        }
+    }
+
+    /// Prints a message when the verbosity is at least `level`. The message is written
+    /// while stdout is locked so the lines of worker threads do not interleave.
+    /// A progress bar is moved below the message. Errors are written to stderr so
+    /// they can be told apart from the output.
+    pub fn write(level: Verbosity, message: &str) {
+        if level <= verbosity() && TO_STDERR.load(Ordering::SeqCst) {
+            let _ = writeln!(io::stderr(), "{}", message);
+        } else if level == Verbosity::Quiet {
+            let bar = BAR.lock().unwrap();
+            let stdout = io::stdout();
+            let mut out = stdout.lock();
+
+            match *bar {
+                Some(ref bar) => {
+                    let _ = write!(out, "\r\x1b[2K");
+                    let _ = out.flush();
+                    let _ = writeln!(io::stderr(), "{}", message);
+                    let _ = write!(out, "{}", bar);
+                    let _ = out.flush();
+                }
+                None => {
+                    let _ = writeln!(io::stderr(), "{}", message);
+                }
+            }
+        } else if level <= verbosity() {
+            let bar = BAR.lock().unwrap();
+            let stdout = io::stdout();
+            let mut out = stdout.lock();
+
+            match *bar {
+                Some(ref bar) => {
+                    let _ = write!(out, "\r\x1b[2K{}\n{}", message, bar);
+                    let _ = out.flush();
+                }
+                None => {
+                    let _ = writeln!(out, "{}", message);
+                }
+            }
+        }
+    }
+
+    /// Formats a duration as minutes and seconds
+    fn format_duration(duration: Duration) -> String {
+        let secs = duration.as_secs();
+
+        if secs >= 60 {
+            format!("{}m {}s", secs / 60, secs % 60)
+        } else {
+            format!("{}s", secs)
+        }
+    }
+
+    /// The progress of the files being documented. It is a bar on a terminal and
+    /// a line every few seconds otherwise, nothing is shown with `-q`.
+    pub struct Progress {
+        /// What is done with the files, e.g. `Parsing`
+        phase: &'static str,
+        total: usize,
+        done: usize,
+        start: Instant,
+        last_line: Instant,
+        bar: bool,
+    }
+
+    impl Progress {
+        pub fn new(phase: &'static str, total: usize) -> Progress {
+            Progress {
+                phase,
+                total,
+                done: 0,
+                start: Instant::now(),
+                last_line: Instant::now(),
+                bar: io::stdout().is_terminal() && !TO_STDERR.load(Ordering::SeqCst),
+            }
+        }
+        /// Starts the next phase of the run, e.g. rendering the files once they are parsed
+        pub fn start(&mut self, phase: &'static str, total: usize) {
+            self.phase = phase;
+            self.total = total;
+            self.done = 0;
+            self.start = Instant::now();
+        }
+        /// Counts a finished file
+        pub fn update(&mut self, file: &Path) {
+            self.done += 1;
+
+            if verbosity() == Verbosity::Quiet {
+                return;
+            }
+
+            if self.bar {
+                let line = progress_bar(self.phase, self.done, self.total, file, self.start.elapsed());
+                let mut bar = BAR.lock().unwrap();
+                let stdout = io::stdout();
+                let mut out = stdout.lock();
+
+                let _ = write!(out, "\r\x1b[2K{}", line);
+                let _ = out.flush();
+                *bar = Some(line);
+            } else if self.last_line.elapsed() >= Duration::from_secs(5) {
+                self.last_line = Instant::now();
+                info!("{} {} of {} files", self.phase, self.done, self.total);
+            }
+        }
+        /// Removes the progress bar
+        pub fn finish(&mut self) {
+            let mut bar = BAR.lock().unwrap();
+
+            if bar.take().is_some() {
+                let stdout = io::stdout();
+                let mut out = stdout.lock();
+                let _ = write!(out, "\r\x1b[2K");
+                let _ = out.flush();
+            }
+        }
+    }
+
+    /// Formats a progress bar line such as `Parsing [=====>    ] 5/10 Foo.java ETA 3s`
+    pub fn progress_bar(phase: &str, done: usize, total: usize, file: &Path, elapsed: Duration) -> String {
+        const WIDTH: usize = 30;
+
+        let filled = (done * WIDTH).checked_div(total).unwrap_or(WIDTH);
+        let mut bar = "=".repeat(filled);
+        if filled < WIDTH {
+            bar.push('>');
+            bar.push_str(&" ".repeat(WIDTH - filled - 1));
+        }
+
+        let eta = if done == 0 {
+            Duration::from_secs(0)
+        } else {
+            elapsed * (total - done) as u32 / done as u32
+        };
+        let name = file.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+
+        format!("{} [{}] {}/{} {} ETA {}", phase, bar, done, total, name, format_duration(eta))
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,23 @@
+use log::log::*;
+
+#[test]
+fn test_verbosity() {
+    assert!(Verbosity::Quiet < Verbosity::Normal);
+    assert!(Verbosity::Verbose < Verbosity::Debug);
+    assert_eq!(Verbosity::Normal, verbosity());
+}
+
+#[test]
+fn test_progress_bar() {
+    use std::path::Path;
+    use std::time::Duration;
+
+    assert_eq!(
+        "Parsing [=====>                        ] 1/6 Foo.java ETA 1m 15s",
+        progress_bar("Parsing", 1, 6, Path::new("src/Foo.java"), Duration::from_secs(15))
+    );
+    assert_eq!(
+        "Documenting [==============================] 6/6 Bar.java ETA 0s",
+        progress_bar("Documenting", 6, 6, Path::new("Bar.java"), Duration::from_secs(40))
+    );
+}
@@ -1,191 +1,170 @@
 extern crate clap;
-extern crate colored;
-extern crate mdbook;
-extern crate threadpool;
-
-mod document;
-mod grammar;
-mod model;
-mod parse;
-
-use mdbook::config::Config;
-use mdbook::MDBook;
+#[macro_use]
+extern crate lojidoc;
+extern crate num_cpus;
 
 use clap::App;
 use clap::Arg;
+use clap::ArgMatches;
+use clap::Shell;
+use std::collections::BTreeMap;
 use std::fs;
+use std::io;
 use std::path::Path;
 use std::path::PathBuf;
+use std::process;
 use std::sync::Arc;
-use threadpool::ThreadPool;
-
-use document::document::find_java_files;
-use document::document::gen_md_book;
-use document::document::generate_markdown;
-use document::document::lint_project;
-use document::document::resolve_context;
-use model::model::ObjectType;
-use model::model::Project;
-use parse::parse::parse_file;
-
-/// Handles linting javadocs without saving the documentation
-///
-/// # Arguments
-///
-/// * `file_paths` - A vector of the file paths of java files
-/// * `dest` - The file path where the markdown will be saved
-pub fn lint_javadoc(file_paths: Vec<PathBuf>) {
-    let mut project: Project = Project::new();
-
-    for file in file_paths.clone() {
-        match parse_file(&file, true) {
-            ObjectType::Class(mut class) => {
-                class.ch_file_path(file.to_str().unwrap().to_string());
-                project.add_class(class);
-            }
-            ObjectType::Interface(mut inter) => {
-                inter.ch_file_path(file.to_str().unwrap().to_string());
-                project.add_interface(inter)
-            }
-            ObjectType::Enumeration(mut enumeration) => {
-                enumeration.ch_file_path(file.to_str().unwrap().to_string());
-                project.add_enumeration(enumeration);
-            }
-        }
-    }
+use std::time::Instant;
 
-    println!("{}", lint_project(project));
+use lojidoc::config::config::read_config;
+use lojidoc::config::config::ConfigFile;
+use lojidoc::config::config::CONFIG_FILE;
+use lojidoc::config::config::CONFIG_TEMPLATE;
+use lojidoc::coverage::coverage::BadgeColors;
+use lojidoc::coverage::coverage::Coverage;
+use lojidoc::coverage::coverage::Stats;
+use lojidoc::document::document::expand_input;
+use lojidoc::document::document::MethodLayout;
+use lojidoc::document::document::MethodOrder;
+use lojidoc::document::document::OnConflict;
+use lojidoc::document::document::prepare_destination;
+use lojidoc::document::document::SourceLinks;
+use lojidoc::document::document::DEFAULT_EXCLUDES;
+use lojidoc::document::document::MAIN_SOURCE_ROOTS;
+use lojidoc::document::document::TEST_SOURCE_ROOTS;
+use lojidoc::document::document::Format;
+use lojidoc::document::document::FrontMatter;
+use lojidoc::document::document::Output;
+use lojidoc::document::document::PathFilter;
+use lojidoc::document::document::Visibility;
+use lojidoc::links::links::check_links;
+use lojidoc::parse::parse::Charset;
+use lojidoc::pipeline::pipeline::below_threshold;
+use lojidoc::pipeline::pipeline::check_javadoc;
+use lojidoc::pipeline::pipeline::conflicting_files;
+use lojidoc::pipeline::pipeline::dry_run;
+use lojidoc::pipeline::pipeline::find_input_files;
+use lojidoc::pipeline::pipeline::generate;
+use lojidoc::pipeline::pipeline::git_changes;
+use lojidoc::pipeline::pipeline::init_book;
+use lojidoc::pipeline::pipeline::print_pages;
+use lojidoc::pipeline::pipeline::watch;
+use lojidoc::pipeline::pipeline::write_badge;
+use lojidoc::pipeline::pipeline::write_coverage_report;
+use lojidoc::pipeline::pipeline::write_stats;
+use lojidoc::pipeline::pipeline::Refresh;
+use lojidoc::pipeline::pipeline::Run;
+use lojidoc::strings::strings::read_strings;
+use lojidoc::strings::strings::set_labels;
+use lojidoc::strings::strings::Labels;
+use lojidoc::strings::strings::Lang;
+use lojidoc::template::template::Templates;
+use lojidoc::log::log::log_to_stderr;
+use lojidoc::log::log::set_verbosity;
+use lojidoc::log::log::Verbosity;
+use lojidoc::error::error::warning_report;
+use lojidoc::error::error::LojidocError;
+use lojidoc::error::error::Warning;
+use lojidoc::error::error::EXIT_GENERATION;
+use lojidoc::error::error::EXIT_INVALID_ARGS;
+use lojidoc::error::error::EXIT_NO_INPUTS;
+
+/// Prints the errors of a run after the documentation of the other files
+fn report_errors(errors: &[LojidocError]) {
+    for err in errors {
+        error!("{}", err);
+    }
+    if !errors.is_empty() {
+        error!("{} files could not be read, parsed or written", errors.len());
+    }
 }
 
-/// Handles the single threaded option for running the application
-///
-/// # Arguments
-///
-/// * `file_paths` - A vector of the file paths of java files
-/// * `dest` - The file path where the markdown will be saved
-/// * `context` - The project context e.g. `github.com/user/repo`
-/// * `verbose` - Whether the program will output verbose logging
-pub fn document_single(
-    file_paths: Vec<PathBuf>,
-    dest: String,
-    context: String,
-    verbose: bool,
-    book: bool,
-) {
-    let mut project: Project = Project::new();
-
-    for file in file_paths.clone() {
-        let m_context = resolve_context(&file, &context);
-
-        match parse_file(&file, verbose) {
-            ObjectType::Class(mut class) => {
-                class.ch_file_path(m_context);
-                project.add_class(class.clone());
-            }
-            ObjectType::Interface(mut inter) => {
-                inter.ch_file_path(m_context);
-                project.add_interface(inter.clone());
-            }
-            ObjectType::Enumeration(mut enumeration) => {
-                enumeration.ch_file_path(m_context);
-                project.add_enumeration(enumeration.clone());
-            }
-        }
+/// Prints the parse warnings of a run grouped by file, as errors when they fail the run
+fn report_warnings(warnings: &[Warning], as_errors: bool) {
+    if warnings.is_empty() {
+        return;
     }
 
-    generate_markdown(project, dest.as_str(), book);
-    println!(
-        "\nDocumentation finished. Generated {} markdown files.",
-        file_paths.len()
-    );
+    let report = warning_report(warnings);
+    if as_errors {
+        error!("{}", report.trim_end());
+        error!("{} parse warnings, which fail the run with --warnings-as-errors", warnings.len());
+    } else {
+        info!("{}", report.trim_end());
+        info!("{} parse warnings", warnings.len());
+    }
 }
 
-/// Handles thread pooling the application
-///
-/// # Arguments
-///
-/// * `file_paths` - A vector of the file paths of java files
-/// * `dest` - The file path where the markdown will be saved
-pub fn document(
-    file_paths: Vec<PathBuf>,
-    dest: String,
-    context: String,
-    verbose: bool,
-    book: bool,
-) {
-    let files = Arc::new(file_paths);
-    let size = files.len();
-    let mut pool_size = size / 4;
-
-    if files.len() % 4 != 0 {
-        pool_size += 1;
+/// Exits with the exit code of a finished run: `EXIT_GENERATION` when files failed or
+/// a check such as `--fail-under` did not pass, and `EXIT_NO_INPUTS` when no java files
+/// were found. A successful run returns.
+fn exit_run(errors: &[LojidocError], failed: bool, no_inputs: bool) {
+    if !errors.is_empty() || failed {
+        process::exit(EXIT_GENERATION);
     }
-    let pool = ThreadPool::new(pool_size);
-    let safe_dest = Arc::new(dest);
-
-    for i in 0..pool_size {
-        let file_cp = files.clone();
-        let new_dest = safe_dest.clone();
-        let new_context = context.clone();
-
-        pool.execute(move || {
-            let mut project: Project = Project::new();
-
-            for j in 0..4 {
-                if (i * 4) + j < size {
-                    let mut m_context = String::new();
-                    let mut file = file_cp[(i * 4) + j].clone();
-
-                    if new_context != "" {
-                        m_context = resolve_context(&file, &new_context);
-                    }
-
-                    match parse_file(&file, verbose) {
-                        ObjectType::Class(mut class) => {
-                            class.ch_file_path(m_context);
-                            project.add_class(class.clone());
-                        }
-                        ObjectType::Interface(mut inter) => {
-                            inter.ch_file_path(m_context);
-                            project.add_interface(inter.clone());
-                        }
-                        ObjectType::Enumeration(mut enumeration) => {
-                            enumeration.ch_file_path(m_context);
-                            project.add_enumeration(enumeration.clone());
-                        }
-                    }
-                }
-            }
-
-            generate_markdown(project, new_dest.as_str(), book);
-        });
+    if no_inputs {
+        process::exit(EXIT_NO_INPUTS);
     }
+}
+
+/// Prints an error which stops the run and exits with its exit code
+fn exit_with(err: LojidocError) -> ! {
+    error!("{}", err);
+    process::exit(err.exit_code());
+}
 
-    pool.join();
+/// The command line arguments, shared by `main` and the completion scripts
+/// The number of lines up to which `--include-source` shows the source of a method
+/// when it is given without a number
+const INCLUDE_SOURCE_LINES: usize = 20;
 
-    println!(
-        "\nDocumentation finished. Generated {} markdown files.",
-        files.len()
-    );
+/// Gets the number of lines up to which the source of a method is shown, `None`
+/// without `--include-source`
+fn include_source(matches: &ArgMatches) -> Option<usize> {
+    if !matches.is_present("include-source") {
+        return None;
+    }
+
+    Some(
+        matches
+            .value_of("include-source")
+            .and_then(|lines| lines.parse().ok())
+            .unwrap_or(INCLUDE_SOURCE_LINES),
+    )
 }
 
-fn main() {
-    let matches = App::new("Javadoc-To-Markdown")
+fn app() -> App<'static, 'static> {
+    App::new("Javadoc-To-Markdown")
         .version("0.2.1")
         .author("Josh Brudnak <jobrud314@gmail.com>")
         .about("A tool for generating markdown documentation for java projects")
+        .after_help(
+            "METHOD ANCHORS:\n    The anchor of a method is its lowercase name followed by the simple names of its \
+             parameter types,\n    e.g. #findbyid-long for findById(long id) and #of-string-array for of(String[] parts). \
+             The methods\n    of nested types start with the names of the types, e.g. #builder.build, and a method \
+             whose anchor\n    is the anchor of a section starts with method-, e.g. #method-fields. Anchors do not \
+             change when\n    methods are added or moved, so other documents can link to them.",
+        )
         .arg(
             Arg::with_name("INPUT")
                 .value_name("FILE")
-                .required(true)
-                .help("Set the input directory to use")
+                .required_unless_one(&["init", "completions"])
+                .multiple(true)
+                .help("Set the input directories or java files to use")
                 .index(1),
         )
         .arg(
             Arg::with_name("context")
-                .help("Set the context path of the project")
-                .value_name("FILE")
-                .short("c"),
+                .help("Set the url of the repository which the source links point to")
+                .value_name("URL")
+                .short("c")
+                .long("context"),
+        )
+        .arg(
+            Arg::with_name("source-url")
+                .help("Set the template of the source links, e.g. https://host/repo/blob/main/{path}#L{line}")
+                .value_name("TEMPLATE")
+                .long("source-url"),
         )
         .arg(
             Arg::with_name("book")
@@ -196,73 +175,752 @@ fn main() {
         )
         .arg(
             Arg::with_name("lint")
-                .help("Check a java project for incorrent and missing javadocs")
+                .help("Report the public API without javadocs instead of generating documentation")
+                .long("lint")
                 .short("l"),
         )
         .arg(
             Arg::with_name("verbose")
                 .short("v")
-                .help("Generate documentation for a project and provide verbose output"),
+                .multiple(true)
+                .help("Prints the progress of each file and parse warnings, -vv also prints the parsed types"),
+        )
+        .arg(
+            Arg::with_name("quiet")
+                .short("q")
+                .long("quiet")
+                .conflicts_with("verbose")
+                .help("Only prints errors"),
         )
         .arg(
             Arg::with_name("multi-thread")
                 .short("m")
                 .help("Use multiple threads to execute the program"),
         )
+        .arg(
+            Arg::with_name("jobs")
+                .short("j")
+                .long("jobs")
+                .value_name("N")
+                .validator(|value| match value.parse::<usize>() {
+                    Ok(jobs) if jobs > 0 => Ok(()),
+                    _ => Err("The number of jobs must be a positive number".to_string()),
+                })
+                .help("Sets the number of threads, defaults to the number of CPUs"),
+        )
+        .arg(
+            Arg::with_name("flat")
+                .long("flat")
+                .help("Writes every page to the destination directory instead of package directories"),
+        )
+        .arg(
+            Arg::with_name("front-matter")
+                .long("front-matter")
+                .help("Writes YAML front matter with the title, description and package before each type page"),
+        )
+        .arg(
+            Arg::with_name("front-matter-extra")
+                .long("front-matter-extra")
+                .value_name("KEY=VALUE")
+                .multiple(true)
+                .number_of_values(1)
+                .requires("front-matter")
+                .help("Adds a key and value to the front matter of each type page, e.g. layout=api"),
+        )
+        .arg(
+            Arg::with_name("template")
+                .long("template")
+                .value_name("DIR")
+                .help("Renders the type pages with the handlebars templates of a directory, e.g. class.hbs"),
+        )
+        .arg(
+            Arg::with_name("format")
+                .long("format")
+                .value_name("FORMAT")
+                .possible_values(&["markdown", "html", "asciidoc", "rst", "json"])
+                .default_value("markdown")
+                .help("Sets the format of the generated documentation"),
+        )
+        .arg(
+            Arg::with_name("charset")
+                .long("charset")
+                .value_name("CHARSET")
+                .possible_values(&["auto", "utf8", "latin1"])
+                .default_value("auto")
+                .help("Sets the encoding of the java files, auto reads the files which are not UTF-8 as ISO-8859-1"),
+        )
+        .arg(
+            Arg::with_name("lang")
+                .long("lang")
+                .value_name("LANG")
+                .possible_values(&["en", "de", "fr", "es"])
+                .default_value("en")
+                .help("Sets the language of the headings and labels of the pages, the javadoc is left as it is"),
+        )
+        .arg(
+            Arg::with_name("strings")
+                .long("strings")
+                .value_name("PATH")
+                .help("Replaces the labels of the pages with the labels of a TOML file, e.g. methods = \"Metodi\". \
+                       The labels it does not set stay in the language of --lang."),
+        )
+        .arg(
+            Arg::with_name("min-visibility")
+                .long("min-visibility")
+                .value_name("LEVEL")
+                .possible_values(&["public", "protected", "package", "private"])
+                .help("Only documents the types and members which are at least as visible as LEVEL"),
+        )
+        .arg(
+            Arg::with_name("sort-methods")
+                .long("sort-methods")
+                .value_name("ORDER")
+                .possible_values(&["source", "alpha"])
+                .help("Orders the methods of each type as declared or by name, with overloads by their number of parameters"),
+        )
+        .arg(
+            Arg::with_name("group-by-visibility")
+                .long("group-by-visibility")
+                .help("Lists the methods of each type in a section per visibility, the public methods first"),
+        )
+        .arg(
+            Arg::with_name("include-source")
+                .long("include-source")
+                .value_name("MAX-LINES")
+                .min_values(0)
+                .max_values(1)
+                .require_equals(true)
+                .validator(|value| match value.parse::<usize>() {
+                    Ok(lines) if lines > 0 => Ok(()),
+                    _ => Err("The number of lines must be a positive number".to_string()),
+                })
+                .help("Shows the source of the methods of at most MAX-LINES lines, 20 when it is not set. \
+                       The longer methods note their number of lines."),
+        )
+        .arg(
+            Arg::with_name("hide-jdk-imports")
+                .long("hide-jdk-imports")
+                .help("Leaves the java.* and javax.* imports out of the dependencies of each type"),
+        )
+        .arg(
+            Arg::with_name("single-file")
+                .long("single-file")
+                .help("Writes every type to a single API.md, or api.json for the json format"),
+        )
+        .arg(
+            Arg::with_name("mdbook")
+                .long("mdbook")
+                .help("Writes the pages to the src directory of an mdBook with a generated SUMMARY.md"),
+        )
+        .arg(
+            Arg::with_name("docusaurus")
+                .long("docusaurus")
+                .help("Writes the pages to the docs of a Docusaurus site with doc ids and a generated sidebar.json"),
+        )
+        .arg(
+            Arg::with_name("diagrams")
+                .long("diagrams")
+                .help("Writes a mermaid class diagram of the types of each package to its diagram.md"),
+        )
+        .arg(
+            Arg::with_name("diagram-methods")
+                .long("diagram-methods")
+                .requires("diagrams")
+                .help("Lists the public methods of the types in the class diagrams"),
+        )
+        .arg(
+            Arg::with_name("exclude")
+                .long("exclude")
+                .value_name("GLOB")
+                .multiple(true)
+                .number_of_values(1)
+                .help("Skips the files and directories matching a glob relative to the input directory"),
+        )
+        .arg(
+            Arg::with_name("include")
+                .long("include")
+                .value_name("GLOB")
+                .multiple(true)
+                .number_of_values(1)
+                .help("Only documents the java files matching a glob relative to the input directory"),
+        )
+        .arg(
+            Arg::with_name("use-gitignore")
+                .long("use-gitignore")
+                .help("Skips the files and directories matching the .gitignore files of the project"),
+        )
+        .arg(
+            Arg::with_name("follow-symlinks")
+                .long("follow-symlinks")
+                .help("Also traverses symlinked directories, each directory is traversed once"),
+        )
+        .arg(
+            Arg::with_name("source-root")
+                .long("source-root")
+                .value_name("PATH")
+                .multiple(true)
+                .number_of_values(1)
+                .help("Documents PATH of each Maven or Gradle module instead of src/main/java, `.` documents every file"),
+        )
+        .arg(
+            Arg::with_name("include-tests")
+                .long("include-tests")
+                .help("Also documents src/test/java of each Maven or Gradle module"),
+        )
+        .arg(
+            Arg::with_name("no-default-excludes")
+                .long("no-default-excludes")
+                .help("Also documents the java files in .git, target, build and out directories"),
+        )
+        .arg(
+            Arg::with_name("config")
+                .long("config")
+                .value_name("FILE")
+                .help("Reads the settings from a configuration file, defaults to ./lojidoc.toml"),
+        )
+        .arg(
+            Arg::with_name("init")
+                .long("init")
+                .help("Writes a commented lojidoc.toml configuration file"),
+        )
+        .arg(
+            Arg::with_name("fail-under")
+                .long("fail-under")
+                .value_name("PERCENT")
+                .validator(|value| match value.parse::<f64>() {
+                    Ok(percent) if (0.0..=100.0).contains(&percent) => Ok(()),
+                    _ => Err("The coverage threshold must be a percentage from 0 to 100".to_string()),
+                })
+                .help("Fails when less than PERCENT of the public types, methods and parameters are documented"),
+        )
+        .arg(
+            Arg::with_name("count-private")
+                .long("count-private")
+                .help("Counts the members which are not public in the documentation coverage"),
+        )
+        .arg(
+            Arg::with_name("dry-run")
+                .long("dry-run")
+                .help("Parses the java files and prints the files which would be written without writing them"),
+        )
+        .arg(
+            Arg::with_name("stdout")
+                .long("stdout")
+                .conflicts_with_all(&["single-file", "mdbook", "book", "dry-run", "clean", "watch", "incremental", "coverage", "lint"])
+                .help("Prints the pages to stdout one after another instead of writing them, json prints a single document"),
+        )
+        .arg(
+            Arg::with_name("on-conflict")
+                .long("on-conflict")
+                .value_name("POLICY")
+                .possible_values(&["overwrite", "skip", "error"])
+                .default_value("overwrite")
+                .help("Overwrites, keeps or stops at the files of the destination which lojidoc did not write, e.g. hand-edited pages"),
+        )
+        .arg(
+            Arg::with_name("clean")
+                .long("clean")
+                .help("Removes the pages of types which were not written by the run, e.g. of deleted types, from the destination"),
+        )
+        .arg(
+            Arg::with_name("watch")
+                .long("watch")
+                .conflicts_with_all(&["single-file", "fail-under", "lint", "coverage", "dry-run"])
+                .help("Keeps running and documents the java files which are created, changed or deleted"),
+        )
+        .arg(
+            Arg::with_name("incremental")
+                .long("incremental")
+                .conflicts_with_all(&["single-file", "fail-under"])
+                .help("Only documents the java files which changed since the last run in the destination"),
+        )
+        .arg(
+            Arg::with_name("changed-since")
+                .long("changed-since")
+                .value_name("REF")
+                .conflicts_with_all(&["single-file", "fail-under", "watch", "incremental"])
+                .help("Only documents the java files which changed in git between REF and HEAD, the pages of deleted files are removed"),
+        )
+        .arg(
+            Arg::with_name("coverage")
+                .long("coverage")
+                .help("Prints the documentation coverage of each package and type instead of generating documentation"),
+        )
+        .arg(
+            Arg::with_name("coverage-output")
+                .long("coverage-output")
+                .value_name("FORMAT")
+                .possible_values(&["md", "json"])
+                .requires("coverage")
+                .help("Also writes the coverage report to coverage.md or coverage.json in the destination"),
+        )
+        .arg(
+            Arg::with_name("badge")
+                .long("badge")
+                .value_name("PATH")
+                .conflicts_with_all(&["stdout", "dry-run", "watch", "incremental", "changed-since"])
+                .help("Writes the shields.io endpoint json of a documentation coverage badge to PATH"),
+        )
+        .arg(
+            Arg::with_name("badge-colors")
+                .long("badge-colors")
+                .value_name("RED,YELLOW")
+                .requires("badge")
+                .validator(|value| BadgeColors::parse(&value).map(|_| ()))
+                .help("Sets the coverage below which the badge is red and yellow, 50,80 by default"),
+        )
+        .arg(
+            Arg::with_name("warnings-as-errors")
+                .long("warnings-as-errors")
+                .conflicts_with("watch")
+                .help("Fails the run when the parser warns about a java file, e.g. about a @param tag without a parameter"),
+        )
+        .arg(
+            Arg::with_name("stats")
+                .long("stats")
+                .conflicts_with_all(&["stdout", "dry-run", "lint", "coverage"])
+                .help("Also writes the summary of the run to stats.json in the destination"),
+        )
+        .arg(
+            Arg::with_name("check-links")
+                .long("check-links")
+                .conflicts_with_all(&["stdout", "dry-run", "lint", "coverage", "watch"])
+                .help("Checks that the links between the pages lead to pages and anchors which exist and that no page repeats an anchor, and fails otherwise"),
+        )
         .arg(
             Arg::with_name("destination")
                 .required(false)
                 .value_name("FILE")
                 .short("d")
+                .long("destination")
                 .help("Sets the destination directory of the created markdown files"),
         )
-        .get_matches();
+        .arg(
+            // A flag instead of a subcommand, so an input directory may be named `completions`
+            Arg::with_name("completions")
+                .long("completions")
+                .value_name("SHELL")
+                .possible_values(&["bash", "zsh", "fish", "powershell"])
+                .hidden(true)
+                .help("Prints the completion script of a shell, e.g. `lojidoc --completions bash > lojidoc.bash`"),
+        )
+}
+
+/// Prints the completion script of a shell for the arguments of `app`
+fn print_completions(shell: &str) {
+    let shell = match shell {
+        "zsh" => Shell::Zsh,
+        "fish" => Shell::Fish,
+        "powershell" => Shell::PowerShell,
+        _ => Shell::Bash,
+    };
 
-    let dir = matches
-        .value_of("INPUT")
+    app().gen_completions_to("lojidoc", shell, &mut io::stdout());
+}
+
+fn main() {
+    let started = Instant::now();
+    let matches = app()
+        .get_matches_safe()
+        .unwrap_or_else(|err| {
+            // The help and the version are printed to stdout and are not errors
+            if !err.use_stderr() {
+                err.exit();
+            }
+            eprintln!("{}", err.message);
+            process::exit(EXIT_INVALID_ARGS);
+        });
+
+    if let Some(shell) = matches.value_of("completions") {
+        print_completions(shell);
+        return;
+    }
+
+    set_verbosity(match matches.occurrences_of("verbose") {
+        0 if matches.is_present("quiet") => Verbosity::Quiet,
+        0 => Verbosity::Normal,
+        1 => Verbosity::Verbose,
+        _ => Verbosity::Debug,
+    });
+    // The messages are kept out of the printed documentation
+    if matches.is_present("stdout") {
+        log_to_stderr();
+    }
+
+    let config_path = matches.value_of("config").unwrap_or(CONFIG_FILE);
+
+    if matches.is_present("init") {
+        if Path::new(config_path).exists() {
+            exit_with(LojidocError::InvalidArgs(format!("{} already exists", config_path)));
+        }
+
+        if let Err(err) = fs::write(config_path, CONFIG_TEMPLATE) {
+            exit_with(LojidocError::io(Path::new(config_path), err));
+        }
+        info!("{} was created", config_path);
+        return;
+    }
+
+    // The default configuration file is optional, a file set with --config is not
+    let config = if matches.is_present("config") || Path::new(config_path).exists() {
+        match read_config(Path::new(config_path)) {
+            Ok(config) => config,
+            Err(err) => exit_with(LojidocError::InvalidArgs(format!(
+                "Invalid configuration {}: {}",
+                config_path, err
+            ))),
+        }
+    } else {
+        ConfigFile::default()
+    };
+
+    let inputs: Vec<String> = matches
+        .values_of("INPUT")
         .expect("Documentation directory not chosen")
-        .to_string();
+        .flat_map(expand_input)
+        .map(|path| path.display().to_string())
+        .collect();
+    let inputs: Vec<&str> = inputs.iter().map(|input| input.as_str()).collect();
     let dest = matches
         .value_of("destination")
-        .unwrap_or("./generated/")
-        .to_string();
+        .map(|dest| dest.to_string())
+        .or(config.destination)
+        .unwrap_or("./generated/".to_string());
 
-    let context = matches.value_of("context").unwrap_or("").to_string();
+    let context = matches
+        .value_of("context")
+        .map(|context| context.to_string())
+        .or(config.context)
+        .unwrap_or_default();
+    let links = SourceLinks {
+        context,
+        template: matches
+            .value_of("source-url")
+            .map(|template| template.to_string())
+            .or(config.source_url)
+            .unwrap_or_default(),
+        inputs: inputs.iter().map(PathBuf::from).collect(),
+    };
     let book = matches.value_of("book").unwrap_or("").to_string();
-    let file_paths = find_java_files(Path::new(dir.clone().as_str()));
-    let multi_thread = matches.is_present("multi_thread");
+    let globs = |name: &str, config: Option<Vec<String>>| -> Vec<String> {
+        match matches.values_of(name) {
+            Some(values) => values.map(|value| value.to_string()).collect(),
+            None => config.unwrap_or_default(),
+        }
+    };
+    let mut filter = PathFilter {
+        exclude: globs("exclude", config.exclude),
+        include: globs("include", config.include),
+        gitignore: matches.is_present("use-gitignore"),
+        follow_symlinks: matches.is_present("follow-symlinks"),
+        source_roots: match matches.values_of("source-root") {
+            Some(values) => values.map(|value| value.to_string()).collect(),
+            None => MAIN_SOURCE_ROOTS.iter().map(|root| root.to_string()).collect(),
+        },
+    };
+    if matches.is_present("include-tests") {
+        filter
+            .source_roots
+            .extend(TEST_SOURCE_ROOTS.iter().map(|root| root.to_string()));
+    }
+    if !matches.is_present("no-default-excludes") {
+        filter
+            .exclude
+            .extend(DEFAULT_EXCLUDES.iter().map(|pattern| pattern.to_string()));
+    }
+    let (file_paths, mut errors) = find_input_files(&inputs, &filter);
+    let no_inputs = file_paths.is_empty();
+    let multi_thread =
+        matches.is_present("multi-thread") || matches.is_present("jobs") || config.jobs.is_some();
+    let jobs = matches
+        .value_of("jobs")
+        .map(|value| value.parse().unwrap())
+        .or(config.jobs)
+        .unwrap_or_else(num_cpus::get);
     let lint = matches.is_present("lint");
-    let verbose = matches.is_present("verbose");
+    let report_coverage = matches.is_present("coverage");
+    let on_conflict = matches
+        .value_of("on-conflict")
+        .and_then(OnConflict::from_name)
+        .unwrap_or(OnConflict::Overwrite);
+    let badge = matches.value_of("badge");
+    let badge_colors = matches
+        .value_of("badge-colors")
+        .and_then(|value| BadgeColors::parse(value).ok())
+        .unwrap_or_default();
+    let fail_under = matches
+        .value_of("fail-under")
+        .map(|value| value.parse::<f64>().unwrap());
+    let mut coverage = Coverage::new(matches.is_present("count-private"));
+    let mut warnings = Vec::new();
+    let warnings_as_errors = matches.is_present("warnings-as-errors");
+    // The format has a default value, so the file is used when it is not on the command line
+    let format_name = if matches.occurrences_of("format") > 0 {
+        matches.value_of("format")
+    } else {
+        config.format.as_deref()
+    };
+    let format = match format_name {
+        Some("html") => Format::Html,
+        Some("asciidoc") => Format::Asciidoc,
+        Some("rst") => Format::Rst,
+        Some("json") => Format::Json,
+        _ => Format::Markdown,
+    };
+    // Docusaurus reads the ids and titles of the pages from their front matter
+    let docusaurus = matches.is_present("docusaurus");
+    let front_matter = if matches.is_present("front-matter") || docusaurus {
+        let extra = matches
+            .values_of("front-matter-extra")
+            .map(|values| values.map(FrontMatter::parse_extra).collect::<Result<Vec<_>, String>>())
+            .unwrap_or(Ok(Vec::new()));
 
-    let gen_book = if book != "" { true } else { false };
+        match extra {
+            Ok(extra) => Some(FrontMatter { extra, docusaurus }),
+            Err(message) => exit_with(LojidocError::InvalidArgs(message)),
+        }
+    } else {
+        None
+    };
+    let custom_labels = match matches.value_of("strings") {
+        Some(path) => match read_strings(Path::new(path)) {
+            Ok((labels, warnings)) => {
+                for warning in warnings {
+                    info!("{}", warning);
+                }
+                labels
+            }
+            Err(err) => exit_with(err),
+        },
+        None => BTreeMap::new(),
+    };
+    set_labels(Labels::new(
+        matches.value_of("lang").and_then(Lang::from_name).unwrap_or(Lang::En),
+        custom_labels,
+    ));
+    let templates = matches.value_of("template").map(|dir| match Templates::read_dir(Path::new(dir)) {
+        Ok(templates) => Arc::new(templates),
+        Err(err) => exit_with(err),
+    });
+    let mut output = Output {
+        format,
+        // The markdown book is only made from markdown pages
+        book: !book.is_empty() && format == Format::Markdown,
+        flat: matches.is_present("flat"),
+        single_file: matches.is_present("single-file"),
+        mdbook: matches.is_present("mdbook"),
+        docusaurus,
+        // Everything is documented unless a visibility is set
+        min_visibility: matches
+            .value_of("min-visibility")
+            .or(config.min_visibility.as_deref())
+            .and_then(Visibility::from_name)
+            .unwrap_or(Visibility::Private),
+        methods: MethodLayout {
+            order: matches
+                .value_of("sort-methods")
+                .and_then(MethodOrder::from_name)
+                .unwrap_or(MethodOrder::Source),
+            group_by_visibility: matches.is_present("group-by-visibility"),
+            include_source: include_source(&matches),
+        },
+        hide_jdk_imports: matches.is_present("hide-jdk-imports"),
+        front_matter,
+        templates,
+        // The pages of the java files which could not be found are kept
+        clean: matches.is_present("clean") && errors.is_empty(),
+        diagrams: matches.is_present("diagrams"),
+        diagram_methods: matches.is_present("diagram-methods"),
+        // The files in conflict are found once the settings are known
+        kept: Arc::default(),
+        charset: matches
+            .value_of("charset")
+            .and_then(Charset::from_name)
+            .unwrap_or(Charset::Auto),
+    };
+    if matches.is_present("clean") && !errors.is_empty() {
+        info!("Not cleaning {} since {} paths could not be read", dest, errors.len());
+    }
 
-    fs::create_dir_all(dest.as_str()).expect("File path not able to be created");
-    println!("\nGenerating documentation from {}\n", dir);
+    if output.single_file && matches!(format, Format::Html | Format::Asciidoc | Format::Rst) {
+        exit_with(LojidocError::InvalidArgs(
+            "--single-file is only supported by the markdown and json formats".to_string(),
+        ));
+    }
+    if output.templates.is_some() && format == Format::Json {
+        exit_with(LojidocError::InvalidArgs(
+            "--template renders pages, it is not supported by the json format".to_string(),
+        ));
+    }
+    if output.diagrams && (format != Format::Markdown || output.flat || output.single_file) {
+        exit_with(LojidocError::InvalidArgs(
+            "--diagrams needs markdown pages in package directories".to_string(),
+        ));
+    }
+    if output.mdbook && (format != Format::Markdown || output.flat || output.single_file) {
+        exit_with(LojidocError::InvalidArgs(
+            "--mdbook needs markdown pages in package directories".to_string(),
+        ));
+    }
+    if output.docusaurus && (format != Format::Markdown || output.flat || output.single_file) {
+        exit_with(LojidocError::InvalidArgs(
+            "--docusaurus needs markdown pages in package directories".to_string(),
+        ));
+    }
 
-    if file_paths.len() > 0 {
-        if book != "" {
-            let mut cfg = Config::default();
-            cfg.book.title = Some(book.clone());
+    // No documentation is written when linting or measuring the coverage
+    if lint || report_coverage {
+        if file_paths.is_empty() {
+            error!("No java files found in {}", inputs.join(", "));
+        } else {
+            errors.extend(check_javadoc(file_paths, lint, &mut coverage, output.charset, &mut warnings));
+        }
 
-            let init_res = MDBook::init("./markdown-book").with_config(cfg).build();
+        if report_coverage {
+            println!("{}", coverage.report());
 
-            if !init_res.is_ok() {
-                println!("Error initializing markdown book");
+            if let Some(extension) = matches.value_of("coverage-output") {
+                if let Err(err) = write_coverage_report(&coverage, dest.as_str(), extension) {
+                    errors.push(err);
+                }
+            }
+        }
+        if let Some(path) = badge {
+            if let Err(err) = write_badge(&coverage, path, badge_colors) {
+                errors.push(err);
             }
         }
+        let below = fail_under.is_some_and(|threshold| below_threshold(&coverage, threshold));
+        report_warnings(&warnings, warnings_as_errors);
+        report_errors(&errors);
+
+        exit_run(&errors, below || (warnings_as_errors && !warnings.is_empty()), no_inputs);
+        return;
+    }
 
-        if multi_thread {
-            document(file_paths, dest.clone(), context, verbose, gen_book);
-        } else if lint {
-            lint_javadoc(file_paths);
+    if matches.is_present("stdout") {
+        if file_paths.is_empty() {
+            error!("No java files found in {}", inputs.join(", "));
         } else {
-            document_single(file_paths, dest.clone(), context, verbose, gen_book);
+            errors.extend(print_pages(file_paths, &links, &output, &mut warnings));
+        }
+        report_warnings(&warnings, warnings_as_errors);
+        report_errors(&errors);
+
+        exit_run(&errors, warnings_as_errors && !warnings.is_empty(), no_inputs);
+        return;
+    }
+
+    if matches.is_present("dry-run") {
+        if file_paths.is_empty() {
+            error!("No java files found in {}", inputs.join(", "));
+        } else {
+            errors.extend(dry_run(file_paths, dest.as_str(), output, &mut warnings));
+        }
+        report_warnings(&warnings, warnings_as_errors);
+        report_errors(&errors);
+
+        exit_run(&errors, warnings_as_errors && !warnings.is_empty(), no_inputs);
+        return;
+    }
+
+    // The files in conflict are found before anything is written, so an error leaves
+    // the destination as it is
+    if on_conflict != OnConflict::Overwrite && !file_paths.is_empty() {
+        let conflicts = conflicting_files(&file_paths, dest.as_str(), &output);
+
+        if on_conflict == OnConflict::Error && !conflicts.is_empty() {
+            errors.extend(conflicts.into_iter().map(|path| LojidocError::Conflict { path }));
+            report_errors(&errors);
+
+            exit_run(&errors, false, no_inputs);
+            return;
+        }
+        for path in conflicts.iter() {
+            info!("{} already exists, it is kept", path.display());
         }
+        output.kept = Arc::new(conflicts.into_iter().collect());
+    }
+
+    if let Err(err) = prepare_destination(dest.as_str()) {
+        exit_with(err);
+    }
+    verbose!("Generating documentation from {}", inputs.join(", "));
+
+    let watching = matches.is_present("watch");
+    // Watching reuses the manifest of incremental runs to document the changed files
+    let incremental = matches.is_present("incremental") || watching;
+    let refresh = match matches.value_of("changed-since") {
+        Some(git_ref) => git_changes(&inputs, git_ref, &output).unwrap_or_else(|err| exit_with(err)),
+        None if incremental => Refresh::Incremental,
+        None => Refresh::All,
+    };
+    let jobs = if multi_thread { Some(jobs) } else { None };
 
-        if book != "" {
-            gen_md_book(dest);
+    if file_paths.len() > 0 {
+        if output.book {
+            init_book(&book);
+        }
+
+        let files = file_paths.len();
+
+        let run = Run {
+            dest: dest.as_str(),
+            links: &links,
+            output: &output,
+            jobs,
+            refresh: &refresh,
+        };
+        errors.extend(generate(file_paths, &run, &mut coverage, &mut warnings));
+
+        let parse_failures = errors
+            .iter()
+            .filter(|err| matches!(err, LojidocError::Parse { .. }))
+            .count();
+        let stats = Stats::new(&coverage, files, parse_failures, started.elapsed());
+        info!("\n{}", stats.summary());
+
+        if matches.is_present("stats") {
+            if let Err(err) = write_stats(&stats, dest.as_str()) {
+                errors.push(err);
+            }
+        }
+
+        if let Some(path) = badge {
+            if let Err(err) = write_badge(&coverage, path, badge_colors) {
+                errors.push(err);
+            }
+        }
+
+        if matches.is_present("check-links") {
+            let broken = check_links(Path::new(dest.as_str()), output.format);
+
+            verbose!("Checked the links of the pages, {} do not resolve", broken.len());
+            errors.extend(broken);
         }
     } else {
-        println!("No java files found");
+        error!("No java files found in {}", inputs.join(", "));
+    }
+
+    // The errors of the first run are reported before the inputs are watched
+    report_warnings(&warnings, warnings_as_errors);
+    report_errors(&errors);
+
+    if watching {
+        let report = |warnings: &[Warning], errors: &[LojidocError]| {
+            report_warnings(warnings, false);
+            report_errors(errors);
+        };
+
+        if let Err(err) = watch(&inputs, &filter, dest.as_str(), &links, &output, jobs, report) {
+            exit_with(err);
+        }
     }
+
+    let below = fail_under.is_some_and(|threshold| below_threshold(&coverage, threshold));
+
+    exit_run(&errors, below || (warnings_as_errors && !warnings.is_empty()), no_inputs);
 }
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,72 @@
+use model::doc::first_sentence;
+use model::member::Member;
+use model::model::ObjectType;
+
+#[derive(Debug, Serialize)]
+/// Struct containing annotation type documentation information
+/// Includes package name, imports, elements, and other data
+pub struct Annotation {
+    pub package_name: String,
+    /// The deprecation message, empty when deprecated without a message
+    pub deprecated: Option<String>,
+    /// The annotations of the declaration as they are written, e.g. `@Path("/items")`
+    pub annotations: Vec<String>,
+    pub access: String,
+    pub file_path: String,
+    /// The line of the declaration
+    pub line_num: String,
+    pub version: String,
+    pub since: String,
+    pub author: String,
+    pub name: String,
+    pub description: String,
+    pub dependencies: Vec<String>,
+    /// The retention policy from `@Retention`, such as `RUNTIME`
+    pub retention: String,
+    /// The element types from `@Target`, such as `METHOD`
+    pub targets: Vec<String>,
+    /// Annotation elements, the value of each element is its default
+    pub elements: Vec<Member>,
+    pub variables: Vec<Member>,
+    /// Classes, interfaces and enums declared in the body
+    pub inner_classes: Vec<ObjectType>,
+}
+
+impl Annotation {
+    pub fn ch_file_path(&mut self, value: String) {
+        for inner in self.inner_classes.iter_mut() {
+            inner.ch_file_path(value.clone());
+        }
+
+        self.file_path = value;
+    }
+    /// Gets the first sentence of the description of the annotation, for the index, the
+    /// front matter and the contents of a single page
+    pub fn summary(&self) -> String {
+        first_sentence(&self.description)
+    }
+}
+
+impl Clone for Annotation {
+    fn clone(&self) -> Annotation {
+        Annotation {
+            package_name: self.package_name.clone(),
+            dependencies: self.dependencies.clone(),
+            deprecated: self.deprecated.clone(),
+            annotations: self.annotations.clone(),
+            access: self.access.clone(),
+            file_path: self.file_path.clone(),
+            line_num: self.line_num.clone(),
+            version: self.version.clone(),
+            since: self.since.clone(),
+            author: self.author.clone(),
+            name: self.name.clone(),
+            description: self.description.clone(),
+            retention: self.retention.clone(),
+            targets: self.targets.clone(),
+            elements: self.elements.clone(),
+            variables: self.variables.clone(),
+            inner_classes: self.inner_classes.clone(),
+        }
+    }
+}
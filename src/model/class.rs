@@ -1,27 +1,46 @@
+use model::doc::first_sentence;
 use model::exception::Exception;
 use model::member::Member;
+use model::model::ObjectType;
 use model::method::Method;
+use model::param::Param;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 /// Struct containing class documentation information
 /// Includes package name, imports, methods, and other data
 pub struct Class {
     pub file_path: String,
+    /// The line of the declaration
+    pub line_num: String,
     pub package_name: String,
-    pub deprecation: String,
+    /// The deprecation message, empty when deprecated without a message
+    pub deprecated: Option<String>,
+    /// The annotations of the declaration as they are written, e.g. `@Path("/items")`
+    pub annotations: Vec<String>,
     pub license: String,
     pub parent: String,
     pub access: String,
     pub version: String,
+    pub since: String,
     pub author: String,
     pub name: String,
     pub description: String,
     pub exceptions: Vec<Exception>,
     pub interfaces: Vec<String>,
+    /// The subtypes a sealed class permits
+    pub permits: Vec<String>,
+    pub type_params: Vec<Param>,
+    /// Whether the class is declared as a record
+    pub record: bool,
+    /// Components of a record, documented by its `@param` tags
+    pub components: Vec<Param>,
     pub dependencies: Vec<String>,
     pub modifiers: Vec<String>,
+    pub constructors: Vec<Method>,
     pub methods: Vec<Method>,
     pub variables: Vec<Member>,
+    /// Classes, interfaces and enums declared in the body
+    pub inner_classes: Vec<ObjectType>,
 }
 
 impl Class {
@@ -29,23 +48,48 @@ impl Class {
         Class {
             package_name: String::new(),
             file_path: String::new(),
+            line_num: String::new(),
             dependencies: Vec::new(),
-            deprecation: String::new(),
+            deprecated: None,
+            annotations: Vec::new(),
             license: String::new(),
             parent: String::new(),
             interfaces: Vec::new(),
+            permits: Vec::new(),
+            type_params: Vec::new(),
+            record: false,
+            components: Vec::new(),
             access: String::new(),
             version: String::new(),
+            since: String::new(),
             author: String::new(),
             name: String::new(),
             exceptions: Vec::new(),
             description: String::new(),
             modifiers: Vec::new(),
             variables: Vec::new(),
+            inner_classes: Vec::new(),
+            constructors: Vec::new(),
             methods: Vec::new(),
         }
     }
-    pub fn clone(&mut self) -> Class {
+    pub fn ch_file_path(&mut self, value: String) {
+        for inner in self.inner_classes.iter_mut() {
+            inner.ch_file_path(value.clone());
+        }
+
+        self.file_path = value;
+    }
+    /// Gets the first sentence of the description of the class, for the index, the
+    /// front matter and the contents of a single page
+    pub fn summary(&self) -> String {
+        first_sentence(&self.description)
+    }
+}
+
+impl Clone for Class {
+    fn clone(&self) -> Class {
+        let mut new_constructors = Vec::new();
         let mut new_methods = Vec::new();
         let mut new_vars = Vec::new();
         let mut new_mods = Vec::new();
@@ -63,27 +107,36 @@ impl Class {
         for i in 0..self.exceptions.len() {
             new_except.push(self.exceptions[i].clone());
         }
+        for c in self.constructors.iter() {
+            new_constructors.push(c.clone());
+        }
 
         Class {
             parent: self.parent.clone(),
             file_path: self.file_path.clone(),
+            line_num: self.line_num.clone(),
             package_name: self.package_name.clone(),
             license: self.license.clone(),
             dependencies: self.dependencies.clone(),
-            deprecation: self.deprecation.clone(),
+            deprecated: self.deprecated.clone(),
+            annotations: self.annotations.clone(),
             access: self.access.clone(),
             version: self.version.clone(),
+            since: self.since.clone(),
             author: self.author.clone(),
             name: self.name.clone(),
             description: self.description.clone(),
             exceptions: new_except,
             interfaces: self.interfaces.clone(),
+            permits: self.permits.clone(),
+            type_params: self.type_params.clone(),
+            record: self.record,
+            components: self.components.clone(),
             modifiers: new_mods,
             variables: new_vars,
+            inner_classes: self.inner_classes.clone(),
+            constructors: new_constructors,
             methods: new_methods,
         }
     }
-    pub fn ch_file_path(&mut self, value: String) {
-        self.file_path = value;
-    }
 }
@@ -1,25 +1,59 @@
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+/// Struct for a documented type listed in the index
+pub struct PackageMember {
+    pub name: String,
+    /// Path of the type's page relative to the destination directory
+    pub path: String,
+    /// The first sentence of the type's description
+    pub summary: String,
+    /// Whether the type is deprecated, it is struck through in the listings
+    #[serde(default)]
+    pub deprecated: bool,
+    /// The type in the class diagram of its package
+    #[serde(default)]
+    pub diagram: DiagramNode,
+    /// The public methods of the type and its nested types for the API index
+    #[serde(default)]
+    pub methods: Vec<ApiEntry>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+/// Struct for a public method listed in the API index
+pub struct ApiEntry {
+    pub name: String,
+    /// The name with the parameter types, e.g. `withTimeout(Duration)`
+    pub title: String,
+    /// The type which declares the method, e.g. `Client.Builder` for a nested type
+    pub owner: String,
+    /// The anchor of the method on the page of its type, empty when it is not known
+    pub anchor: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+/// Struct for a type in the class diagram of its package
+pub struct DiagramNode {
+    /// `class`, `interface`, `enum` or `annotation`
+    pub kind: String,
+    /// The extended class, or the interfaces an interface extends
+    pub extends: Vec<String>,
+    /// The interfaces a class or enum implements
+    pub implements: Vec<String>,
+    /// The names of the public methods
+    pub methods: Vec<String>,
+}
+
 #[derive(Clone)]
 /// Struct for a java package. stores the name and member files
 pub struct Package {
-    name: String,
-    members: Vec<String>,
+    pub name: String,
+    /// The javadoc of the `package-info.java` file of the package
+    pub description: String,
+    pub members: Vec<PackageMember>,
 }
 
 impl Package {
-    pub fn add_class(&mut self, class_name: String) {
-        self.members.push(class_name);
-    }
-    pub fn clone(&mut self) -> Package {
-        let mut new_members: Vec<String> = Vec::new();
-
-        for m in self.members.clone() {
-            new_members.push(m);
-        }
-
-        Package {
-            name: self.name.clone(),
-            members: new_members,
-        }
+    pub fn add_member(&mut self, member: PackageMember) {
+        self.members.push(member);
     }
 }
 
@@ -42,12 +76,12 @@ impl ApplicationDoc {
             packages: Vec::new(),
         }
     }
-    pub fn add_package_class(&mut self, package: String, class: String) {
+    pub fn add_package_member(&mut self, package: String, member: PackageMember) {
         let mut found = false;
 
         for (i, p) in self.packages.clone().iter().enumerate() {
             if package == p.name {
-                self.packages[i].add_class(class.clone());
+                self.packages[i].add_member(member.clone());
                 found = true;
             }
         }
@@ -55,8 +89,31 @@ impl ApplicationDoc {
         if !found {
             self.packages.push(Package {
                 name: package,
-                members: vec![class],
+                description: String::new(),
+                members: vec![member],
             });
         }
     }
+    /// Adds the description of a package read from its `package-info.java` file
+    pub fn add_package_doc(&mut self, package: String, description: String) {
+        match self.packages.iter_mut().find(|p| p.name == package) {
+            Some(p) => p.description = description,
+            None => self.packages.push(Package {
+                name: package,
+                description,
+                members: Vec::new(),
+            }),
+        }
+    }
+    /// Adds the packages and members documented by another run of `generate_markdown`
+    pub fn merge(&mut self, other: ApplicationDoc) {
+        for package in other.packages {
+            if !package.description.is_empty() {
+                self.add_package_doc(package.name.clone(), package.description);
+            }
+            for member in package.members {
+                self.add_package_member(package.name.clone(), member);
+            }
+        }
+    }
 }
@@ -8,8 +8,11 @@ pub struct Doc {
     pub description: String,
     pub author: String,
     pub version: String,
+    pub since: String,
     pub exceptions: Vec<Exception>,
-    pub deprecated: String,
+    pub deprecated: Option<String>,
+    /// The annotations of the declaration as they are written, e.g. `@Path("/items")`
+    pub annotations: Vec<String>,
     pub return_desc: String,
     pub see: String,
 }
@@ -22,9 +25,120 @@ impl Doc {
             return_desc: String::new(),
             author: String::new(),
             version: String::new(),
+            since: String::new(),
             exceptions: Vec::new(),
-            deprecated: String::new(),
+            deprecated: None,
+            annotations: Vec::new(),
             see: String::new(),
         }
     }
 }
+
+/// Words ending with a period which do not end the summary sentence
+const ABBREVIATIONS: &[&str] = &["e.g.", "i.e.", "etc.", "vs.", "cf.", "approx.", "Mr.", "Mrs.", "Dr.", "No."];
+
+/// Gets the summary sentence of a javadoc description, which ends at the first period
+/// followed by whitespace like the summary javadoc shows. Periods of abbreviations such
+/// as `e.g.` do not end the sentence, and the first line is used when no sentence ends.
+/// Inline tags are replaced by their text, e.g. `List` for `{@link List}`.
+pub fn first_sentence(description: &str) -> String {
+    let text = strip_inline_tags(description);
+    let text = text.trim();
+    let mut end = None;
+
+    for (i, ch) in text.char_indices() {
+        // The text after a character starts after all of its bytes
+        let next = i + ch.len_utf8();
+        let followed_by_space = text[next..].starts_with(char::is_whitespace);
+
+        if ch == '.' && followed_by_space && !is_abbreviation(&text[..next]) {
+            end = Some(next);
+            break;
+        }
+    }
+
+    let sentence = match end {
+        Some(end) => &text[..end],
+        None => text.lines().next().unwrap_or(""),
+    };
+
+    sentence.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+/// Whether the last word of a text is an abbreviation, either a known one or one of
+/// letters between periods like `U.S.`
+fn is_abbreviation(text: &str) -> bool {
+    let word = text.rsplit(char::is_whitespace).next().unwrap_or(text);
+    let word = word.trim_start_matches(['(', '"']);
+
+    ABBREVIATIONS.iter().any(|abbreviation| word.eq_ignore_ascii_case(abbreviation))
+        || (word[..word.len() - 1].contains('.')
+            && word[..word.len() - 1]
+                .split('.')
+                .all(|part| !part.is_empty() && part.len() <= 2 && part.chars().all(char::is_alphabetic)))
+}
+
+/// Replaces the inline tags of a description by their text. Links keep their label or
+/// their reference with `.` for `#`, other tags keep their content.
+fn strip_inline_tags(text: &str) -> String {
+    let mut result = String::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find("{@") {
+        result.push_str(&rest[..start]);
+        let tag = &rest[start..];
+        let mut depth = 0;
+        let end = tag.char_indices().find(|&(_, ch)| {
+            match ch {
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                _ => (),
+            }
+            depth == 0
+        });
+
+        match end {
+            Some((end, _)) => {
+                let body = &tag[2..end];
+                let (name, content) = match body.find(char::is_whitespace) {
+                    Some(i) => (&body[..i], body[i..].trim()),
+                    None => (body, ""),
+                };
+
+                if name == "link" || name == "linkplain" {
+                    result.push_str(link_text(content).as_str());
+                } else {
+                    result.push_str(content);
+                }
+
+                rest = &tag[end + 1..];
+            }
+            None => {
+                result.push_str(rest);
+                rest = "";
+            }
+        }
+    }
+
+    result.push_str(rest);
+
+    result
+}
+
+/// Gets the text of a link, its label or its reference, e.g. `add` for
+/// `List#add(Object) add` and `List.add(Object)` without a label
+fn link_text(content: &str) -> String {
+    // The parameters of a method reference may contain spaces
+    let reference_end = match content.find(|ch: char| ch.is_whitespace() || ch == '(') {
+        Some(i) if content[i..].starts_with('(') => content.find(')').map_or(content.len(), |close| close + 1),
+        Some(i) => i,
+        None => content.len(),
+    };
+    let label = content[reference_end..].trim();
+
+    if label.is_empty() {
+        content[..reference_end].trim_start_matches('#').replace('#', ".")
+    } else {
+        label.to_string()
+    }
+}
@@ -1,32 +1,43 @@
+use model::doc::first_sentence;
 use model::exception::Exception;
 use model::member::Member;
+use model::model::ObjectType;
 use model::method::Method;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
+/// Struct representing an enum constant, its constructor arguments, and its javadoc
 pub struct EnumerationField {
     pub name: String,
     pub value: String,
+    pub desc: String,
 }
 
-impl EnumerationField {
-    pub fn clone(&self) -> EnumerationField {
+impl Clone for EnumerationField {
+    fn clone(&self) -> EnumerationField {
         EnumerationField {
             name: self.name.clone(),
             value: self.value.clone(),
+            desc: self.desc.clone(),
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 /// Struct containing enumeration documentation information
 /// Includes package name, imports, methods, and other data
 pub struct Enumeration {
     pub file_path: String,
+    /// The line of the declaration
+    pub line_num: String,
     pub package_name: String,
-    pub deprecation: String,
+    /// The deprecation message, empty when deprecated without a message
+    pub deprecated: Option<String>,
+    /// The annotations of the declaration as they are written, e.g. `@Path("/items")`
+    pub annotations: Vec<String>,
     pub license: String,
     pub access: String,
     pub version: String,
+    pub since: String,
     pub author: String,
     pub name: String,
     pub description: String,
@@ -35,8 +46,11 @@ pub struct Enumeration {
     pub dependencies: Vec<String>,
     pub modifiers: Vec<String>,
     pub fields: Vec<EnumerationField>,
+    pub constructors: Vec<Method>,
     pub methods: Vec<Method>,
     pub variables: Vec<Member>,
+    /// Classes, interfaces and enums declared in the body
+    pub inner_classes: Vec<ObjectType>,
 }
 
 impl Enumeration {
@@ -44,12 +58,15 @@ impl Enumeration {
         Enumeration {
             package_name: String::new(),
             file_path: String::new(),
+            line_num: String::new(),
             dependencies: Vec::new(),
-            deprecation: String::new(),
+            deprecated: None,
+            annotations: Vec::new(),
             license: String::new(),
             interfaces: Vec::new(),
             access: String::new(),
             version: String::new(),
+            since: String::new(),
             author: String::new(),
             name: String::new(),
             exceptions: Vec::new(),
@@ -57,51 +74,11 @@ impl Enumeration {
             modifiers: Vec::new(),
             fields: Vec::new(),
             variables: Vec::new(),
+            inner_classes: Vec::new(),
+            constructors: Vec::new(),
             methods: Vec::new(),
         }
     }
-    pub fn clone(&mut self) -> Enumeration {
-        let mut new_methods = Vec::new();
-        let mut new_vars = Vec::new();
-        let mut new_mods = Vec::new();
-        let mut new_except = Vec::new();
-        let mut new_fields = Vec::new();
-
-        for i in 0..self.methods.len() {
-            new_methods.push(self.methods[i].clone());
-        }
-        for i in 0..self.variables.len() {
-            new_vars.push(self.variables[i].clone());
-        }
-        for i in 0..self.modifiers.len() {
-            new_mods.push(self.modifiers[i].clone());
-        }
-        for i in 0..self.exceptions.len() {
-            new_except.push(self.exceptions[i].clone());
-        }
-        for i in 0..self.fields.len() {
-            new_fields.push(self.fields[i].clone());
-        }
-
-        Enumeration {
-            file_path: self.file_path.clone(),
-            package_name: self.package_name.clone(),
-            license: self.license.clone(),
-            dependencies: self.dependencies.clone(),
-            deprecation: self.deprecation.clone(),
-            access: self.access.clone(),
-            version: self.version.clone(),
-            author: self.author.clone(),
-            name: self.name.clone(),
-            description: self.description.clone(),
-            exceptions: new_except,
-            interfaces: self.interfaces.clone(),
-            modifiers: new_mods,
-            fields: new_fields,
-            variables: new_vars,
-            methods: new_methods,
-        }
-    }
     pub fn ch_access(&mut self, value: String) {
         self.access = value;
     }
@@ -109,6 +86,10 @@ impl Enumeration {
         self.license = value;
     }
     pub fn ch_file_path(&mut self, value: String) {
+        for inner in self.inner_classes.iter_mut() {
+            inner.ch_file_path(value.clone());
+        }
+
         self.file_path = value;
     }
     pub fn ch_package_name(&mut self, value: String) {
@@ -121,7 +102,13 @@ impl Enumeration {
         self.description = value;
     }
     pub fn ch_version(&mut self, value: String) {
-        self.deprecation = value;
+        self.version = value;
+    }
+    pub fn ch_since(&mut self, value: String) {
+        self.since = value;
+    }
+    pub fn ch_deprecated(&mut self, value: Option<String>) {
+        self.deprecated = value;
     }
     pub fn ch_author(&mut self, value: String) {
         self.author = value;
@@ -147,4 +134,63 @@ impl Enumeration {
     pub fn add_exception(&mut self, value: Exception) {
         self.exceptions.push(value);
     }
+    /// Gets the first sentence of the description of the enum, for the index, the
+    /// front matter and the contents of a single page
+    pub fn summary(&self) -> String {
+        first_sentence(&self.description)
+    }
+}
+
+impl Clone for Enumeration {
+    fn clone(&self) -> Enumeration {
+        let mut new_constructors = Vec::new();
+        let mut new_methods = Vec::new();
+        let mut new_vars = Vec::new();
+        let mut new_mods = Vec::new();
+        let mut new_except = Vec::new();
+        let mut new_fields = Vec::new();
+
+        for i in 0..self.methods.len() {
+            new_methods.push(self.methods[i].clone());
+        }
+        for i in 0..self.variables.len() {
+            new_vars.push(self.variables[i].clone());
+        }
+        for i in 0..self.modifiers.len() {
+            new_mods.push(self.modifiers[i].clone());
+        }
+        for i in 0..self.exceptions.len() {
+            new_except.push(self.exceptions[i].clone());
+        }
+        for i in 0..self.fields.len() {
+            new_fields.push(self.fields[i].clone());
+        }
+        for c in self.constructors.iter() {
+            new_constructors.push(c.clone());
+        }
+
+        Enumeration {
+            file_path: self.file_path.clone(),
+            line_num: self.line_num.clone(),
+            package_name: self.package_name.clone(),
+            license: self.license.clone(),
+            dependencies: self.dependencies.clone(),
+            deprecated: self.deprecated.clone(),
+            annotations: self.annotations.clone(),
+            access: self.access.clone(),
+            version: self.version.clone(),
+            since: self.since.clone(),
+            author: self.author.clone(),
+            name: self.name.clone(),
+            description: self.description.clone(),
+            exceptions: new_except,
+            interfaces: self.interfaces.clone(),
+            modifiers: new_mods,
+            fields: new_fields,
+            variables: new_vars,
+            inner_classes: self.inner_classes.clone(),
+            constructors: new_constructors,
+            methods: new_methods,
+        }
+    }
 }
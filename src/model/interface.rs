@@ -1,25 +1,56 @@
+use model::doc::first_sentence;
 use model::member::Member;
+use model::model::ObjectType;
 use model::method::Method;
+use model::param::Param;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 /// Struct containing interface documentation information
 /// Includes package name, imports, method templates, and other data
 pub struct Interface {
     pub package_name: String,
-    pub deprecation: String,
+    /// The deprecation message, empty when deprecated without a message
+    pub deprecated: Option<String>,
+    /// The annotations of the declaration as they are written, e.g. `@Path("/items")`
+    pub annotations: Vec<String>,
     pub access: String,
     pub file_path: String,
+    /// The line of the declaration
+    pub line_num: String,
     pub version: String,
+    pub since: String,
     pub author: String,
     pub name: String,
     pub description: String,
+    /// The interfaces the interface extends
+    pub extends: Vec<String>,
+    /// The subtypes a sealed interface permits
+    pub permits: Vec<String>,
     pub dependencies: Vec<String>,
+    pub type_params: Vec<Param>,
     pub variables: Vec<Member>,
+    /// Classes, interfaces and enums declared in the body
+    pub inner_classes: Vec<ObjectType>,
     pub methods: Vec<Method>,
 }
 
 impl Interface {
-    pub fn clone(&mut self) -> Interface {
+    pub fn ch_file_path(&mut self, value: String) {
+        for inner in self.inner_classes.iter_mut() {
+            inner.ch_file_path(value.clone());
+        }
+
+        self.file_path = value;
+    }
+    /// Gets the first sentence of the description of the interface, for the index, the
+    /// front matter and the contents of a single page
+    pub fn summary(&self) -> String {
+        first_sentence(&self.description)
+    }
+}
+
+impl Clone for Interface {
+    fn clone(&self) -> Interface {
         let mut new_methods = Vec::new();
         let mut new_variables = Vec::new();
 
@@ -34,18 +65,22 @@ impl Interface {
         Interface {
             package_name: self.package_name.clone(),
             dependencies: self.dependencies.clone(),
-            deprecation: self.deprecation.clone(),
+            deprecated: self.deprecated.clone(),
+            annotations: self.annotations.clone(),
             access: self.access.clone(),
-            file_path: self.access.clone(),
+            file_path: self.file_path.clone(),
+            line_num: self.line_num.clone(),
             version: self.version.clone(),
+            since: self.since.clone(),
             author: self.author.clone(),
             name: self.name.clone(),
             description: self.description.clone(),
+            extends: self.extends.clone(),
+            permits: self.permits.clone(),
+            type_params: self.type_params.clone(),
             variables: new_variables,
+            inner_classes: self.inner_classes.clone(),
             methods: new_methods,
         }
     }
-    pub fn ch_file_path(&mut self, value: String) {
-        self.file_path = value;
-    }
 }
@@ -1,4 +1,4 @@
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 /// Struct representing member variable data contained in javadoc and declaration
 pub struct Member {
     pub line_num: String,
@@ -7,19 +7,11 @@ pub struct Member {
     pub access: String,
     pub name: String,
     pub var_type: String,
+    /// Initial value of a `static final` constant
+    pub value: String,
 }
 
 impl Member {
-    pub fn clone(&self) -> Member {
-        Member {
-            line_num: self.line_num.clone(),
-            desc: self.desc.clone(),
-            access: self.access.clone(),
-            name: self.name.clone(),
-            modifiers: self.modifiers.clone(),
-            var_type: self.var_type.clone(),
-        }
-    }
     pub fn new() -> Member {
         Member {
             line_num: String::new(),
@@ -28,6 +20,7 @@ impl Member {
             name: String::new(),
             modifiers: Vec::new(),
             var_type: String::new(),
+            value: String::new(),
         }
     }
     pub fn ch_name(&mut self, value: String) {
@@ -43,6 +36,26 @@ impl Member {
         self.modifiers.push(value);
     }
     pub fn ch_line_number(&mut self, value: String) {
-        self.modifiers.push(value);
+        self.line_num = value;
+    }
+    pub fn ch_description(&mut self, value: String) {
+        self.desc = value;
+    }
+    pub fn ch_value(&mut self, value: String) {
+        self.value = value;
+    }
+}
+
+impl Clone for Member {
+    fn clone(&self) -> Member {
+        Member {
+            line_num: self.line_num.clone(),
+            desc: self.desc.clone(),
+            access: self.access.clone(),
+            name: self.name.clone(),
+            modifiers: self.modifiers.clone(),
+            var_type: self.var_type.clone(),
+            value: self.value.clone(),
+        }
     }
 }
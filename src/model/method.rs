@@ -1,59 +1,58 @@
+use model::doc::first_sentence;
 use model::exception::Exception;
 use model::param::Param;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 /// Struct containing method data from the javadoc and method declaration
 pub struct Method {
     pub line_num: String,
     pub parameters: Vec<Param>,
+    pub type_params: Vec<Param>,
     pub modifiers: Vec<String>,
     pub name: String,
     pub privacy: String,
     pub description: String,
     pub exceptions: Vec<Exception>,
+    /// The exceptions in the throws clause of the declaration
+    pub throws: Vec<String>,
     pub return_type: String,
+    pub return_desc: String,
+    pub since: String,
+    /// The deprecation message, empty when deprecated without a message
+    pub deprecated: Option<String>,
+    /// The annotations of the declaration as they are written, e.g. `@Path("/items")`
+    pub annotations: Vec<String>,
+    /// The names of the `@param` tags which do not match a parameter, for the lint mode
+    #[serde(skip)]
+    pub unmatched_params: Vec<String>,
+    /// The source of the method from its declaration to the end of its body, empty
+    /// when it has no body, for `--include-source`
+    #[serde(skip)]
+    pub source: String,
 }
 
 impl Method {
     pub fn new() -> Method {
         Method {
             parameters: Vec::new(),
+            type_params: Vec::new(),
             modifiers: Vec::new(),
             exceptions: Vec::new(),
+            throws: Vec::new(),
             line_num: String::new(),
             name: String::new(),
             privacy: String::new(),
             description: String::new(),
             return_type: String::new(),
+            return_desc: String::new(),
+            since: String::new(),
+            deprecated: None,
+            annotations: Vec::new(),
+            unmatched_params: Vec::new(),
+            source: String::new(),
         }
     }
-    pub fn clone(&mut self) -> Method {
-        let mut new_params = Vec::new();
-        let mut new_excepts = Vec::new();
-        let mut new_modifiers = Vec::new();
-
-        for i in 0..self.parameters.len() {
-            new_params.push(self.parameters[i].clone());
-        }
-        for i in 0..self.exceptions.len() {
-            new_excepts.push(self.exceptions[i].clone());
-        }
-        for i in 0..self.modifiers.len() {
-            new_modifiers.push(self.modifiers[i].clone());
-        }
-
-        Method {
-            line_num: self.line_num.clone(),
-            parameters: new_params,
-            modifiers: new_modifiers,
-            exceptions: new_excepts,
-            name: self.name.clone(),
-            privacy: self.privacy.clone(),
-            description: self.description.clone(),
-            return_type: self.return_type.clone(),
-        }
-    }
-    pub fn clone_params(&mut self) -> Vec<Param> {
+    pub fn clone_params(&self) -> Vec<Param> {
         let mut new_params = Vec::new();
 
         for i in 0..self.parameters.len() {
@@ -80,13 +79,73 @@ impl Method {
     pub fn add_exception(&mut self, value: Exception) {
         self.exceptions.push(value);
     }
+    pub fn add_throws(&mut self, value: String) {
+        self.throws.push(value);
+    }
     pub fn add_param(&mut self, value: Param) {
         self.parameters.push(value);
     }
     pub fn ch_params(&mut self, value: Vec<Param>) {
         self.parameters = value;
     }
+    pub fn ch_type_params(&mut self, value: Vec<Param>) {
+        self.type_params = value;
+    }
     pub fn ch_return_type(&mut self, value: String) {
         self.return_type = value;
     }
+    pub fn ch_return_desc(&mut self, value: String) {
+        self.return_desc = value;
+    }
+    pub fn ch_deprecated(&mut self, value: Option<String>) {
+        self.deprecated = value;
+    }
+    pub fn ch_annotations(&mut self, value: Vec<String>) {
+        self.annotations = value;
+    }
+    pub fn ch_since(&mut self, value: String) {
+        self.since = value;
+    }
+    /// Gets the first sentence of the description of the method, the summary
+    /// javadoc shows in the lists of methods
+    pub fn summary(&self) -> String {
+        first_sentence(&self.description)
+    }
+}
+
+impl Clone for Method {
+    fn clone(&self) -> Method {
+        let mut new_params = Vec::new();
+        let mut new_excepts = Vec::new();
+        let mut new_modifiers = Vec::new();
+
+        for i in 0..self.parameters.len() {
+            new_params.push(self.parameters[i].clone());
+        }
+        for i in 0..self.exceptions.len() {
+            new_excepts.push(self.exceptions[i].clone());
+        }
+        for i in 0..self.modifiers.len() {
+            new_modifiers.push(self.modifiers[i].clone());
+        }
+
+        Method {
+            line_num: self.line_num.clone(),
+            parameters: new_params,
+            type_params: self.type_params.clone(),
+            modifiers: new_modifiers,
+            exceptions: new_excepts,
+            throws: self.throws.clone(),
+            name: self.name.clone(),
+            privacy: self.privacy.clone(),
+            description: self.description.clone(),
+            return_type: self.return_type.clone(),
+            return_desc: self.return_desc.clone(),
+            since: self.since.clone(),
+            deprecated: self.deprecated.clone(),
+            annotations: self.annotations.clone(),
+            unmatched_params: self.unmatched_params.clone(),
+            source: self.source.clone(),
+        }
+    }
 }
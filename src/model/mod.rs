@@ -1,3 +1,4 @@
+pub mod annotation;
 pub mod class;
 pub mod contents;
 pub mod doc;
@@ -6,6 +7,7 @@ pub mod exception;
 pub mod interface;
 pub mod member;
 pub mod method;
+pub mod module;
 pub mod object;
 pub mod param;
 pub mod project;
@@ -13,23 +15,65 @@ pub mod project;
 pub mod model {
     //! Module that contains all necessary data stuctures for parsing javadocs and generating docs
 
+    pub use model::annotation::Annotation;
     pub use model::class::Class;
+    pub use model::contents::ApiEntry;
     pub use model::contents::ApplicationDoc;
+    pub use model::contents::DiagramNode;
+    pub use model::contents::Package;
+    pub use model::contents::PackageMember;
+    pub use model::doc::first_sentence;
     pub use model::doc::Doc;
     pub use model::enumeration::Enumeration;
+    pub use model::enumeration::EnumerationField;
     pub use model::exception::Exception;
     pub use model::interface::Interface;
     pub use model::member::Member;
     pub use model::method::Method;
+    pub use model::module::Module;
+    pub use model::module::ModuleDirective;
     pub use model::object::Object;
     pub use model::object::ObjectState;
     pub use model::param::Param;
     pub use model::project::Project;
 
+    #[derive(Debug, Serialize)]
+    #[serde(tag = "kind", rename_all = "lowercase")]
     pub enum ObjectType {
         Class(Class),
         Interface(Interface),
         Enumeration(Enumeration),
+        Annotation(Annotation),
+    }
+
+    impl ObjectType {
+        pub fn name(&self) -> &str {
+            match self {
+                ObjectType::Class(class) => &class.name,
+                ObjectType::Interface(inter) => &inter.name,
+                ObjectType::Enumeration(enumeration) => &enumeration.name,
+                ObjectType::Annotation(annotation) => &annotation.name,
+            }
+        }
+        pub fn ch_file_path(&mut self, value: String) {
+            match self {
+                ObjectType::Class(class) => class.ch_file_path(value),
+                ObjectType::Interface(inter) => inter.ch_file_path(value),
+                ObjectType::Enumeration(enumeration) => enumeration.ch_file_path(value),
+                ObjectType::Annotation(annotation) => annotation.ch_file_path(value),
+            }
+        }
+    }
+
+    impl Clone for ObjectType {
+        fn clone(&self) -> ObjectType {
+            match self {
+                ObjectType::Class(class) => ObjectType::Class(class.clone()),
+                ObjectType::Interface(inter) => ObjectType::Interface(inter.clone()),
+                ObjectType::Enumeration(enumeration) => ObjectType::Enumeration(enumeration.clone()),
+                ObjectType::Annotation(annotation) => ObjectType::Annotation(annotation.clone()),
+            }
+        }
     }
 }
 
@@ -0,0 +1,31 @@
+#[derive(Debug, Clone, PartialEq, Serialize)]
+/// Struct for a directive of a module declaration, e.g. `requires transitive java.sql;`
+pub struct ModuleDirective {
+    /// The directive: `requires`, `exports`, `opens`, `uses` or `provides`
+    pub kind: String,
+    /// The `transitive` and `static` modifiers of a `requires` directive
+    pub modifiers: Vec<String>,
+    /// The module, package or service named by the directive
+    pub name: String,
+    /// The modules a package is exported or opened to, or the implementations of a
+    /// provided service
+    pub targets: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+/// Struct for the module declared in a `module-info.java` file
+pub struct Module {
+    pub name: String,
+    /// The javadoc of the module declaration
+    pub description: String,
+    /// Whether every package of the module is open to reflection
+    pub open: bool,
+    pub directives: Vec<ModuleDirective>,
+}
+
+impl Module {
+    /// Gets the directives of a kind in the order they are declared
+    pub fn directives_of<'a>(&'a self, kind: &'a str) -> impl Iterator<Item = &'a ModuleDirective> + 'a {
+        self.directives.iter().filter(move |directive| directive.kind == kind)
+    }
+}
@@ -1,25 +1,32 @@
+use model::annotation::Annotation;
 use model::class::Class;
 use model::enumeration::Enumeration;
 use model::enumeration::EnumerationField;
 use model::exception::Exception;
 use model::interface::Interface;
 use model::member::Member;
+use model::model::ObjectType;
 use model::method::Method;
+use model::param::Param;
 
 #[derive(Debug)]
 pub enum ObjectState {
     Class,
     Interface,
     Enumeration,
+    Annotation,
+    Record,
     Unset,
 }
 
-impl ObjectState {
-    pub fn clone(&self) -> ObjectState {
+impl Clone for ObjectState {
+    fn clone(&self) -> ObjectState {
         match self {
             ObjectState::Class => ObjectState::Class,
             ObjectState::Interface => ObjectState::Interface,
             ObjectState::Enumeration => ObjectState::Enumeration,
+            ObjectState::Annotation => ObjectState::Annotation,
+            ObjectState::Record => ObjectState::Record,
             ObjectState::Unset => ObjectState::Unset,
         }
     }
@@ -31,22 +38,40 @@ impl ObjectState {
 pub struct Object {
     pub state: ObjectState,
     pub file_path: String,
+    /// The line of the declaration
+    pub line_num: String,
     pub package_name: String,
-    pub deprecation: String,
+    /// The deprecation message, empty when deprecated without a message
+    pub deprecated: Option<String>,
+    /// The annotations of the declaration as they are written, e.g. `@Path("/items")`
+    pub annotations: Vec<String>,
     pub license: String,
     pub parent: String,
     pub access: String,
     pub version: String,
+    pub since: String,
     pub author: String,
     pub name: String,
     pub description: String,
     pub exceptions: Vec<Exception>,
     pub interfaces: Vec<String>,
+    /// The subtypes a sealed type permits
+    pub permits: Vec<String>,
+    pub type_params: Vec<Param>,
+    /// Components of a record
+    pub components: Vec<Param>,
     pub dependencies: Vec<String>,
     pub fields: Vec<EnumerationField>,
     pub modifiers: Vec<String>,
+    pub constructors: Vec<Method>,
     pub methods: Vec<Method>,
     pub variables: Vec<Member>,
+    /// Elements of an annotation type
+    pub elements: Vec<Member>,
+    pub retention: String,
+    pub targets: Vec<String>,
+    /// Classes, interfaces and enums declared in the body
+    pub inner_classes: Vec<ObjectType>,
 }
 
 impl Object {
@@ -55,13 +80,19 @@ impl Object {
             state: ObjectState::Unset,
             package_name: String::new(),
             file_path: String::new(),
+            line_num: String::new(),
             dependencies: Vec::new(),
-            deprecation: String::new(),
+            deprecated: None,
+            annotations: Vec::new(),
             license: String::new(),
             parent: String::new(),
             interfaces: Vec::new(),
+            permits: Vec::new(),
+            type_params: Vec::new(),
+            components: Vec::new(),
             access: String::new(),
             version: String::new(),
+            since: String::new(),
             author: String::new(),
             name: String::new(),
             exceptions: Vec::new(),
@@ -69,15 +100,20 @@ impl Object {
             fields: Vec::new(),
             modifiers: Vec::new(),
             variables: Vec::new(),
+            elements: Vec::new(),
+            retention: String::new(),
+            targets: Vec::new(),
+            inner_classes: Vec::new(),
+            constructors: Vec::new(),
             methods: Vec::new(),
         }
     }
-    pub fn clone(&mut self) -> Object {
+    pub fn to_class(&mut self) -> Class {
+        let mut new_constructors = Vec::new();
         let mut new_methods = Vec::new();
         let mut new_vars = Vec::new();
         let mut new_mods = Vec::new();
         let mut new_except = Vec::new();
-        let mut new_fields = Vec::new();
 
         for i in 0..self.methods.len() {
             new_methods.push(self.methods[i].clone());
@@ -91,68 +127,49 @@ impl Object {
         for i in 0..self.exceptions.len() {
             new_except.push(self.exceptions[i].clone());
         }
-        for i in 0..self.fields.len() {
-            new_fields.push(self.fields[i].clone());
+        for c in self.constructors.iter() {
+            new_constructors.push(c.clone());
         }
 
-        Object {
-            state: self.state.clone(),
+        Class {
+            type_params: self.type_params.clone(),
+            record: matches!(self.state, ObjectState::Record),
+            components: self.components.clone(),
             parent: self.parent.clone(),
             file_path: self.file_path.clone(),
+            line_num: self.line_num.clone(),
             package_name: self.package_name.clone(),
             license: self.license.clone(),
             dependencies: self.dependencies.clone(),
-            deprecation: self.deprecation.clone(),
+            deprecated: self.deprecated.clone(),
+            annotations: self.annotations.clone(),
             access: self.access.clone(),
             version: self.version.clone(),
+            since: self.since.clone(),
             author: self.author.clone(),
             name: self.name.clone(),
             description: self.description.clone(),
             exceptions: new_except,
             interfaces: self.interfaces.clone(),
-            fields: new_fields,
+            permits: self.permits.clone(),
             modifiers: new_mods,
             variables: new_vars,
+            inner_classes: self.inner_classes.clone(),
+            constructors: new_constructors,
             methods: new_methods,
         }
     }
-    pub fn to_class(&mut self) -> Class {
-        let mut new_methods = Vec::new();
-        let mut new_vars = Vec::new();
-        let mut new_mods = Vec::new();
-        let mut new_except = Vec::new();
+    /// Gets the interfaces an interface extends. The first one is read as the parent
+    /// of a class, the others as implemented interfaces.
+    pub fn extended_interfaces(&self) -> Vec<String> {
+        let mut types = Vec::new();
 
-        for i in 0..self.methods.len() {
-            new_methods.push(self.methods[i].clone());
-        }
-        for i in 0..self.variables.len() {
-            new_vars.push(self.variables[i].clone());
-        }
-        for i in 0..self.modifiers.len() {
-            new_mods.push(self.modifiers[i].clone());
-        }
-        for i in 0..self.exceptions.len() {
-            new_except.push(self.exceptions[i].clone());
+        if !self.parent.is_empty() {
+            types.push(self.parent.clone());
         }
+        types.extend(self.interfaces.iter().cloned());
 
-        Class {
-            parent: self.parent.clone(),
-            file_path: self.file_path.clone(),
-            package_name: self.package_name.clone(),
-            license: self.license.clone(),
-            dependencies: self.dependencies.clone(),
-            deprecation: self.deprecation.clone(),
-            access: self.access.clone(),
-            version: self.version.clone(),
-            author: self.author.clone(),
-            name: self.name.clone(),
-            description: self.description.clone(),
-            exceptions: new_except,
-            interfaces: self.interfaces.clone(),
-            modifiers: new_mods,
-            variables: new_vars,
-            methods: new_methods,
-        }
+        types
     }
     pub fn to_interface(&mut self) -> Interface {
         let mut new_methods = Vec::new();
@@ -168,18 +185,26 @@ impl Object {
         Interface {
             package_name: self.package_name.clone(),
             dependencies: self.dependencies.clone(),
-            deprecation: self.deprecation.clone(),
+            deprecated: self.deprecated.clone(),
+            annotations: self.annotations.clone(),
             access: self.access.clone(),
             file_path: self.file_path.clone(),
+            line_num: self.line_num.clone(),
             version: self.version.clone(),
+            since: self.since.clone(),
             author: self.author.clone(),
             name: self.name.clone(),
             description: self.description.clone(),
+            extends: self.extended_interfaces(),
+            permits: self.permits.clone(),
+            type_params: self.type_params.clone(),
             variables: new_variables,
+            inner_classes: self.inner_classes.clone(),
             methods: new_methods,
         }
     }
     pub fn to_enumeration(&mut self) -> Enumeration {
+        let mut new_constructors = Vec::new();
         let mut new_methods = Vec::new();
         let mut new_vars = Vec::new();
         let mut new_mods = Vec::new();
@@ -201,15 +226,21 @@ impl Object {
         for i in 0..self.fields.len() {
             new_fields.push(self.fields[i].clone());
         }
+        for c in self.constructors.iter() {
+            new_constructors.push(c.clone());
+        }
 
         Enumeration {
             file_path: self.file_path.clone(),
+            line_num: self.line_num.clone(),
             package_name: self.package_name.clone(),
             license: self.license.clone(),
             dependencies: self.dependencies.clone(),
-            deprecation: self.deprecation.clone(),
+            deprecated: self.deprecated.clone(),
+            annotations: self.annotations.clone(),
             access: self.access.clone(),
             version: self.version.clone(),
+            since: self.since.clone(),
             author: self.author.clone(),
             name: self.name.clone(),
             description: self.description.clone(),
@@ -218,9 +249,32 @@ impl Object {
             fields: new_fields,
             modifiers: new_mods,
             variables: new_vars,
+            inner_classes: self.inner_classes.clone(),
+            constructors: new_constructors,
             methods: new_methods,
         }
     }
+    pub fn to_annotation(&mut self) -> Annotation {
+        Annotation {
+            package_name: self.package_name.clone(),
+            dependencies: self.dependencies.clone(),
+            deprecated: self.deprecated.clone(),
+            annotations: self.annotations.clone(),
+            access: self.access.clone(),
+            file_path: self.file_path.clone(),
+            line_num: self.line_num.clone(),
+            version: self.version.clone(),
+            since: self.since.clone(),
+            author: self.author.clone(),
+            name: self.name.clone(),
+            description: self.description.clone(),
+            retention: self.retention.clone(),
+            targets: self.targets.clone(),
+            elements: self.elements.clone(),
+            variables: self.variables.clone(),
+            inner_classes: self.inner_classes.clone(),
+        }
+    }
     pub fn ch_access(&mut self, value: String) {
         self.access = value;
     }
@@ -233,6 +287,9 @@ impl Object {
     pub fn ch_file_path(&mut self, value: String) {
         self.file_path = value;
     }
+    pub fn ch_line_num(&mut self, value: String) {
+        self.line_num = value;
+    }
     pub fn ch_package_name(&mut self, value: String) {
         self.package_name = value;
     }
@@ -242,11 +299,23 @@ impl Object {
     pub fn ch_description(&mut self, value: String) {
         self.description = value;
     }
+    pub fn ch_type_params(&mut self, value: Vec<Param>) {
+        self.type_params = value;
+    }
     pub fn ch_parent(&mut self, value: String) {
         self.parent = value;
     }
     pub fn ch_version(&mut self, value: String) {
-        self.deprecation = value;
+        self.version = value;
+    }
+    pub fn ch_since(&mut self, value: String) {
+        self.since = value;
+    }
+    pub fn ch_deprecated(&mut self, value: Option<String>) {
+        self.deprecated = value;
+    }
+    pub fn ch_annotations(&mut self, value: Vec<String>) {
+        self.annotations = value;
     }
     pub fn ch_author(&mut self, value: String) {
         self.author = value;
@@ -254,6 +323,21 @@ impl Object {
     pub fn add_method(&mut self, value: Method) {
         self.methods.push(value);
     }
+    pub fn add_constructor(&mut self, value: Method) {
+        self.constructors.push(value);
+    }
+    pub fn ch_retention(&mut self, value: String) {
+        self.retention = value;
+    }
+    pub fn ch_targets(&mut self, value: Vec<String>) {
+        self.targets = value;
+    }
+    pub fn add_component(&mut self, value: Param) {
+        self.components.push(value);
+    }
+    pub fn add_element(&mut self, value: Member) {
+        self.elements.push(value);
+    }
     pub fn add_dependency(&mut self, value: String) {
         self.dependencies.push(value);
     }
@@ -263,10 +347,81 @@ impl Object {
     pub fn add_interface(&mut self, value: String) {
         self.interfaces.push(value);
     }
+    pub fn add_permit(&mut self, value: String) {
+        self.permits.push(value);
+    }
     pub fn add_modifier(&mut self, value: String) {
         self.modifiers.push(value);
     }
     pub fn add_exception(&mut self, value: Exception) {
         self.exceptions.push(value);
     }
+    pub fn add_inner_class(&mut self, value: ObjectType) {
+        self.inner_classes.push(value);
+    }
+    pub fn add_field(&mut self, value: EnumerationField) {
+        self.fields.push(value);
+    }
+}
+
+impl Clone for Object {
+    fn clone(&self) -> Object {
+        let mut new_constructors = Vec::new();
+        let mut new_methods = Vec::new();
+        let mut new_vars = Vec::new();
+        let mut new_mods = Vec::new();
+        let mut new_except = Vec::new();
+        let mut new_fields = Vec::new();
+
+        for i in 0..self.methods.len() {
+            new_methods.push(self.methods[i].clone());
+        }
+        for i in 0..self.variables.len() {
+            new_vars.push(self.variables[i].clone());
+        }
+        for i in 0..self.modifiers.len() {
+            new_mods.push(self.modifiers[i].clone());
+        }
+        for i in 0..self.exceptions.len() {
+            new_except.push(self.exceptions[i].clone());
+        }
+        for i in 0..self.fields.len() {
+            new_fields.push(self.fields[i].clone());
+        }
+        for c in self.constructors.iter() {
+            new_constructors.push(c.clone());
+        }
+
+        Object {
+            state: self.state.clone(),
+            parent: self.parent.clone(),
+            file_path: self.file_path.clone(),
+            line_num: self.line_num.clone(),
+            package_name: self.package_name.clone(),
+            license: self.license.clone(),
+            dependencies: self.dependencies.clone(),
+            deprecated: self.deprecated.clone(),
+            annotations: self.annotations.clone(),
+            access: self.access.clone(),
+            version: self.version.clone(),
+            since: self.since.clone(),
+            author: self.author.clone(),
+            name: self.name.clone(),
+            description: self.description.clone(),
+            exceptions: new_except,
+            interfaces: self.interfaces.clone(),
+            permits: self.permits.clone(),
+            type_params: self.type_params.clone(),
+            components: self.components.clone(),
+            fields: new_fields,
+            modifiers: new_mods,
+            variables: new_vars,
+            elements: self.elements.clone(),
+            retention: self.retention.clone(),
+            targets: self.targets.clone(),
+            inner_classes: self.inner_classes.clone(),
+            constructors: new_constructors,
+            methods: new_methods,
+        }
+    }
 }
@@ -1,21 +1,9 @@
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 /// Struct representing method parameter data contained in javadoc and method declaration
 pub struct Param {
     pub desc: String,
     pub name: String,
     pub var_type: String,
-}
-
-impl Param {
-    pub fn clone(&mut self) -> Param {
-        let new_desc = self.desc.clone();
-        let new_name = self.name.clone();
-        let new_type = self.var_type.clone();
-
-        Param {
-            desc: new_desc,
-            name: new_name,
-            var_type: new_type,
-        }
-    }
+    /// The line of the declaration, empty for parameters read from a javadoc
+    pub line_num: String,
 }
@@ -1,12 +1,15 @@
+use model::annotation::Annotation;
 use model::class::Class;
 use model::enumeration::Enumeration;
 use model::interface::Interface;
 
+#[derive(Serialize)]
 /// Struct representing all the project data
 pub struct Project {
     pub classes: Vec<Class>,
     pub interfaces: Vec<Interface>,
     pub enumerations: Vec<Enumeration>,
+    pub annotations: Vec<Annotation>,
 }
 
 impl Project {
@@ -15,6 +18,7 @@ impl Project {
             classes: Vec::new(),
             interfaces: Vec::new(),
             enumerations: Vec::new(),
+            annotations: Vec::new(),
         }
     }
     pub fn add_class(&mut self, value: Class) {
@@ -26,4 +30,25 @@ impl Project {
     pub fn add_enumeration(&mut self, value: Enumeration) {
         self.enumerations.push(value);
     }
+    pub fn add_annotation(&mut self, value: Annotation) {
+        self.annotations.push(value);
+    }
+    /// Adds the types of another project, such as the types of another file
+    pub fn merge(&mut self, other: Project) {
+        self.classes.extend(other.classes);
+        self.interfaces.extend(other.interfaces);
+        self.enumerations.extend(other.enumerations);
+        self.annotations.extend(other.annotations);
+    }
+    /// Sorts the types by package and then by name
+    pub fn sort(&mut self) {
+        self.classes
+            .sort_by(|a, b| (&a.package_name, &a.name).cmp(&(&b.package_name, &b.name)));
+        self.interfaces
+            .sort_by(|a, b| (&a.package_name, &a.name).cmp(&(&b.package_name, &b.name)));
+        self.enumerations
+            .sort_by(|a, b| (&a.package_name, &a.name).cmp(&(&b.package_name, &b.name)));
+        self.annotations
+            .sort_by(|a, b| (&a.package_name, &a.name).cmp(&(&b.package_name, &b.name)));
+    }
 }
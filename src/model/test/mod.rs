@@ -29,3 +29,19 @@ fn test_method_impl() {
     assert_eq!("sample2", method2.name.as_str());
     assert_eq!("private", method2.privacy.as_str());
 }
+
+#[test]
+fn test_summary() {
+    let mut class = Class::new();
+    class.description = "Parses files, e.g. {@code Foo.java}, in order. Then stops.".to_string();
+
+    assert_eq!("Parses files, e.g. Foo.java, in order.", class.summary());
+    assert_eq!("Uses List.add(Object).", first_sentence("Uses {@link List#add(Object)}. More"));
+    assert_eq!("Calls add twice.", first_sentence("Calls {@linkplain List#add(Object) add} twice."));
+    assert_eq!("Made in the U.S. today.", first_sentence("Made in the U.S. today. Really"));
+    assert_eq!("The first line", first_sentence("The first line\nthe second line"));
+    assert_eq!("Spans two lines.", first_sentence("Spans two\nlines.\nMore"));
+    assert_eq!("", first_sentence(""));
+    assert_eq!("Größe.", first_sentence("Größe. Rest"));
+    assert_eq!("Ändert die Größe.", first_sentence("Ändert die Größe.\nRest über ß"));
+}
@@ -2,21 +2,367 @@ pub mod parse {
     //! A module which handles the parsing for java files
 
     use grammar::grammar::*;
-    use model::model::Class;
     use model::model::Doc;
+    use model::model::EnumerationField;
     use model::model::Exception;
     use model::model::Member;
     use model::model::Method;
+    use model::model::Module;
+    use model::model::ModuleDirective;
     use model::model::Object;
     use model::model::ObjectState;
     use model::model::ObjectType;
+    use model::model::Package;
     use model::model::Param;
+    use error::error::LojidocError;
+    use error::error::Warning;
 
-    use colored::*;
     use std::fs::File;
-    use std::io::BufReader;
     use std::io::Read;
     use std::path::Path;
+    use std::path::PathBuf;
+
+    /// Reads an html tag at the start of `text` and returns its lowercase name,
+    /// whether it is a closing tag, and its length. Text such as a generic type
+    /// `<K, V>` which is not a known html tag returns `None`, as does any opening tag
+    /// right after a word such as the `<User>` of `List<User>`. Other tags which are not
+    /// known, such as `<custom>`, are returned so they can be removed.
+    fn read_html_tag(text: &str, after_word: bool) -> Option<(String, bool, usize)> {
+        let end = text.find('>')?;
+        let inner = &text[1..end];
+
+        if inner.contains('<') {
+            return None;
+        }
+
+        let closing = inner.starts_with('/');
+        let inner = inner.trim_start_matches('/');
+        let name: String = inner
+            .chars()
+            .take_while(|ch| ch.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_lowercase();
+        let after = &inner[name.len()..];
+
+        if !after.is_empty() && !after.starts_with(char::is_whitespace) && after != "/" {
+            return None;
+        }
+
+        let known = get_html_tags().contains(&name.as_str());
+        let unknown = (closing || !after_word) && inner.starts_with(|ch: char| ch.is_ascii_alphabetic());
+
+        if known || unknown {
+            Some((name, closing, end + 1))
+        } else {
+            None
+        }
+    }
+
+    /// Adds text to the markdown with its whitespace collapsed to single spaces. The
+    /// markdown characters of text which is not code are escaped with `escape_markdown`,
+    /// the other characters are kept as they are and each renderer escapes them. A line starting with a `*` bullet, like in a list copied into a
+    /// javadoc, becomes a list item.
+    fn push_doc_text(md: &mut String, text: &str, code: bool) {
+        for (i, line) in text.split('\n').enumerate() {
+            let item = line.trim_start().strip_prefix("* ").filter(|_| i > 0 && !code);
+
+            match item {
+                Some(item) => {
+                    push_doc_break(md, 1);
+                    md.push_str("- ");
+                    push_doc_words(md, &escape_markdown(item));
+                }
+                None => {
+                    // The line break is whitespace between the words of the lines
+                    if i > 0 && !md.is_empty() && !md.ends_with(char::is_whitespace) {
+                        md.push(' ');
+                    }
+                    if code {
+                        push_doc_words(md, line);
+                    } else {
+                        push_doc_words(md, &escape_markdown(line));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Escapes the characters of javadoc text which markdown reads as emphasis, code
+    /// spans and links, such as in `*args`, `` `x` `` or `[0](1)`. Javadoc shows them as
+    /// they are. A `*` between spaces, such as in `2 * 3`, and a `\` which does not
+    /// escape a character are kept.
+    fn escape_markdown(text: &str) -> String {
+        let chars: Vec<char> = text.chars().collect();
+        let spaced = |j: Option<&char>| j.is_none_or(|ch| ch.is_whitespace());
+        let mut md = String::new();
+
+        for (i, &ch) in chars.iter().enumerate() {
+            let escape = match ch {
+                '`' | '[' => true,
+                '*' => !(spaced(i.checked_sub(1).and_then(|j| chars.get(j))) && spaced(chars.get(i + 1))),
+                '\\' => chars.get(i + 1).is_some_and(|next| next.is_ascii_punctuation()),
+                _ => false,
+            };
+
+            if escape {
+                md.push('\\');
+            }
+            md.push(ch);
+        }
+
+        md
+    }
+
+    /// Adds the words of a line of text to the markdown, separated by single spaces
+    fn push_doc_words(md: &mut String, text: &str) {
+        let words: Vec<&str> = text.split_whitespace().collect();
+
+        if text.starts_with(char::is_whitespace) && !md.is_empty() && !md.ends_with(char::is_whitespace)
+        {
+            md.push(' ');
+        }
+
+        md.push_str(words.join(" ").as_str());
+
+        if !words.is_empty() && text.ends_with(char::is_whitespace) {
+            md.push(' ');
+        }
+    }
+
+    /// Ends the current line of the markdown with `breaks` newlines
+    fn push_doc_break(md: &mut String, breaks: usize) {
+        let trimmed = md.trim_end_matches(' ').len();
+        md.truncate(trimmed);
+
+        if md.is_empty() {
+            return;
+        }
+
+        let existing = md.len() - md.trim_end_matches('\n').len();
+        for _ in existing..breaks {
+            md.push('\n');
+        }
+    }
+
+    /// Converts the contents of a `<pre>` block to a fenced java code block
+    fn push_code_block(md: &mut String, code: &str) {
+        let mut code = code.trim_end();
+
+        if code.trim_start().starts_with("{@code") && code.ends_with('}') {
+            let tag = code.trim_start();
+            code = tag[6..tag.len() - 1].trim_end();
+        }
+
+        let code = code
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&#64;", "@")
+            .replace("&amp;", "&");
+        // The indentation the lines share is removed, the rest of it is kept
+        let lines: Vec<&str> = code.lines().map(|line| line.trim_end()).collect();
+        let indent = lines
+            .iter()
+            .filter(|line| !line.is_empty())
+            .map(|line| line.len() - line.trim_start().len())
+            .min()
+            .unwrap_or(0);
+        let lines: Vec<&str> = lines.iter().map(|line| line.get(indent..).unwrap_or("")).collect();
+
+        push_doc_break(md, 2);
+        md.push_str("```java\n");
+        md.push_str(lines.join("\n").trim_matches('\n'));
+        md.push_str("\n```");
+        push_doc_break(md, 2);
+    }
+
+    /// Gets the length of the inline tag such as `{@code x}` at the start of `text`,
+    /// `None` when its braces are not balanced
+    fn inline_tag_len(text: &str) -> Option<usize> {
+        let mut depth = 0;
+
+        for (i, ch) in text.char_indices() {
+            match ch {
+                '{' => depth += 1,
+                '}' => {
+                    depth -= 1;
+
+                    if depth == 0 {
+                        return Some(i + 1);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        None
+    }
+
+    /// Converts the html in javadoc text to markdown. Paragraphs are separated by blank
+    /// lines, lists become `-` items, `<code>` becomes backticks and `<pre>` blocks become
+    /// fenced java code. Other html tags are removed while generic types are kept.
+    /// Only the markdown characters of the text are escaped, the renderers escape the
+    /// rest for their format, and inline tags are left to be converted when the pages
+    /// are generated.
+    pub fn html_to_markdown(text: &str) -> String {
+        let mut md = String::new();
+        let mut lists: Vec<String> = Vec::new();
+        let mut code = false;
+        let mut rest = text;
+
+        while let Some(start) = rest.find(['<', '{']) {
+            push_doc_text(&mut md, &rest[..start], code);
+            let tail = &rest[start..];
+
+            if let Some(brace) = tail.strip_prefix('{') {
+                match inline_tag_len(tail) {
+                    Some(len) if brace.starts_with('@') => {
+                        push_doc_text(&mut md, &tail[..len], true);
+                        rest = &tail[len..];
+                    }
+                    _ => {
+                        md.push('{');
+                        rest = brace;
+                    }
+                }
+                continue;
+            }
+
+            let after_word = md.ends_with(|ch: char| ch.is_alphanumeric() || ch == '_');
+            let (name, closing, len) = match read_html_tag(tail, after_word) {
+                Some(tag) => tag,
+                None => {
+                    md.push('<');
+                    rest = &tail[1..];
+                    continue;
+                }
+            };
+            rest = &tail[len..];
+
+            match name.as_ref() {
+                "p" | "div" | "blockquote" | "table" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                    push_doc_break(&mut md, 2)
+                }
+                "br" | "tr" | "dt" | "dd" => push_doc_break(&mut md, 1),
+                "b" | "strong" => md.push_str("**"),
+                "i" | "em" | "cite" | "dfn" | "var" => md.push('*'),
+                "code" | "tt" | "kbd" => {
+                    code = !closing;
+                    md.push('`');
+                }
+                "ul" | "ol" => {
+                    // Lists which are not nested are separated from the text around them
+                    if closing {
+                        lists.pop();
+                        push_doc_break(&mut md, if lists.is_empty() { 2 } else { 1 });
+                    } else {
+                        lists.push(name.clone());
+                        push_doc_break(&mut md, if lists.len() == 1 { 2 } else { 1 });
+                    }
+                }
+                "li" if !closing => {
+                    push_doc_break(&mut md, 1);
+
+                    for _ in 1..lists.len() {
+                        md.push_str("  ");
+                    }
+
+                    match lists.last() {
+                        Some(list) if list == "ol" => md.push_str("1. "),
+                        _ => md.push_str("- "),
+                    }
+                }
+                "pre" if !closing => {
+                    let end = rest.to_lowercase().find("</pre>").unwrap_or(rest.len());
+                    push_code_block(&mut md, &rest[..end]);
+                    rest = &rest[(end + 6).min(rest.len())..];
+                }
+                _ => {}
+            }
+        }
+
+        push_doc_text(&mut md, rest, code);
+
+        md.trim().to_string()
+    }
+
+    /// Joins the lines of converted javadoc text for fields shown on a single line
+    fn single_line(text: &str) -> String {
+        text.split_whitespace().collect::<Vec<&str>>().join(" ")
+    }
+
+    /// Converts the text of a javadoc comment to markdown, which the text of a markdown
+    /// comment already is
+    fn doc_markdown(text: &str, markdown_comment: bool) -> String {
+        if markdown_comment {
+            text.trim().to_string()
+        } else {
+            html_to_markdown(text)
+        }
+    }
+
+    /// Splits the name at the start of a `@param` or `@throws` text from its description.
+    /// The name is kept as it is written and only the description is converted.
+    fn split_doc_name(text: &str, markdown_comment: bool) -> (String, String) {
+        let text = text.trim_start();
+
+        match text.find(char::is_whitespace) {
+            Some(i) => (
+                text[..i].to_string(),
+                single_line(doc_markdown(&text[i..], markdown_comment).as_str()),
+            ),
+            None => (text.to_string(), String::new()),
+        }
+    }
+
+    /// Stores the text collected after a javadoc keyword in the `Doc` struct
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - The javadoc keyword the text belongs to
+    /// * `raw` - The text that followed the keyword
+    /// * `doc` - The Doc struct to be modified with the new information
+    /// * `markdown_comment` - Whether the text is from a `///` comment written in markdown
+    fn store_doc_field(state: &JdocState, raw: String, doc: &mut Doc, markdown_comment: bool) {
+        let markdown = doc_markdown(raw.as_str(), markdown_comment);
+        let text = single_line(markdown.as_str());
+
+        match state {
+            JdocState::JdocReturn => doc.return_desc = text,
+            JdocState::Param => {
+                let (name, desc) = split_doc_name(raw.as_str(), markdown_comment);
+
+                doc.params.push(Param {
+                    var_type: String::new(),
+                    name,
+                    desc,
+                    line_num: String::new(),
+                });
+            }
+            JdocState::Author => {
+                if !doc.author.is_empty() {
+                    doc.author.push_str(", ");
+                }
+                doc.author.push_str(text.as_str());
+            }
+            JdocState::Deprecated => doc.deprecated = Some(text),
+            JdocState::Since => doc.since = text,
+            JdocState::Link => doc.see = text,
+            JdocState::See => doc.see = text,
+            JdocState::Exception => {
+                let (exception_type, desc) = split_doc_name(raw.as_str(), markdown_comment);
+
+                if !exception_type.is_empty() {
+                    doc.exceptions.push(Exception {
+                        exception_type,
+                        desc,
+                    });
+                }
+            }
+            JdocState::Version => doc.version = text,
+            JdocState::Desc => doc.description = markdown,
+            _ => verbose!("Code javadoc field not supported"),
+        }
+    }
 
     /// Handles token streams for javadoc comments and returns a `Doc` struct
     /// containing the information parsed from the javadoc comment.
@@ -25,106 +371,129 @@ pub mod parse {
     ///
     /// * `tokens` - A vector of tokens from the javadoc comment
     fn get_doc(tokens: &Vec<JdocToken>) -> Doc {
-        let mut return_str = String::from("");
-        let mut desc = String::from("");
-        let mut parameters: Vec<Param> = Vec::new();
-        let mut author = String::new();
-        let mut version = String::new();
-        let mut link = String::new();
-        let mut deprecated = String::new();
-        let mut exceptions: Vec<Exception> = Vec::new();
+        let mut doc = Doc::new();
         let mut state = JdocState::Desc;
         let mut word_buf = String::new();
+        let mut line_start = true;
 
         for i in 0..tokens.len() {
             match tokens[i].clone() {
                 JdocToken::Keyword(key) => {
-                    let new_desc = word_buf.clone();
                     if i != 0 {
-                        match state {
-                            JdocState::JdocReturn => {
-                                return_str = new_desc;
-                            }
-                            JdocState::Param => {
-                                let word_parts: Vec<&str> = new_desc.split(" ").collect();
-
-                                if word_parts.len() > 1 {
-                                    parameters.push(Param {
-                                        var_type: String::new(),
-                                        name: word_parts[0].to_string(),
-                                        desc: word_parts[1..].join(" "),
-                                    });
-                                } else if word_parts.len() == 1 {
-                                    parameters.push(Param {
-                                        var_type: String::new(),
-                                        name: word_parts[0].to_string(),
-                                        desc: String::new(),
-                                    });
-                                }
-                            }
-                            JdocState::Author => author = new_desc,
-                            JdocState::Deprecated => deprecated = new_desc,
-                            JdocState::Since => version = new_desc,
-                            JdocState::Link => link = new_desc,
-                            JdocState::See => link = new_desc,
-                            JdocState::Exception => {
-                                let word_parts: Vec<&str> = new_desc.split(" ").collect();
-
-                                if exceptions.len() > 0 {
-                                    exceptions.push(Exception {
-                                        exception_type: word_parts[0].to_string(),
-                                        desc: word_parts[1..].join(""),
-                                    });
-                                }
-                            }
-                            JdocState::Version => version = new_desc,
-                            JdocState::Desc => desc = new_desc,
-                            _ => println!("Code javadoc field not supported"),
-                        }
-
+                        store_doc_field(&state, word_buf.clone(), &mut doc, false);
                         word_buf.clear();
                     }
 
-                    match key.as_ref() {
-                        "@return" => state = JdocState::JdocReturn,
-                        "@param" => state = JdocState::Param,
-                        "@author" => state = JdocState::Author,
-                        "@code" => state = JdocState::Code,
-                        "@deprecated" => state = JdocState::Deprecated,
-                        "@docRoot" => state = JdocState::DocRoot,
-                        "@exception" => state = JdocState::Exception,
-                        "@inheritDoc" => state = JdocState::InheritDoc,
-                        "@link" => state = JdocState::Link,
-                        "@linkplain" => state = JdocState::Linkplain,
-                        "@literal" => state = JdocState::Literal,
-                        "@see" => state = JdocState::See,
-                        "@throws" => state = JdocState::Exception,
-                        "@since" => state = JdocState::Since,
-                        "@serialData" => state = JdocState::SerialData,
-                        "@serialField" => state = JdocState::SerialField,
-                        "@value" => state = JdocState::Value,
-                        "@version" => state = JdocState::Version,
-                        _ => println!("Unsupported javadoc keyword used"),
+                    match doc_state(&key) {
+                        Some(tag) => state = tag,
+                        None => verbose!("Unsupported javadoc keyword used"),
                     }
                 }
+                // Only the `*` at the start of a line is the gutter of the comment, the
+                // indentation after it is kept for preformatted blocks
                 JdocToken::Symbol(key) => {
-                    if key != "*" {
+                    if key == "\n" {
+                        word_buf.push('\n');
+                    } else if key.trim().is_empty() {
+                        word_buf.push_str(key.as_str());
+                    } else if key != "*" || !line_start {
                         word_buf.push_str(format!("{} ", key.as_str()).as_str());
                     }
                 }
             }
+
+            line_start = matches!(&tokens[i], JdocToken::Symbol(key) if key == "\n");
+        }
+
+        // The text after the last keyword is not followed by another keyword.
+        // A deprecated tag is kept even without a message.
+        if !word_buf.trim().is_empty() || matches!(state, JdocState::Deprecated) {
+            store_doc_field(&state, word_buf, &mut doc, false);
+        }
+
+        doc
+    }
+
+    /// Adds a word of the arguments of an annotation to their text, separated by spaces
+    /// apart from parentheses, braces and commas
+    fn push_annotation_arg(args: &mut String, word: &str) {
+        let joined = args.is_empty() || args.ends_with('(') || args.ends_with('{') || word == ")" || word == "}";
+
+        if !joined {
+            args.push(' ');
+        }
+        args.push_str(word);
+    }
+
+    /// Whether a line comment is a markdown documentation comment, which starts with
+    /// exactly three slashes
+    fn is_markdown_comment(word: &str) -> bool {
+        word.starts_with("///") && !word.starts_with("////")
+    }
+
+    /// Gets the state of the parser of a javadoc for a keyword such as `@param`
+    fn doc_state(key: &str) -> Option<JdocState> {
+        match key {
+            "@return" => Some(JdocState::JdocReturn),
+            "@param" => Some(JdocState::Param),
+            "@author" => Some(JdocState::Author),
+            "@code" => Some(JdocState::Code),
+            "@deprecated" => Some(JdocState::Deprecated),
+            "@docRoot" => Some(JdocState::DocRoot),
+            "@exception" => Some(JdocState::Exception),
+            "@inheritDoc" => Some(JdocState::InheritDoc),
+            "@link" => Some(JdocState::Link),
+            "@linkplain" => Some(JdocState::Linkplain),
+            "@literal" => Some(JdocState::Literal),
+            "@see" => Some(JdocState::See),
+            "@throws" => Some(JdocState::Exception),
+            "@since" => Some(JdocState::Since),
+            "@serialData" => Some(JdocState::SerialData),
+            "@serialField" => Some(JdocState::SerialField),
+            "@value" => Some(JdocState::Value),
+            "@version" => Some(JdocState::Version),
+            _ => None,
+        }
+    }
+
+    /// Reads a markdown documentation comment from its `///` lines. The text is kept as
+    /// markdown without the indentation the lines share, and block tags such as
+    /// `@param` start a line like in a javadoc comment.
+    ///
+    /// # Arguments
+    ///
+    /// * `lines` - The lines of the comment without their `///`
+    fn get_markdown_doc(lines: &[String]) -> Doc {
+        let mut doc = Doc::new();
+        let mut state = JdocState::Desc;
+        let mut buf = String::new();
+        let indent = lines
+            .iter()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| line.len() - line.trim_start().len())
+            .min()
+            .unwrap_or(0);
+
+        for line in lines {
+            let line = if line.trim().is_empty() { "" } else { &line[indent..] };
+            let word = line.split_whitespace().next().unwrap_or("");
+
+            match doc_state(word) {
+                Some(tag) if line.starts_with('@') => {
+                    store_doc_field(&state, buf, &mut doc, true);
+                    state = tag;
+                    buf = line[word.len()..].to_string();
+                }
+                _ => buf.push_str(line),
+            }
+            buf.push('\n');
         }
 
-        Doc {
-            params: parameters,
-            description: desc,
-            return_desc: return_str,
-            author: author,
-            version: version,
-            exceptions: exceptions,
-            deprecated: deprecated,
-            see: link,
+        if !buf.trim().is_empty() || matches!(state, JdocState::Deprecated) {
+            store_doc_field(&state, buf, &mut doc, true);
         }
+
+        doc
     }
 
     /// Handles token streams for object declarations and modifies the `Class` struct
@@ -142,11 +511,25 @@ pub mod parse {
         let mut exception = false;
         let mut parent = false;
         let mut class_name = false;
+        let mut permits = false;
+        let mut components = false;
+        let mut component_type = String::new();
 
         for i in 0..gram_parts.len() {
             match gram_parts[i].clone() {
+                Stream::Type(var) if components => component_type = var,
                 Stream::Variable(var) => {
-                    if implement {
+                    if components {
+                        ob.add_component(Param {
+                            var_type: component_type.clone(),
+                            name: var,
+                            desc: String::new(),
+                            line_num: ob.line_num.clone(),
+                        });
+                        component_type.clear();
+                    } else if permits {
+                        ob.add_permit(var);
+                    } else if implement {
                         ob.add_interface(var);
                     } else if exception {
                         ob.add_exception(Exception {
@@ -154,14 +537,30 @@ pub mod parse {
                             exception_type: var,
                         });
                     } else if class_name {
-                        ob.ch_name(var);
+                        match var.find('<') {
+                            Some(i) => {
+                                let (type_params, _) = split_type_params(&var[i..]);
+                                ob.ch_name(var[..i].to_string());
+                                ob.ch_type_params(type_params);
+                            }
+                            None => ob.ch_name(var),
+                        }
                         class_name = false;
                     } else if parent {
-                        ob.ch_parent(var);
-                        parent = false;
+                        // An interface extends any number of interfaces
+                        if ob.parent.is_empty() {
+                            ob.ch_parent(var);
+                        } else {
+                            ob.add_interface(var);
+                        }
                     }
                 }
                 Stream::Object(_) => class_name = true,
+                // The parameter list of a record declaration holds its components
+                Stream::Parameters => {
+                    components = true;
+                    class_name = false;
+                }
                 Stream::Access(key) => ob.ch_access(key),
                 Stream::Modifier(key) => ob.add_modifier(key),
                 Stream::Exception => {
@@ -171,6 +570,7 @@ pub mod parse {
                     class_name = false;
                 }
                 Stream::Implement => {
+                    components = false;
                     exception = false;
                     implement = true;
                     parent = false;
@@ -182,7 +582,14 @@ pub mod parse {
                     parent = true;
                     class_name = false;
                 }
-                _ => println!("Class pattern not supported {:?}", gram_parts[i]),
+                // The subtypes of a sealed type are listed after its supertypes
+                Stream::Permits => {
+                    permits = true;
+                    implement = false;
+                    parent = false;
+                    class_name = false;
+                }
+                _ => verbose!("Class pattern not supported {:?}", gram_parts[i]),
             }
         }
 
@@ -197,195 +604,457 @@ pub mod parse {
         if java_doc.version != "" {
             ob.ch_version(java_doc.version.clone());
         }
+
+        if !java_doc.since.is_empty() {
+            ob.ch_since(java_doc.since.clone());
+        }
+
+        ob.ch_deprecated(java_doc.deprecated.clone());
+        ob.ch_annotations(java_doc.annotations.clone());
+
+        match_type_params(&mut ob.type_params, &java_doc.params);
+
+        for component in ob.components.iter_mut() {
+            if let Some(param) = java_doc.params.iter().find(|p| p.name == component.name) {
+                component.desc = param.desc.clone();
+            }
+        }
+    }
+
+    /// Splits the type parameter section at the start of `text`, such as
+    /// `<K, V extends Comparable<V>>`, from the rest of the text.
+    /// The bound of each type parameter is stored as its type.
+    fn split_type_params(text: &str) -> (Vec<Param>, String) {
+        let mut type_params = Vec::new();
+        let mut depth = 0;
+        let mut start = 1;
+
+        for (i, ch) in text.char_indices() {
+            match ch {
+                '<' => depth += 1,
+                ',' if depth == 1 => {
+                    type_params.push(get_type_param(&text[start..i]));
+                    start = i + 1;
+                }
+                '>' => {
+                    depth -= 1;
+
+                    if depth == 0 {
+                        type_params.push(get_type_param(&text[start..i]));
+                        return (type_params, text[i + 1..].trim().to_string());
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        (type_params, String::new())
+    }
+
+    /// Creates a type parameter from a declaration such as `T extends Comparable<T>`
+    fn get_type_param(declaration: &str) -> Param {
+        let declaration = declaration.trim();
+        let (name, bound) = match declaration.find(" extends ") {
+            Some(i) => (&declaration[..i], declaration[i + 9..].trim()),
+            None => (declaration, ""),
+        };
+
+        Param {
+            name: name.to_string(),
+            var_type: bound.to_string(),
+            desc: String::new(),
+            line_num: String::new(),
+        }
+    }
+
+    /// Adds the descriptions from `@param <T>` javadoc tags to type parameters
+    fn match_type_params(type_params: &mut [Param], jparams: &[Param]) {
+        for type_param in type_params.iter_mut() {
+            let tag_name = format!("<{}>", type_param.name);
+
+            if let Some(jparam) = jparams.iter().find(|j| j.name == tag_name) {
+                type_param.desc = jparam.desc.clone();
+            }
+        }
     }
 
     /// Handles token streams for methods and returns a `Method` struct
     /// Containing the methods information from it's declaration
     ///
+    /// The name of the method is the last symbol before the parameter list.
+    /// A method without a return type before its name is a constructor.
+    ///
     /// # Arguments
     ///
     /// * `gram_parts` - A vector of tokens from the method's declaration
-    /// * `_java_doc` - The java doc struct with the documentation for the method
-    fn get_method(gram_parts: Vec<Stream>, java_doc: &Doc, line_num: String) -> Method {
+    /// * `java_doc` - The java doc struct with the documentation for the method
+    /// * `line_num` - The line number of the method
+    /// * `warnings` - The parse warnings of the file, e.g. `@param` tags without a parameter
+    fn get_method(
+        gram_parts: Vec<Stream>,
+        java_doc: &Doc,
+        line_num: String,
+        warnings: &mut Vec<(usize, String)>,
+    ) -> Method {
         let mut method = Method::new();
         let mut exception = false;
-        let mut method_name = false;
+        let mut params = false;
         let mut param_name = false;
         let mut param_type = String::new();
 
         for i in 0..gram_parts.len() {
             match gram_parts[i].clone() {
-                Stream::Variable(var) => {
+                Stream::Variable(mut var) => {
                     if exception {
-                        if java_doc.exceptions.len() > 0 {
-                            method.add_exception(Exception {
-                                desc: java_doc.exceptions[0].clone().desc,
-                                exception_type: var,
-                            });
-                        }
-                    } else if method_name {
-                        method.ch_method_name(var);
-                        method_name = false;
-                    } else if param_name {
-                        method.add_param(Param {
-                            var_type: param_type.clone(),
-                            name: var,
+                        method.add_throws(var.clone());
+                        method.add_exception(Exception {
                             desc: String::new(),
+                            exception_type: var,
                         });
+                    } else if params {
+                        // `String...args` is read as a single word
+                        if let (false, Some(i)) = (param_name, var.find("...")) {
+                            param_type = var[..i].to_string();
+                            param_name = true;
+                            var = var[i..].to_string();
+                        }
+
+                        if param_name {
+                            let (var_type, name) = param_declaration(&param_type, &var);
+                            method.add_param(Param {
+                                var_type,
+                                name,
+                                desc: String::new(),
+                                line_num: line_num.clone(),
+                            });
 
-                        param_type = String::new();
-                        param_name = false;
-                    } else if method.name == "" {
-                        method.ch_return_type(var);
-                        method_name = true;
+                            param_type = String::new();
+                            param_name = false;
+                        }
+                    } else {
+                        method.ch_method_name(var);
                     }
                 }
                 Stream::Type(key) => {
-                    if method.return_type == "" {
-                        method.ch_return_type(key);
-                        method_name = true;
-                    } else {
+                    if params {
                         param_type = key;
                         param_name = true;
+                    } else if key.starts_with('<') {
+                        let (type_params, return_type) = split_type_params(&key);
+                        method.ch_type_params(type_params);
+                        method.ch_return_type(return_type);
+                    } else {
+                        method.ch_return_type(key);
                     }
                 }
+                Stream::Parameters => params = true,
+                Stream::Default => method.add_modifier("default".to_string()),
                 Stream::Access(key) => method.ch_privacy(key),
+                // `final` parameters are not final methods
+                Stream::Modifier(_) if params && !exception => {}
                 Stream::Modifier(key) => method.add_modifier(key),
                 Stream::Exception => exception = true,
-                _ => println!("Method pattern not supported"),
+                _ => verbose!("Method pattern not supported"),
             }
         }
         method.ch_line_num(line_num);
 
-        if java_doc.return_desc != "" {
-            method.ch_return_type(java_doc.return_desc.clone());
+        if !java_doc.return_desc.trim().is_empty() {
+            method.ch_return_desc(java_doc.return_desc.trim().to_string());
         }
 
         if java_doc.description != "" {
             method.ch_description(java_doc.description.clone());
         }
 
-        let n_params: Vec<Param> =
-            match_params(&mut method, &java_doc.params, &mut String::new(), false);
+        method.ch_deprecated(java_doc.deprecated.clone());
+        method.ch_annotations(java_doc.annotations.clone());
+
+        if !java_doc.since.is_empty() {
+            method.ch_since(java_doc.since.clone());
+        }
+
+        // Type parameters are documented with `@param <T>`
+        for jparam in java_doc.params.iter().filter(|jparam| !jparam.name.starts_with('<')) {
+            if !method.parameters.iter().any(|param| param.name == jparam.name) {
+                warnings.push((
+                    method.line_num.parse().unwrap_or(0),
+                    format!("@param {} does not match a parameter of {}", jparam.name, method.name),
+                ));
+                method.unmatched_params.push(jparam.name.clone());
+            }
+        }
+
+        let n_params: Vec<Param> = match_params(&method, &java_doc.params);
         method.ch_params(n_params);
+        match_type_params(&mut method.type_params, &java_doc.params);
+        match_exceptions(&mut method, &java_doc.exceptions);
 
         method
     }
 
-    /// Handles token streams for member variables and returns a `Member` struct
-    /// Containing the member variable's data
+    /// Gets the type and name of a parameter with the varargs and array brackets in its
+    /// type, e.g. `String...` for `String ... args` and `int[]` for `int rows[]`
+    fn param_declaration(param_type: &str, name: &str) -> (String, String) {
+        let mut var_type = param_type.replace(" ...", "...").replace(" []", "[]");
+        let mut name = name;
+
+        if name.starts_with("...") {
+            var_type.push_str("...");
+            name = name[3..].trim_start();
+        }
+        while name.ends_with("[]") {
+            var_type.push_str("[]");
+            name = name[..name.len() - 2].trim_end();
+        }
+
+        (var_type, name.to_string())
+    }
+
+    /// Gets the name of a type without its package e.g. `IOException` for `java.io.IOException`
+    fn simple_type_name(type_name: &str) -> &str {
+        type_name.rsplit('.').next().unwrap_or(type_name)
+    }
+
+    /// Adds the javadoc descriptions to the exceptions declared in a method's throws clause.
+    /// Documented exceptions which are not declared, such as unchecked exceptions, are
+    /// added after the declared ones.
     ///
     /// # Arguments
     ///
-    /// * `gram_parts` - A vector of tokens in the member variable expression
-    fn get_var(gram_parts: Vec<Stream>, line_num: String) -> Member {
-        let mut member = Member::new();
-        let mut member_name = false;
+    /// * `method` - The method with the exceptions from its declaration
+    /// * `jexceptions` - The exceptions documented with `@throws` or `@exception` tags
+    fn match_exceptions(method: &mut Method, jexceptions: &[Exception]) {
+        let mut documented = vec![false; jexceptions.len()];
 
-        for i in 0..gram_parts.len() {
-            match gram_parts[i].clone() {
-                Stream::Variable(var) => {
-                    if var == "=" {
-                        return member;
-                    } else if member_name {
-                        member.ch_name(var);
-                        return member;
-                    } else {
-                        member.ch_type(var);
-                        member_name = true;
-                    }
-                }
-                Stream::Type(key) => {
-                    if key.contains("=") {
-                        let parts: Vec<&str> = key.split("=").collect();
-                        member.ch_name(parts[0].to_string());
+        for exception in &mut method.exceptions {
+            let declared = simple_type_name(exception.exception_type.as_str()).to_string();
 
-                        return member;
-                    } else {
-                        member.ch_type(key);
-                        member_name = true;
-                    }
+            for (i, jexception) in jexceptions.iter().enumerate() {
+                if !documented[i] && simple_type_name(jexception.exception_type.as_str()) == declared
+                {
+                    exception.desc = jexception.desc.clone();
+                    documented[i] = true;
+                    break;
                 }
-                Stream::Access(key) => member.ch_access(key),
-                Stream::Modifier(key) => member.add_modifier(key),
-                _ => println!("Member variable pattern not supported"),
             }
         }
-        member.ch_line_number(line_num);
 
-        member
+        for (i, jexception) in jexceptions.iter().enumerate() {
+            if !documented[i] {
+                method.add_exception(jexception.clone());
+            }
+        }
     }
 
-    fn match_params(
-        method: &mut Method,
-        jparams: &Vec<Param>,
-        jdoc_err: &mut String,
-        lint: bool,
-    ) -> Vec<Param> {
-        let params = method.clone_params();
-        let mut new_param: Vec<Param> = Vec::new();
+    /// Splits a field declaration such as `int a = 1, b = 2` into its declarators.
+    /// Commas in initializers, type arguments and literals do not end a declarator.
+    fn split_declarators(decl_text: &str) -> Vec<&str> {
+        let mut declarators = Vec::new();
+        let mut depth = 0;
+        let mut initializer = false;
+        let mut quote = None;
+        let mut escaped = false;
+        let mut start = 0;
 
-        for mut param in params {
-            let mut found = false;
-            for i in 0..jparams.len() {
-                if param.name == jparams[i].name {
-                    new_param.push(Param {
-                        name: param.name.clone(),
-                        var_type: param.var_type.clone(),
-                        desc: jparams[i].desc.clone(),
-                    });
-                    found = true;
+        for (i, ch) in decl_text.char_indices() {
+            if let Some(mark) = quote {
+                if escaped {
+                    escaped = false;
+                } else if ch == '\\' {
+                    escaped = true;
+                } else if ch == mark {
+                    quote = None;
                 }
+                continue;
             }
 
-            if !found {
-                new_param.push(Param {
-                    name: param.name.clone(),
-                    var_type: param.var_type.clone(),
-                    desc: "No description found".to_string(),
-                });
-
-                if lint {
-                    jdoc_err.push_str(
-                        "\tJavadoc parameter not found "
-                            .yellow()
-                            .to_string()
-                            .as_str(),
-                    );
-                    jdoc_err.push_str(
-                        format!(
-                            "{} in method: {} (Line: {})\n",
-                            param.name,
-                            method.name.clone(),
-                            method.line_num
-                        ).as_str(),
-                    );
+            match ch {
+                '"' | '\'' => quote = Some(ch),
+                '(' | '[' | '{' => depth += 1,
+                ')' | ']' | '}' => depth -= 1,
+                // In an initializer they are comparisons or lambda arrows
+                '<' if !initializer => depth += 1,
+                '>' if !initializer => depth -= 1,
+                '=' if depth == 0 => initializer = true,
+                ',' if depth == 0 => {
+                    declarators.push(&decl_text[start..i]);
+                    start = i + 1;
+                    initializer = false;
                 }
+                _ => {}
             }
         }
+        declarators.push(&decl_text[start..]);
 
-        new_param
+        declarators
     }
 
-    macro_rules! is_keyword {
-        ($w:expr, $k:expr) => {{
-            let mut found = false;
-            for key in $k {
-                if key == $w {
-                    found = true
-                }
+    /// Handles token streams for member variables and returns a `Member` struct
+    /// for each variable in the declaration, e.g. `a` and `b` for `int a = 1, b = 2`
+    ///
+    /// # Arguments
+    ///
+    /// * `gram_parts` - A vector of tokens in the member variable expression
+    /// * `decl_text` - The source text of the declaration without its keywords
+    /// * `java_doc` - The javadoc preceding the declaration
+    fn get_vars(gram_parts: Vec<Stream>, decl_text: &str, java_doc: &Doc, line_num: String) -> Vec<Member> {
+        let mut field = Member::new();
+
+        for part in gram_parts {
+            match part {
+                Stream::Access(key) => field.ch_access(key),
+                Stream::Modifier(key) => field.add_modifier(key),
+                _ => {}
             }
+        }
 
-            found
-        }};
+        field.ch_description(java_doc.description.clone());
+        field.ch_line_number(line_num);
+
+        declared_vars(field, decl_text)
     }
 
-    fn push_token(curr_token: &String, tokens: &mut Vec<Token>) {
-        if curr_token != "" {
-            let keywords = get_keywords();
-            let jdoc_keywords = get_jdoc_keywords();
-            if is_keyword!(curr_token, keywords) {
-                tokens.push(Token::Keyword(curr_token.to_string()));
+    /// Gets a variable for each declarator of a declaration. `field` has the modifiers
+    /// and javadoc of the declaration, and its type when the type was declared before,
+    /// e.g. for the declarators after an array initializer.
+    fn declared_vars(mut field: Member, decl_text: &str) -> Vec<Member> {
+        let is_static = field.modifiers.iter().any(|m| m == "static");
+        let is_final = field.modifiers.iter().any(|m| m == "final");
+        let mut vars = Vec::new();
+
+        for declarator in split_declarators(decl_text) {
+            // The type and name come before the initializer
+            let (declaration, value) = match declarator.find('=') {
+                Some(i) => (declarator[..i].trim(), declarator[i + 1..].trim()),
+                None => (declarator.trim(), ""),
+            };
+            if declaration.is_empty() {
+                continue;
+            }
+
+            // Only the first declarator has the type, the others share it
+            let mut member = field.clone();
+            match declaration.rfind(' ').filter(|_| field.var_type.is_empty()) {
+                Some(i) => {
+                    member.ch_type(declaration[..i].trim().to_string());
+                    member.ch_name(declaration[i + 1..].to_string());
+                }
+                None => member.ch_name(declaration.to_string()),
+            }
+
+            if is_static && is_final {
+                member.ch_value(value.to_string());
+            }
+
+            field.ch_type(member.var_type.clone());
+            vars.push(member);
+        }
+
+        vars
+    }
+
+    /// Handles the declaration of an annotation type element such as
+    /// `String value() default ""` and returns it as a member.
+    /// The default value of the element is stored as the member's value.
+    ///
+    /// # Arguments
+    ///
+    /// * `decl_text` - The source text of the declaration
+    /// * `java_doc` - The java doc struct with the documentation for the element
+    /// * `line_num` - The line number of the element
+    fn get_element(decl_text: &str, java_doc: &Doc, line_num: String) -> Member {
+        let mut member = Member::new();
+
+        let declaration = match decl_text.find('(') {
+            Some(i) => decl_text[..i].trim(),
+            None => decl_text.trim(),
+        };
+
+        match declaration.rfind(' ') {
+            Some(i) => {
+                member.ch_type(declaration[..i].trim().to_string());
+                member.ch_name(declaration[i + 1..].to_string());
+            }
+            None => member.ch_name(declaration.to_string()),
+        }
+
+        if let Some(i) = decl_text.find(") default ") {
+            member.ch_value(decl_text[i + ") default ".len()..].trim().to_string());
+        }
+
+        member.ch_description(java_doc.description.clone());
+        member.ch_line_number(line_num);
+
+        member
+    }
+
+    /// Appends a symbol or punctuation mark to the source text of a declaration
+    fn push_decl_text(decl_text: &mut String, text: &str) {
+        let attached = text == "(" || text == ")" || text == ",";
+        if !decl_text.is_empty() && !attached && !decl_text.ends_with('(') {
+            decl_text.push(' ');
+        }
+
+        decl_text.push_str(text);
+    }
+
+    /// Adds an enum constant to the object using the javadoc which preceded it.
+    /// The constant name and argument buffers are cleared afterward.
+    ///
+    /// # Arguments
+    ///
+    /// * `ob` - The Object struct to add the constant to
+    /// * `name` - The name of the enum constant
+    /// * `value` - The constructor arguments of the enum constant
+    /// * `java_doc` - The java doc struct with the documentation for the constant
+    fn add_enum_constant(ob: &mut Object, name: &mut String, value: &mut String, java_doc: &Doc) {
+        if !name.is_empty() {
+            ob.add_field(EnumerationField {
+                name: name.trim().to_string(),
+                value: value.trim().to_string(),
+                desc: java_doc.description.trim().to_string(),
+            });
+        }
+
+        name.clear();
+        value.clear();
+    }
+
+    /// Pairs the parameters of a method with the `@param` tags of its javadoc by name.
+    /// Parameters without a tag get an empty description.
+    fn match_params(method: &Method, jparams: &[Param]) -> Vec<Param> {
+        method
+            .parameters
+            .iter()
+            .map(|param| Param {
+                desc: jparams
+                    .iter()
+                    .find(|jparam| jparam.name == param.name)
+                    .map_or(String::new(), |jparam| jparam.desc.clone()),
+                ..param.clone()
+            }).collect()
+    }
+
+    macro_rules! is_keyword {
+        ($w:expr, $k:expr) => {{
+            let mut found = false;
+            for key in $k {
+                if key == $w {
+                    found = true
+                }
+            }
+
+            found
+        }};
+    }
+
+    fn push_token(curr_token: &str, tokens: &mut Vec<Token>) {
+        if !curr_token.is_empty() {
+            let keywords = get_keywords();
+            let jdoc_keywords = get_jdoc_keywords();
+            if is_keyword!(curr_token, keywords) {
+                tokens.push(Token::Keyword(curr_token.to_string()));
             } else if is_keyword!(curr_token, jdoc_keywords) {
                 tokens.push(Token::Keyword(curr_token.to_string()));
             } else {
@@ -394,82 +1063,425 @@ pub mod parse {
         }
     }
 
+    /// Pushes a lexeme to the token list while keeping track of whether the lexer is
+    /// inside a javadoc comment. Javadoc delimiters that share a lexeme with text,
+    /// like `/**Text` or `text*/`, are split into their own tokens. So is the `*` gutter
+    /// when it is not followed by a space, like in `*@param`.
+    ///
+    /// # Arguments
+    ///
+    /// * `lexeme` - The characters read since the last separator
+    /// * `tokens` - The list of tokens from the lexer
+    /// * `in_doc` - Whether the lexer is inside a javadoc comment
+    /// * `emit` - Whether the tokens should be added to the token list
+    fn push_lexeme(lexeme: &str, tokens: &mut Vec<Token>, in_doc: &mut bool, emit: bool) {
+        let mut text = lexeme;
+
+        if !*in_doc && text.starts_with("/**") && text != "/**/" {
+            *in_doc = true;
+            text = &text[3..];
+
+            if emit {
+                push_token("/**", tokens);
+            }
+        }
+
+        // The gutter is its own token like when it is followed by a space
+        let word = text.trim_start_matches('*');
+        let line_start = matches!(tokens.last(), Some(Token::LineNumber(_)));
+
+        if *in_doc && line_start && word.len() < text.len() && !word.is_empty() && word != "/" {
+            if emit {
+                push_token("*", tokens);
+            }
+            text = word;
+        }
+
+        if *in_doc && text.ends_with("*/") {
+            *in_doc = false;
+
+            if emit {
+                push_token(&text[..text.len() - 2], tokens);
+                push_token("*/", tokens);
+            }
+        } else if emit {
+            push_token(text, tokens);
+        }
+    }
+
+    /// Checks whether a `<` starts a type parameter list rather than a comparison.
+    /// A type name directly before it, a modifier such as in `public <T> T get()`
+    /// or an object name such as in `class Cache <K, V>` starts a type parameter list.
+    /// The object name is moved into the current token.
+    fn starts_type_params(curr_token: &mut String, tokens: &mut Vec<Token>) -> bool {
+        if !curr_token.is_empty() {
+            return !curr_token.starts_with("//") && !curr_token.starts_with('"');
+        }
+
+        let len = tokens.len();
+        match tokens.last() {
+            Some(Token::Keyword(_)) => return true,
+            Some(Token::Symbol(_)) if len > 1 => match tokens[len - 2] {
+                Token::Keyword(ref key) if key == "class" || key == "interface" => {}
+                Token::Symbol(ref word) if word == "record" => {}
+                _ => return false,
+            },
+            _ => return false,
+        }
+
+        if let Some(Token::Symbol(name)) = tokens.pop() {
+            curr_token.push_str(name.as_str());
+        }
+
+        true
+    }
+
+    /// Checks whether the declaration before an opening brace is the compact
+    /// constructor of a record, such as `public Point {`
+    fn is_compact_constructor(gram_parts: &[Stream], ob: &Object) -> bool {
+        if !matches!(ob.state, ObjectState::Record) {
+            return false;
+        }
+
+        match gram_parts.last() {
+            Some(Stream::Variable(name)) => *name == ob.name,
+            _ => false,
+        }
+    }
+
+    /// Marks `record` as a keyword when it starts a record declaration such as
+    /// `record Point(`. Anywhere else `record` is an ordinary identifier.
+    fn mark_record_keyword(tokens: &mut [Token]) {
+        let len = tokens.len();
+        if len < 2 {
+            return;
+        }
+
+        let declaration = match (&tokens[len - 2], &tokens[len - 1]) {
+            (Token::Symbol(word), Token::Symbol(_)) => word == "record",
+            _ => false,
+        };
+
+        if declaration {
+            tokens[len - 2] = Token::Keyword("record".to_string());
+        }
+    }
+
+    /// Checks whether the declaration before an opening brace is a class, interface or enum.
+    /// Keywords in comments are ignored.
+    fn opens_type_body(tokens: &[Token]) -> bool {
+        let start = tokens[..tokens.len() - 1]
+            .iter()
+            .rposition(|token| matches!(token, Token::ExpressionEnd(_)))
+            .map_or(0, |i| i + 1);
+        let mut comment = false;
+        let mut line_comment = false;
+
+        for token in &tokens[start..] {
+            match token {
+                Token::Symbol(word) => {
+                    if word.starts_with("//") {
+                        line_comment = true;
+                    } else if word == "/*" || word == "/**" {
+                        comment = true;
+                    } else if word == "*/" {
+                        comment = false;
+                    } else if word == "@interface" && !comment && !line_comment {
+                        return true;
+                    }
+                }
+                Token::Keyword(key) if !comment && !line_comment => match key.as_ref() {
+                    "class" | "interface" | "enum" | "record" => return true,
+                    _ => {}
+                },
+                Token::LineNumber(_) => line_comment = false,
+                _ => {}
+            }
+        }
+
+        false
+    }
+
+    /// The literal or comment a character of java source is in. Their braces, parentheses
+    /// and keywords are text and do not change the structure of the file.
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub enum Text {
+        Code,
+        /// A string literal, which is kept in one token with its spaces
+        String,
+        /// A character literal such as `'{'`
+        Char,
+        /// A text block, a string literal between `"""` which spans lines
+        TextBlock,
+        LineComment,
+        /// A block comment, javadoc comments included
+        BlockComment,
+    }
+
+    /// Classifies every character of java source as code or as part of a literal or
+    /// comment, before the lexer reads the structure of the file. The quotes and the
+    /// delimiters of a comment are part of their literal or comment. A string or
+    /// character literal which is not closed ends with its line.
+    ///
+    /// Returns the class of each character and the literal or comment which is still
+    /// open at the end of the source.
+    pub fn classify_source(content: &str) -> (Vec<Text>, Text) {
+        let chars: Vec<char> = content.chars().collect();
+        let mut classes = Vec::with_capacity(chars.len());
+        let mut state = Text::Code;
+        let mut i = 0;
+
+        while i < chars.len() {
+            let ch = chars[i];
+            let next = chars.get(i + 1).cloned();
+            let text_block = chars[i..].starts_with(&['"', '"', '"']);
+            // The number of characters which get the class
+            let mut len = 1;
+
+            let class = match state {
+                Text::Code => {
+                    state = match (ch, next) {
+                        ('"', _) if text_block => {
+                            len = 3;
+                            Text::TextBlock
+                        }
+                        ('"', _) => Text::String,
+                        ('\'', _) => Text::Char,
+                        ('/', Some('/')) => Text::LineComment,
+                        ('/', Some('*')) => {
+                            len = 2;
+                            Text::BlockComment
+                        }
+                        _ => Text::Code,
+                    };
+                    state
+                }
+                Text::String | Text::Char if ch == '\n' => {
+                    state = Text::Code;
+                    Text::Code
+                }
+                Text::String | Text::Char => {
+                    let class = state;
+                    let quote = if state == Text::String { '"' } else { '\'' };
+
+                    if ch == '\\' && next != Some('\n') {
+                        len = 2;
+                    } else if ch == quote {
+                        state = Text::Code;
+                    }
+                    class
+                }
+                Text::TextBlock => {
+                    if ch == '\\' {
+                        len = 2;
+                    } else if text_block {
+                        len = 3;
+                        state = Text::Code;
+                    }
+                    Text::TextBlock
+                }
+                Text::LineComment if ch == '\n' => {
+                    state = Text::Code;
+                    Text::Code
+                }
+                Text::LineComment => Text::LineComment,
+                Text::BlockComment => {
+                    if ch == '*' && next == Some('/') {
+                        len = 2;
+                        state = Text::Code;
+                    }
+                    Text::BlockComment
+                }
+            };
+
+            for _ in 0..len.min(chars.len() - i) {
+                classes.push(class);
+            }
+            i += len;
+        }
+
+        (classes, state)
+    }
+
     pub fn lex_contents(content: &String) -> Vec<Token> {
         let mut tokens: Vec<Token> = Vec::new();
         let mut curr_token = String::new();
-        let mut block_depth = 0;
+        // Whether each open brace starts the body of a class, interface or enum
+        let mut braces: Vec<bool> = Vec::new();
+        let mut generic_depth = 0;
+        // The depth of the parentheses of the arguments of an annotation, whose braces
+        // are array values instead of blocks
+        let mut annotation_depth = 0;
         let mut line_number = 1;
-        let mut blob = content.chars();
+        let mut in_doc = false;
+        // The spaces read in a javadoc since its last token
+        let mut doc_spaces = String::new();
+        let (classes, _) = classify_source(content);
+        let mut blob = content.chars().zip(classes);
 
         tokens.push(Token::LineNumber(line_number.to_string()));
         loop {
+            let emit = braces.iter().all(|&body| body);
+
             match blob.next() {
-                Some(ch) => match ch {
-                    ' ' | '\t' => {
-                        if block_depth < 2 {
-                            push_token(&curr_token, &mut tokens);
+                // A text block is one lexeme, so its lines are not read as code or javadoc
+                Some(('\n', Text::TextBlock)) => {
+                    if emit {
+                        curr_token.push(' ');
+                    }
+
+                    line_number += 1;
+                    tokens.push(Token::LineNumber(line_number.to_string()));
+                }
+                // The lines of block comments are counted like code
+                Some(('\n', _)) => {
+                    doc_spaces.clear();
+
+                    // A type parameter list which spans lines is kept in one token
+                    if generic_depth > 0 {
+                        if !curr_token.ends_with(' ') {
+                            curr_token.push(' ');
                         }
+                    } else {
+                        push_lexeme(&curr_token, &mut tokens, &mut in_doc, emit);
                         curr_token = String::new();
                     }
-                    '\n' => {
-                        if block_depth < 2 {
-                            push_token(&curr_token, &mut tokens);
+
+                    line_number += 1;
+                    tokens.push(Token::LineNumber(line_number.to_string()));
+                }
+                // The lines of markdown comments are kept whole for their indentation
+                Some((ch, Text::LineComment)) if curr_token.starts_with("///") => curr_token.push(ch),
+                // The spaces after the first one between the words of a javadoc are kept
+                // as a token, so preformatted blocks keep their indentation
+                Some((ch @ ' ', Text::BlockComment)) | Some((ch @ '\t', Text::BlockComment))
+                    if in_doc && curr_token.is_empty() =>
+                {
+                    if !matches!(tokens.last(), Some(Token::LineNumber(_))) {
+                        doc_spaces.push(ch);
+                    }
+                }
+                // The words of comments are separate tokens, punctuation is part of them
+                Some((' ', Text::LineComment))
+                | Some((' ', Text::BlockComment))
+                | Some(('\t', Text::LineComment))
+                | Some(('\t', Text::BlockComment)) => {
+                    push_lexeme(&curr_token, &mut tokens, &mut in_doc, emit);
+                    curr_token = String::new();
+                }
+                Some((ch, text)) if text != Text::Code => {
+                    if emit {
+                        if !doc_spaces.is_empty() {
+                            tokens.push(Token::Symbol(doc_spaces.clone()));
+                        }
+                        curr_token.push(ch);
+                    }
+                    doc_spaces.clear();
+                }
+                Some((ch, _)) => match ch {
+                    // Type parameter lists such as `Map<K, V>` are kept in one token
+                    '<' if emit => {
+                        if generic_depth > 0 || starts_type_params(&mut curr_token, &mut tokens) {
+                            generic_depth += 1;
                         }
 
-                        line_number = line_number + 1;
-                        tokens.push(Token::LineNumber(line_number.to_string()));
+                        curr_token.push(ch);
+                    }
+                    '>' if generic_depth > 0 => {
+                        curr_token.push(ch);
+                        generic_depth -= 1;
+                    }
+                    ',' if generic_depth > 0 => curr_token.push_str(", "),
+                    ' ' | '\t' if generic_depth > 0 => {
+                        if !curr_token.ends_with(' ') {
+                            curr_token.push(' ');
+                        }
+                    }
+                    ' ' | '\t' => {
+                        push_lexeme(&curr_token, &mut tokens, &mut in_doc, emit);
                         curr_token = String::new();
                     }
                     ',' => {
-                        if block_depth < 2 {
-                            push_token(&curr_token, &mut tokens);
+                        push_lexeme(&curr_token, &mut tokens, &mut in_doc, emit);
+                        if emit {
                             tokens.push(Token::Join)
                         }
                         curr_token = String::new();
                     }
                     ';' => {
-                        if block_depth < 2 {
-                            push_token(&curr_token, &mut tokens);
+                        generic_depth = 0;
+                        push_lexeme(&curr_token, &mut tokens, &mut in_doc, emit);
+                        if emit {
                             tokens.push(Token::ExpressionEnd(";".to_string()));
                         }
                         curr_token = String::new();
                     }
                     '(' => {
-                        if block_depth < 2 {
-                            push_token(&curr_token, &mut tokens);
+                        generic_depth = 0;
+                        push_lexeme(&curr_token, &mut tokens, &mut in_doc, emit);
+                        if emit {
+                            let annotation = match tokens.last() {
+                                Some(Token::Symbol(word)) => word.starts_with('@') && word != "@interface",
+                                _ => false,
+                            };
+                            if annotation_depth > 0 || annotation {
+                                annotation_depth += 1;
+                            }
+
+                            mark_record_keyword(&mut tokens);
                             tokens.push(Token::ParamStart);
                         }
                         curr_token = String::new();
                     }
                     ')' => {
-                        if block_depth < 2 {
-                            push_token(&curr_token, &mut tokens);
+                        generic_depth = 0;
+                        push_lexeme(&curr_token, &mut tokens, &mut in_doc, emit);
+                        if emit {
+                            if annotation_depth > 0 {
+                                annotation_depth -= 1;
+                            }
                             tokens.push(Token::ParamEnd);
                         }
                         curr_token = String::new();
                     }
+                    '{' | '}' if annotation_depth > 0 => {
+                        push_lexeme(&curr_token, &mut tokens, &mut in_doc, emit);
+                        if emit {
+                            tokens.push(Token::Symbol(ch.to_string()));
+                        }
+                        curr_token = String::new();
+                    }
                     '{' => {
-                        if block_depth < 2 {
-                            push_token(&curr_token, &mut tokens);
+                        generic_depth = 0;
+                        push_lexeme(&curr_token, &mut tokens, &mut in_doc, emit);
+                        if emit {
                             tokens.push(Token::ExpressionEnd("{".to_string()));
+                            braces.push(braces.is_empty() || opens_type_body(&tokens));
+                        } else {
+                            braces.push(false);
                         }
                         curr_token = String::new();
-                        block_depth = block_depth + 1;
                     }
                     '}' => {
-                        if block_depth < 2 {
-                            push_token(&curr_token, &mut tokens);
-                        }
+                        generic_depth = 0;
+                        push_lexeme(&curr_token, &mut tokens, &mut in_doc, emit);
                         curr_token = String::new();
-                        block_depth = block_depth - 1;
+
+                        // Only the end of a type body is emitted, other blocks are skipped
+                        if braces.pop() == Some(true) && emit {
+                            tokens.push(Token::ExpressionEnd("}".to_string()));
+                        }
                     }
                     _ => {
-                        if block_depth < 2 {
-                            curr_token.push_str(ch.to_string().as_str());
+                        if emit {
+                            curr_token.push(ch);
                         }
                     }
                 },
-                None => break,
+                None => {
+                    push_lexeme(&curr_token, &mut tokens, &mut in_doc, emit);
+                    break;
+                }
             }
         }
 
@@ -492,7 +1504,8 @@ pub mod parse {
         ($e:expr) => {
             match $e {
                 Token::Keyword(value) => match value.as_ref() {
-                    "static" | "final" | "abstract" | "synchronized" | "volatile" => true,
+                    "static" | "final" | "abstract" | "synchronized" | "volatile"
+                    | "transient" | "native" | "strictfp" => true,
                     _ => false,
                 },
                 _ => false,
@@ -501,33 +1514,140 @@ pub mod parse {
     }
 
     /// Constucts a syntax tree based on the stream of token from the lexing
-    /// Outputs a Class struct containing all the data for a java class
+    /// Outputs the classes, interfaces and enums declared at the top level of a java file
     ///
     /// # Arguments
     ///
     /// * `tokens` - The list of tokens from the lexer
-    pub fn construct_ast(tokens: Vec<Token>) -> ObjectType {
+    pub fn construct_ast(tokens: Vec<Token>) -> Vec<ObjectType> {
+        build_ast(tokens, &mut Vec::new())
+    }
+
+    /// Constructs the syntax tree like `construct_ast` and adds the line and message of a
+    /// warning for each declaration which does not match its javadoc, and for each
+    /// javadoc which is not followed by a declaration
+    fn build_ast(tokens: Vec<Token>, warnings: &mut Vec<(usize, String)>) -> Vec<ObjectType> {
         let mut annotation = false;
         let mut ignore = false;
+        // The depth of the parentheses in the skipped arguments of an annotation
+        let mut ignore_depth = 0;
+        let mut objects: Vec<ObjectType> = Vec::new();
         let mut object = Object::new();
+        let mut outer_objects: Vec<Object> = Vec::new();
         let mut in_object = false;
+        let mut in_body = false;
         let mut parse_state = ParseState::new();
         let mut doc = false;
         let mut comment = false;
+        let mut line_comment = false;
+        let mut param_list = false;
+        let mut enum_constants = false;
+        let mut const_depth = 0;
+        let mut const_name = String::new();
+        let mut const_value = String::new();
         let mut jdoc = Doc::new();
+        // The lines the last javadoc and the javadoc being read start on
+        let mut jdoc_line = 0;
+        let mut doc_line = 0;
+        // The javadoc of the package statement, which documents the package in a
+        // `package-info.java` file
+        let mut package_doc = String::new();
         let mut _jdoc_errs = String::new();
         let mut symbols: Vec<String> = Vec::new();
         let mut doc_tokens: Vec<JdocToken> = Vec::new();
+        // The lines of a `///` markdown comment which is not followed by a declaration yet
+        let mut markdown_lines: Vec<String> = Vec::new();
+        let mut line_start = true;
         let mut method: Method = Method::new();
         let mut gram_parts: Vec<Stream> = Vec::new();
         let mut comment_buf = String::new();
+        let mut decl_text = String::new();
         let mut line_num = String::new();
+        // The line a declaration starts on, after its annotations
+        let mut decl_line = String::new();
+        // The last field declared with an array initializer, any declarators after the
+        // initializer share its type
+        let mut array_field: Option<Member> = None;
+        let mut meta_annotation = String::new();
+        let mut meta_args: Vec<String> = Vec::new();
+        // The arguments of the last annotation of a declaration as they are written
+        let mut annotation_args: Option<String> = None;
+        let mut retention = String::new();
+        let mut targets: Vec<String> = Vec::new();
 
         for token in tokens.clone() {
+            let first_on_line = line_start;
+            line_start = matches!(token, Token::LineNumber(_));
+
+            // A markdown comment ends at the first line which does not start with `///`
+            if !markdown_lines.is_empty() {
+                let continues = match token {
+                    Token::Symbol(ref word) => first_on_line && is_markdown_comment(word),
+                    Token::LineNumber(_) => !first_on_line,
+                    _ => false,
+                };
+
+                if !continues {
+                    jdoc = get_markdown_doc(&markdown_lines);
+                    markdown_lines.clear();
+                    parse_state = ParseState::new();
+                    gram_parts.clear();
+                    decl_text.clear();
+                }
+            }
+
             if ignore {
                 match token.clone() {
-                    Token::ParamEnd => ignore = false,
-                    _ => continue,
+                    Token::ParamStart => {
+                        ignore_depth += 1;
+                        if let Some(args) = annotation_args.as_mut() {
+                            push_annotation_arg(args, "(");
+                        }
+                    }
+                    Token::ParamEnd if ignore_depth > 0 => {
+                        ignore_depth -= 1;
+                        if let Some(args) = annotation_args.as_mut() {
+                            push_annotation_arg(args, ")");
+                        }
+                    }
+                    Token::ParamEnd => {
+                        ignore = false;
+
+                        if let (Some(args), Some(annotation)) = (annotation_args.take(), jdoc.annotations.last_mut()) {
+                            annotation.push_str(format!("({})", args).as_str());
+                        }
+
+                        // Constants like `ElementType.METHOD` are shown without their enum
+                        let mut values = meta_args
+                            .iter()
+                            .map(|arg| arg.rsplit('.').next().unwrap_or(arg).to_string());
+
+                        match meta_annotation.as_ref() {
+                            "Retention" => retention = values.next_back().unwrap_or_default(),
+                            "Target" => targets = values.collect(),
+                            _ => {}
+                        }
+
+                        meta_annotation.clear();
+                        meta_args.clear();
+                    }
+                    Token::Symbol(word) | Token::Keyword(word) => {
+                        if let Some(args) = annotation_args.as_mut() {
+                            push_annotation_arg(args, &word);
+                        }
+
+                        if word == "=" {
+                            meta_args.clear();
+                        } else if word != "{" && word != "}" {
+                            meta_args.push(word);
+                        }
+                    }
+                    Token::Join => {
+                        if let Some(args) = annotation_args.as_mut() {
+                            args.push(',');
+                        }
+                    }
+                    _ => {}
                 }
 
                 continue;
@@ -535,6 +1655,23 @@ pub mod parse {
 
             match token.clone() {
                 Token::Keyword(key) => {
+                    // Java keywords used as words in javadocs and comments are plain text
+                    if doc {
+                        if is_keyword!(key, get_jdoc_keywords()) {
+                            doc_tokens.push(JdocToken::Keyword(key.clone()));
+                        } else {
+                            doc_tokens.push(JdocToken::Symbol(key.clone()));
+                        }
+                        continue;
+                    } else if comment {
+                        comment_buf.push_str(format!("{} ", key).as_str());
+                        continue;
+                    }
+
+                    if gram_parts.is_empty() && symbols.is_empty() {
+                        decl_line = line_num.clone();
+                    }
+
                     let temp_sym = symbols.clone();
                     if temp_sym.len() == 1 {
                         gram_parts.push(Stream::Variable(temp_sym[0].clone()));
@@ -542,30 +1679,27 @@ pub mod parse {
                         gram_parts.push(Stream::Type(temp_sym[..temp_sym.len() - 1].join(" ")));
                         gram_parts.push(Stream::Variable(temp_sym[temp_sym.len() - 1].clone()));
                     }
+                    symbols.clear();
 
                     match key.as_ref() {
                         "class" => {
-                            if !doc && !comment {
-                                object.ch_state(ObjectState::Class);
-                                gram_parts.push(Stream::Object(key.to_string()));
-                                parse_state.ch_class(true);
-                            }
+                            gram_parts.push(Stream::Object(key.to_string()));
+                            parse_state.ch_class(true);
                             in_object = true;
                         }
                         "interface" => {
-                            if !doc && !comment {
-                                object.ch_state(ObjectState::Interface);
-                                gram_parts.push(Stream::Object(key.to_string()));
-                                parse_state.ch_interface(true);
-                            }
+                            gram_parts.push(Stream::Object(key.to_string()));
+                            parse_state.ch_interface(true);
                             in_object = true;
                         }
                         "enum" => {
-                            if !doc && !comment {
-                                object.ch_state(ObjectState::Enumeration);
-                                gram_parts.push(Stream::Object(key.to_string()));
-                                parse_state.ch_enum(true);
-                            }
+                            gram_parts.push(Stream::Object(key.to_string()));
+                            parse_state.ch_enum(true);
+                            in_object = true;
+                        }
+                        "record" => {
+                            gram_parts.push(Stream::Object(key.to_string()));
+                            parse_state.ch_record(true);
                             in_object = true;
                         }
                         "package" => {
@@ -574,6 +1708,10 @@ pub mod parse {
                             }
                             gram_parts.push(Stream::Package);
                         }
+                        "default" => {
+                            gram_parts.push(Stream::Default);
+                            push_decl_text(&mut decl_text, "default");
+                        }
                         "throws" => gram_parts.push(Stream::Exception),
                         "extends" => gram_parts.push(Stream::Parent),
                         "implements" => gram_parts.push(Stream::Implement),
@@ -583,37 +1721,67 @@ pub mod parse {
                                 gram_parts.push(Stream::Access(key.to_string()));
                             } else if modifier_match!(token.clone()) {
                                 gram_parts.push(Stream::Modifier(key.to_string()));
-                            } else if is_keyword!(key, get_jdoc_keywords()) {
-                                doc_tokens.push(JdocToken::Keyword(key.clone()));
-                            } else if doc {
-                                doc_tokens.push(JdocToken::Symbol(key.clone()));
-                            } else if !comment && !doc {
-                                println!("Keyword not supported: {}", key);
+                            } else {
+                                verbose!("Keyword not supported: {}", key);
                             }
                         }
                     }
 
-                    if comment {
-                        comment_buf.push_str(format!("{} ", key).as_str());
-                    }
-
                     annotation = false;
                 }
                 Token::Symbol(word) => {
                     match word.as_ref() {
-                        "/**" => doc = true,
+                        "/**" => {
+                            doc = true;
+                            doc_line = line_num.parse().unwrap_or(0);
+                        }
                         "*/" => {
                             if doc {
+                                // The last javadoc is replaced before a declaration used it
+                                if !jdoc.description.is_empty() || !jdoc.params.is_empty() {
+                                    warnings.push((jdoc_line, "the javadoc is not followed by a declaration".to_string()));
+                                }
                                 jdoc = get_doc(&doc_tokens);
+                                jdoc_line = doc_line;
                                 parse_state = ParseState::new();
                                 doc_tokens.clear();
                                 gram_parts.clear();
+                                decl_text.clear();
                             }
 
                             doc = false;
                             comment = false;
                         }
-                        "//" => comment = true,
+                        // `sealed`, `non-sealed` and `permits` are only keywords in the
+                        // declaration of a type
+                        "sealed" | "non-sealed" if !doc && !comment && symbols.is_empty() => {
+                            if gram_parts.is_empty() {
+                                decl_line = line_num.clone();
+                            }
+                            gram_parts.push(Stream::Modifier(word.clone()));
+                        }
+                        "permits"
+                            if !doc
+                                && !comment
+                                && gram_parts.iter().any(|part| matches!(part, Stream::Object(_))) =>
+                        {
+                            if symbols.len() == 1 {
+                                gram_parts.push(Stream::Variable(symbols[0].clone()));
+                            } else if symbols.len() > 1 {
+                                gram_parts.push(Stream::Type(symbols[..symbols.len() - 1].join(" ")));
+                                gram_parts.push(Stream::Variable(symbols[symbols.len() - 1].clone()));
+                            }
+                            symbols.clear();
+                            gram_parts.push(Stream::Permits);
+                        }
+                        _ if first_on_line && !doc && !comment && is_markdown_comment(&word) => {
+                            markdown_lines.push(word[3..].to_string());
+                            continue;
+                        }
+                        _ if word.starts_with("//") && !doc && !comment => {
+                            line_comment = true;
+                            comment = true;
+                        }
                         "/*" => {
                             comment_buf = String::new();
                             comment = true;
@@ -625,10 +1793,37 @@ pub mod parse {
                                 } else {
                                     doc_tokens.push(JdocToken::Symbol(word.clone()));
                                 }
-                            } else if word.contains("@") && !doc {
+                            } else if word == "@interface" && !comment {
+                                gram_parts.push(Stream::Object(word.to_string()));
+                                parse_state.ch_annotation(true);
+                                in_object = true;
+                            } else if word.contains("@") && !doc && !comment && !word.starts_with('"') {
+                                // Deprecated declarations without a javadoc tag get a generic notice
+                                let deprecated = word == "@Deprecated" || word == "@java.lang.Deprecated";
+                                if deprecated && jdoc.deprecated.is_none() {
+                                    jdoc.deprecated = Some(String::new());
+                                }
+                                // The annotations of parameters are not the method's
+                                if !param_list {
+                                    jdoc.annotations.push(word.clone());
+                                }
+
+                                // The arguments of meta annotations are read instead of skipped
+                                meta_annotation = match word.rsplit('.').next() {
+                                    Some("@Retention") | Some("Retention") => "Retention".to_string(),
+                                    Some("@Target") | Some("Target") => "Target".to_string(),
+                                    _ => String::new(),
+                                };
+                                meta_args.clear();
+
                                 annotation = true;
                                 continue;
                             } else if !comment {
+                                if gram_parts.is_empty() && symbols.is_empty() {
+                                    decl_line = line_num.clone();
+                                }
+
+                                push_decl_text(&mut decl_text, &word);
                                 symbols.push(word.to_string());
                             }
                         }
@@ -643,7 +1838,25 @@ pub mod parse {
                     annotation = false;
                 }
                 Token::Join => {
-                    if symbols.len() > 1 {
+                    if enum_constants && !comment && !doc {
+                        if const_depth == 0 {
+                            if const_name.is_empty() {
+                                const_name = symbols.join(" ");
+                            }
+
+                            add_enum_constant(&mut object, &mut const_name, &mut const_value, &jdoc);
+                            jdoc = Doc::new();
+                        } else {
+                            const_value.push_str(format!("{}, ", symbols.join(" ")).as_str());
+                        }
+
+                        symbols.clear();
+                        continue;
+                    }
+
+                    if symbols.len() == 1 {
+                        gram_parts.push(Stream::Variable(symbols[0].clone()));
+                    } else if symbols.len() > 1 {
                         let temp_sym = symbols.clone();
                         gram_parts.push(Stream::Type(temp_sym[..temp_sym.len() - 1].join(" ")));
                         gram_parts.push(Stream::Variable(temp_sym[temp_sym.len() - 1].clone()));
@@ -651,14 +1864,31 @@ pub mod parse {
 
                     if comment {
                         comment_buf.push_str(",");
+                    } else if !doc {
+                        push_decl_text(&mut decl_text, ",");
                     }
 
                     symbols.clear();
                 }
                 Token::ParamStart => {
+                    if enum_constants && !comment && !doc && !annotation {
+                        if const_depth == 0 {
+                            const_name = symbols.join(" ");
+                        } else {
+                            const_value.push_str(format!("{}(", symbols.join(" ")).as_str());
+                        }
+
+                        const_depth += 1;
+                        symbols.clear();
+                        continue;
+                    }
+
                     if annotation {
                         ignore = true;
                         annotation = false;
+                        if !param_list {
+                            annotation_args = Some(String::new());
+                        }
                     } else {
                         let temp_sym = symbols.clone();
                         if temp_sym.len() == 1 {
@@ -667,6 +1897,16 @@ pub mod parse {
                             gram_parts.push(Stream::Type(temp_sym[..temp_sym.len() - 1].join(" ")));
                             gram_parts.push(Stream::Variable(temp_sym[temp_sym.len() - 1].clone()));
                         }
+
+                        if !comment && !doc {
+                            // Parentheses in a field initializer are not a parameter list
+                            if !param_list && !decl_text.contains('=') {
+                                gram_parts.push(Stream::Parameters);
+                                param_list = true;
+                            }
+
+                            push_decl_text(&mut decl_text, "(");
+                        }
                     }
 
                     if comment {
@@ -676,8 +1916,23 @@ pub mod parse {
                     symbols.clear();
                 }
                 Token::ParamEnd => {
+                    if enum_constants && !comment && !doc {
+                        const_value.push_str(symbols.join(" ").as_str());
+                        const_depth -= 1;
+
+                        if const_depth > 0 {
+                            const_value.push(')');
+                        }
+
+                        symbols.clear();
+                        continue;
+                    }
+
                     let temp_sym = symbols.clone();
-                    if symbols.len() == 1 {
+                    if symbols.len() == 1 && temp_sym[0].contains("...") {
+                        // The type and name of `String...args` are split by `get_method`
+                        gram_parts.push(Stream::Variable(temp_sym[0].clone()));
+                    } else if symbols.len() == 1 {
                         method.ch_method_name(temp_sym[0].clone());
                     } else if symbols.len() > 1 {
                         gram_parts.push(Stream::Type(temp_sym[..temp_sym.len() - 1].join(" ")));
@@ -686,10 +1941,41 @@ pub mod parse {
 
                     if comment {
                         comment_buf.push_str(")");
+                    } else if !doc {
+                        push_decl_text(&mut decl_text, ")");
                     }
                     symbols.clear();
                 }
                 Token::ExpressionEnd(end) => {
+                    // Semicolons and braces inside comments do not end a declaration
+                    if comment || doc {
+                        continue;
+                    }
+
+                    // The constant list of an enum ends with the first semicolon.
+                    // Constant specific class bodies are skipped by the lexer.
+                    if enum_constants {
+                        if const_name.is_empty() {
+                            const_name = symbols.join(" ");
+                        }
+
+                        if end == ";" || end == "}" {
+                            add_enum_constant(&mut object, &mut const_name, &mut const_value, &jdoc);
+                            enum_constants = false;
+                            parse_state = ParseState::new();
+                            jdoc = Doc::new();
+                            param_list = false;
+                            gram_parts.clear();
+                            decl_text.clear();
+                        }
+
+                        symbols.clear();
+
+                        if end != "}" {
+                            continue;
+                        }
+                    }
+
                     // For any symbols not included add them to the stream for parsing
                     if symbols.len() == 1 {
                         gram_parts.push(Stream::Variable(symbols[0].clone()));
@@ -700,41 +1986,238 @@ pub mod parse {
 
                     let mut temp_gram = gram_parts.clone();
 
+                    // The declarators after an array initializer belong to its declaration
+                    if let Some(field) = array_field.take() {
+                        if end == ";" && decl_text.starts_with(',') {
+                            for var in declared_vars(field, &decl_text) {
+                                object.add_variable(var);
+                            }
+                            temp_gram.clear();
+                        }
+                    }
+
                     match end.as_ref() {
                         ";" => {
                             if !in_object {
                                 if temp_gram.len() > 1 {
                                     match temp_gram[0].clone() {
-                                        Stream::Import => match temp_gram[1].clone() {
-                                            Stream::Variable(key) => object.add_dependency(key),
-                                            _ => println!("Pattern not supported"),
+                                        Stream::Import => match (temp_gram[1].clone(), temp_gram.get(2)) {
+                                            (Stream::Variable(key), _) => object.add_dependency(key),
+                                            // Static imports keep the keyword to tell them apart
+                                            (Stream::Modifier(ref modifier), Some(Stream::Variable(key)))
+                                                if modifier == "static" =>
+                                            {
+                                                object.add_dependency(format!("static {}", key))
+                                            }
+                                            _ => verbose!("Pattern not supported"),
                                         },
                                         Stream::Package => match temp_gram[1].clone() {
-                                            Stream::Variable(key) => object.ch_package_name(key),
-                                            _ => println!("Pattern not supported"),
+                                            Stream::Variable(key) => {
+                                                object.ch_package_name(key);
+                                                package_doc = jdoc.description.clone();
+                                            }
+                                            _ => verbose!("Pattern not supported"),
                                         },
-                                        _ => object
-                                            .add_variable(get_var(temp_gram, line_num.clone())),
+                                        _ => {
+                                            for var in get_vars(temp_gram, &decl_text, &jdoc, decl_line.clone()) {
+                                                object.add_variable(var);
+                                            }
+                                        }
                                     }
                                 }
-                            } else {
+                            } else if !temp_gram.is_empty() {
                                 match object.state {
-                                    ObjectState::Class => {
-                                        object.add_variable(get_var(temp_gram, line_num.clone()))
+                                    ObjectState::Class | ObjectState::Record => {
+                                        if param_list {
+                                            object.add_method(get_method(
+                                                temp_gram,
+                                                &jdoc,
+                                                decl_line.clone(),
+                                                warnings,
+                                            ))
+                                        } else {
+                                            for var in get_vars(temp_gram, &decl_text, &jdoc, decl_line.clone()) {
+                                                object.add_variable(var);
+                                            }
+                                        }
+                                    }
+                                    ObjectState::Enumeration => {
+                                        if param_list {
+                                            object.add_method(get_method(
+                                                temp_gram,
+                                                &jdoc,
+                                                decl_line.clone(),
+                                                warnings,
+                                            ))
+                                        } else {
+                                            for var in get_vars(temp_gram, &decl_text, &jdoc, decl_line.clone()) {
+                                                object.add_variable(var);
+                                            }
+                                        }
+                                    }
+                                    ObjectState::Annotation if param_list => object.add_element(
+                                        get_element(&decl_text, &jdoc, decl_line.clone()),
+                                    ),
+                                    ObjectState::Interface | ObjectState::Annotation => {
+                                        if param_list {
+                                            let mut inter_method = get_method(
+                                                temp_gram,
+                                                &jdoc,
+                                                decl_line.clone(),
+                                                warnings,
+                                            );
+
+                                            // Interface methods are implicitly public, and
+                                            // abstract when they have no body
+                                            if inter_method.privacy.is_empty() {
+                                                inter_method.ch_privacy("public".to_string());
+                                            }
+                                            if !inter_method.modifiers.iter().any(|m| m == "abstract") {
+                                                inter_method.add_modifier("abstract".to_string());
+                                            }
+
+                                            object.add_method(inter_method);
+                                        } else {
+                                            // Interface fields are implicitly public static final
+                                            for key in &["static", "final"] {
+                                                let declared = temp_gram.iter().any(|part| match part {
+                                                    Stream::Modifier(m) => m == key,
+                                                    _ => false,
+                                                });
+
+                                                if !declared {
+                                                    temp_gram.push(Stream::Modifier(key.to_string()));
+                                                }
+                                            }
+
+                                            for mut field in get_vars(temp_gram, &decl_text, &jdoc, decl_line.clone()) {
+                                                if field.access.is_empty() {
+                                                    field.ch_access("public".to_string());
+                                                }
+
+                                                object.add_variable(field);
+                                            }
+                                        }
                                     }
                                     _ => object.add_method(get_method(
                                         temp_gram,
                                         &jdoc,
-                                        line_num.clone(),
+                                        decl_line.clone(),
+                                        warnings,
                                     )),
                                 }
                             }
                         }
                         "{" => {
-                            if parse_state.interface || parse_state.class {
+                            if parse_state.interface
+                                || parse_state.class
+                                || parse_state.enum_ob
+                                || parse_state.annotation
+                                || parse_state.record
+                            {
+                                // A type declared in the body of another type is nested in it
+                                if in_body {
+                                    outer_objects.push(object);
+                                    object = Object::new();
+                                }
+
+                                if parse_state.class {
+                                    object.ch_state(ObjectState::Class);
+                                } else if parse_state.record {
+                                    object.ch_state(ObjectState::Record);
+                                } else if parse_state.interface {
+                                    object.ch_state(ObjectState::Interface);
+                                } else if parse_state.annotation {
+                                    object.ch_state(ObjectState::Annotation);
+                                    object.ch_retention(retention.clone());
+                                    object.ch_targets(targets.clone());
+                                } else {
+                                    object.ch_state(ObjectState::Enumeration);
+                                }
+
+                                object.ch_line_num(decl_line.clone());
                                 get_object(temp_gram.clone(), &jdoc, &mut object);
-                            } else {
-                                object.add_method(get_method(temp_gram, &jdoc, line_num.clone()));
+
+                                // Types nested in an interface are implicitly public, like its members
+                                let in_interface = outer_objects.last().is_some_and(|outer| {
+                                    matches!(outer.state, ObjectState::Interface | ObjectState::Annotation)
+                                });
+                                if in_body && in_interface && object.access.is_empty() {
+                                    object.ch_access("public".to_string());
+                                }
+                                enum_constants = parse_state.enum_ob;
+                                in_body = true;
+                            } else if param_list && matches!(object.state, ObjectState::Annotation) {
+                                // Array defaults of elements are skipped by the lexer
+                                let text = format!("{} {{ ... }}", decl_text);
+                                object.add_element(get_element(&text, &jdoc, decl_line.clone()));
+                            } else if param_list {
+                                let mut method =
+                                    get_method(temp_gram, &jdoc, decl_line.clone(), warnings);
+
+                                if method.return_type.is_empty() && method.name == object.name {
+                                    // Enum constructors are implicitly private
+                                    if let ObjectState::Enumeration = object.state {
+                                        if method.privacy.is_empty() {
+                                            method.ch_privacy("private".to_string());
+                                        }
+                                    }
+
+                                    object.add_constructor(method);
+                                } else {
+                                    // Default and static interface methods are implicitly public
+                                    if let ObjectState::Interface = object.state {
+                                        if method.privacy.is_empty() {
+                                            method.ch_privacy("public".to_string());
+                                        }
+                                    }
+
+                                    object.add_method(method);
+                                }
+                            } else if is_compact_constructor(&temp_gram, &object) {
+                                let mut method = Method::new();
+                                method.ch_method_name(object.name.clone());
+                                method.ch_params(object.components.clone());
+                                method.ch_description(jdoc.description.clone());
+                                method.ch_deprecated(jdoc.deprecated.clone());
+                                method.ch_line_num(decl_line.clone());
+
+                                for part in temp_gram {
+                                    if let Stream::Access(key) = part {
+                                        method.ch_privacy(key);
+                                    }
+                                }
+
+                                object.add_constructor(method);
+                            } else if decl_text.contains('=') {
+                                // Array initializers are skipped by the lexer
+                                let text = format!("{} {{ ... }}", decl_text);
+                                let vars = get_vars(temp_gram, &text, &jdoc, decl_line.clone());
+
+                                array_field = vars.last().cloned();
+                                for var in vars {
+                                    object.add_variable(var);
+                                }
+                            }
+                        }
+                        "}" => {
+                            if let Some(mut outer) = outer_objects.pop() {
+                                object.ch_package_name(outer.package_name.clone());
+                                outer.add_inner_class(get_object_type(&mut object));
+                                object = outer;
+                            } else if in_body {
+                                // Another top level type may follow in the same file
+                                let mut next = Object::new();
+                                next.ch_package_name(object.package_name.clone());
+                                next.ch_license(object.license.clone());
+                                for dep in &object.dependencies {
+                                    next.add_dependency(dep.clone());
+                                }
+
+                                objects.push(get_object_type(&mut object));
+                                object = next;
+                                in_object = false;
+                                in_body = false;
                             }
                         }
                         _ => {
@@ -748,43 +2231,591 @@ pub mod parse {
 
                     parse_state = ParseState::new();
                     jdoc = Doc::new();
+                    param_list = false;
                     gram_parts.clear();
+                    decl_text.clear();
                     symbols.clear();
+                    retention.clear();
+                    targets.clear();
+                }
+                Token::LineNumber(num) => {
+                    // Line breaks are kept for preformatted blocks in javadocs
+                    if doc {
+                        doc_tokens.push(JdocToken::Symbol("\n".to_string()));
+                    }
+
+                    if line_comment {
+                        line_comment = false;
+                        comment = false;
+                    }
+
+                    line_num = num
                 }
-                Token::LineNumber(num) => line_num = num,
             }
         }
 
+        // Enums without a semicolon end their constant list with the closing brace
+        if enum_constants {
+            if const_name.is_empty() {
+                const_name = symbols.join(" ");
+            }
+
+            add_enum_constant(&mut object, &mut const_name, &mut const_value, &jdoc);
+        }
+
+        // Nested types which are not closed belong to the type around them
+        while let Some(mut outer) = outer_objects.pop() {
+            object.ch_package_name(outer.package_name.clone());
+            outer.add_inner_class(get_object_type(&mut object));
+            object = outer;
+        }
+
+        if in_body || objects.is_empty() {
+            // A file without a type only documents its package
+            if matches!(object.state, ObjectState::Unset) {
+                object.ch_description(package_doc);
+            }
+            objects.push(get_object_type(&mut object));
+        }
+
+        objects
+    }
+
+    /// Converts a parsed object into the class, interface or enum it declares
+    fn get_object_type(object: &mut Object) -> ObjectType {
         match object.state {
-            ObjectState::Class => return ObjectType::Class(object.to_class()),
-            ObjectState::Interface => return ObjectType::Interface(object.to_interface()),
-            ObjectState::Enumeration => return ObjectType::Enumeration(object.to_enumeration()),
+            ObjectState::Class | ObjectState::Record => ObjectType::Class(object.to_class()),
+            ObjectState::Interface => ObjectType::Interface(object.to_interface()),
+            ObjectState::Enumeration => ObjectType::Enumeration(object.to_enumeration()),
+            ObjectState::Annotation => ObjectType::Annotation(object.to_annotation()),
             ObjectState::Unset => {
-                println!("Java file type not supported. Supported types: class, interface, enum");
-                return ObjectType::Class(object.to_class());
+                if !object.name.is_empty() {
+                    verbose!("Java file type not supported. Supported types: class, interface, enum");
+                }
+                ObjectType::Class(object.to_class())
+            }
+        }
+    }
+
+    /// The byte order mark some editors on Windows write at the start of UTF-8 files
+    const UTF8_BOM: &[u8] = b"\xef\xbb\xbf";
+
+    /// The encoding java files are read with
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Charset {
+        /// UTF-8, with ISO-8859-1 for the files which are not valid UTF-8
+        Auto,
+        /// UTF-8, invalid bytes become the replacement character
+        Utf8,
+        /// ISO-8859-1, where every byte is a character
+        Latin1,
+    }
+
+    impl Charset {
+        pub fn from_name(name: &str) -> Option<Charset> {
+            match name {
+                "auto" => Some(Charset::Auto),
+                "utf8" => Some(Charset::Utf8),
+                "latin1" => Some(Charset::Latin1),
+                _ => None,
             }
         }
+        pub fn name(&self) -> &'static str {
+            match self {
+                Charset::Auto => "auto",
+                Charset::Utf8 => "utf8",
+                Charset::Latin1 => "latin1",
+            }
+        }
+    }
+
+    /// Finds the line of a block comment which is not closed at the end of a file.
+    /// Literals and line comments are skipped so a `/*` in them is not a comment.
+    pub fn unclosed_comment(content: &str) -> Option<usize> {
+        let (classes, open) = classify_source(content);
+
+        if open != Text::BlockComment {
+            return None;
+        }
+
+        // The comment starts after the last character which is not part of it
+        let start = classes
+            .iter()
+            .rposition(|&class| class != Text::BlockComment)
+            .map_or(0, |i| i + 1);
+        let lines = content.chars().take(start).filter(|&ch| ch == '\n').count();
+
+        Some(lines + 1)
     }
 
-    /// Root function of the module. Calls the lex and parse functions and returns
-    /// a `Class` struct.
+    /// Decodes the contents of a java file. With `Charset::Auto` files which are not valid UTF-8 are read as ISO-8859-1,
+    /// the usual encoding of legacy java sources, and a warning names the file.
+    /// A UTF-8 byte order mark is removed and Windows line endings become `\n`.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The contents of the file
+    /// * `charset` - The encoding of the file
+    /// * `path` - The path of the file, named in the warning
+    /// * `warnings` - The warnings the ISO-8859-1 fallback is added to
+    pub fn decode_source(mut bytes: Vec<u8>, charset: Charset, path: &Path, warnings: &mut Vec<Warning>) -> String {
+        if bytes.starts_with(UTF8_BOM) {
+            bytes.drain(..UTF8_BOM.len());
+        }
+
+        let latin1 = |bytes: &[u8]| -> String { bytes.iter().map(|&byte| byte as char).collect() };
+        let contents: String = match charset {
+            Charset::Latin1 => latin1(&bytes),
+            Charset::Utf8 => String::from_utf8_lossy(&bytes).into_owned(),
+            Charset::Auto => match String::from_utf8(bytes) {
+                Ok(contents) => contents,
+                Err(err) => {
+                    warnings.push(Warning {
+                        file: path.to_path_buf(),
+                        line: 0,
+                        message: "the file is not valid UTF-8, it is read as ISO-8859-1".to_string(),
+                    });
+                    latin1(err.as_bytes())
+                }
+            },
+        };
+
+        if contents.contains('\r') {
+            contents.replace("\r\n", "\n").replace('\r', "\n")
+        } else {
+            contents
+        }
+    }
+
+    /// The characters of java source by line, for copying the source of methods
+    struct SourceLines {
+        chars: Vec<char>,
+        classes: Vec<Text>,
+        /// The index of the first character of each line
+        starts: Vec<usize>,
+    }
+
+    impl SourceLines {
+        fn new(source: &str) -> SourceLines {
+            let chars: Vec<char> = source.chars().collect();
+            let (classes, _) = classify_source(source);
+            let mut starts = vec![0];
+
+            for (i, ch) in chars.iter().enumerate() {
+                if *ch == '\n' {
+                    starts.push(i + 1);
+                }
+            }
+
+            SourceLines { chars, classes, starts }
+        }
+        fn is_code(&self, i: usize, ch: char) -> bool {
+            self.chars[i] == ch && self.classes.get(i) == Some(&Text::Code)
+        }
+        /// Gets the source of a method from the line its declaration starts on to the
+        /// line its body ends on, without the indentation the lines have in common.
+        /// Methods without a body, such as abstract methods, have no source.
+        fn method_source(&self, line: usize) -> String {
+            let start = match line.checked_sub(1).and_then(|line| self.starts.get(line)) {
+                Some(start) => *start,
+                None => return String::new(),
+            };
+            let mut parens = 0;
+            let mut i = start;
+
+            // The body starts with the first brace which is not in the parameters or
+            // the arguments of an annotation
+            while i < self.chars.len() {
+                if self.is_code(i, '(') {
+                    parens += 1;
+                } else if self.is_code(i, ')') {
+                    parens -= 1;
+                } else if parens == 0 && (self.is_code(i, ';') || self.is_code(i, '}')) {
+                    return String::new();
+                } else if parens == 0 && self.is_code(i, '{') {
+                    break;
+                }
+                i += 1;
+            }
+
+            let mut depth = 0;
+            while i < self.chars.len() {
+                if self.is_code(i, '{') {
+                    depth += 1;
+                } else if self.is_code(i, '}') {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                i += 1;
+            }
+            if depth != 0 {
+                return String::new();
+            }
+
+            let end = self.chars[i..]
+                .iter()
+                .position(|ch| *ch == '\n')
+                .map_or(self.chars.len(), |len| i + len);
+            let text: String = self.chars[start..end].iter().collect();
+            let indent = text
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| line.len() - line.trim_start().len())
+                .min()
+                .unwrap_or(0);
+
+            text.lines()
+                .map(|line| line.get(indent..).unwrap_or("").trim_end())
+                .collect::<Vec<&str>>()
+                .join("\n")
+        }
+        /// Sets the source of the methods and constructors of types and their nested types
+        fn add_sources(&self, objects: &mut [ObjectType]) {
+            for object in objects.iter_mut() {
+                let (methods, inner_classes) = match object {
+                    ObjectType::Class(class) => {
+                        self.add_method_sources(&mut class.constructors);
+                        (&mut class.methods, &mut class.inner_classes)
+                    }
+                    ObjectType::Interface(inter) => (&mut inter.methods, &mut inter.inner_classes),
+                    ObjectType::Enumeration(enumeration) => {
+                        self.add_method_sources(&mut enumeration.constructors);
+                        (&mut enumeration.methods, &mut enumeration.inner_classes)
+                    }
+                    ObjectType::Annotation(annotation) => {
+                        self.add_sources(&mut annotation.inner_classes);
+                        continue;
+                    }
+                };
+
+                self.add_method_sources(methods);
+                self.add_sources(inner_classes);
+            }
+        }
+        fn add_method_sources(&self, methods: &mut [Method]) {
+            for method in methods.iter_mut() {
+                method.source = self.method_source(method.line_num.parse().unwrap_or(0));
+            }
+        }
+    }
+
+    /// The types declared in java source with the warnings of the parser
+    #[derive(Debug)]
+    pub struct Parsed {
+        pub objects: Vec<ObjectType>,
+        /// The problems which may make the documentation wrong, sorted by line
+        pub warnings: Vec<Warning>,
+    }
+
+    /// Parses java source code and collects the warnings of the parser instead of
+    /// printing them, e.g. for `--warnings-as-errors`.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The java source code
+    /// * `origin` - The path the source is named by in the warnings, if it has one
+    pub fn parse_source(source: &str, origin: Option<&Path>) -> Parsed {
+        let file = origin.map_or(PathBuf::from("<source>"), |path| path.to_path_buf());
+        let mut warnings = Vec::new();
+
+        if let Some(line) = unclosed_comment(source) {
+            warnings.push((line, "the comment is not closed".to_string()));
+        }
+
+        let mut objects = build_ast(lex_contents(&source.to_string()), &mut warnings);
+        SourceLines::new(source).add_sources(&mut objects);
+        warnings.sort_by_key(|(line, _)| *line);
+
+        verbose!("Parsed {}", file.display());
+        debug!("{:#?}", objects);
+
+        Parsed {
+            objects,
+            warnings: warnings
+                .into_iter()
+                .map(|(line, message)| Warning {
+                    file: file.clone(),
+                    line,
+                    message,
+                }).collect(),
+        }
+    }
+
+    /// Leaves out the class without a name which the parser reads from source that
+    /// declares no type, e.g. an empty file or one with only a package and imports
+    pub fn declared_types(objects: Vec<ObjectType>) -> Vec<ObjectType> {
+        objects.into_iter().filter(|object| !object.name().is_empty()).collect()
+    }
+
+    /// Parses java source code which is not read from a file, such as a snippet kept in
+    /// a database or generated on the fly, and returns the types declared in it.
+    /// The warnings are printed with `-v`. Source which declares no type is a
+    /// `LojidocError::NoTypes`.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The java source code
+    /// * `origin` - The path the source is named by in parse warnings, if it has one
+    pub fn parse_str(source: &str, origin: Option<&Path>) -> Result<Vec<ObjectType>, LojidocError> {
+        let parsed = parse_source(source, origin);
+
+        for warning in parsed.warnings {
+            verbose!("{}", warning);
+        }
+
+        let objects = declared_types(parsed.objects);
+        if objects.is_empty() {
+            return Err(LojidocError::NoTypes {
+                file: origin.map_or(PathBuf::from("<source>"), |path| path.to_path_buf()),
+            });
+        }
+
+        Ok(objects)
+    }
+
+    /// Reads a java file with `decode_source`
     ///
     /// # Arguments
     ///
     /// * `path` - The path of the java file
+    /// * `charset` - The encoding of the file
+    /// * `warnings` - The warnings the ISO-8859-1 fallback is added to
+    pub fn read_source(path: &Path, charset: Charset, warnings: &mut Vec<Warning>) -> Result<String, LojidocError> {
+        let mut bytes = Vec::new();
+        File::open(path)
+            .and_then(|mut file| file.read_to_end(&mut bytes))
+            .map_err(|err| LojidocError::io(path, err))?;
+
+        Ok(decode_source(bytes, charset, path, warnings))
+    }
+
+    /// Root function of the module. Reads a java file with `read_source` and parses it
+    /// with `parse_str`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the java file
+    /// * `charset` - The encoding of the java file
     /// * `lint` - A bool representing whether the class's javadoc comments should be linted
-    pub fn parse_file(path: &Path, _lint: bool) -> ObjectType {
-        let file = File::open(path).expect("Could not open file");
-        let mut contents = String::new();
-        let mut buf = BufReader::new(file);
-        let res = buf.read_to_string(&mut contents);
-        if res.is_ok() {
-            let tokens = lex_contents(&contents);
-            construct_ast(tokens)
-        } else {
-            println!("Unable to read file");
-            ObjectType::Class(Class::new())
+    pub fn parse_file(path: &Path, charset: Charset, _lint: bool) -> Result<Vec<ObjectType>, LojidocError> {
+        let mut warnings = Vec::new();
+        let source = read_source(path, charset, &mut warnings)?;
+        for warning in warnings {
+            verbose!("{}", warning);
+        }
+
+        parse_str(&source, Some(path))
+    }
+
+    /// Reads the package and its javadoc from a `package-info.java` file. The package
+    /// has no members.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the `package-info.java` file
+    /// * `charset` - The encoding of the file
+    /// * `warnings` - The warnings of the run, which the warnings of the file are added to
+    pub fn parse_package_info(
+        path: &Path,
+        charset: Charset,
+        warnings: &mut Vec<Warning>,
+    ) -> Result<Package, LojidocError> {
+        let mut package = Package {
+            name: String::new(),
+            description: String::new(),
+            members: Vec::new(),
+        };
+
+        // A file without a type is parsed as a single class without a name
+        let parsed = parse_source(&read_source(path, charset, warnings)?, Some(path));
+        if let Some(ObjectType::Class(class)) = parsed.objects.into_iter().next() {
+            package.name = class.package_name;
+            package.description = class.description;
+        }
+
+        Ok(package)
+    }
+
+    /// Reads a javadoc comment which is not part of a type, such as the javadoc of a
+    /// module declaration, like `build_ast` reads the javadocs of declarations
+    fn read_javadoc(comment: &str) -> Doc {
+        let mut doc_tokens = Vec::new();
+
+        for token in lex_contents(&comment.to_string()) {
+            match token {
+                Token::Symbol(ref word) | Token::Keyword(ref word) if word == "/**" || word == "*/" => {}
+                Token::Symbol(word) | Token::Keyword(word) => {
+                    if is_keyword!(word, get_jdoc_keywords()) {
+                        doc_tokens.push(JdocToken::Keyword(word));
+                    } else {
+                        doc_tokens.push(JdocToken::Symbol(word));
+                    }
+                }
+                Token::LineNumber(_) if !doc_tokens.is_empty() => {
+                    doc_tokens.push(JdocToken::Symbol("\n".to_string()));
+                }
+                _ => {}
+            }
+        }
+
+        get_doc(&doc_tokens)
+    }
+
+    /// Reads a directive of a module declaration from its words without the `;`
+    fn get_module_directive(words: &[&str]) -> Option<ModuleDirective> {
+        let (kind, rest) = words.split_first()?;
+        let mut directive = ModuleDirective {
+            kind: kind.to_string(),
+            modifiers: Vec::new(),
+            name: String::new(),
+            targets: Vec::new(),
+        };
+
+        match *kind {
+            "requires" => {
+                let (name, modifiers) = rest.split_last()?;
+                directive.name = name.to_string();
+                directive.modifiers = modifiers.iter().map(|word| word.to_string()).collect();
+            }
+            "exports" | "opens" | "provides" => {
+                let separator = if *kind == "provides" { "with" } else { "to" };
+                let (name, targets) = rest.split_first()?;
+
+                directive.name = name.to_string();
+                directive.targets = targets
+                    .iter()
+                    .skip_while(|&&word| word == separator)
+                    .filter(|&&word| word != ",")
+                    .map(|word| word.to_string())
+                    .collect();
+            }
+            "uses" => directive.name = rest.first()?.to_string(),
+            _ => return None,
+        }
+
+        Some(directive)
+    }
+
+    /// Parses the source of a `module-info.java` file and returns the module it declares,
+    /// or `None` when it does not declare one
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The java source code
+    /// * `origin` - The path the source is named by in parse warnings
+    pub fn parse_module_str(source: &str, origin: &Path) -> Option<Module> {
+        let (classes, _) = classify_source(source);
+        let chars: Vec<char> = source.chars().collect();
+        // The words of the code with the character they start at, comments and
+        // literals are left out
+        let mut words: Vec<(String, usize)> = Vec::new();
+        let mut word = String::new();
+
+        for (i, (&ch, &class)) in chars.iter().zip(classes.iter()).enumerate() {
+            let separator = ch.is_whitespace() || ";{},()".contains(ch);
+
+            if (separator || class != Text::Code) && !word.is_empty() {
+                words.push((word.clone(), i - word.chars().count()));
+                word.clear();
+            }
+            if class == Text::Code && separator && !ch.is_whitespace() {
+                words.push((ch.to_string(), i));
+            } else if class == Text::Code && !separator {
+                word.push(ch);
+            }
+        }
+        if !word.is_empty() {
+            words.push((word.clone(), chars.len() - word.chars().count()));
+        }
+
+        // The declaration is `module` followed by the name and the body, which comes
+        // after the imports and the annotations of the module
+        let mut depth = 0;
+        let mut start = None;
+        for i in 0..words.len() {
+            match words[i].0.as_str() {
+                "(" => depth += 1,
+                ")" => depth -= 1,
+                "module" if depth == 0 && words.get(i + 2).is_some_and(|(word, _)| word == "{") => {
+                    start = Some(i);
+                    break;
+                }
+                _ => {}
+            }
         }
+        let start = start?;
+        let mut module = Module {
+            name: words[start + 1].0.clone(),
+            description: String::new(),
+            open: start > 0 && words[start - 1].0 == "open",
+            directives: Vec::new(),
+        };
+
+        // The javadoc of the module is the last one after the imports
+        let imports_end = words[..start]
+            .iter()
+            .rposition(|(word, _)| word == ";")
+            .map_or(0, |i| words[i].1 + 1);
+        let mut comment_start = None;
+        let mut javadoc = None;
+        for i in imports_end..words[start].1 {
+            if classes[i] != Text::BlockComment {
+                comment_start = None;
+            } else if comment_start.is_none() {
+                comment_start = Some(i);
+            }
+            if let Some(comment) = comment_start {
+                if chars[i] == '/' && i > comment + 2 && chars[i - 1] == '*' {
+                    javadoc = Some((comment, i + 1));
+                    comment_start = None;
+                }
+            }
+        }
+        if let Some((comment_start, comment_end)) = javadoc {
+            let comment: String = chars[comment_start..comment_end].iter().collect();
+
+            if comment.starts_with("/**") && comment != "/**/" {
+                module.description = read_javadoc(&comment).description;
+            }
+        }
+
+        let mut directive: Vec<&str> = Vec::new();
+        for (word, _) in words[start + 3..].iter() {
+            match word.as_str() {
+                "}" => break,
+                ";" => {
+                    match get_module_directive(&directive) {
+                        Some(parsed) => module.directives.push(parsed),
+                        None => verbose!("{}: Unknown module directive {}", origin.display(), directive.join(" ")),
+                    }
+                    directive.clear();
+                }
+                _ => directive.push(word),
+            }
+        }
+
+        verbose!("Parsed {}", origin.display());
+        Some(module)
+    }
+
+    /// Reads the module declared in a `module-info.java` file with its javadoc and
+    /// directives
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the `module-info.java` file
+    /// * `charset` - The encoding of the file
+    /// * `warnings` - The warnings of the run, which the warnings of the file are added to
+    ///
+    /// Returns `None` when the file does not declare a module
+    pub fn parse_module_info(
+        path: &Path,
+        charset: Charset,
+        warnings: &mut Vec<Warning>,
+    ) -> Result<Option<Module>, LojidocError> {
+        Ok(parse_module_str(&read_source(path, charset, warnings)?, path))
     }
 }
 
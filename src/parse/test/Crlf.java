@@ -0,0 +1,23 @@
+﻿package com.example;
+
+import java.util.List;
+
+/**
+ * Keeps the orders of a customer
+ */
+public class Orders {
+    /**
+     * The number of orders
+     */
+    public int count;
+
+    /**
+     * Finds the orders
+     *
+     * @param customer The name of the customer
+     * @return The orders
+     */
+    public List<String> find(String customer) {
+        return null;
+    }
+}
@@ -0,0 +1,36 @@
+package com.example;
+
+/**
+ * Keeps java source in strings
+ */
+public class Literals {
+    /** The source of a class in a string */
+    public static final String SOURCE = "public class Fake {";
+
+    /** The character which opens a block */
+    public static final char OPEN = '{';
+
+    // public void commented(int count) {
+    // }
+
+    /*
+     * public void blockCommented(String name) {
+     * }
+     */
+
+    /**
+     * Runs the source
+     *
+     * @param times How many times the source is run
+     */
+    public void run(int times) {
+        /** Not the javadoc of a declaration */
+        String inner = "class Inner { void fake() {} }";
+        char quote = '"';
+    }
+
+    /** Gets the source */
+    public String source() {
+        return SOURCE;
+    }
+}
@@ -0,0 +1,39 @@
+package com.example;
+
+/**
+ * A shape which is drawn on a canvas
+ */
+public abstract class Shape {
+    /**
+     * Gets the area of the shape
+     *
+     * @param scale The scale the shape is drawn at
+     * @return The area
+     * @throws IllegalStateException When the shape has no points
+     */
+    public abstract double area(double scale) throws IllegalStateException;
+
+    /**
+     * Draws the shape
+     *
+     * @param canvas The canvas the shape is drawn on
+     * @param color The color of the outline
+     */
+    protected abstract void draw(Canvas canvas, Color color);
+
+    /**
+     * Hashes the points of the shape
+     *
+     * @return The hash
+     */
+    public native int hash();
+
+    /**
+     * Gets the name of the shape
+     *
+     * @return The name
+     */
+    public String name() {
+        return getClass().getSimpleName();
+    }
+}
@@ -0,0 +1,64 @@
+package com.example;
+
+/**
+ * Builds the test data
+ */
+public class TextBlocks {
+    /** A template with a fake class */
+    public static final String TEMPLATE = """
+        /** Not a javadoc */
+/** Not a javadoc either
+        public class Fake {
+            void run() { }
+        }
+        """;
+
+    /**
+     * Builds the json of an order
+     *
+     * @return The json
+     */
+    public String order() {
+        return """
+            {
+              "id": 42,
+              "class": "Order",
+              "note": "a \"""quoted\""" block",
+              "items": [
+                {
+                  "sku": "A-1",
+                  "text": "/** not a javadoc */",
+                  "nested": { "open": "{", "close": "}" }
+                },
+                {
+                  "sku": "B-2",
+                  "text": "interface Fake { void x(); }",
+                  "escaped": \"""
+                },
+                {
+                  "sku": "C-3",
+                  "comment": "// not a comment",
+                  "block": "/* not a comment */"
+                }
+              ],
+              "customer": {
+                "name": "Jim",
+                "enum": "enum Kind { A, B }"
+              },
+              "total": 12.5,
+              "tags": ["a", "b"],
+              "empty": {}
+            }
+            """;
+    }
+
+    /**
+     * Gets the id of the order
+     *
+     * @param json The json of the order
+     * @return The id
+     */
+    public int id(String json) {
+        return 42;
+    }
+}
@@ -0,0 +1,35 @@
+package com.example;
+
+/**
+   Builds widgets
+   for the toolbar.
+
+   @author Jim
+ */
+public class Widgets {
+    /**
+       Builds the widget.
+       @param name the widget name
+       @return the widget
+     */
+    public Widget build(String name) {
+        return null;
+    }
+
+    /**
+	 *	Removes the widget.
+	 *
+	 *	@param name	the widget name
+	 */
+    public void remove(String name) {
+    }
+
+    /** Does the thing. */
+    public void run() {
+    }
+
+    /**Stops the widgets.
+     *@param force whether running widgets are stopped*/
+    public void stop(boolean force) {
+    }
+}
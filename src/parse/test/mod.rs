@@ -1,5 +1,7 @@
 use grammar::grammar::Token;
 use model::model::*;
+use error::error::LojidocError;
+use error::error::Warning;
 use parse::parse::*;
 
 #[test]
@@ -122,3 +124,1753 @@ fn test_doc_lex() {
     assert_eq!(Token::Symbol(String::from("The")), tokens[23]);
     assert_eq!(Token::Symbol(String::from("value")), tokens[24]);
 }
+
+#[test]
+fn test_interface_parse() {
+    let j_inter = "// Copyright notice
+                   package com.example;
+
+                   /**
+                    * The service interface for users
+                    */
+                   public interface UserService {
+                       /**
+                        * Finds a user
+                        *
+                        * @param id The user id
+                        */
+                       User find(long id);
+
+                       /** Lists every public user */
+                       List<User> all();
+                   }";
+
+    let tokens = lex_contents(&j_inter.to_string());
+
+    match construct_ast(tokens).remove(0) {
+        ObjectType::Interface(inter) => {
+            assert_eq!("UserService", inter.name);
+            assert_eq!("com.example", inter.package_name);
+            assert_eq!("The service interface for users", inter.description.trim());
+            assert_eq!(2, inter.methods.len());
+            assert_eq!("find", inter.methods[0].name);
+            assert_eq!("public", inter.methods[0].privacy);
+            assert_eq!("Finds a user", inter.methods[0].description.trim());
+            assert_eq!("The user id", inter.methods[0].parameters[0].desc.trim());
+            assert_eq!("all", inter.methods[1].name);
+            assert_eq!("Lists every public user", inter.methods[1].description.trim());
+        }
+        _ => panic!("Expected an interface"),
+    }
+}
+
+#[test]
+fn test_enum_parse() {
+    let j_enum = "package com.example;
+
+                  /**
+                   * The supported colors
+                   */
+                  public enum Color {
+                      /** The red color */
+                      RED(\"#ff0000\", 1),
+                      /** The green color */
+                      GREEN(\"#00ff00\", 2),
+                      BLUE(\"#0000ff\", 3);
+
+                      private final String hex;
+
+                      /**
+                       * Gets the hex value
+                       *
+                       * @return The hex value
+                       */
+                      public String getHex() {
+                          return hex;
+                      }
+                  }";
+
+    let tokens = lex_contents(&j_enum.to_string());
+
+    match construct_ast(tokens).remove(0) {
+        ObjectType::Enumeration(enumeration) => {
+            assert_eq!("Color", enumeration.name);
+            assert_eq!(3, enumeration.fields.len());
+            assert_eq!("RED", enumeration.fields[0].name);
+            assert_eq!("\"#ff0000\", 1", enumeration.fields[0].value);
+            assert_eq!("The red color", enumeration.fields[0].desc);
+            assert_eq!("GREEN", enumeration.fields[1].name);
+            assert_eq!("The green color", enumeration.fields[1].desc);
+            assert_eq!("BLUE", enumeration.fields[2].name);
+            assert_eq!("", enumeration.fields[2].desc);
+            assert_eq!(1, enumeration.variables.len());
+            assert_eq!("hex", enumeration.variables[0].name);
+            assert_eq!(1, enumeration.methods.len());
+            assert_eq!("getHex", enumeration.methods[0].name);
+        }
+        _ => panic!("Expected an enum"),
+    }
+}
+
+#[test]
+fn test_method_exceptions() {
+    let j_class = "public class Reader {
+                       /**
+                        * Reads the data
+                        *
+                        * @param path The path to read
+                        * @throws IOException when the socket closes
+                        * @throws IllegalStateException if the reader is closed
+                        */
+                       public String read(String path) throws java.sql.SQLException, IOException {
+                           return null;
+                       }
+                   }";
+
+    let tokens = lex_contents(&j_class.to_string());
+
+    match construct_ast(tokens).remove(0) {
+        ObjectType::Class(class) => {
+            let exceptions = &class.methods[0].exceptions;
+
+            assert_eq!(3, exceptions.len());
+            assert_eq!("java.sql.SQLException", exceptions[0].exception_type);
+            assert_eq!("", exceptions[0].desc);
+            assert_eq!("IOException", exceptions[1].exception_type);
+            assert_eq!("when the socket closes", exceptions[1].desc);
+            assert_eq!("IllegalStateException", exceptions[2].exception_type);
+            assert_eq!("if the reader is closed", exceptions[2].desc);
+        }
+        _ => panic!("Expected a class"),
+    }
+}
+
+#[test]
+fn test_method_return() {
+    let j_class = "public class User {
+                       /**
+                        * Gets the name
+                        *
+                        * @return the user's display name
+                        *         which is never null
+                        */
+                       public String getName() {
+                           return name;
+                       }
+
+                       /**
+                        * Clears the name
+                        *
+                        * @return nothing at all
+                        */
+                       public void clear() {
+                           name = null;
+                       }
+
+                       public int getAge() {
+                           return age;
+                       }
+                   }";
+
+    let tokens = lex_contents(&j_class.to_string());
+
+    match construct_ast(tokens).remove(0) {
+        ObjectType::Class(class) => {
+            assert_eq!("String", class.methods[0].return_type);
+            assert_eq!("the user's display name which is never null", class.methods[0].return_desc);
+            assert_eq!("void", class.methods[1].return_type);
+            assert_eq!("nothing at all", class.methods[1].return_desc);
+            assert_eq!("int", class.methods[2].return_type);
+            assert_eq!("", class.methods[2].return_desc);
+        }
+        _ => panic!("Expected a class"),
+    }
+}
+
+#[test]
+fn test_multi_line_doc() {
+    let j_class = "public class Client {
+                       /**
+                        * Sends a request to the server,
+                        * retrying (with backoff) when the
+                        * {@code config} allows it.
+                        *
+                        * @param config the configuration object
+                        *        used to control retry behaviour,
+                        *        never null
+                        * @param timeout the timeout
+                        *        in milliseconds for
+                        *        each attempt
+                        * @return the response sent
+                        *         by the server, or an empty
+                        *         response on failure
+                        */
+                       public Response send(Config config, int timeout) {
+                           return null;
+                       }
+                   }";
+
+    let tokens = lex_contents(&j_class.to_string());
+
+    match construct_ast(tokens).remove(0) {
+        ObjectType::Class(class) => {
+            let method = &class.methods[0];
+
+            assert_eq!(
+                "Sends a request to the server, retrying (with backoff) when the {@code config} allows it.",
+                method.description
+            );
+            assert_eq!(
+                "the configuration object used to control retry behaviour, never null",
+                method.parameters[0].desc
+            );
+            assert_eq!(
+                "the timeout in milliseconds for each attempt",
+                method.parameters[1].desc
+            );
+            assert_eq!(
+                "the response sent by the server, or an empty response on failure",
+                method.return_desc
+            );
+        }
+        _ => panic!("Expected a class"),
+    }
+}
+
+#[test]
+fn test_constructor_parse() {
+    let j_class = "public class Widget {
+                       /**
+                        * Creates a widget
+                        *
+                        * @param name The widget name
+                        */
+                       public Widget(String name) {
+                           this.name = name;
+                       }
+
+                       Widget() {
+                           this(null);
+                       }
+
+                       /**
+                        * Builds a widget
+                        */
+                       public static Widget build() {
+                           return new Widget();
+                       }
+                   }";
+
+    let tokens = lex_contents(&j_class.to_string());
+
+    match construct_ast(tokens).remove(0) {
+        ObjectType::Class(class) => {
+            assert_eq!(2, class.constructors.len());
+            assert_eq!("Widget", class.constructors[0].name);
+            assert_eq!("public", class.constructors[0].privacy);
+            assert_eq!("Creates a widget", class.constructors[0].description);
+            assert_eq!("name", class.constructors[0].parameters[0].name);
+            assert_eq!("String", class.constructors[0].parameters[0].var_type);
+            assert_eq!("The widget name", class.constructors[0].parameters[0].desc);
+            assert_eq!("", class.constructors[1].privacy);
+            assert_eq!(1, class.methods.len());
+            assert_eq!("build", class.methods[0].name);
+            assert_eq!("Widget", class.methods[0].return_type);
+        }
+        _ => panic!("Expected a class"),
+    }
+}
+
+#[test]
+fn test_field_parse() {
+    let j_class = "public class Config {
+                       /** How many times a request is retried */
+                       public static final int MAX_RETRIES = 5;
+
+                       protected Map<String, Integer> counts = new HashMap<>();
+
+                       int plain;
+
+                       public void run() {
+                           int local = 3;
+                       }
+                   }";
+
+    let tokens = lex_contents(&j_class.to_string());
+
+    match construct_ast(tokens).remove(0) {
+        ObjectType::Class(class) => {
+            assert_eq!(3, class.variables.len());
+            assert_eq!("MAX_RETRIES", class.variables[0].name);
+            assert_eq!("int", class.variables[0].var_type);
+            assert_eq!("public", class.variables[0].access);
+            assert_eq!(vec!["static", "final"], class.variables[0].modifiers);
+            assert_eq!("5", class.variables[0].value);
+            assert_eq!("How many times a request is retried", class.variables[0].desc);
+            assert_eq!("3", class.variables[0].line_num);
+            assert_eq!("counts", class.variables[1].name);
+            assert_eq!("Map<String, Integer>", class.variables[1].var_type);
+            assert_eq!("", class.variables[1].value);
+            assert_eq!("plain", class.variables[2].name);
+            assert_eq!("", class.variables[2].access);
+            assert_eq!(1, class.methods.len());
+        }
+        _ => panic!("Expected a class"),
+    }
+}
+
+#[test]
+fn test_multiple_field_declarators() {
+    let j_class = "public class Grid {
+                       /** The size of the grid */
+                       public static final int WIDTH = 4, HEIGHT = max(2, 3);
+
+                       private Map<String, Integer> rows = new HashMap<>(), cols;
+
+                       String label = \"a, b\", hint = format(\"%s, %s\", x, y);
+
+                       int[] cells = {1, 2}, empty;
+
+                       boolean wide = WIDTH < HEIGHT, tall = WIDTH > HEIGHT;
+                   }";
+
+    let tokens = lex_contents(&j_class.to_string());
+
+    match construct_ast(tokens).remove(0) {
+        ObjectType::Class(class) => {
+            let names: Vec<&str> = class.variables.iter().map(|var| var.name.as_str()).collect();
+            assert_eq!(
+                vec!["WIDTH", "HEIGHT", "rows", "cols", "label", "hint", "cells", "empty", "wide", "tall"],
+                names
+            );
+            assert_eq!("int", class.variables[1].var_type);
+            assert_eq!("max(2, 3)", class.variables[1].value);
+            assert_eq!(vec!["static", "final"], class.variables[1].modifiers);
+            assert_eq!("The size of the grid", class.variables[1].desc);
+            assert_eq!("Map<String, Integer>", class.variables[3].var_type);
+            assert_eq!("private", class.variables[3].access);
+            assert_eq!("int[]", class.variables[7].var_type);
+            assert_eq!("boolean", class.variables[9].var_type);
+        }
+        _ => panic!("Expected a class"),
+    }
+}
+
+#[test]
+fn test_generic_parse() {
+    let j_class = "/**
+                   * A small cache
+                   *
+                   * @param <K> the key type
+                   */
+                   public class Cache<K, V extends Comparable<V>> {
+                       /**
+                        * Finds the largest
+                        *
+                        * @param <T> the element type
+                        * @param items the items to search
+                        */
+                       public static <T extends Comparable<T>> T max(Map<K, T> items) {
+                           return null;
+                       }
+                   }";
+
+    let tokens = lex_contents(&j_class.to_string());
+
+    match construct_ast(tokens).remove(0) {
+        ObjectType::Class(class) => {
+            assert_eq!("Cache", class.name);
+            assert_eq!(2, class.type_params.len());
+            assert_eq!("K", class.type_params[0].name);
+            assert_eq!("the key type", class.type_params[0].desc);
+            assert_eq!("V", class.type_params[1].name);
+            assert_eq!("Comparable<V>", class.type_params[1].var_type);
+            assert_eq!("max", class.methods[0].name);
+            assert_eq!("T", class.methods[0].return_type);
+            assert_eq!("T", class.methods[0].type_params[0].name);
+            assert_eq!("Comparable<T>", class.methods[0].type_params[0].var_type);
+            assert_eq!("the element type", class.methods[0].type_params[0].desc);
+            assert_eq!(1, class.methods[0].parameters.len());
+            assert_eq!("Map<K, T>", class.methods[0].parameters[0].var_type);
+            assert_eq!("the items to search", class.methods[0].parameters[0].desc);
+        }
+        _ => panic!("Expected a class"),
+    }
+}
+
+#[test]
+fn test_generic_param_commas() {
+    let j_class = "public class Index<K, V, R> {
+                       /**
+                        * Puts the values
+                        *
+                        * @param index The index
+                        * @param fn The function
+                        * @param groups The groups
+                        * @param limit The limit
+                        */
+                       void put(Map<String, List<Integer>> index, BiFunction<K, V, R> fn,
+                               Map<String,? extends Collection<?>> groups,
+                               @Range(min = (1), max = 10) int limit) {
+                       }
+                   }";
+
+    let tokens = lex_contents(&j_class.to_string());
+
+    match construct_ast(tokens).remove(0) {
+        ObjectType::Class(class) => {
+            let params = &class.methods[0].parameters;
+            assert_eq!(
+                vec![
+                    "Map<String, List<Integer>>",
+                    "BiFunction<K, V, R>",
+                    "Map<String, ? extends Collection<?>>",
+                    "int",
+                ],
+                params.iter().map(|p| p.var_type.as_str()).collect::<Vec<_>>()
+            );
+            assert_eq!(
+                vec!["index", "fn", "groups", "limit"],
+                params.iter().map(|p| p.name.as_str()).collect::<Vec<_>>()
+            );
+            assert_eq!("The limit", params[3].desc);
+        }
+        _ => panic!("Expected a class"),
+    }
+}
+
+#[test]
+fn test_inner_class_parse() {
+    let j_class = "public class Outer {
+                       public static Builder builder() {
+                           return new Builder();
+                       }
+
+                       /**
+                        * Builds outers
+                        */
+                       public static class Builder {
+                           private int size;
+
+                           public Builder size(int size) {
+                               this.size = size;
+                               return this;
+                           }
+
+                           public Outer build() {
+                               return new Outer();
+                           }
+                       }
+
+                       private enum Mode { FAST, SLOW }
+
+                       public void run() {
+                       }
+                   }";
+
+    let tokens = lex_contents(&j_class.to_string());
+
+    match construct_ast(tokens).remove(0) {
+        ObjectType::Class(class) => {
+            assert_eq!("Outer", class.name);
+            assert_eq!(2, class.methods.len());
+            assert_eq!("builder", class.methods[0].name);
+            assert_eq!("run", class.methods[1].name);
+            assert_eq!(0, class.variables.len());
+            assert_eq!(2, class.inner_classes.len());
+
+            match class.inner_classes[0] {
+                ObjectType::Class(ref builder) => {
+                    assert_eq!("Builder", builder.name);
+                    assert_eq!("Builds outers", builder.description);
+                    assert_eq!(1, builder.variables.len());
+                    assert_eq!(2, builder.methods.len());
+                    assert_eq!("size", builder.methods[0].name);
+                    assert_eq!("Builder", builder.methods[0].return_type);
+                    assert_eq!("build", builder.methods[1].name);
+                }
+                _ => panic!("Expected an inner class"),
+            }
+
+            match class.inner_classes[1] {
+                ObjectType::Enumeration(ref mode) => {
+                    assert_eq!("Mode", mode.name);
+                    assert_eq!(2, mode.fields.len());
+                }
+                _ => panic!("Expected an inner enum"),
+            }
+        }
+        _ => panic!("Expected a class"),
+    }
+}
+
+#[test]
+fn test_nested_type_access() {
+    let source = "package com.foo;
+
+                  interface Shapes {
+                      class Circle {
+                      }
+
+                      enum Kind { ROUND, SQUARE }
+
+                      @interface Marker {
+                      }
+                  }
+
+                  class Helper {
+                      static class Cache {
+                      }
+                  }";
+
+    let mut types = parse_str(source, None).unwrap();
+    let helper = types.remove(1);
+    let shapes = types.remove(0);
+
+    match shapes {
+        ObjectType::Interface(shapes) => {
+            // The interface declares no access, its nested types are public
+            assert_eq!("", shapes.access);
+            let access: Vec<&str> = shapes.inner_classes.iter().map(type_access).collect();
+            assert_eq!(vec!["public", "public", "public"], access);
+        }
+        _ => panic!("Expected an interface"),
+    }
+    match helper {
+        ObjectType::Class(helper) => {
+            assert_eq!("", helper.access);
+            assert_eq!("", type_access(&helper.inner_classes[0]));
+        }
+        _ => panic!("Expected a class"),
+    }
+}
+
+/// Gets the access of a parsed type
+fn type_access(object: &ObjectType) -> &str {
+    match object {
+        ObjectType::Class(class) => &class.access,
+        ObjectType::Interface(inter) => &inter.access,
+        ObjectType::Enumeration(enumeration) => &enumeration.access,
+        ObjectType::Annotation(annotation) => &annotation.access,
+    }
+}
+
+#[test]
+fn test_multiple_class_parse() {
+    let j_file = "package com.example;
+
+                  import java.util.List;
+
+                  /**
+                   * The public class
+                   */
+                  public class Registry {
+                      public void register(Entry entry) {
+                      }
+                  }
+
+                  class Entry {
+                      private String name;
+                  }
+
+                  /** Listens for entries */
+                  interface EntryListener {
+                      void added(Entry entry);
+                  }";
+
+    let tokens = lex_contents(&j_file.to_string());
+    let objects = construct_ast(tokens);
+
+    assert_eq!(3, objects.len());
+
+    match objects[0] {
+        ObjectType::Class(ref class) => {
+            assert_eq!("Registry", class.name);
+            assert_eq!("The public class", class.description);
+            assert_eq!("register", class.methods[0].name);
+        }
+        _ => panic!("Expected a class"),
+    }
+
+    match objects[1] {
+        ObjectType::Class(ref class) => {
+            assert_eq!("Entry", class.name);
+            assert_eq!("", class.access);
+            assert_eq!("com.example", class.package_name);
+            assert_eq!(vec!["java.util.List"], class.dependencies);
+            assert_eq!("name", class.variables[0].name);
+            assert_eq!(0, class.methods.len());
+        }
+        _ => panic!("Expected a class"),
+    }
+
+    match objects[2] {
+        ObjectType::Interface(ref inter) => {
+            assert_eq!("EntryListener", inter.name);
+            assert_eq!("Listens for entries", inter.description);
+            assert_eq!("added", inter.methods[0].name);
+        }
+        _ => panic!("Expected an interface"),
+    }
+}
+
+#[test]
+fn test_html_to_markdown() {
+    assert_eq!(
+        "First.\n\nSecond with **bold** and `code` for a List<String>.",
+        html_to_markdown("First. <p> Second with <b>bold</b> and <code>code</code> for a List<String>.")
+    );
+    assert_eq!(
+        "Items:\n\n- one\n- two",
+        html_to_markdown("Items: <ul> <li>one</li> <li><span>two</span></li> </ul>")
+    );
+    assert_eq!(
+        "Example:\n\n```java\nFoo f = new Foo();\nf.run(\"a<b\");\n```",
+        html_to_markdown("Example: <pre>{@code \n Foo f = new Foo(); \n f.run(\"a<b\"); \n}</pre>")
+    );
+}
+
+#[test]
+fn test_raw_doc_text() {
+    assert_eq!(
+        "Clamps to max_value and MIN_VALUE, 2 * 3 \\ 2.",
+        html_to_markdown("Clamps to max_value and MIN_VALUE, 2 * 3 \\ 2.")
+    );
+    assert_eq!(
+        "Options:\n- first\n- second",
+        html_to_markdown("Options:\n * first\n * second")
+    );
+    assert_eq!("# TODO fix #12", html_to_markdown("# TODO fix #12"));
+    assert_eq!(
+        "Maps a Map<String, List<Foo>> to `List<Foo>`",
+        html_to_markdown("Maps a Map<String, List<Foo>> to <code>List<Foo></code>")
+    );
+    assert_eq!(
+        "Uses {@code a_b * c<d>} and {@link Foo#max_value}",
+        html_to_markdown("Uses {@code a_b * c<d>} and {@link Foo#max_value}")
+    );
+    assert_eq!(
+        "Reads \\*.java, `a*b`, \\`c\\` and \\[a](b) from C:\\\\\\*",
+        html_to_markdown("Reads *.java, <code>a*b</code>, `c` and [a](b) from C:\\*")
+    );
+}
+
+#[test]
+fn test_unknown_html_tags() {
+    assert_eq!(
+        "A tagged word for a List<T> where 1 < 2",
+        html_to_markdown("A <custom kind=\"x\">tagged</custom> word for a List<T> where 1 < 2")
+    );
+    assert_eq!(
+        "```java\nif (ready) {\n    run();\n}\n```",
+        html_to_markdown("<pre>{@code\n  if (ready) {\n      run();\n  }\n}</pre>")
+    );
+}
+
+#[test]
+fn test_html_javadoc_parse() {
+    let j_class = "/**
+                   * Parses things.
+                   * <p>
+                   * Second paragraph.
+                   * <pre>
+                   *   int x = 1;
+                   *   int y = 2;
+                   * </pre>
+                   */
+                   public class Html {
+                   }";
+
+    let tokens = lex_contents(&j_class.to_string());
+
+    match construct_ast(tokens).remove(0) {
+        ObjectType::Class(class) => {
+            assert_eq!(
+                "Parses things.\n\nSecond paragraph.\n\n```java\nint x = 1;\nint y = 2;\n```",
+                class.description
+            );
+        }
+        _ => panic!("Expected a class"),
+    }
+}
+
+#[test]
+fn test_preformatted_javadoc_parse() {
+    let j_class = "/**
+                   * Multiplies 2 * 3 for a <custom>List</custom>.
+                   * <pre>{@code
+                   * if (ready) {
+                   *     run();
+                   * }
+                   * }</pre>
+                   */
+                   public class Pre {
+                   }";
+
+    let tokens = lex_contents(&j_class.to_string());
+
+    match construct_ast(tokens).remove(0) {
+        ObjectType::Class(class) => {
+            assert_eq!(
+                "Multiplies 2 * 3 for a List.\n\n```java\nif (ready) {\n    run();\n}\n```",
+                class.description
+            );
+        }
+        _ => panic!("Expected a class"),
+    }
+}
+
+#[test]
+fn test_deprecated_parse() {
+    let j_class = "/**
+                   * Old stuff
+                   * @deprecated use Cache instead
+                   */
+                   @Deprecated
+                   public class Old {
+                       /**
+                        * Runs
+                        * @deprecated
+                        */
+                       public void run() {}
+
+                       @Deprecated(since = \"9\")
+                       public void walk() {}
+
+                       public void fly() {}
+                   }";
+
+    let tokens = lex_contents(&j_class.to_string());
+
+    match construct_ast(tokens).remove(0) {
+        ObjectType::Class(class) => {
+            assert_eq!(Some("use Cache instead".to_string()), class.deprecated);
+            assert_eq!(Some(String::new()), class.methods[0].deprecated);
+            assert_eq!(Some(String::new()), class.methods[1].deprecated);
+            assert_eq!(None, class.methods[2].deprecated);
+        }
+        _ => panic!("Expected a class"),
+    }
+}
+
+#[test]
+fn test_author_since_version_parse() {
+    let j_class = "/**
+                   * Reads configuration files
+                   * @author Jane Doe
+                   * @author John Smith
+                   * @since 2.3
+                   * @version 1.4.0
+                   */
+                   public class Reader {
+                       /**
+                        * Reads a file
+                        * @since 2.4
+                        */
+                       public void read() {}
+                   }";
+
+    let tokens = lex_contents(&j_class.to_string());
+
+    match construct_ast(tokens).remove(0) {
+        ObjectType::Class(class) => {
+            assert_eq!("Jane Doe, John Smith", class.author);
+            assert_eq!("2.3", class.since);
+            assert_eq!("1.4.0", class.version);
+            assert_eq!("2.4", class.methods[0].since);
+        }
+        _ => panic!("Expected a class"),
+    }
+}
+
+#[test]
+fn test_inheritance_parse() {
+    let j_class = "public class JsonParser<T> extends AbstractParser<T>
+                       implements Closeable,
+                                  Comparable<Money> {
+                   }";
+
+    let tokens = lex_contents(&j_class.to_string());
+
+    match construct_ast(tokens).remove(0) {
+        ObjectType::Class(class) => {
+            assert_eq!("JsonParser", class.name);
+            assert_eq!("AbstractParser<T>", class.parent);
+            assert_eq!(vec!["Closeable", "Comparable<Money>"], class.interfaces);
+        }
+        _ => panic!("Expected a class"),
+    }
+}
+
+#[test]
+fn test_interface_inheritance_parse() {
+    let j_inter = "public sealed interface Shape extends Comparable<Shape>, Serializable
+                       permits Circle, Square {
+                   }";
+    let j_class = "public sealed class Base extends Root implements Cloneable permits Leaf {
+                   }";
+
+    match construct_ast(lex_contents(&j_inter.to_string())).remove(0) {
+        ObjectType::Interface(inter) => {
+            assert_eq!(vec!["Comparable<Shape>", "Serializable"], inter.extends);
+        }
+        _ => panic!("Expected an interface"),
+    }
+    match construct_ast(lex_contents(&j_class.to_string())).remove(0) {
+        ObjectType::Class(class) => {
+            assert_eq!("Root", class.parent);
+            assert_eq!(vec!["Cloneable"], class.interfaces);
+        }
+        _ => panic!("Expected a class"),
+    }
+}
+
+#[test]
+fn test_modifier_parse() {
+    let j_class = "public abstract class Shape {
+                       /** Area of the shape */
+                       public abstract double area();
+
+                       protected abstract void draw(Canvas canvas)
+                           throws IOException;
+
+                       public static synchronized Shape unit() {
+                           return null;
+                       }
+
+                       public final native int hash();
+                   }";
+
+    let tokens = lex_contents(&j_class.to_string());
+
+    match construct_ast(tokens).remove(0) {
+        ObjectType::Class(class) => {
+            assert_eq!(vec!["abstract"], class.modifiers);
+            assert_eq!(4, class.methods.len());
+            assert_eq!("area", class.methods[0].name);
+            assert_eq!(vec!["abstract"], class.methods[0].modifiers);
+            assert_eq!("Area of the shape", class.methods[0].description);
+            assert_eq!("draw", class.methods[1].name);
+            assert_eq!("IOException", class.methods[1].exceptions[0].exception_type);
+            assert_eq!(vec!["static", "synchronized"], class.methods[2].modifiers);
+            assert_eq!(vec!["final", "native"], class.methods[3].modifiers);
+        }
+        _ => panic!("Expected a class"),
+    }
+}
+
+#[test]
+fn test_annotation_parse() {
+    let j_class = "package com.example;
+
+                   import java.lang.annotation.*;
+
+                   /**
+                    * Marks audited methods
+                    */
+                   @Retention(RetentionPolicy.RUNTIME)
+                   @Target({ElementType.METHOD, ElementType.TYPE})
+                   public @interface Audited {
+                       /** The audit category */
+                       String value() default \"\";
+
+                       String[] tags() default {\"a\", \"b\"};
+
+                       int level();
+                   }";
+
+    let tokens = lex_contents(&j_class.to_string());
+
+    match construct_ast(tokens).remove(0) {
+        ObjectType::Annotation(annotation) => {
+            assert_eq!("Audited", annotation.name);
+            assert_eq!("com.example", annotation.package_name);
+            assert_eq!("Marks audited methods", annotation.description);
+            assert_eq!("RUNTIME", annotation.retention);
+            assert_eq!(vec!["METHOD", "TYPE"], annotation.targets);
+            assert_eq!(3, annotation.elements.len());
+            assert_eq!("value", annotation.elements[0].name);
+            assert_eq!("String", annotation.elements[0].var_type);
+            assert_eq!("\"\"", annotation.elements[0].value);
+            assert_eq!("The audit category", annotation.elements[0].desc);
+            assert_eq!("String[]", annotation.elements[1].var_type);
+            assert_eq!("{ ... }", annotation.elements[1].value);
+            assert_eq!("level", annotation.elements[2].name);
+            assert_eq!("", annotation.elements[2].value);
+        }
+        _ => panic!("Expected an annotation"),
+    }
+}
+
+#[test]
+fn test_record_parse() {
+    let j_class = "/**
+                   * A point on a plane
+                   * @param x the horizontal position
+                   * @param y the vertical position
+                   */
+                   public record Point(int x, int y) implements Comparable<Point> {
+                       /** Checks the bounds */
+                       public Point {
+                           if (x < 0) {
+                               throw new IllegalArgumentException();
+                           }
+                       }
+
+                       public double length() {
+                           return Math.sqrt(x * x + y * y);
+                       }
+
+                       public void record(String event) {}
+                   }
+
+                   record Range(long start, long end) {
+                       Range(long start, long end) {
+                           this.start = start;
+                           this.end = end;
+                       }
+                   }";
+
+    let tokens = lex_contents(&j_class.to_string());
+    let mut objects = construct_ast(tokens);
+
+    match objects.remove(0) {
+        ObjectType::Class(class) => {
+            assert!(class.record);
+            assert_eq!("Point", class.name);
+            assert_eq!(vec!["Comparable<Point>"], class.interfaces);
+            assert_eq!(2, class.components.len());
+            assert_eq!("x", class.components[0].name);
+            assert_eq!("int", class.components[0].var_type);
+            assert_eq!("the vertical position", class.components[1].desc);
+            assert_eq!(1, class.constructors.len());
+            assert_eq!("Checks the bounds", class.constructors[0].description);
+            assert_eq!(2, class.constructors[0].parameters.len());
+            assert_eq!(2, class.methods.len());
+            assert_eq!("length", class.methods[0].name);
+            assert_eq!("record", class.methods[1].name);
+        }
+        _ => panic!("Expected a record"),
+    }
+
+    match objects.remove(0) {
+        ObjectType::Class(class) => {
+            assert!(class.record);
+            assert_eq!(2, class.components.len());
+            assert_eq!(1, class.constructors.len());
+            assert!(class.methods.is_empty());
+        }
+        _ => panic!("Expected a record"),
+    }
+}
+
+#[test]
+fn test_parse_latin1_file() {
+    let path = std::env::temp_dir().join(format!("lojidoc-latin1-{}.java", std::process::id()));
+    let mut source = b"/** Caf".to_vec();
+    source.push(0xe9);
+    source.extend_from_slice(b" menu */\npublic class Cafe {\n}\n");
+    std::fs::write(&path, source).unwrap();
+
+    let result = parse_file(&path, Charset::Auto, false);
+    std::fs::remove_file(&path).unwrap();
+
+    match result.unwrap().remove(0) {
+        ObjectType::Class(class) => {
+            assert_eq!("Cafe", class.name);
+            assert_eq!("Café menu", class.description);
+        }
+        _ => panic!("Expected a class"),
+    }
+}
+
+#[test]
+fn test_decode_source() {
+    use std::path::Path;
+
+    let path = Path::new("Cafe.java");
+    let latin1 = b"/** Caf\xe9 */".to_vec();
+    let utf8 = "/** Café */".as_bytes().to_vec();
+
+    let mut warnings = Vec::new();
+
+    assert_eq!("/** Café */", decode_source(latin1.clone(), Charset::Auto, path, &mut warnings));
+    // Only the fallback of `Charset::Auto` is reported
+    assert_eq!(
+        vec![Warning {
+            file: path.to_path_buf(),
+            line: 0,
+            message: "the file is not valid UTF-8, it is read as ISO-8859-1".to_string(),
+        }],
+        warnings
+    );
+    warnings.clear();
+    assert_eq!("/** Café */", decode_source(latin1.clone(), Charset::Latin1, path, &mut warnings));
+    assert_eq!("/** Caf\u{fffd} */", decode_source(latin1, Charset::Utf8, path, &mut warnings));
+    assert_eq!("/** Café */", decode_source(utf8.clone(), Charset::Auto, path, &mut warnings));
+    assert_eq!("/** Café */", decode_source(utf8.clone(), Charset::Utf8, path, &mut warnings));
+    assert_eq!("/** CafÃ© */", decode_source(utf8, Charset::Latin1, path, &mut warnings));
+    assert!(warnings.is_empty());
+    assert_eq!(Some(Charset::Latin1), Charset::from_name("latin1"));
+    assert_eq!(None, Charset::from_name("cp1252"));
+}
+
+#[test]
+fn test_parse_str() {
+    let source = "package com.example;
+
+                  /** Builds users */
+                  public class UserBuilder {
+                      /**
+                       * Sets the name
+                       *
+                       * @param name The name of the user
+                       */
+                      public UserBuilder name(String name) {
+                          return this;
+                      }
+                  }
+
+                  interface Named {
+                  }";
+
+    let objects = parse_str(source, None).unwrap();
+
+    assert_eq!(2, objects.len());
+    match &objects[0] {
+        ObjectType::Class(class) => {
+            assert_eq!("UserBuilder", class.name);
+            assert_eq!("com.example", class.package_name);
+            assert_eq!("name", class.methods[0].name);
+            assert_eq!("The name of the user", class.methods[0].parameters[0].desc);
+        }
+        _ => panic!("Expected a class"),
+    }
+    match &objects[1] {
+        ObjectType::Interface(inter) => assert_eq!("Named", inter.name),
+        _ => panic!("Expected an interface"),
+    }
+}
+
+#[test]
+fn test_parse_missing_file() {
+    let path = std::env::temp_dir().join("lojidoc-missing/Missing.java");
+
+    match parse_file(&path, Charset::Auto, false) {
+        Err(LojidocError::Io { path: error_path, .. }) => assert_eq!(path, error_path),
+        _ => panic!("Expected a read error"),
+    }
+}
+
+#[test]
+fn test_unclosed_comment() {
+    assert_eq!(None, unclosed_comment("/** Docs */\nclass A {\n}\n"));
+    assert_eq!(None, unclosed_comment("class A {\n    String s = \"/*\"; // /*\n}\n"));
+    assert_eq!(Some(3), unclosed_comment("class A {\n    int a;\n    /* not closed\n}\n"));
+    assert_eq!(None, unclosed_comment("class A {\n    String s = \"\"\"\n        /*\n        \"\"\";\n}\n"));
+}
+
+#[test]
+fn test_classify_source() {
+    let (classes, open) = classify_source("a \"{\\\"}\" '}' // {\n/* } */b");
+
+    assert_eq!(Text::Code, open);
+    assert_eq!(vec![Text::Code; 2], classes[..2].to_vec());
+    assert_eq!(vec![Text::String; 6], classes[2..8].to_vec());
+    assert_eq!(vec![Text::Char; 3], classes[9..12].to_vec());
+    assert_eq!(vec![Text::LineComment; 4], classes[13..17].to_vec());
+    assert_eq!(Text::Code, classes[17]);
+    assert_eq!(vec![Text::BlockComment; 7], classes[18..25].to_vec());
+    assert_eq!(Text::Code, classes[25]);
+    assert_eq!(Text::TextBlock, classify_source("s = \"\"\"\n  }\n").1);
+}
+
+#[test]
+fn test_braces_in_literals() {
+    let j_class = "public class Parser {
+                       /** Parses the json */
+                       public void parse() {
+                           String json = \"{\\\"a\\\": {}}\";
+                           char open = '{';
+                           char close = '}';
+                           String block = \"\"\"
+                               { \"b\": [} \\\"\"\"
+                               \"\"\";
+                           // }
+                           /* } } */
+                       }
+
+                       /**
+                        * Checks the json
+                        *
+                        * @param strict Whether unknown keys fail
+                        */
+                       public boolean check(boolean strict) {
+                           return true;
+                       }
+                   }";
+
+    let tokens = lex_contents(&j_class.to_string());
+
+    match construct_ast(tokens).remove(0) {
+        ObjectType::Class(class) => {
+            assert_eq!(2, class.methods.len());
+            assert_eq!("parse", class.methods[0].name);
+            assert_eq!("check", class.methods[1].name);
+            assert_eq!("Checks the json", class.methods[1].description);
+            assert_eq!("Whether unknown keys fail", class.methods[1].parameters[0].desc);
+            assert_eq!("19", class.methods[1].line_num);
+        }
+        _ => panic!("Expected a class"),
+    }
+}
+
+#[test]
+fn test_parse_literals_and_comments() {
+    use std::path::Path;
+
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/parse/test/Literals.java");
+    let mut objects = parse_file(&fixture, Charset::Auto, false).unwrap();
+
+    assert_eq!(1, objects.len());
+    match objects.remove(0) {
+        ObjectType::Class(class) => {
+            assert_eq!("Literals", class.name);
+            assert!(class.inner_classes.is_empty());
+            assert_eq!(2, class.variables.len());
+            assert_eq!("\"public class Fake {\"", class.variables[0].value);
+            assert_eq!("The source of a class in a string", class.variables[0].desc);
+            assert_eq!("'{'", class.variables[1].value);
+            assert_eq!(
+                vec!["run", "source"],
+                class.methods.iter().map(|m| m.name.as_str()).collect::<Vec<_>>()
+            );
+            assert_eq!("Runs the source", class.methods[0].description);
+            assert_eq!("How many times the source is run", class.methods[0].parameters[0].desc);
+            assert_eq!("Gets the source", class.methods[1].description);
+        }
+        _ => panic!("Expected a class"),
+    }
+}
+
+#[test]
+fn test_parse_text_blocks() {
+    use std::path::Path;
+
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/parse/test/TextBlocks.java");
+    let mut objects = parse_file(&fixture, Charset::Auto, false).unwrap();
+
+    assert_eq!(1, objects.len());
+    match objects.remove(0) {
+        ObjectType::Class(class) => {
+            assert_eq!("TextBlocks", class.name);
+            assert_eq!("Builds the test data", class.description);
+            assert!(class.inner_classes.is_empty());
+            assert_eq!(1, class.variables.len());
+            assert_eq!("TEMPLATE", class.variables[0].name);
+            assert_eq!("A template with a fake class", class.variables[0].desc);
+            assert_eq!(
+                vec!["order", "id"],
+                class.methods.iter().map(|m| m.name.as_str()).collect::<Vec<_>>()
+            );
+            assert_eq!("Builds the json of an order", class.methods[0].description);
+            assert_eq!("The json", class.methods[0].return_desc);
+            assert_eq!("Gets the id of the order", class.methods[1].description);
+            assert_eq!("The json of the order", class.methods[1].parameters[0].desc);
+            assert_eq!("The id", class.methods[1].return_desc);
+            // The lines of the text blocks are counted
+            assert_eq!("21", class.methods[0].line_num);
+            assert_eq!("61", class.methods[1].line_num);
+        }
+        _ => panic!("Expected a class"),
+    }
+}
+
+#[test]
+fn test_parse_crlf_and_bom() {
+    use std::path::Path;
+
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/parse/test/Crlf.java");
+    let class = match parse_file(&fixture, Charset::Auto, false).unwrap().remove(0) {
+        ObjectType::Class(class) => class,
+        _ => panic!("Expected a class"),
+    };
+
+    assert_eq!("com.example", class.package_name);
+    assert_eq!(vec!["java.util.List"], class.dependencies);
+    assert_eq!("Orders", class.name);
+    assert_eq!("Keeps the orders of a customer", class.description);
+    assert_eq!("The number of orders", class.variables[0].desc);
+    assert_eq!("Finds the orders", class.methods[0].description);
+    assert_eq!("The name of the customer", class.methods[0].parameters[0].desc);
+    assert_eq!("The orders", class.methods[0].return_desc);
+    assert_eq!("20", class.methods[0].line_num);
+}
+
+#[test]
+fn test_multi_line_signature() {
+    let j_class = "public class Search {
+                       /**
+                        * Searches the index
+                        *
+                        * @param query The query
+                        * @param limit The most results
+                        * @throws SearchException When the index is closed
+                        */
+                       @Override
+                       @Nullable
+                       public Map<String, List<Result>> search(
+                               String query,
+                               int limit) throws SearchException {
+                           return null;
+                       }
+
+                       public
+                       static Map<String,
+                                  Integer> weights(String query,
+                                                   int limit);
+                   }";
+
+    let tokens = lex_contents(&j_class.to_string());
+
+    match construct_ast(tokens).remove(0) {
+        ObjectType::Class(class) => {
+            let search = &class.methods[0];
+            assert_eq!("search", search.name);
+            assert_eq!("Map<String, List<Result>>", search.return_type);
+            assert_eq!("public", search.privacy);
+            assert_eq!("11", search.line_num);
+            assert_eq!(
+                vec!["query", "limit"],
+                search.parameters.iter().map(|p| p.name.as_str()).collect::<Vec<_>>()
+            );
+            assert_eq!("int", search.parameters[1].var_type);
+            assert_eq!("The most results", search.parameters[1].desc);
+            assert_eq!(vec!["SearchException"], search.throws);
+
+            let weights = &class.methods[1];
+            assert_eq!("weights", weights.name);
+            assert_eq!("Map<String, Integer>", weights.return_type);
+            assert_eq!(vec!["static"], weights.modifiers);
+            assert_eq!("17", weights.line_num);
+            assert_eq!(2, weights.parameters.len());
+        }
+        _ => panic!("Expected a class"),
+    }
+}
+
+#[test]
+fn test_abstract_methods_parse() {
+    use std::path::Path;
+
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/parse/test/Shape.java");
+    let class = match parse_file(&fixture, Charset::Auto, false).unwrap().remove(0) {
+        ObjectType::Class(class) => class,
+        _ => panic!("Expected a class"),
+    };
+
+    assert_eq!(vec!["abstract"], class.modifiers);
+    assert_eq!(
+        vec!["area", "draw", "hash", "name"],
+        class.methods.iter().map(|m| m.name.as_str()).collect::<Vec<_>>()
+    );
+
+    let area = &class.methods[0];
+    assert_eq!(vec!["abstract"], area.modifiers);
+    assert_eq!("Gets the area of the shape", area.description);
+    assert_eq!("The area", area.return_desc);
+    assert_eq!("The scale the shape is drawn at", area.parameters[0].desc);
+    assert_eq!(vec!["IllegalStateException"], area.throws);
+    assert_eq!("When the shape has no points", area.exceptions[0].desc);
+
+    let draw = &class.methods[1];
+    assert_eq!("protected", draw.privacy);
+    assert_eq!(vec!["abstract"], draw.modifiers);
+    assert_eq!("The color of the outline", draw.parameters[1].desc);
+    assert_eq!(vec!["native"], class.methods[2].modifiers);
+    assert_eq!("The hash", class.methods[2].return_desc);
+    assert!(class.methods[3].modifiers.is_empty());
+
+    let inter = "public interface Repository<T> {
+                     /**
+                      * Finds an item
+                      *
+                      * @param id The id of the item
+                      */
+                     T find(long id) throws NotFoundException;
+
+                     abstract void save(T item);
+                 }";
+
+    match construct_ast(lex_contents(&inter.to_string())).remove(0) {
+        ObjectType::Interface(inter) => {
+            assert_eq!("Finds an item", inter.methods[0].description);
+            assert_eq!(vec!["abstract"], inter.methods[0].modifiers);
+            assert_eq!(vec!["NotFoundException"], inter.methods[0].throws);
+            assert_eq!(vec!["abstract"], inter.methods[1].modifiers);
+        }
+        _ => panic!("Expected an interface"),
+    }
+}
+
+#[test]
+fn test_varargs_and_array_params() {
+    let j_class = "public class Matrix {
+                       /**
+                        * Joins the cells
+                        *
+                        * @param matrix The cells
+                        * @param rows The rows
+                        * @param name The name
+                        * @param args The arguments
+                        */
+                       public static String[] join(int[][] matrix, int rows[],
+                               final @Nullable String name, @Size(max = 3) List<String>... args) {
+                           return null;
+                       }
+
+                       public void format(final String format, Object ... values) {
+                       }
+
+                       public void print(String ...lines) {
+                       }
+
+                       public void log(String...messages) {
+                       }
+                   }";
+
+    let tokens = lex_contents(&j_class.to_string());
+    let params = |method: &Method| -> Vec<(String, String)> {
+        method
+            .parameters
+            .iter()
+            .map(|param| (param.var_type.clone(), param.name.clone()))
+            .collect()
+    };
+    let param = |var_type: &str, name: &str| (var_type.to_string(), name.to_string());
+
+    match construct_ast(tokens).remove(0) {
+        ObjectType::Class(class) => {
+            assert_eq!(vec!["static"], class.methods[0].modifiers);
+            assert_eq!(
+                vec![
+                    param("int[][]", "matrix"),
+                    param("int[]", "rows"),
+                    param("String", "name"),
+                    param("List<String>...", "args"),
+                ],
+                params(&class.methods[0])
+            );
+            assert_eq!("The rows", class.methods[0].parameters[1].desc);
+            assert!(class.methods[1].modifiers.is_empty());
+            assert_eq!(
+                vec![param("String", "format"), param("Object...", "values")],
+                params(&class.methods[1])
+            );
+            assert_eq!(vec![param("String...", "lines")], params(&class.methods[2]));
+            assert_eq!(vec![param("String...", "messages")], params(&class.methods[3]));
+        }
+        _ => panic!("Expected a class"),
+    }
+}
+
+#[test]
+fn test_params_matched_by_name() {
+    let j_class = "public class Mover {
+                       /**
+                        * Moves a piece
+                        *
+                        * @param to The target square
+                        * @param piece The piece
+                        * @param from The removed parameter
+                        */
+                       public void move(String piece, int to, boolean capture) {
+                       }
+                   }";
+
+    let tokens = lex_contents(&j_class.to_string());
+
+    match construct_ast(tokens).remove(0) {
+        ObjectType::Class(class) => {
+            let method = &class.methods[0];
+            assert_eq!(
+                vec!["The piece", "The target square", ""],
+                method.parameters.iter().map(|param| param.desc.as_str()).collect::<Vec<_>>()
+            );
+            assert_eq!(vec!["from"], method.unmatched_params);
+        }
+        _ => panic!("Expected a class"),
+    }
+}
+
+#[test]
+fn test_static_imports_parse() {
+    let j_class = "package com.example;
+
+                   import java.util.*;
+                   import static java.util.Collections.sort;
+                   import static com.example.Util.*;
+
+                   public class Sorter {
+                   }";
+
+    let tokens = lex_contents(&j_class.to_string());
+
+    match construct_ast(tokens).remove(0) {
+        ObjectType::Class(class) => assert_eq!(
+            vec!["java.util.*", "static java.util.Collections.sort", "static com.example.Util.*"],
+            class.dependencies
+        ),
+        _ => panic!("Expected a class"),
+    }
+}
+
+#[test]
+fn test_parse_package_info() {
+    use std::path::Path;
+
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/parse/test/package-info.java");
+    let package = parse_package_info(&fixture, Charset::Auto, &mut Vec::new()).unwrap();
+
+    assert_eq!("com.example.shapes", package.name);
+    assert_eq!("The shapes which can be drawn.\n\nEvery shape has an area.", package.description);
+    assert!(package.members.is_empty());
+
+    // A package statement with a comment but no javadoc documents nothing
+    match parse_source("// The shapes\npackage com.example.shapes;\n", None).objects.remove(0) {
+        ObjectType::Class(class) => {
+            assert_eq!("com.example.shapes", class.package_name);
+            assert_eq!("", class.description);
+        }
+        _ => panic!("Expected a class"),
+    }
+}
+
+#[test]
+fn test_no_types() {
+    use std::path::Path;
+
+    // Stubs of deleted classes and empty files declare no type to write a page for
+    for source in &["package com.foo;\n\nimport java.util.List;\n", ""] {
+        match parse_str(source, Some(Path::new("Stub.java"))) {
+            Err(LojidocError::NoTypes { file }) => assert_eq!(Path::new("Stub.java"), file),
+            result => panic!("Expected no types, got {:?}", result),
+        }
+    }
+    assert_eq!(
+        "<source>: no classes found",
+        parse_str("import java.util.List;", None).unwrap_err().to_string()
+    );
+    assert_eq!(1, parse_str("package com.foo;\nclass Foo {}\n", None).unwrap().len());
+}
+
+#[test]
+fn test_parse_module_info() {
+    use std::path::Path;
+
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/parse/test/module-info.java");
+    let module = parse_module_info(&fixture, Charset::Auto, &mut Vec::new()).unwrap().unwrap();
+    let directive = |i: usize| {
+        let directive = &module.directives[i];
+        (
+            directive.kind.as_str(),
+            directive.modifiers.join(" "),
+            directive.name.as_str(),
+            directive.targets.join(", "),
+        )
+    };
+
+    assert_eq!("com.example.shapes", module.name);
+    assert!(module.open);
+    assert_eq!(
+        "Draws shapes on a canvas.\n\nThe renderers are found with the {@link ShapeProvider} service.",
+        module.description
+    );
+    assert_eq!(8, module.directives.len());
+    assert_eq!(("requires", "transitive".to_string(), "java.desktop", String::new()), directive(0));
+    assert_eq!(("requires", "static".to_string(), "com.example.annotations", String::new()), directive(1));
+    assert_eq!(("requires", String::new(), "java.logging", String::new()), directive(2));
+    assert_eq!(("exports", String::new(), "com.example.shapes", String::new()), directive(3));
+    assert_eq!(
+        ("exports", String::new(), "com.example.shapes.internal", "com.example.canvas, com.example.test".to_string()),
+        directive(4)
+    );
+    assert_eq!(
+        ("opens", String::new(), "com.example.shapes.model", "com.fasterxml.jackson.databind".to_string()),
+        directive(5)
+    );
+    assert_eq!(("uses", String::new(), "ShapeProvider", String::new()), directive(6));
+    assert_eq!(
+        (
+            "provides",
+            String::new(),
+            "ShapeProvider",
+            "com.example.shapes.Circles, com.example.shapes.Squares".to_string()
+        ),
+        directive(7)
+    );
+    assert_eq!(2, module.directives_of("exports").count());
+
+    // A file without a module declaration is skipped
+    let source = "/** Not a module */\nimport java.util.List;\n";
+    assert_eq!(None, parse_module_str(source, Path::new("module-info.java")));
+}
+
+#[test]
+fn test_gutterless_docs_parse() {
+    use std::path::Path;
+
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/parse/test/Widgets.java");
+    let class = match parse_file(&fixture, Charset::Auto, false).unwrap().remove(0) {
+        ObjectType::Class(class) => class,
+        _ => panic!("Expected a class"),
+    };
+
+    // Without a gutter
+    assert_eq!("Builds widgets for the toolbar.", class.description);
+    assert_eq!("Jim", class.author);
+    assert_eq!("Builds the widget.", class.methods[0].description);
+    assert_eq!("the widget name", class.methods[0].parameters[0].desc);
+    assert_eq!("the widget", class.methods[0].return_desc);
+    // Indented with tabs
+    assert_eq!("Removes the widget.", class.methods[1].description);
+    assert_eq!("the widget name", class.methods[1].parameters[0].desc);
+    // On one line
+    assert_eq!("Does the thing.", class.methods[2].description);
+    // Sharing the lines of the delimiters, with the gutter next to the tag
+    assert_eq!("Stops the widgets.", class.methods[3].description);
+    assert_eq!("whether running widgets are stopped", class.methods[3].parameters[0].desc);
+}
+
+#[test]
+fn test_markdown_docs_parse() {
+    let source = "package p;
+
+/// Builds *widgets* for the `Toolbar`.
+///
+/// ```java
+/// new Builder()
+///     .build();
+/// ```
+public class Builder {
+    /// Builds the widget.
+    ///
+    /// @param name the widget name, _not_ empty
+    /// @return the widget
+    /// @throws IllegalStateException when it was built
+    public Widget build(String name) throws IllegalStateException {
+        return null; /// not a comment of a declaration
+    }
+
+    /** Removes the <b>widget</b>. */
+    public void remove() {
+    }
+
+    //// A separator
+
+    /// Stops
+    public void stop() {
+    }
+}
+";
+    let class = match parse_str(source, None).unwrap().remove(0) {
+        ObjectType::Class(class) => class,
+        _ => panic!("Expected a class"),
+    };
+
+    assert_eq!(
+        "Builds *widgets* for the `Toolbar`.\n\n```java\nnew Builder()\n    .build();\n```",
+        class.description
+    );
+    let build = &class.methods[0];
+    assert_eq!("Builds the widget.", build.description);
+    assert_eq!("the widget name, _not_ empty", build.parameters[0].desc);
+    assert_eq!("the widget", build.return_desc);
+    assert_eq!("when it was built", build.exceptions[0].desc);
+    assert_eq!("Removes the **widget**.", class.methods[1].description);
+    assert_eq!("Stops", class.methods[2].description);
+}
+
+#[test]
+fn test_declaration_annotations_parse() {
+    let source = "package p;
+
+@RestController
+@RequestMapping(value = \"/items\",
+                produces = {\"application/json\", \"text/plain\"})
+public class Items extends Base {
+    /** Saves an item */
+    @Transactional(readOnly = false, timeout = 5)
+    @Override
+    public Item save(@Valid Item item) {
+        return item;
+    }
+
+    @SuppressWarnings({\"unchecked\"})
+    @java.lang.Deprecated
+    public List<Item> all() {
+        return null;
+    }
+}
+";
+    let class = match parse_str(source, None).unwrap().remove(0) {
+        ObjectType::Class(class) => class,
+        _ => panic!("Expected a class"),
+    };
+
+    assert_eq!(
+        vec![
+            "@RestController",
+            "@RequestMapping(value = \"/items\", produces = {\"application/json\", \"text/plain\"})",
+        ],
+        class.annotations
+    );
+    assert_eq!("Items", class.name);
+    assert_eq!("Base", class.parent);
+
+    let save = &class.methods[0];
+    assert_eq!("save", save.name);
+    assert_eq!("Item", save.return_type);
+    assert_eq!("Saves an item", save.description);
+    assert_eq!(vec!["@Transactional(readOnly = false, timeout = 5)", "@Override"], save.annotations);
+    assert_eq!("Item", save.parameters[0].var_type);
+
+    let all = &class.methods[1];
+    assert_eq!("all", all.name);
+    assert_eq!("List<Item>", all.return_type);
+    assert_eq!(vec!["@SuppressWarnings({\"unchecked\"})", "@java.lang.Deprecated"], all.annotations);
+    assert_eq!(Some(String::new()), all.deprecated);
+}
+
+#[test]
+fn test_sealed_parse() {
+    let shape = "package p;
+
+/** A shape */
+public sealed interface Shape permits Circle, Square {
+    double area();
+}
+";
+    match parse_str(shape, None).unwrap().remove(0) {
+        ObjectType::Interface(inter) => {
+            assert_eq!("Shape", inter.name);
+            assert_eq!(vec!["Circle", "Square"], inter.permits);
+            assert_eq!("area", inter.methods[0].name);
+        }
+        _ => panic!("Expected an interface"),
+    }
+
+    let base = "package p;
+
+public abstract sealed class Base<T> extends Node implements Shape
+    permits Base.Leaf, p.Other {
+    int permits;
+
+    public static non-sealed class Leaf extends Base<String> {
+    }
+}
+";
+    let class = match parse_str(base, None).unwrap().remove(0) {
+        ObjectType::Class(class) => class,
+        _ => panic!("Expected a class"),
+    };
+
+    assert_eq!("Base", class.name);
+    assert_eq!(vec!["abstract", "sealed"], class.modifiers);
+    assert_eq!("Node", class.parent);
+    assert_eq!(vec!["Shape"], class.interfaces);
+    assert_eq!(vec!["Base.Leaf", "p.Other"], class.permits);
+    assert_eq!("permits", class.variables[0].name);
+    match &class.inner_classes[0] {
+        ObjectType::Class(leaf) => {
+            assert_eq!("Leaf", leaf.name);
+            assert_eq!(vec!["static", "non-sealed"], leaf.modifiers);
+            assert!(leaf.permits.is_empty());
+        }
+        _ => panic!("Expected a class"),
+    }
+}
+
+#[test]
+fn test_parse_warnings() {
+    use std::path::Path;
+
+    let source = "package p;
+
+/** Left over from a removed field. */
+
+/** Finds users. */
+public class Users {
+    /**
+     * Finds a user
+     *
+     * @param name The name
+     */
+    public User find(long id) {
+        return null;
+    }
+
+    /* not closed
+}
+";
+    let parsed = parse_source(source, Some(Path::new("src/Users.java")));
+    let warnings: Vec<String> = parsed.warnings.iter().map(|warning| warning.to_string()).collect();
+
+    assert_eq!(
+        vec![
+            "src/Users.java:3: the javadoc is not followed by a declaration",
+            "src/Users.java:12: @param name does not match a parameter of find",
+            "src/Users.java:16: the comment is not closed",
+        ],
+        warnings
+    );
+    match &parsed.objects[0] {
+        ObjectType::Class(class) => assert_eq!("Finds users.", class.description),
+        _ => panic!("Users is a class"),
+    }
+    assert!(parse_source("/** A */\nclass A {}\n", None).warnings.is_empty());
+}
+
+#[test]
+fn test_method_source_parse() {
+    let source = "package p;
+
+public abstract class Counter {
+    private int count;
+
+    /** Adds one */
+    @Override
+    public int next(@Named(\"step\") int step) {
+        // \"}\" is not the end of the body
+        count += step;
+        return count;
+    }
+
+    public abstract void reset();
+
+    public Counter() { count = 0; }
+
+    class Inner {
+        int get() {
+            return 1;
+        }
+    }
+}
+";
+    let parsed = parse_source(source, None);
+    let class = match &parsed.objects[0] {
+        ObjectType::Class(class) => class,
+        _ => panic!("Counter is a class"),
+    };
+
+    assert_eq!(
+        "public int next(@Named(\"step\") int step) {\n    // \"}\" is not the end of the body\n    count += step;\n    return count;\n}",
+        class.methods[0].source
+    );
+    assert_eq!("", class.methods[1].source);
+    assert_eq!("public Counter() { count = 0; }", class.constructors[0].source);
+    match &class.inner_classes[0] {
+        ObjectType::Class(inner) => assert_eq!("int get() {\n    return 1;\n}", inner.methods[0].source),
+        _ => panic!("Inner is a class"),
+    }
+}
@@ -0,0 +1,25 @@
+/*
+ * Copyright the authors
+ */
+
+import com.example.shapes.spi.ShapeProvider;
+
+/**
+ * Draws shapes on a canvas.
+ * <p>
+ * The renderers are found with the {@link ShapeProvider} service.
+ */
+@SuppressWarnings({"module", "exports"})
+open module com.example.shapes {
+    requires transitive java.desktop;
+    requires static com.example.annotations;
+    requires java.logging;
+
+    // The shapes are public API
+    exports com.example.shapes;
+    exports com.example.shapes.internal to com.example.canvas, com.example.test;
+    opens com.example.shapes.model to com.fasterxml.jackson.databind;
+
+    uses ShapeProvider;
+    provides ShapeProvider with com.example.shapes.Circles, com.example.shapes.Squares;
+}
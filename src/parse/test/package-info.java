@@ -0,0 +1,13 @@
+/*
+ * Copyright the authors
+ */
+
+/**
+ * The shapes which can be drawn.
+ * <p>
+ * Every shape has an area.
+ */
+@ParametersAreNonnullByDefault
+package com.example.shapes;
+
+import javax.annotation.ParametersAreNonnullByDefault;
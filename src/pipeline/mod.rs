@@ -0,0 +1,1361 @@
+pub mod pipeline {
+    //! Module that runs the documentation of a project: it finds and parses the java
+    //! files, writes their pages and listings, and keeps the manifest of incremental runs.
+    //! The command line tool maps its arguments and results onto this module, which needs
+    //! the `cli` feature for the worker threads.
+
+    use std::collections::BTreeMap;
+    use std::collections::HashMap;
+    use std::collections::HashSet;
+    use std::fs;
+    use std::io;
+    use std::io::Write;
+    use std::mem;
+    use std::panic;
+    use std::path::Path;
+    use std::path::PathBuf;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+    use std::sync::mpsc;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    use mdbook::config::Config;
+    use mdbook::MDBook;
+    use notify::DebouncedEvent;
+    use notify::RecursiveMode;
+    use notify::Watcher;
+    use threadpool::ThreadPool;
+
+    use coverage::coverage::write_atomic;
+    use coverage::coverage::BadgeColors;
+    use coverage::coverage::Coverage;
+    use coverage::coverage::Stats;
+    use document::document::find_java_files;
+    use document::document::gen_md_book;
+    use document::document::generate_api_json;
+    use document::document::generate_api_index;
+    use document::document::generate_diagrams;
+    use document::document::generate_index;
+    use document::document::generate_json;
+    use document::document::generate_mdbook;
+    use document::document::generate_module_page;
+    use document::document::generate_package_indexes;
+    use document::document::generate_pages;
+    use document::document::generate_sidebar;
+    use document::document::generate_single_page;
+    use document::document::write_pages;
+    use document::document::gen_api_json;
+    use document::document::get_type_names;
+    use document::document::is_module_info;
+    use document::document::is_package_info;
+    use document::document::lint_project;
+    use document::document::is_generated_file;
+    use document::document::output_paths;
+    use document::document::module_page_path;
+    use document::document::package_index_path;
+    use document::document::render_members;
+    use document::document::render_pages;
+    use document::document::SourceLinks;
+    use document::document::stale_pages;
+    use document::document::Format;
+    use document::document::Output;
+    use document::document::PathFilter;
+    use document::document::filter_visibility;
+    use incremental::incremental::hash_contents;
+    use incremental::incremental::members_of;
+    use incremental::incremental::plan;
+    use incremental::incremental::plan_changed;
+    use incremental::incremental::read_manifest;
+    use incremental::incremental::write_manifest;
+    use incremental::incremental::Build;
+    use incremental::incremental::BuiltFile;
+    use incremental::incremental::Manifest;
+    use incremental::incremental::ManifestEntry;
+    use incremental::incremental::Plan;
+    use git::git::changed_since;
+    use git::git::read_at;
+    use inherit::inherit::inherit_docs;
+    use inherit::inherit::supertype_names;
+    use model::model::ApplicationDoc;
+    use model::model::ObjectType;
+    use model::model::Project;
+    use parse::parse::declared_types;
+    use parse::parse::parse_source;
+    use parse::parse::parse_module_info;
+    use parse::parse::parse_package_info;
+    use parse::parse::read_source;
+    use parse::parse::Charset;
+    use parse::parse::parse_str;
+    use strings::strings::labels_settings;
+    use log::log::Progress;
+    use error::error::LojidocError;
+    use error::error::Warning;
+
+    /// Parses a java file into a project of its types
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - The path of the java file
+    /// * `file_path` - The path the types link to, such as the file in the repository
+    /// * `charset` - The encoding of the java file
+    /// * `warnings` - The parse warnings of the run, which the warnings of the file are added to
+    fn parse_types(
+        file: &Path,
+        file_path: &str,
+        charset: Charset,
+        warnings: &mut Vec<Warning>,
+    ) -> Result<Project, LojidocError> {
+        let source = read_source(file, charset, warnings)?;
+        // A bug of the parser fails the file instead of the run or its worker thread
+        let parsed = panic::catch_unwind(|| parse_source(&source, Some(file))).map_err(|_| LojidocError::Parse {
+            file: file.to_path_buf(),
+            line: 0,
+            message: "the parser failed, please report the file".to_string(),
+        })?;
+        warnings.extend(parsed.warnings);
+
+        // A file without a type gets no page instead of a page without a name
+        let objects = declared_types(parsed.objects);
+        if objects.is_empty() {
+            verbose!("Skipping {}, no classes found", file.display());
+        }
+
+        Ok(project_of(objects, file_path))
+    }
+
+    /// Collects the parsed types of a java file into a project
+    ///
+    /// # Arguments
+    ///
+    /// * `objects` - The types declared in the file
+    /// * `file_path` - The path the types link to, such as the file in the repository
+    fn project_of(objects: Vec<ObjectType>, file_path: &str) -> Project {
+        let mut project = Project::new();
+
+        for object in objects {
+            match object {
+                ObjectType::Class(mut class) => {
+                    class.ch_file_path(file_path.to_string());
+                    project.add_class(class);
+                }
+                ObjectType::Interface(mut inter) => {
+                    inter.ch_file_path(file_path.to_string());
+                    project.add_interface(inter);
+                }
+                ObjectType::Enumeration(mut enumeration) => {
+                    enumeration.ch_file_path(file_path.to_string());
+                    project.add_enumeration(enumeration);
+                }
+                ObjectType::Annotation(mut annotation) => {
+                    annotation.ch_file_path(file_path.to_string());
+                    project.add_annotation(annotation);
+                }
+            }
+        }
+
+        project
+    }
+
+    /// Parses java files without documenting them. The types keep the path of their file
+    /// and inherit the documentation of the methods they override.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_paths` - A vector of the file paths of java files
+    /// * `charset` - The encoding of the java files
+    /// * `warnings` - The parse warnings of the run
+    ///
+    /// Returns the parsed types and the errors of the files which could not be parsed
+    fn parse_project(
+        file_paths: Vec<PathBuf>,
+        charset: Charset,
+        warnings: &mut Vec<Warning>,
+    ) -> (Project, Vec<LojidocError>) {
+        let mut projects = Vec::new();
+        let mut errors = Vec::new();
+
+        // Package and module info files do not declare types
+        for file in file_paths.into_iter().filter(|file| !is_package_info(file) && !is_module_info(file)) {
+            match parse_types(&file, &file.to_string_lossy(), charset, warnings) {
+                Ok(project) => projects.push(project),
+                Err(err) => errors.push(err),
+            }
+        }
+        inherit_docs(&mut projects, &[]);
+
+        let mut project = Project::new();
+        for file_project in projects {
+            project.merge(file_project);
+        }
+
+        (project, errors)
+    }
+
+    /// Handles linting javadocs and measuring their coverage without saving the documentation
+    ///
+    /// # Arguments
+    ///
+    /// * `file_paths` - A vector of the file paths of java files
+    /// * `lint` - Whether the public API without javadocs is printed
+    /// * `coverage` - The documentation coverage the parsed files are counted in
+    /// * `charset` - The encoding of the java files
+    /// * `warnings` - The parse warnings of the run
+    ///
+    /// Returns the errors of the files which could not be parsed
+    pub fn check_javadoc(
+        file_paths: Vec<PathBuf>,
+        lint: bool,
+        coverage: &mut Coverage,
+        charset: Charset,
+        warnings: &mut Vec<Warning>,
+    ) -> Vec<LojidocError> {
+        let (project, errors) = parse_project(file_paths, charset, warnings);
+        coverage.add_project(&project);
+
+        if lint {
+            for finding in lint_project(project) {
+                println!("{}", finding);
+            }
+        }
+
+        errors
+    }
+
+    /// Prints the path of every file the documentation would be written to without
+    /// writing anything. Paths which more than one type would be written to are reported.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_paths` - A vector of the file paths of java files
+    /// * `dest` - The destination directory of the documentation
+    /// * `output` - The format and layout of the documentation
+    /// * `warnings` - The parse warnings of the run
+    ///
+    /// Returns the errors of the files which could not be parsed
+    pub fn dry_run(file_paths: Vec<PathBuf>, dest: &str, output: Output, warnings: &mut Vec<Warning>) -> Vec<LojidocError> {
+        let (paths, errors) = planned_files(&file_paths, dest, &output, warnings);
+        let mut collisions = 0;
+
+        for (path, names) in paths.iter() {
+            if names.len() > 1 {
+                collisions += 1;
+                println!("{} (collision: {})", path.display(), names.join(", "));
+            } else if names.is_empty() {
+                println!("{}", path.display());
+            } else {
+                println!("{} ({})", path.display(), names[0]);
+            }
+        }
+
+        info!("Dry run: {} files would be written, {} collisions", paths.len(), collisions);
+
+        if output.clean && errors.is_empty() {
+            let written: HashSet<String> = output_files_of(&paths, dest);
+
+            for path in stale_pages(dest, &written, output.format) {
+                println!("{} (would be removed)", path.display());
+            }
+        }
+
+        errors
+    }
+
+    /// Finds every file a run writes to the destination with the types written to it,
+    /// without writing anything. Each path is listed once.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_paths` - A vector of the file paths of java files
+    /// * `dest` - The destination directory of the documentation
+    /// * `output` - The format and layout of the documentation
+    /// * `warnings` - The parse warnings of the run
+    ///
+    /// Returns the paths with the qualified names of their types and the errors of the
+    /// files which could not be parsed
+    fn planned_files(
+        file_paths: &[PathBuf],
+        dest: &str,
+        output: &Output,
+        warnings: &mut Vec<Warning>,
+    ) -> (Vec<(PathBuf, Vec<String>)>, Vec<LojidocError>) {
+        let (mut project, mut errors) = parse_project(file_paths.to_vec(), output.charset, warnings);
+        filter_visibility(&mut project, output.min_visibility);
+        let mut output_files = output_paths(&project, output);
+        let mut paths: Vec<(PathBuf, Vec<String>)> = Vec::new();
+        let mut index: HashMap<PathBuf, usize> = HashMap::new();
+
+        // Documented packages get an index page in their package directory
+        if let (false, false, Some(r)) = (output.flat, output.single_file, output.format.renderer()) {
+            for file in file_paths.iter().filter(|file| is_package_info(file)) {
+                match parse_package_info(file, output.charset, warnings) {
+                    Ok(package) if !package.name.is_empty() && !package.description.trim().is_empty() => {
+                        output_files.push((package_index_path(&package.name, r.extension()), String::new()));
+                    }
+                    Ok(_) => {}
+                    Err(err) => errors.push(err),
+                }
+            }
+        }
+        if let (false, Some(r)) = (output.single_file, output.format.renderer()) {
+            for file in file_paths.iter().filter(|file| is_module_info(file)) {
+                match parse_module_info(file, output.charset, warnings) {
+                    Ok(Some(module)) => output_files.push((module_page_path(r.extension()), module.name)),
+                    Ok(None) => {}
+                    Err(err) => errors.push(err),
+                }
+            }
+        }
+
+        // Each path is listed once with every type written to it
+        for (path, name) in output_files {
+            let path = Path::new(dest).join(path);
+            let i = *index.entry(path.clone()).or_insert_with(|| {
+                paths.push((path, Vec::new()));
+                paths.len() - 1
+            });
+
+            if !name.is_empty() {
+                paths[i].1.push(name);
+            }
+        }
+
+        (paths, errors)
+    }
+
+    /// Finds the files a run writes which already exist in the destination and were not
+    /// written by an earlier run, such as hand-edited pages, for `--on-conflict`
+    pub fn conflicting_files(file_paths: &[PathBuf], dest: &str, output: &Output) -> Vec<PathBuf> {
+        // The warnings and errors of the files are reported when they are documented
+        let (paths, _) = planned_files(file_paths, dest, output, &mut Vec::new());
+
+        paths
+            .into_iter()
+            .map(|(path, _)| path)
+            .filter(|path| path.is_file())
+            .filter(|path| !fs::read_to_string(path).is_ok_and(|contents| is_generated_file(path, &contents, output.format)))
+            .collect()
+    }
+
+    /// Prints the pages of the types of java files to stdout instead of writing them, with a
+    /// rule between the pages. The json format prints every type in a single document.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_paths` - A vector of the file paths of java files
+    /// * `links` - Where the source links of the pages point to
+    /// * `output` - The format and layout of the documentation
+    /// * `warnings` - The parse warnings of the run
+    ///
+    /// Returns the errors of the files which could not be parsed or printed
+    pub fn print_pages(
+        file_paths: Vec<PathBuf>,
+        links: &SourceLinks,
+        output: &Output,
+        warnings: &mut Vec<Warning>,
+    ) -> Vec<LojidocError> {
+        let file_paths: Vec<PathBuf> = file_paths
+            .into_iter()
+            .filter(|file| !is_package_info(file) && !is_module_info(file))
+            .collect();
+        let type_names = get_type_names(&file_paths);
+        let mut errors = Vec::new();
+        let mut projects = Vec::new();
+
+        for file in file_paths.iter() {
+            match parse_types(file, &links.file_url(file), output.charset, warnings) {
+                Ok(project) => projects.push(project),
+                Err(err) => errors.push(err),
+            }
+        }
+        inherit_docs(&mut projects, &[]);
+
+        let stdout = io::stdout();
+        let mut out = stdout.lock();
+
+        match output.format.renderer() {
+            Some(r) => {
+                let mut docs = Vec::new();
+                for mut project in projects {
+                    filter_visibility(&mut project, output.min_visibility);
+
+                    match render_pages(project, &type_names, output, r) {
+                        Ok(pages) => docs.extend(pages.into_iter().map(|page| page.doc)),
+                        Err(err) => errors.push(err),
+                    }
+                }
+                if let Err(err) = write_pages(&mut out, r, &docs) {
+                    errors.push(LojidocError::io(Path::new("stdout"), err));
+                }
+            }
+            None => {
+                let mut api = Project::new();
+                for mut project in projects {
+                    filter_visibility(&mut project, output.min_visibility);
+                    api.merge(project);
+                }
+
+                if let Err(err) = writeln!(out, "{}", gen_api_json(&mut api)) {
+                    errors.push(LojidocError::io(Path::new("stdout"), err));
+                }
+            }
+        }
+
+        errors
+    }
+
+    /// Gets the paths of the files of a dry run relative to the destination
+    fn output_files_of(paths: &[(PathBuf, Vec<String>)], dest: &str) -> HashSet<String> {
+        paths
+            .iter()
+            .filter_map(|(path, _)| path.strip_prefix(dest).ok())
+            .map(|path| path.display().to_string())
+            .collect()
+    }
+
+    /// Removes the pages of types which the run did not write from the destination, with
+    /// the package directories left empty
+    ///
+    /// # Arguments
+    ///
+    /// * `dest` - The destination directory
+    /// * `written` - The pages written by the run, relative to the destination
+    /// * `output` - The format of the run
+    ///
+    /// Returns the errors of the files which could not be removed
+    fn clean_destination(dest: &str, written: &HashSet<String>, output: &Output) -> Vec<LojidocError> {
+        let mut errors = Vec::new();
+        let mut removed = 0;
+
+        for path in stale_pages(dest, written, output.format) {
+            if let Err(err) = fs::remove_file(&path) {
+                errors.push(LojidocError::io(&path, err));
+                continue;
+            }
+            info!("Removed {}", path.display());
+            removed += 1;
+
+            let mut dir = path.parent();
+            while let Some(parent) = dir.filter(|parent| *parent != Path::new(dest)) {
+                if fs::remove_dir(parent).is_err() {
+                    break;
+                }
+                dir = parent.parent();
+            }
+        }
+        verbose!("Removed {} stale files from {}", removed, dest);
+
+        errors
+    }
+
+    /// Writes the shields.io endpoint json of the `--badge` with the documentation coverage
+    pub fn write_badge(coverage: &Coverage, path: &str, colors: BadgeColors) -> Result<(), LojidocError> {
+        write_atomic(Path::new(path), &coverage.to_badge(colors))?;
+        verbose!("Coverage badge written to {}", path);
+
+        Ok(())
+    }
+
+    /// Compares the documentation coverage with the `--fail-under` threshold and prints
+    /// the coverage of each package when it is below
+    ///
+    /// Returns whether the coverage is below the threshold
+    pub fn below_threshold(coverage: &Coverage, threshold: f64) -> bool {
+        let percent = coverage.total().percent();
+
+        if percent < threshold {
+            error!("\n{}", coverage.summary());
+            error!(
+                "Documentation coverage {:.1}% is below the threshold of {}%",
+                percent, threshold
+            );
+            true
+        } else {
+            info!("Documentation coverage {:.1}%", percent);
+            false
+        }
+    }
+
+    /// Writes the pages or json files of the types of a java file
+    ///
+    /// Returns the documented types for the index
+    fn write_project(
+        project: Project,
+        dest: &str,
+        output: &Output,
+        type_names: &[String],
+    ) -> Result<ApplicationDoc, LojidocError> {
+        match output.format.renderer() {
+            Some(r) => generate_pages(project, dest, type_names, output, r),
+            None => generate_json(project, dest, &output.kept, output.flat).map(|_| ApplicationDoc::new()),
+        }
+    }
+
+    /// Writes the files which list every documented type once every java file is documented:
+    /// the single file, or the index, the API index and the mdBook navigation, and the
+    /// markdown book
+    fn write_listings(
+        app_doc: &ApplicationDoc,
+        api: Project,
+        dest: &str,
+        output: &Output,
+    ) -> Result<(), LojidocError> {
+        if output.single_file {
+            write_single_file(api, dest, output)?;
+        } else if let Some(r) = output.format.renderer() {
+            generate_index(r, app_doc, dest, &output.kept)?;
+            generate_api_index(r, app_doc, dest, &output.kept)?;
+
+            if !output.flat {
+                generate_package_indexes(r, app_doc, dest, &output.kept)?;
+            }
+
+            if output.mdbook {
+                generate_mdbook(app_doc, dest, &output.kept)?;
+            }
+            if output.docusaurus {
+                generate_sidebar(app_doc, dest, &output.kept)?;
+            }
+            if output.diagrams {
+                generate_diagrams(app_doc, dest, &output.kept, output.diagram_methods)?;
+            }
+        }
+
+        if output.book {
+            gen_md_book(dest, app_doc)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the page of the module of every `module-info.java` file. The files are read
+    /// in every run since they are not in the manifest.
+    ///
+    /// Returns the errors of the files which could not be read or written
+    fn document_modules(
+        module_infos: &[PathBuf],
+        dest: &str,
+        output: &Output,
+        warnings: &mut Vec<Warning>,
+    ) -> Vec<LojidocError> {
+        let mut errors = Vec::new();
+
+        for file in module_infos {
+            let r = match output.format.renderer() {
+                Some(r) if !output.single_file => r,
+                _ => {
+                    verbose!("Skipping {}, modules are only documented in pages", file.display());
+                    continue;
+                }
+            };
+
+            match parse_module_info(file, output.charset, warnings) {
+                Ok(Some(module)) => {
+                    if let Err(err) = generate_module_page(r, &module, dest, &output.kept) {
+                        errors.push(err);
+                    }
+                }
+                Ok(None) => verbose!("Skipping {}, it does not declare a module", file.display()),
+                Err(err) => errors.push(err),
+            }
+        }
+
+        errors
+    }
+
+    /// Handles the single threaded option for running the application
+    ///
+    /// # Arguments
+    ///
+    /// * `file_paths` - A vector of the file paths of java files
+    /// * `dest` - The file path where the markdown will be saved
+    /// * `links` - Where the source links of the pages point to
+    /// * `output` - The format and layout of the generated documentation
+    /// * `coverage` - The documentation coverage the parsed files are counted in
+    /// * `build` - The type names and index of the run, which records the documented files
+    ///   and their parse warnings
+    ///
+    /// Returns the errors of the files which could not be parsed or written. The other
+    /// files are still documented.
+    pub fn document_single(
+        file_paths: Vec<PathBuf>,
+        dest: String,
+        links: SourceLinks,
+        output: Output,
+        coverage: &mut Coverage,
+        build: &mut Build,
+    ) -> Vec<LojidocError> {
+        let mut app_doc = ApplicationDoc::new();
+        let mut api = Project::new();
+        let mut errors = Vec::new();
+        let mut count = 0;
+        let mut progress = Progress::new("Parsing", file_paths.len());
+        let mut files = Vec::new();
+        let mut projects = Vec::new();
+
+        for file in file_paths.clone() {
+            let file_url = links.file_url(&file);
+
+            progress.update(&file);
+            match parse_types(&file, &file_url, output.charset, &mut build.warnings) {
+                Ok(project) => {
+                    files.push(file);
+                    projects.push(project);
+                }
+                Err(err) => errors.push(err),
+            }
+        }
+        // The types are documented once every file is parsed so the documentation of
+        // overridden methods in other files can be inherited
+        inherit_docs(&mut projects, &build.supertypes);
+        progress.start("Documenting", files.len());
+
+        for (file, mut file_project) in files.into_iter().zip(projects) {
+            progress.update(&file);
+            filter_visibility(&mut file_project, output.min_visibility);
+
+            let types = file_project.classes.len()
+                + file_project.interfaces.len()
+                + file_project.enumerations.len()
+                + file_project.annotations.len();
+            coverage.add_project(&file_project);
+            let outputs = type_outputs(&file_project, &output);
+
+            // A single file is written once every file is parsed
+            if output.single_file {
+                count += types;
+                api.merge(file_project);
+                build.files.push(BuiltFile {
+                    path: file,
+                    outputs,
+                    members: Vec::new(),
+                });
+                continue;
+            }
+
+            match write_project(file_project, dest.as_str(), &output, &build.type_names) {
+                Ok(documented) => {
+                    count += types;
+                    build.files.push(BuiltFile {
+                        path: file,
+                        members: members_of(&documented, &outputs),
+                        outputs,
+                    });
+                    app_doc.merge(documented);
+                }
+                Err(err) => errors.push(err),
+            }
+        }
+
+        progress.finish();
+        let failed = errors.len();
+
+        app_doc.merge(mem::replace(&mut build.previous, ApplicationDoc::new()));
+        if let Err(err) = write_listings(&app_doc, api, dest.as_str(), &output) {
+            errors.push(err);
+        }
+
+        info!(
+            "Documentation finished. Generated {} {} files from {} java files, {} failed",
+            count,
+            output.format.name(),
+            file_paths.len() - failed,
+            failed
+        );
+
+        errors
+    }
+
+    /// Handles thread pooling the application
+    ///
+    /// # Arguments
+    ///
+    /// * `file_paths` - A vector of the file paths of java files
+    /// * `dest` - The file path where the markdown will be saved
+    /// * `links` - Where the source links of the pages point to
+    /// * `output` - The format and layout of the generated documentation
+    /// * `jobs` - The number of worker threads
+    /// * `coverage` - The documentation coverage the parsed files are counted in
+    /// * `build` - The type names and index of the run, which records the documented files
+    ///   and their parse warnings
+    ///
+    /// Returns the errors of the files which could not be parsed or written. The other
+    /// files are still documented.
+    pub fn document(
+        file_paths: Vec<PathBuf>,
+        dest: String,
+        links: SourceLinks,
+        output: Output,
+        jobs: usize,
+        coverage: &mut Coverage,
+        build: &mut Build,
+    ) -> Vec<LojidocError> {
+        let type_names = Arc::new(build.type_names.clone());
+        let count = Arc::new(AtomicUsize::new(0));
+        let errors = Arc::new(Mutex::new(Vec::new()));
+        let app_doc = Arc::new(Mutex::new(ApplicationDoc::new()));
+        let api = Arc::new(Mutex::new(Project::new()));
+        let file_coverage = Arc::new(Mutex::new(Coverage::new(coverage.count_private)));
+        let size = file_paths.len();
+        let pool = ThreadPool::new(jobs);
+        let safe_dest = Arc::new(dest);
+        let mut progress = Progress::new("Parsing", size);
+        let mut parsed = Vec::new();
+        let (sender, receiver) = mpsc::channel();
+
+        // Each file is its own job. The workers take files from the pool's queue
+        // so only `jobs` files are parsed at the same time.
+        for (i, file) in file_paths.into_iter().enumerate() {
+            let links = links.clone();
+            let charset = output.charset;
+            let new_sender = sender.clone();
+
+            pool.execute(move || {
+                let file_url = links.file_url(&file);
+                let mut warnings = Vec::new();
+                let project = parse_types(&file, &file_url, charset, &mut warnings);
+                let _ = new_sender.send((i, file, project, warnings));
+            });
+        }
+        drop(sender);
+
+        for (i, file, project, warnings) in receiver {
+            build.warnings.extend(warnings);
+            progress.update(&file);
+
+            match project {
+                Ok(project) => parsed.push((i, file, project)),
+                Err(err) => errors.lock().unwrap().push(err),
+            }
+        }
+        // The files are documented in the order they were found, once every file is
+        // parsed so the documentation of overridden methods in other files can be inherited
+        parsed.sort_by_key(|(i, _, _)| *i);
+        let (files, mut projects): (Vec<PathBuf>, Vec<Project>) =
+            parsed.into_iter().map(|(_, file, project)| (file, project)).unzip();
+        inherit_docs(&mut projects, &build.supertypes);
+        progress.start("Documenting", files.len());
+
+        // The workers send each file they finished to update the progress
+        let (sender, receiver) = mpsc::channel();
+
+        for (file, mut project) in files.into_iter().zip(projects) {
+            let new_dest = safe_dest.clone();
+            let new_type_names = type_names.clone();
+            let new_count = count.clone();
+            let new_errors = errors.clone();
+            let new_app_doc = app_doc.clone();
+            let new_api = api.clone();
+            let new_coverage = file_coverage.clone();
+            let new_sender = sender.clone();
+            let output = output.clone();
+
+            pool.execute(move || {
+                filter_visibility(&mut project, output.min_visibility);
+
+                let types = project.classes.len()
+                    + project.interfaces.len()
+                    + project.enumerations.len()
+                    + project.annotations.len();
+                new_coverage.lock().unwrap().add_project(&project);
+
+                // A single file is written once every file is parsed
+                if output.single_file {
+                    new_count.fetch_add(types, Ordering::SeqCst);
+                    new_api.lock().unwrap().merge(project);
+                    let _ = new_sender.send((file, Some((Vec::new(), Vec::new()))));
+                    return;
+                }
+
+                let outputs = type_outputs(&project, &output);
+
+                match write_project(project, new_dest.as_str(), &output, &new_type_names) {
+                    Ok(documented) => {
+                        new_count.fetch_add(types, Ordering::SeqCst);
+                        let members = members_of(&documented, &outputs);
+                        new_app_doc.lock().unwrap().merge(documented);
+                        let _ = new_sender.send((file, Some((outputs, members))));
+                    }
+                    Err(err) => {
+                        new_errors.lock().unwrap().push(err);
+                        let _ = new_sender.send((file, None));
+                    }
+                }
+            });
+        }
+
+        // The receiver stops once every job, and with it every sender, is dropped
+        drop(sender);
+        for (file, built) in receiver {
+            progress.update(&file);
+
+            if let Some((outputs, members)) = built {
+                build.files.push(BuiltFile {
+                    path: file,
+                    outputs,
+                    members,
+                });
+            }
+        }
+        progress.finish();
+
+        pool.join();
+        coverage.merge(mem::take(&mut *file_coverage.lock().unwrap()));
+        app_doc
+            .lock()
+            .unwrap()
+            .merge(mem::replace(&mut build.previous, ApplicationDoc::new()));
+
+        let mut errors = mem::take(&mut *errors.lock().unwrap());
+        let failed = errors.len();
+
+        // The index and the single file are written once every worker has finished
+        let project = mem::replace(&mut *api.lock().unwrap(), Project::new());
+        if let Err(err) = write_listings(&app_doc.lock().unwrap(), project, safe_dest.as_str(), &output) {
+            errors.push(err);
+        }
+
+        info!(
+            "Documentation finished. Generated {} {} files from {} java files, {} failed",
+            count.load(Ordering::SeqCst),
+            output.format.name(),
+            size - failed,
+            failed
+        );
+
+        errors
+    }
+
+    /// The pages written for the types of a project, relative to the destination
+    fn type_outputs(proj: &Project, output: &Output) -> Vec<String> {
+        output_paths(proj, output)
+            .into_iter()
+            .filter(|(_, name)| !name.is_empty())
+            .map(|(path, _)| path)
+            .collect()
+    }
+
+    /// The options which change the generated pages, an incremental run with other
+    /// options documents every file again
+    fn incremental_settings(output: &Output, links: &SourceLinks) -> String {
+        format!(
+            "format={} flat={} mdbook={} docusaurus={} book={} visibility={} methods={} grouped={} source={:?} jdk_imports={} \
+             context={} front_matter={:?} template={:?} charset={} diagrams={} diagram_methods={} \
+             labels={}",
+            output.format.name(),
+            output.flat,
+            output.mdbook,
+            output.docusaurus,
+            output.book,
+            output.min_visibility.name(),
+            output.methods.order.name(),
+            output.methods.group_by_visibility,
+            output.methods.include_source,
+            !output.hide_jdk_imports,
+            links,
+            output.front_matter.as_ref().map(|front_matter| (&front_matter.extra, front_matter.docusaurus)),
+            output.templates.as_ref().map(|templates| templates.dir()),
+            output.charset.name(),
+            output.diagrams,
+            output.diagram_methods,
+            labels_settings()
+        )
+    }
+
+    /// Writes the manifest of an incremental run and removes the pages of deleted and
+    /// renamed types which no file of the run wrote
+    ///
+    /// # Arguments
+    ///
+    /// * `dest` - The destination directory
+    /// * `settings` - The options of the run
+    /// * `hashes` - Every java file of the run with the hash of its contents
+    /// * `unchanged` - The entries of the files which were up to date
+    /// * `previous_outputs` - The files written for the documented and deleted files in the last run
+    /// * `build` - The files documented in the run
+    fn finish_incremental(
+        dest: &str,
+        settings: &str,
+        hashes: &[(PathBuf, String)],
+        unchanged: BTreeMap<String, ManifestEntry>,
+        previous_outputs: Vec<String>,
+        build: Build,
+    ) -> Result<(), LojidocError> {
+        let mut manifest = Manifest::new(settings);
+        manifest.files = unchanged;
+
+        for file in build.files {
+            let hash = hashes
+                .iter()
+                .find(|(path, _)| *path == file.path)
+                .map(|(_, hash)| hash.clone())
+                .unwrap_or_default();
+
+            manifest.files.insert(
+                file.path.display().to_string(),
+                ManifestEntry {
+                    hash,
+                    outputs: file.outputs,
+                    members: file.members,
+                },
+            );
+        }
+        // The files which could not be parsed have no hash so they are parsed again
+        for (path, _) in hashes {
+            manifest
+                .files
+                .entry(path.display().to_string())
+                .or_default();
+        }
+
+        for output in previous_outputs {
+            let written = manifest
+                .files
+                .values()
+                .any(|entry| entry.outputs.contains(&output));
+
+            if !written && fs::remove_file(Path::new(dest).join(&output)).is_ok() {
+                verbose!("Removed {}", output);
+            }
+        }
+
+        write_manifest(dest, &manifest)
+    }
+
+    /// Adds the unchanged files of an incremental run whose types extend a type of a
+    /// changed file to the changed files, since they may inherit its method documentation
+    ///
+    /// Returns the types of the files which are still unchanged
+    fn plan_inheriting(plan: &mut Plan, charset: Charset) -> Vec<Project> {
+        let type_name = |path: &Path| path.file_stem().map(|stem| stem.to_string_lossy().to_string());
+        let mut changed: HashSet<String> = plan.changed.iter().filter_map(|path| type_name(path)).collect();
+        let mut unchanged: Vec<(String, Project)> = plan
+            .unchanged
+            .keys()
+            .filter_map(|path| Some((path.clone(), parse_types(Path::new(path), path, charset, &mut Vec::new()).ok()?)))
+            .collect();
+
+        loop {
+            let (inheriting, rest): (Vec<_>, Vec<_>) = unchanged
+                .into_iter()
+                .partition(|(_, project)| !supertype_names(project).is_disjoint(&changed));
+            unchanged = rest;
+
+            if inheriting.is_empty() {
+                break;
+            }
+            for (path, _) in inheriting {
+                if let Some(entry) = plan.unchanged.remove(&path) {
+                    plan.previous_outputs.extend(entry.outputs);
+                }
+                changed.extend(type_name(Path::new(&path)));
+                plan.changed.push(PathBuf::from(path));
+            }
+        }
+
+        unchanged.into_iter().map(|(_, project)| project).collect()
+    }
+
+    /// Finds the java files of every input, without the files found twice when inputs overlap,
+    /// e.g. a directory and a file in it, or when they are reached through other paths such
+    /// as `./src` and `src`
+    ///
+    /// Returns the java files and the errors of the paths which could not be read
+    pub fn find_input_files(inputs: &[&str], filter: &PathFilter) -> (Vec<PathBuf>, Vec<LojidocError>) {
+        let mut file_paths = Vec::new();
+        let mut errors = Vec::new();
+        let mut seen = HashSet::new();
+
+        for input in inputs.iter() {
+            let (files, failed) = find_java_files(Path::new(input), filter);
+
+            // A set keeps large trees from comparing every file with all files found before
+            for file in files {
+                if seen.insert(fs::canonicalize(&file).unwrap_or_else(|_| file.clone())) {
+                    file_paths.push(file);
+                }
+            }
+            errors.extend(failed);
+        }
+
+        (file_paths, errors)
+    }
+
+    /// Which java files of a run are documented
+    pub enum Refresh {
+        /// Every java file
+        All,
+        /// The files which changed since the last run in the destination
+        Incremental,
+        /// The files which changed in git since a ref, for `--changed-since`
+        ChangedSince {
+            changed: Vec<PathBuf>,
+            /// The pages of the types of the deleted files
+            deleted_outputs: Vec<String>,
+        },
+    }
+
+    /// The settings of a run of `generate`
+    pub struct Run<'a> {
+        /// The destination directory
+        pub dest: &'a str,
+        /// Where the source links of the pages point to
+        pub links: &'a SourceLinks,
+        /// The format and layout of the generated documentation
+        pub output: &'a Output,
+        /// The number of worker threads, a single thread is used when it is `None`
+        pub jobs: Option<usize>,
+        /// Which of the files are documented, the others are skipped
+        pub refresh: &'a Refresh,
+    }
+
+    /// Finds the java files of the inputs which changed in git since a ref, and the pages
+    /// of the types of the files which were deleted
+    ///
+    /// # Arguments
+    ///
+    /// * `inputs` - The input directories and java files
+    /// * `git_ref` - The branch, tag or commit the inputs are compared with
+    /// * `output` - The format and layout of the documentation
+    pub fn git_changes(inputs: &[&str], git_ref: &str, output: &Output) -> Result<Refresh, LojidocError> {
+        let mut changed = Vec::new();
+        let mut deleted_outputs = Vec::new();
+
+        for input in inputs {
+            let input = Path::new(input);
+            let dir = match input.parent() {
+                Some(dir) if input.is_file() && dir.as_os_str().is_empty() => Path::new("."),
+                Some(dir) if input.is_file() => dir,
+                _ => input,
+            };
+            let changes = changed_since(dir, git_ref)?;
+
+            // A java file which is an input was not deleted, the other files of its directory are not inputs
+            if !input.is_file() {
+                for path in changes.deleted {
+                    let objects = read_at(dir, git_ref, &path).and_then(|source| parse_str(&source, Some(&path)).ok());
+
+                    if let Some(objects) = objects {
+                        let project = project_of(objects, &path.display().to_string());
+                        deleted_outputs.extend(type_outputs(&project, output));
+                    }
+                }
+            }
+            changed.extend(changes.changed);
+        }
+        info!("{} java files changed since {}", changed.len(), git_ref);
+
+        Ok(Refresh::ChangedSince {
+            changed,
+            deleted_outputs,
+        })
+    }
+
+    /// Finds the pages and index entries of the unchanged files of a `--changed-since` run
+    /// which are not in the manifest, without writing their pages again
+    fn record_unchanged(plan: &mut Plan, links: &SourceLinks, output: &Output, type_names: &[String]) {
+        for path in plan.unrecorded.iter() {
+            let entry = match plan.unchanged.get_mut(&path.display().to_string()) {
+                Some(entry) => entry,
+                None => continue,
+            };
+            let mut project = match parse_types(path, &links.file_url(path), output.charset, &mut Vec::new()) {
+                Ok(project) => project,
+                // The file is parsed again in the next run
+                Err(_) => {
+                    entry.hash = String::new();
+                    continue;
+                }
+            };
+            filter_visibility(&mut project, output.min_visibility);
+            entry.outputs = type_outputs(&project, output);
+            if let Some(r) = output.format.renderer() {
+                let members = render_members(project, type_names, output, r).unwrap_or_default();
+
+                entry.members = members
+                    .into_iter()
+                    .map(|(page, member)| (page.package, member))
+                    .collect();
+            }
+        }
+    }
+
+    /// Documents java files with a single thread or with `jobs` threads. An incremental
+    /// run only documents the files which changed since the last run in the destination.
+    /// A `--changed-since` run documents the files which changed in git.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_paths` - Every java file of the project
+    /// * `run` - The destination, links, output, threads and refresh of the run
+    /// * `coverage` - The documentation coverage the parsed files are counted in
+    /// * `warnings` - The parse warnings of the run, only the documented files are parsed
+    ///
+    /// Returns the errors of the files which could not be parsed or written
+    pub fn generate(
+        file_paths: Vec<PathBuf>,
+        run: &Run,
+        coverage: &mut Coverage,
+        warnings: &mut Vec<Warning>,
+    ) -> Vec<LojidocError> {
+        let Run {
+            dest,
+            links,
+            output,
+            jobs,
+            refresh,
+        } = *run;
+        let (package_infos, file_paths): (Vec<PathBuf>, Vec<PathBuf>) =
+            file_paths.into_iter().partition(|file| is_package_info(file));
+        let (module_infos, file_paths): (Vec<PathBuf>, Vec<PathBuf>) =
+            file_paths.into_iter().partition(|file| is_module_info(file));
+        let mut build = Build::new(get_type_names(&file_paths));
+        let mut errors = Vec::new();
+
+        // The package info files are read in every run since they are not in the manifest
+        for file in package_infos {
+            match parse_package_info(&file, output.charset, warnings) {
+                Ok(package) => build.previous.add_package_doc(package.name, package.description),
+                Err(err) => errors.push(err),
+            }
+        }
+        errors.extend(document_modules(&module_infos, dest, output, warnings));
+
+        let settings = incremental_settings(output, links);
+        let mut file_paths = file_paths;
+        let mut hashes = Vec::new();
+        let mut unchanged = BTreeMap::new();
+        let mut previous_outputs = Vec::new();
+
+        let incremental = !matches!(refresh, Refresh::All);
+
+        if incremental {
+            hashes = file_paths
+                .iter()
+                .map(|file| {
+                    let hash = fs::read(file).map(|bytes| hash_contents(&bytes));
+                    (file.clone(), hash.unwrap_or_default())
+                }).collect();
+
+            let manifest = read_manifest(dest, &settings);
+            let mut plan = match refresh {
+                Refresh::ChangedSince {
+                    changed,
+                    deleted_outputs,
+                } => {
+                    let mut plan = plan_changed(manifest, &hashes, changed);
+                    plan.previous_outputs.extend(deleted_outputs.iter().cloned());
+                    record_unchanged(&mut plan, links, output, &build.type_names);
+                    plan
+                }
+                _ => plan(manifest, &hashes),
+            };
+            build.supertypes = plan_inheriting(&mut plan, output.charset);
+
+            for entry in plan.unchanged.values() {
+                for (package, member) in entry.members.iter() {
+                    build.previous.add_package_member(package.clone(), member.clone());
+                }
+            }
+            info!(
+                "{} up to date, {} regenerated",
+                plan.unchanged.len(),
+                plan.changed.len()
+            );
+
+            file_paths = plan.changed;
+            unchanged = plan.unchanged;
+            previous_outputs = plan.previous_outputs;
+        }
+
+        if !file_paths.is_empty() {
+            let (dest, links) = (dest.to_string(), links.clone());
+
+            errors.extend(match jobs {
+                Some(jobs) => document(file_paths, dest, links, output.clone(), jobs, coverage, &mut build),
+                None => document_single(file_paths, dest, links, output.clone(), coverage, &mut build),
+            });
+        } else if !previous_outputs.is_empty() {
+            // Only java files were deleted, the listings are written again without their types
+            let app_doc = mem::replace(&mut build.previous, ApplicationDoc::new());
+
+            if let Err(err) = write_listings(&app_doc, Project::new(), dest, output) {
+                errors.push(err);
+            }
+        }
+        warnings.append(&mut build.warnings);
+
+        // The pages of the files which failed are kept until they are documented again
+        let mut written: HashSet<String> = unchanged.values().flat_map(|entry| entry.outputs.clone()).collect();
+        written.extend(build.files.iter().flat_map(|file| file.outputs.clone()));
+        let failed = errors.len();
+
+        if incremental {
+            if let Err(err) = finish_incremental(dest, &settings, &hashes, unchanged, previous_outputs, build) {
+                errors.push(err);
+            }
+        }
+
+        if output.clean {
+            if failed == 0 {
+                errors.extend(clean_destination(dest, &written, output));
+            } else {
+                info!("Not cleaning {} since {} files failed", dest, failed);
+            }
+        }
+
+        errors
+    }
+
+    /// Creates the `markdown-book` directory of the markdown book with its title
+    pub fn init_book(title: &str) {
+        let mut cfg = Config::default();
+        cfg.book.title = Some(title.to_string());
+
+        let init_res = MDBook::init("./markdown-book").with_config(cfg).build();
+
+        if init_res.is_err() {
+            error!("Error initializing markdown book");
+        }
+    }
+
+    /// Writes the coverage report of `--coverage-output` to the destination, e.g.
+    /// `coverage.md`, or the coverage as json for the `json` extension
+    pub fn write_coverage_report(coverage: &Coverage, dest: &str, extension: &str) -> Result<(), LojidocError> {
+        let path = Path::new(dest).join(format!("coverage.{}", extension));
+        let contents = if extension == "json" {
+            coverage.to_json()
+        } else {
+            coverage.report()
+        };
+
+        fs::create_dir_all(dest).map_err(|err| LojidocError::io(Path::new(dest), err))?;
+        fs::write(&path, contents).map_err(|err| LojidocError::io(&path, err))?;
+        info!("Coverage report written to {}", path.display());
+
+        Ok(())
+    }
+
+    /// Writes the statistics of a run to `stats.json` in the destination for `--stats`
+    pub fn write_stats(stats: &Stats, dest: &str) -> Result<(), LojidocError> {
+        let path = Path::new(dest).join("stats.json");
+
+        fs::write(&path, stats.to_json()).map_err(|err| LojidocError::io(&path, err))?;
+        verbose!("Stats written to {}", path.display());
+
+        Ok(())
+    }
+
+    /// The time the watcher waits for more events of the same file, so the saves of an
+    /// editor or of many files at once are documented together
+    const WATCH_DELAY: Duration = Duration::from_millis(300);
+
+    /// Gets the java file of an event of the watcher
+    fn changed_java_file(event: DebouncedEvent) -> Option<PathBuf> {
+        let path = match event {
+            DebouncedEvent::Create(path)
+            | DebouncedEvent::Write(path)
+            | DebouncedEvent::Remove(path)
+            | DebouncedEvent::Rename(_, path) => path,
+            _ => return None,
+        };
+
+        if path.extension().is_some_and(|extension| extension == "java") {
+            Some(path)
+        } else {
+            None
+        }
+    }
+
+    /// Documents the inputs again whenever a java file in them is created, changed or deleted.
+    /// Only the changed files are documented, like in an incremental run.
+    ///
+    /// # Arguments
+    ///
+    /// * `inputs` - The input directories and java files
+    /// * `filter` - The patterns of the files which are excluded or included
+    /// * `dest` - The destination directory
+    /// * `links` - Where the source links of the pages point to
+    /// * `output` - The format and layout of the generated documentation
+    /// * `jobs` - The number of worker threads, a single thread is used when it is `None`
+    /// * `report` - Reports the warnings and errors of each run
+    ///
+    /// Returns an error when the inputs cannot be watched
+    pub fn watch<F>(
+        inputs: &[&str],
+        filter: &PathFilter,
+        dest: &str,
+        links: &SourceLinks,
+        output: &Output,
+        jobs: Option<usize>,
+        report: F,
+    ) -> Result<(), LojidocError>
+    where
+        F: Fn(&[Warning], &[LojidocError]),
+    {
+        // The errors of the watcher are not io errors, they are reported for the input
+        let watch_error = |input: &str, err: notify::Error| {
+            LojidocError::io(Path::new(input), io::Error::other(format!("Unable to watch: {}", err)))
+        };
+        let (sender, receiver) = mpsc::channel();
+        let mut watcher = notify::watcher(sender, WATCH_DELAY).map_err(|err| watch_error(&inputs.join(", "), err))?;
+
+        for input in inputs {
+            watcher
+                .watch(input, RecursiveMode::Recursive)
+                .map_err(|err| watch_error(input, err))?;
+        }
+        info!("Watching {} for changes", inputs.join(", "));
+
+        while let Ok(event) = receiver.recv() {
+            let mut changed: Vec<PathBuf> = changed_java_file(event).into_iter().collect();
+
+            // The other files of the same save arrive within the delay of the watcher
+            while let Ok(event) = receiver.recv_timeout(WATCH_DELAY) {
+                if let Some(path) = changed_java_file(event) {
+                    if !changed.contains(&path) {
+                        changed.push(path);
+                    }
+                }
+            }
+            if changed.is_empty() {
+                continue;
+            }
+
+            for path in changed.iter() {
+                info!("{} changed", path.display());
+            }
+
+            let (file_paths, mut errors) = find_input_files(inputs, filter);
+            let mut coverage = Coverage::new(false);
+            let mut warnings = Vec::new();
+            let output = Output {
+                clean: output.clean && errors.is_empty(),
+                ..output.clone()
+            };
+
+            let run = Run {
+                dest,
+                links,
+                output: &output,
+                jobs,
+                refresh: &Refresh::Incremental,
+            };
+            errors.extend(generate(file_paths, &run, &mut coverage, &mut warnings));
+            report(&warnings, &errors);
+        }
+
+        Ok(())
+    }
+
+    /// Writes every documented type to `API.md`, or to `api.json` for the json format
+    fn write_single_file(mut project: Project, dest: &str, output: &Output) -> Result<(), LojidocError> {
+        match output.format {
+            Format::Json => generate_api_json(&mut project, dest, &output.kept),
+            _ => generate_single_page(project, dest, output),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test;
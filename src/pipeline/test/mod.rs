@@ -0,0 +1,438 @@
+use coverage::coverage::Coverage;
+use document::document::find_java_files;
+use document::document::Format;
+use document::document::MethodLayout;
+use document::document::Output;
+use document::document::PathFilter;
+use document::document::SourceLinks;
+use document::document::Visibility;
+use incremental::incremental::Build;
+use parse::parse::Charset;
+use pipeline::pipeline::document;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+#[test]
+fn test_document_every_file() {
+    let root = env::temp_dir().join(format!("lojidoc-document-{}", std::process::id()));
+    let src = root.join("src");
+    let dest = root.join("generated");
+
+    fs::create_dir_all(&src).unwrap();
+    fs::create_dir_all(&dest).unwrap();
+
+    for i in 0..9 {
+        let source = format!("package com.example;\n\npublic class Class{} {{\n}}\n", i);
+        fs::write(src.join(format!("Class{}.java", i)), source).unwrap();
+    }
+
+    let mut coverage = Coverage::new(false);
+    let mut build = Build::new(Vec::new());
+    let errors = document(
+        find_java_files(&src, &PathFilter::default()).0,
+        dest.to_str().unwrap().to_string(),
+        SourceLinks::default(),
+        Output {
+            format: Format::Markdown,
+            book: false,
+            flat: false,
+            single_file: false,
+            mdbook: false,
+            docusaurus: false,
+            min_visibility: Visibility::Private,
+            methods: MethodLayout::default(),
+            hide_jdk_imports: false,
+            front_matter: None,
+            templates: None,
+            clean: false,
+            diagrams: false,
+            diagram_methods: false,
+            kept: Default::default(),
+            charset: Charset::Auto,
+        },
+        4,
+        &mut coverage,
+        &mut build,
+    );
+
+    let generated = fs::read_dir(dest.join("com").join("example"))
+        .unwrap()
+        .filter(|entry| entry.as_ref().unwrap().path().extension() == Some("md".as_ref()))
+        .count();
+
+    fs::remove_dir_all(&root).unwrap();
+
+    assert!(errors.is_empty());
+    assert_eq!(9, coverage.packages["com.example"].types.total);
+    assert_eq!(9, generated);
+    assert_eq!(9, build.files.len());
+}
+
+/// Reads every file in a directory tree by its path relative to `root`
+fn read_tree(root: &Path, dir: &Path, files: &mut BTreeMap<String, Vec<u8>>) {
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+
+        if path.is_dir() {
+            read_tree(root, &path, files);
+        } else {
+            let relative = path.strip_prefix(root).unwrap().display().to_string();
+            files.insert(relative, fs::read(&path).unwrap());
+        }
+    }
+}
+
+#[test]
+fn test_output_is_deterministic() {
+    let root = env::temp_dir().join(format!("lojidoc-deterministic-{}", std::process::id()));
+    let src = root.join("src");
+
+    for (i, package) in ["com.foo", "com.bar", "org.baz"].iter().enumerate() {
+        let dir = src.join(package.replace('.', "/"));
+        fs::create_dir_all(&dir).unwrap();
+
+        for j in 0..6 {
+            let source = format!(
+                "package {};\n\n/** Type {} uses {{@link com.foo.Type0}} */\npublic class Type{} {{\n    \
+                 /** Finds the type */\n    public void find(int id) {{\n    }}\n}}\n",
+                package, j, i * 10 + j
+            );
+            fs::write(dir.join(format!("Type{}.java", i * 10 + j)), source).unwrap();
+        }
+    }
+
+    let run = |name: &str, format: Format, single_file: bool| {
+        let dest = root.join(name);
+        let output = Output {
+            format,
+            book: false,
+            flat: false,
+            single_file,
+            mdbook: false,
+            docusaurus: false,
+            min_visibility: Visibility::Private,
+            methods: MethodLayout::default(),
+            hide_jdk_imports: false,
+            front_matter: None,
+            templates: None,
+            clean: false,
+            diagrams: false,
+            diagram_methods: false,
+            kept: Default::default(),
+            charset: Charset::Auto,
+        };
+        let mut coverage = Coverage::new(false);
+        let mut build = Build::new(Vec::new());
+        let files = find_java_files(&src, &PathFilter::default()).0;
+
+        fs::create_dir_all(&dest).unwrap();
+        document(
+            files,
+            dest.to_str().unwrap().to_string(),
+            SourceLinks::default(),
+            output,
+            4,
+            &mut coverage,
+            &mut build,
+        );
+
+        let mut tree = BTreeMap::new();
+        read_tree(&dest, &dest, &mut tree);
+        tree
+    };
+
+    let pages = (run("markdown-1", Format::Markdown, false), run("markdown-2", Format::Markdown, false));
+    let single = (run("single-1", Format::Markdown, true), run("single-2", Format::Markdown, true));
+    let json = (run("json-1", Format::Json, true), run("json-2", Format::Json, true));
+    let files = find_java_files(&src, &PathFilter::default()).0;
+
+    fs::remove_dir_all(&root).unwrap();
+
+    assert_eq!(20, pages.0.len());
+    assert_eq!(pages.0, pages.1);
+    assert_eq!(single.0, single.1);
+    assert_eq!(json.0, json.1);
+
+    let mut sorted = files.clone();
+    sorted.sort();
+    assert_eq!(sorted, files);
+}
+
+#[test]
+fn test_single_threaded_output_matches_the_workers() {
+    use pipeline::pipeline::document_single;
+
+    let root = env::temp_dir().join(format!("lojidoc-single-threaded-{}", std::process::id()));
+    let src = root.join("src");
+    let dir = src.join("com").join("example");
+    fs::create_dir_all(&dir).unwrap();
+
+    for i in 0..4 {
+        let source = format!(
+            "package com.example;\n\n/** Type {} */\npublic class Type{} {{\n    \
+             /** Finds the type */\n    public void find(int id) {{\n    }}\n}}\n",
+            i, i
+        );
+        fs::write(dir.join(format!("Type{}.java", i)), source).unwrap();
+    }
+
+    let run = |name: &str, links: &SourceLinks, jobs: Option<usize>| {
+        let dest = root.join(name);
+        let output = Output {
+            format: Format::Markdown,
+            book: false,
+            flat: false,
+            single_file: false,
+            mdbook: false,
+            docusaurus: false,
+            min_visibility: Visibility::Private,
+            methods: MethodLayout::default(),
+            hide_jdk_imports: false,
+            front_matter: None,
+            templates: None,
+            clean: false,
+            diagrams: false,
+            diagram_methods: false,
+            kept: Default::default(),
+            charset: Charset::Auto,
+        };
+        let mut coverage = Coverage::new(false);
+        let mut build = Build::new(Vec::new());
+        let files = find_java_files(&src, &PathFilter::default()).0;
+        let dest_dir = dest.to_str().unwrap().to_string();
+
+        fs::create_dir_all(&dest).unwrap();
+        let errors = match jobs {
+            Some(jobs) => document(files, dest_dir, links.clone(), output, jobs, &mut coverage, &mut build),
+            None => document_single(files, dest_dir, links.clone(), output, &mut coverage, &mut build),
+        };
+        assert!(errors.is_empty());
+
+        let mut tree = BTreeMap::new();
+        read_tree(&dest, &dest, &mut tree);
+        tree
+    };
+
+    let no_links = SourceLinks::default();
+    let repo = SourceLinks {
+        context: "https://example.com/repo/".to_string(),
+        ..SourceLinks::default()
+    };
+    let unlinked = (run("unlinked-single", &no_links, None), run("unlinked-workers", &no_links, Some(4)));
+    let linked = (run("linked-single", &repo, None), run("linked-workers", &repo, Some(4)));
+
+    fs::remove_dir_all(&root).unwrap();
+
+    assert_eq!(unlinked.0, unlinked.1);
+    assert_eq!(linked.0, linked.1);
+    let page = String::from_utf8(unlinked.0["com/example/Type0.md"].clone()).unwrap();
+    assert!(!page.contains("[src]"), "{}", page);
+    let page = String::from_utf8(linked.0["com/example/Type0.md"].clone()).unwrap();
+    assert!(page.contains("https://example.com/repo/"), "{}", page);
+}
+
+#[test]
+fn test_doc_text_end_to_end() {
+    let root = env::temp_dir().join(format!("lojidoc-doc-text-{}", std::process::id()));
+    let src = root.join("src");
+    fs::create_dir_all(&src).unwrap();
+    fs::write(
+        src.join("Calc.java"),
+        "package com.example;\n\n\
+         /**\n \
+          * Clamps to max_value when 2 < 3 and 2 * 3 is 6.\n \
+          * Options:\n \
+          *  * first\n \
+          *  * second\n \
+          */\n\
+         public class Calc {\n}\n",
+    )
+    .unwrap();
+
+    let run = |name: &str, format: Format| {
+        let dest = root.join(name);
+        let output = Output {
+            format,
+            book: false,
+            flat: true,
+            single_file: false,
+            mdbook: false,
+            docusaurus: false,
+            min_visibility: Visibility::Private,
+            methods: MethodLayout::default(),
+            hide_jdk_imports: false,
+            front_matter: None,
+            templates: None,
+            clean: false,
+            diagrams: false,
+            diagram_methods: false,
+            kept: Default::default(),
+            charset: Charset::Auto,
+        };
+        let mut coverage = Coverage::new(false);
+        let mut build = Build::new(Vec::new());
+
+        fs::create_dir_all(&dest).unwrap();
+        document(
+            find_java_files(&src, &PathFilter::default()).0,
+            dest.to_str().unwrap().to_string(),
+            SourceLinks::default(),
+            output,
+            1,
+            &mut coverage,
+            &mut build,
+        );
+        dest
+    };
+
+    let markdown = fs::read_to_string(run("markdown", Format::Markdown).join("Calc.md")).unwrap();
+    let json = fs::read_to_string(run("json", Format::Json).join("Calc.json")).unwrap();
+    fs::remove_dir_all(&root).unwrap();
+
+    // The model only escapes the emphasis, code span and link characters of the text,
+    // the markdown page escapes the rest
+    assert!(markdown.contains("Clamps to max_value when 2 \\< 3 and 2 * 3 is 6. Options:\n > - first\n > - second"));
+    assert!(json.contains("\"description\": \"Clamps to max_value when 2 < 3 and 2 * 3 is 6. Options:\\n- first\\n- second\""));
+}
+
+#[test]
+fn test_changed_since_with_deleted_files() {
+    use document::document::api_index_path;
+    use pipeline::pipeline::generate;
+    use pipeline::pipeline::Refresh;
+    use pipeline::pipeline::Run;
+
+    let root = env::temp_dir().join(format!("lojidoc-deleted-{}", std::process::id()));
+    let src = root.join("src");
+    let dest = root.join("generated");
+    fs::create_dir_all(&src).unwrap();
+
+    for name in &["Kept", "Removed"] {
+        let source = format!("package com.example;\n\npublic class {} {{\n    public void run() {{\n    }}\n}}\n", name);
+        fs::write(src.join(format!("{}.java", name)), source).unwrap();
+    }
+
+    let output = Output {
+        format: Format::Markdown,
+        book: false,
+        flat: false,
+        single_file: false,
+        mdbook: false,
+        docusaurus: false,
+        min_visibility: Visibility::Private,
+        methods: MethodLayout::default(),
+        hide_jdk_imports: false,
+        front_matter: None,
+        templates: None,
+        clean: false,
+        diagrams: false,
+        diagram_methods: false,
+        kept: Default::default(),
+        charset: Charset::Auto,
+    };
+    let run = |refresh: Refresh| {
+        let mut coverage = Coverage::new(false);
+        let mut warnings = Vec::new();
+        let run = Run {
+            dest: dest.to_str().unwrap(),
+            links: &SourceLinks::default(),
+            output: &output,
+            jobs: None,
+            refresh: &refresh,
+        };
+        generate(
+            find_java_files(&src, &PathFilter::default()).0,
+            &run,
+            &mut coverage,
+            &mut warnings,
+        )
+    };
+
+    assert!(run(Refresh::Incremental).is_empty());
+    assert!(fs::read_to_string(dest.join("index.md")).unwrap().contains("Removed"));
+
+    // Only a file was deleted, so no page is written but the listings leave out its type
+    fs::remove_file(src.join("Removed.java")).unwrap();
+    let errors = run(Refresh::ChangedSince {
+        changed: Vec::new(),
+        deleted_outputs: vec!["com/example/Removed.md".to_string()],
+    });
+    let index = fs::read_to_string(dest.join("index.md")).unwrap();
+    let api_index = fs::read_to_string(dest.join(api_index_path("md"))).unwrap();
+    let removed = dest.join("com").join("example").join("Removed.md").exists();
+
+    fs::remove_dir_all(&root).unwrap();
+
+    assert!(errors.is_empty());
+    assert!(!removed);
+    assert!(index.contains("Kept"));
+    assert!(!index.contains("Removed"));
+    assert!(api_index.contains("Kept"));
+    assert!(!api_index.contains("Removed"));
+}
+
+/// Times finding the java files of a deep tree given with overlapping inputs, with the
+/// linear search which compared every file with all files found before it and with the
+/// set of `find_input_files`. Run it with
+/// `cargo test --release -- --ignored bench_find_input_files --nocapture`.
+#[test]
+#[ignore]
+fn bench_find_input_files() {
+    use pipeline::pipeline::find_input_files;
+    use std::path::PathBuf;
+    use std::time::Instant;
+
+    let root = env::temp_dir().join(format!("lojidoc-bench-{}", std::process::id()));
+
+    // A binary tree of packages 10 levels deep with 10 files in each package
+    let mut packages = vec![root.clone()];
+    let mut inputs = vec![root.display().to_string()];
+    for depth in 0..10 {
+        packages = packages
+            .iter()
+            .flat_map(|dir| vec![dir.join(format!("a{}", depth)), dir.join(format!("b{}", depth))])
+            .collect();
+
+        // The files below the third and sixth levels are found again from their packages
+        if depth == 2 || depth == 5 {
+            inputs.extend(packages.iter().map(|dir| dir.display().to_string()));
+        }
+        for dir in packages.iter() {
+            fs::create_dir_all(dir).unwrap();
+
+            for class in 0..10 {
+                fs::write(dir.join(format!("Type{}.java", class)), "public class Type {}\n").unwrap();
+            }
+        }
+    }
+    let inputs: Vec<&str> = inputs.iter().map(|input| input.as_str()).collect();
+
+    let started = Instant::now();
+    let mut linear: Vec<PathBuf> = Vec::new();
+    for input in inputs.iter() {
+        for file in find_java_files(Path::new(input), &PathFilter::default()).0 {
+            if !linear.contains(&file) {
+                linear.push(file);
+            }
+        }
+    }
+    let linear_elapsed = started.elapsed();
+
+    let started = Instant::now();
+    let (files, errors) = find_input_files(&inputs, &PathFilter::default());
+    let set_elapsed = started.elapsed();
+
+    fs::remove_dir_all(&root).unwrap();
+
+    println!(
+        "Found {} java files in {:.2}s with a linear search and in {:.2}s with a set",
+        files.len(),
+        linear_elapsed.as_secs_f64(),
+        set_elapsed.as_secs_f64()
+    );
+    assert!(errors.is_empty());
+    assert_eq!(linear, files);
+    assert_eq!(20460, files.len());
+}
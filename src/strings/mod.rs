@@ -0,0 +1,449 @@
+pub mod strings {
+    //! Module with the labels lojidoc writes on the pages, such as the headings of the
+    //! sections, in the language set by `--lang` or replaced by a `--strings` file.
+    //! The javadoc of the types is written as it is.
+
+    use std::collections::BTreeMap;
+    use std::fs;
+    use std::path::Path;
+    use std::sync::RwLock;
+
+    use toml;
+
+    use error::error::LojidocError;
+
+    /// The languages the labels are shipped in
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub enum Lang {
+        En,
+        De,
+        Fr,
+        Es,
+    }
+
+    impl Lang {
+        /// Reads the name of a language used by `--lang`
+        pub fn from_name(name: &str) -> Option<Lang> {
+            match name {
+                "en" => Some(Lang::En),
+                "de" => Some(Lang::De),
+                "fr" => Some(Lang::Fr),
+                "es" => Some(Lang::Es),
+                _ => None,
+            }
+        }
+        pub fn name(&self) -> &'static str {
+            match self {
+                Lang::En => "en",
+                Lang::De => "de",
+                Lang::Fr => "fr",
+                Lang::Es => "es",
+            }
+        }
+        /// Gets the labels of the language by key
+        pub fn labels(&self) -> &'static [(&'static str, &'static str)] {
+            match self {
+                Lang::En => EN,
+                Lang::De => DE,
+                Lang::Fr => FR,
+                Lang::Es => ES,
+            }
+        }
+    }
+
+    /// The English labels by key, which every other language falls back to. A `{}` is
+    /// replaced by a name or a number.
+    pub const EN: &[(&str, &str)] = &[
+        ("access", "Access"),
+        ("annotation", "Annotation {}"),
+        ("api", "API"),
+        ("api_documentation", "API Documentation"),
+        ("api_index", "API Index"),
+        ("author", "Author"),
+        ("bound", "Bound"),
+        ("class", "Class {}"),
+        ("class_diagram", "Class diagram of {}"),
+        ("components", "Components"),
+        ("constants", "Constants"),
+        ("constructors", "Constructors"),
+        ("contents", "Contents"),
+        ("default", "Default"),
+        ("default_package", "Default package"),
+        ("dependencies", "Dependencies"),
+        ("deprecated", "Deprecated"),
+        ("deprecated_notice", "this API is deprecated and may be removed in a future version"),
+        ("description", "Description"),
+        ("elements", "Elements"),
+        ("enum", "Enum {}"),
+        ("exceptions", "Exceptions"),
+        ("exports", "Exports"),
+        ("fields", "Fields"),
+        ("index", "Index"),
+        ("inheritance", "Inheritance"),
+        ("inner_classes", "Inner Classes"),
+        ("interface", "Interface {}"),
+        ("jdk", "JDK"),
+        ("methods", "Methods"),
+        ("modifiers", "Modifiers"),
+        ("module", "Module {}"),
+        ("module_column", "Module"),
+        ("name", "Name"),
+        ("no_constants", "No constants in this enum"),
+        ("no_elements", "No elements in this annotation"),
+        ("no_fields", "No fields in this class"),
+        ("no_methods", "No methods in this class"),
+        ("no_parameters", "This method has no parameters."),
+        ("no_public_methods", "No public methods"),
+        ("opens", "Opens"),
+        ("package", "package"),
+        ("package_column", "Package"),
+        ("package_methods", "Package-private methods"),
+        ("package_private", "package-private"),
+        ("package_title", "Package {}"),
+        ("permitted_subclasses", "Permitted subclasses"),
+        ("private_methods", "Private methods"),
+        ("project", "Project"),
+        ("protected_methods", "Protected methods"),
+        ("provides", "Provides"),
+        ("public_methods", "Public methods"),
+        ("record", "Record {}"),
+        ("requires", "Requires"),
+        ("retention", "Retention"),
+        ("return", "return"),
+        ("returns", "returns"),
+        ("service", "Service"),
+        ("show_dependencies", "Show dependencies"),
+        ("show_license", "Show license"),
+        ("since", "Since"),
+        ("source_omitted", "Source omitted ({} lines)"),
+        ("summary", "Summary"),
+        ("target", "Target"),
+        ("third_party", "Third-party"),
+        ("throws", "Throws {}"),
+        ("to", "To"),
+        ("type", "Type"),
+        ("type_parameters", "Type Parameters"),
+        ("uses", "Uses"),
+        ("value", "Value"),
+        ("version", "Version"),
+        ("with", "With"),
+    ];
+
+    const DE: &[(&str, &str)] = &[
+        ("access", "Zugriff"),
+        ("annotation", "Annotation {}"),
+        ("api", "API"),
+        ("api_documentation", "API-Dokumentation"),
+        ("api_index", "API-Index"),
+        ("author", "Autor"),
+        ("bound", "Schranke"),
+        ("class", "Klasse {}"),
+        ("class_diagram", "Klassendiagramm von {}"),
+        ("components", "Komponenten"),
+        ("constants", "Konstanten"),
+        ("constructors", "Konstruktoren"),
+        ("contents", "Inhalt"),
+        ("default", "Standardwert"),
+        ("default_package", "Standardpaket"),
+        ("dependencies", "Abhängigkeiten"),
+        ("deprecated", "Veraltet"),
+        ("deprecated_notice", "diese API ist veraltet und wird möglicherweise in einer zukünftigen Version entfernt"),
+        ("description", "Beschreibung"),
+        ("elements", "Elemente"),
+        ("enum", "Enum {}"),
+        ("exceptions", "Ausnahmen"),
+        ("exports", "Exportiert"),
+        ("fields", "Felder"),
+        ("index", "Index"),
+        ("inheritance", "Vererbung"),
+        ("inner_classes", "Innere Klassen"),
+        ("interface", "Interface {}"),
+        ("jdk", "JDK"),
+        ("methods", "Methoden"),
+        ("modifiers", "Modifikatoren"),
+        ("module", "Modul {}"),
+        ("module_column", "Modul"),
+        ("name", "Name"),
+        ("no_constants", "Keine Konstanten in diesem Enum"),
+        ("no_elements", "Keine Elemente in dieser Annotation"),
+        ("no_fields", "Keine Felder in dieser Klasse"),
+        ("no_methods", "Keine Methoden in dieser Klasse"),
+        ("no_parameters", "Diese Methode hat keine Parameter."),
+        ("no_public_methods", "Keine öffentlichen Methoden"),
+        ("opens", "Öffnet"),
+        ("package", "Paket"),
+        ("package_column", "Paket"),
+        ("package_methods", "Paketprivate Methoden"),
+        ("package_private", "paketprivat"),
+        ("package_title", "Paket {}"),
+        ("permitted_subclasses", "Erlaubte Unterklassen"),
+        ("private_methods", "Private Methoden"),
+        ("project", "Projekt"),
+        ("protected_methods", "Geschützte Methoden"),
+        ("provides", "Stellt bereit"),
+        ("public_methods", "Öffentliche Methoden"),
+        ("record", "Record {}"),
+        ("requires", "Benötigt"),
+        ("retention", "Aufbewahrung"),
+        ("return", "Rückgabe"),
+        ("returns", "Rückgabe"),
+        ("service", "Dienst"),
+        ("show_dependencies", "Abhängigkeiten anzeigen"),
+        ("show_license", "Lizenz anzeigen"),
+        ("since", "Seit"),
+        ("source_omitted", "Quelltext ausgelassen ({} Zeilen)"),
+        ("summary", "Zusammenfassung"),
+        ("target", "Ziel"),
+        ("third_party", "Drittanbieter"),
+        ("throws", "Wirft {}"),
+        ("to", "An"),
+        ("type", "Typ"),
+        ("type_parameters", "Typparameter"),
+        ("uses", "Verwendet"),
+        ("value", "Wert"),
+        ("version", "Version"),
+        ("with", "Mit"),
+    ];
+
+    const FR: &[(&str, &str)] = &[
+        ("access", "Accès"),
+        ("annotation", "Annotation {}"),
+        ("api", "API"),
+        ("api_documentation", "Documentation de l'API"),
+        ("api_index", "Index de l'API"),
+        ("author", "Auteur"),
+        ("bound", "Borne"),
+        ("class", "Classe {}"),
+        ("class_diagram", "Diagramme de classes de {}"),
+        ("components", "Composants"),
+        ("constants", "Constantes"),
+        ("constructors", "Constructeurs"),
+        ("contents", "Sommaire"),
+        ("default", "Valeur par défaut"),
+        ("default_package", "Paquetage par défaut"),
+        ("dependencies", "Dépendances"),
+        ("deprecated", "Obsolète"),
+        ("deprecated_notice", "cette API est obsolète et pourra être supprimée dans une version future"),
+        ("description", "Description"),
+        ("elements", "Éléments"),
+        ("enum", "Énumération {}"),
+        ("exceptions", "Exceptions"),
+        ("exports", "Exporte"),
+        ("fields", "Champs"),
+        ("index", "Index"),
+        ("inheritance", "Héritage"),
+        ("inner_classes", "Classes internes"),
+        ("interface", "Interface {}"),
+        ("jdk", "JDK"),
+        ("methods", "Méthodes"),
+        ("modifiers", "Modificateurs"),
+        ("module", "Module {}"),
+        ("module_column", "Module"),
+        ("name", "Nom"),
+        ("no_constants", "Aucune constante dans cette énumération"),
+        ("no_elements", "Aucun élément dans cette annotation"),
+        ("no_fields", "Aucun champ dans cette classe"),
+        ("no_methods", "Aucune méthode dans cette classe"),
+        ("no_parameters", "Cette méthode n'a pas de paramètres."),
+        ("no_public_methods", "Aucune méthode publique"),
+        ("opens", "Ouvre"),
+        ("package", "paquetage"),
+        ("package_column", "Paquetage"),
+        ("package_methods", "Méthodes privées au paquetage"),
+        ("package_private", "privé au paquetage"),
+        ("package_title", "Paquetage {}"),
+        ("permitted_subclasses", "Sous-classes autorisées"),
+        ("private_methods", "Méthodes privées"),
+        ("project", "Projet"),
+        ("protected_methods", "Méthodes protégées"),
+        ("provides", "Fournit"),
+        ("public_methods", "Méthodes publiques"),
+        ("record", "Record {}"),
+        ("requires", "Requiert"),
+        ("retention", "Rétention"),
+        ("return", "retour"),
+        ("returns", "retourne"),
+        ("service", "Service"),
+        ("show_dependencies", "Afficher les dépendances"),
+        ("show_license", "Afficher la licence"),
+        ("since", "Depuis"),
+        ("source_omitted", "Source omise ({} lignes)"),
+        ("summary", "Résumé"),
+        ("target", "Cible"),
+        ("third_party", "Tiers"),
+        ("throws", "Lève {}"),
+        ("to", "Vers"),
+        ("type", "Type"),
+        ("type_parameters", "Paramètres de type"),
+        ("uses", "Utilise"),
+        ("value", "Valeur"),
+        ("version", "Version"),
+        ("with", "Avec"),
+    ];
+
+    const ES: &[(&str, &str)] = &[
+        ("access", "Acceso"),
+        ("annotation", "Anotación {}"),
+        ("api", "API"),
+        ("api_documentation", "Documentación de la API"),
+        ("api_index", "Índice de la API"),
+        ("author", "Autor"),
+        ("bound", "Límite"),
+        ("class", "Clase {}"),
+        ("class_diagram", "Diagrama de clases de {}"),
+        ("components", "Componentes"),
+        ("constants", "Constantes"),
+        ("constructors", "Constructores"),
+        ("contents", "Contenido"),
+        ("default", "Valor predeterminado"),
+        ("default_package", "Paquete predeterminado"),
+        ("dependencies", "Dependencias"),
+        ("deprecated", "Obsoleto"),
+        ("deprecated_notice", "esta API está obsoleta y puede eliminarse en una versión futura"),
+        ("description", "Descripción"),
+        ("elements", "Elementos"),
+        ("enum", "Enumeración {}"),
+        ("exceptions", "Excepciones"),
+        ("exports", "Exporta"),
+        ("fields", "Campos"),
+        ("index", "Índice"),
+        ("inheritance", "Herencia"),
+        ("inner_classes", "Clases internas"),
+        ("interface", "Interfaz {}"),
+        ("jdk", "JDK"),
+        ("methods", "Métodos"),
+        ("modifiers", "Modificadores"),
+        ("module", "Módulo {}"),
+        ("module_column", "Módulo"),
+        ("name", "Nombre"),
+        ("no_constants", "No hay constantes en esta enumeración"),
+        ("no_elements", "No hay elementos en esta anotación"),
+        ("no_fields", "No hay campos en esta clase"),
+        ("no_methods", "No hay métodos en esta clase"),
+        ("no_parameters", "Este método no tiene parámetros."),
+        ("no_public_methods", "No hay métodos públicos"),
+        ("opens", "Abre"),
+        ("package", "paquete"),
+        ("package_column", "Paquete"),
+        ("package_methods", "Métodos privados del paquete"),
+        ("package_private", "privado del paquete"),
+        ("package_title", "Paquete {}"),
+        ("permitted_subclasses", "Subclases permitidas"),
+        ("private_methods", "Métodos privados"),
+        ("project", "Proyecto"),
+        ("protected_methods", "Métodos protegidos"),
+        ("provides", "Proporciona"),
+        ("public_methods", "Métodos públicos"),
+        ("record", "Record {}"),
+        ("requires", "Requiere"),
+        ("retention", "Retención"),
+        ("return", "retorno"),
+        ("returns", "devuelve"),
+        ("service", "Servicio"),
+        ("show_dependencies", "Mostrar dependencias"),
+        ("show_license", "Mostrar licencia"),
+        ("since", "Desde"),
+        ("source_omitted", "Código omitido ({} líneas)"),
+        ("summary", "Resumen"),
+        ("target", "Destino"),
+        ("third_party", "Terceros"),
+        ("throws", "Lanza {}"),
+        ("to", "A"),
+        ("type", "Tipo"),
+        ("type_parameters", "Parámetros de tipo"),
+        ("uses", "Usa"),
+        ("value", "Valor"),
+        ("version", "Versión"),
+        ("with", "Con"),
+    ];
+
+    /// The labels of a run
+    #[derive(Debug, PartialEq)]
+    pub struct Labels {
+        pub lang: Lang,
+        /// The labels of a `--strings` file, which replace the labels of the language
+        pub custom: BTreeMap<String, String>,
+    }
+
+    impl Labels {
+        pub const fn new(lang: Lang, custom: BTreeMap<String, String>) -> Labels {
+            Labels { lang, custom }
+        }
+        /// Gets a label, in English when the language has none
+        pub fn get(&self, key: &str) -> String {
+            match self.custom.get(key) {
+                Some(text) => text.clone(),
+                None => self
+                    .lang
+                    .labels()
+                    .iter()
+                    .find(|(name, _)| *name == key)
+                    .map_or_else(|| english_label(key), |(_, text)| *text)
+                    .to_string(),
+            }
+        }
+    }
+
+    static LABELS: RwLock<Labels> = RwLock::new(Labels::new(Lang::En, BTreeMap::new()));
+
+    /// Sets the labels of the pages for every thread
+    pub fn set_labels(labels: Labels) {
+        *LABELS.write().unwrap_or_else(|err| err.into_inner()) = labels;
+    }
+
+    /// Gets the language and the replaced labels of the run, which change the pages
+    pub fn labels_settings() -> String {
+        let labels = LABELS.read().unwrap_or_else(|err| err.into_inner());
+
+        if labels.custom.is_empty() {
+            labels.lang.name().to_string()
+        } else {
+            format!("{} {:?}", labels.lang.name(), labels.custom)
+        }
+    }
+
+    /// Gets a label of the pages in the labels of the run
+    pub fn label(key: &str) -> String {
+        LABELS.read().unwrap_or_else(|err| err.into_inner()).get(key)
+    }
+
+    /// Gets the English label of a key, the key itself when there is none
+    pub fn english_label(key: &str) -> &str {
+        EN.iter().find(|(name, _)| *name == key).map_or(key, |(_, text)| *text)
+    }
+
+    /// Gets a label with its `{}` replaced by a name or number, e.g. `Class Foo`
+    pub fn label_of(key: &str, value: &str) -> String {
+        label(key).replacen("{}", value, 1)
+    }
+
+    /// Reads a `--strings` file, a TOML table of labels by key such as
+    /// `methods = "Metodi"`. The keys which lojidoc does not know are left out.
+    ///
+    /// Returns the labels with a warning for each unknown key
+    pub fn read_strings(path: &Path) -> Result<(BTreeMap<String, String>, Vec<String>), LojidocError> {
+        let text = fs::read_to_string(path).map_err(|err| LojidocError::io(path, err))?;
+        let table: BTreeMap<String, String> = toml::from_str(&text).map_err(|err| {
+            LojidocError::InvalidArgs(format!("{} is not a valid strings file: {}", path.display(), err))
+        })?;
+        let mut labels = BTreeMap::new();
+        let mut warnings = Vec::new();
+
+        for (key, text) in table {
+            if EN.iter().any(|(name, _)| *name == key) {
+                labels.insert(key, text);
+            } else {
+                warnings.push(format!(
+                    "{}: unknown key `{}`, it is skipped",
+                    path.display(),
+                    key
+                ));
+            }
+        }
+
+        Ok((labels, warnings))
+    }
+}
+
+#[cfg(test)]
+mod test;
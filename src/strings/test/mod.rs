@@ -0,0 +1,54 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use strings::strings::*;
+
+#[test]
+fn test_labels() {
+    let mut custom = BTreeMap::new();
+    custom.insert("methods".to_string(), "Metodi".to_string());
+
+    let english = Labels::new(Lang::En, BTreeMap::new());
+    let german = Labels::new(Lang::De, BTreeMap::new());
+    let custom = Labels::new(Lang::De, custom);
+
+    assert_eq!("Methods", english.get("methods"));
+    assert_eq!("Methoden", german.get("methods"));
+    assert_eq!("Klasse {}", german.get("class"));
+    assert_eq!("Metodi", custom.get("methods"));
+    assert_eq!("Konstruktoren", custom.get("constructors"));
+    assert_eq!("unknown", german.get("unknown"));
+    assert_eq!(Some(Lang::Fr), Lang::from_name("fr"));
+    assert_eq!(None, Lang::from_name("it"));
+}
+
+#[test]
+fn test_every_language_has_every_label() {
+    for lang in [Lang::De, Lang::Fr, Lang::Es].iter() {
+        let keys: Vec<&str> = lang.labels().iter().map(|(key, _)| *key).collect();
+        let english: Vec<&str> = EN.iter().map(|(key, _)| *key).collect();
+
+        assert_eq!(english, keys, "{}", lang.name());
+        for (key, text) in EN.iter() {
+            let label = Labels::new(*lang, BTreeMap::new()).get(key);
+            assert_eq!(text.contains("{}"), label.contains("{}"), "{} in {}", key, lang.name());
+        }
+    }
+}
+
+#[test]
+fn test_read_strings() {
+    let path = env::temp_dir().join(format!("lojidoc-strings-{}.toml", std::process::id()));
+    fs::write(&path, "methods = \"Metodi\"\nmethod = \"Metodo\"\n").unwrap();
+
+    let (labels, warnings) = read_strings(&path).unwrap();
+    fs::write(&path, "methods = 1\n").unwrap();
+    let invalid = read_strings(&path);
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(Some(&"Metodi".to_string()), labels.get("methods"));
+    assert_eq!(1, labels.len());
+    assert_eq!(1, warnings.len());
+    assert!(warnings[0].ends_with("unknown key `method`, it is skipped"));
+    assert!(invalid.is_err());
+}
@@ -0,0 +1,93 @@
+pub mod template {
+    //! Module that renders the pages of types through the handlebars templates of a
+    //! `--template` directory instead of the built-in layout
+
+    use std::fs;
+    use std::path::Path;
+    use std::path::PathBuf;
+
+    use handlebars::no_escape;
+    use handlebars::Handlebars;
+    use serde::Serialize;
+
+    use error::error::LojidocError;
+
+    /// The kinds of types which have a template, e.g. `class.hbs` renders the classes
+    pub const TEMPLATE_KINDS: &[&str] = &["class", "interface", "enum", "annotation"];
+
+    /// The extension of template files
+    pub const TEMPLATE_EXTENSION: &str = "hbs";
+
+    /// The templates of a `--template` directory. Every `.hbs` file is registered by its
+    /// name, so the templates of the types can include the other files as partials,
+    /// e.g. `{{> method}}` for `method.hbs`.
+    #[derive(Debug)]
+    pub struct Templates {
+        registry: Handlebars,
+        dir: PathBuf,
+    }
+
+    impl Templates {
+        /// Reads and compiles the templates of a directory
+        pub fn read_dir(dir: &Path) -> Result<Templates, LojidocError> {
+            let mut registry = Handlebars::new();
+            // The pages are markdown or already escaped by the renderer
+            registry.register_escape_fn(no_escape);
+
+            let entries = fs::read_dir(dir).map_err(|err| LojidocError::io(dir, err))?;
+            let mut paths: Vec<PathBuf> = entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == TEMPLATE_EXTENSION))
+                .collect();
+            paths.sort();
+
+            for path in paths {
+                let name = path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+                let text = fs::read_to_string(&path).map_err(|err| LojidocError::io(&path, err))?;
+
+                registry
+                    .register_template_string(&name, text)
+                    .map_err(|err| LojidocError::Template {
+                        template: path.clone(),
+                        line: err.line_no.unwrap_or(1),
+                        message: err.reason.to_string(),
+                    })?;
+                verbose!("Read the template {}", path.display());
+            }
+
+            Ok(Templates {
+                registry,
+                dir: dir.to_path_buf(),
+            })
+        }
+        /// The directory the templates were read from
+        pub fn dir(&self) -> &Path {
+            &self.dir
+        }
+        /// Whether the directory has a template for a kind of type. The other kinds
+        /// keep the built-in layout.
+        pub fn has(&self, kind: &str) -> bool {
+            self.registry.has_template(kind)
+        }
+        /// Renders a type through the template of its kind
+        ///
+        /// # Arguments
+        ///
+        /// * `kind` - The kind of the type, one of `TEMPLATE_KINDS`
+        /// * `data` - The model of the type, e.g. a `Class`
+        pub fn render<T: Serialize>(&self, kind: &str, data: &T) -> Result<String, LojidocError> {
+            self.registry.render(kind, data).map_err(|err| {
+                let name = err.template_name.clone().unwrap_or_else(|| kind.to_string());
+
+                LojidocError::Template {
+                    template: self.dir.join(format!("{}.{}", name, TEMPLATE_EXTENSION)),
+                    line: err.line_no.unwrap_or(1),
+                    message: err.desc,
+                }
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod test;
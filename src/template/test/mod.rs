@@ -0,0 +1,63 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+use error::error::LojidocError;
+use model::model::ObjectType;
+use parse::parse::parse_str;
+use template::template::*;
+
+#[test]
+fn test_render_template() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/template/test/templates");
+    let templates = Templates::read_dir(&dir).unwrap();
+    let source = "package p;
+
+/** A client */
+public class Client {
+    /** Finds a user */
+    public String find(long id) {
+        return null;
+    }
+
+    /** Closes the client */
+    public void close() {
+    }
+}
+";
+    let class = match parse_str(source, None).unwrap().remove(0) {
+        ObjectType::Class(class) => class,
+        _ => panic!("Expected a class"),
+    };
+
+    assert!(templates.has("class"));
+    assert!(!templates.has("interface"));
+    assert_eq!(
+        "# Client\n\nA client\n\n| Méthode | Description |\n| ------- | ----------- |\n\
+         | `find` | Finds a user |\n| `close` | Closes the client |\n\n",
+        templates.render("class", &class).unwrap()
+    );
+}
+
+#[test]
+fn test_template_errors() {
+    let dir = env::temp_dir().join(format!("lojidoc-templates-{}", std::process::id()));
+    let line = |err: LojidocError| match err {
+        LojidocError::Template { template, line, .. } => {
+            (template.file_name().unwrap().to_string_lossy().to_string(), line)
+        }
+        err => panic!("Expected a template error, got {}", err),
+    };
+
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("class.hbs"), "# {{name}}\n\n{{#each methods}}\n{{/if}}\n").unwrap();
+    let compiled = Templates::read_dir(&dir).map(|_| ()).map_err(line);
+
+    fs::write(dir.join("class.hbs"), "# {{name}}\n\n{{shout name}}\n").unwrap();
+    let rendered = Templates::read_dir(&dir).unwrap().render("class", &()).map_err(line);
+
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(Err(("class.hbs".to_string(), 4)), compiled);
+    assert_eq!(Err(("class.hbs".to_string(), 3)), rendered);
+}
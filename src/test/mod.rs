@@ -0,0 +1,73 @@
+#[test]
+fn test_bash_completions() {
+    use app;
+    use clap::Shell;
+
+    let mut script = Vec::new();
+    app().gen_completions_to("lojidoc", Shell::Bash, &mut script);
+    let script = String::from_utf8(script).unwrap();
+
+    assert!(script.contains("--destination"));
+    assert!(script.contains("--format"));
+
+    // The option is hidden from the help
+    let mut help = Vec::new();
+    app().write_help(&mut help).unwrap();
+    let help = String::from_utf8(help).unwrap();
+    assert!(help.contains("--destination"));
+    assert!(!help.contains("--completions"));
+
+    let matches = app().get_matches_from_safe(vec!["lojidoc", "--completions", "zsh"]).unwrap();
+    assert_eq!(Some("zsh"), matches.value_of("completions"));
+    assert!(app().get_matches_from_safe(vec!["lojidoc"]).is_err());
+
+    // A project directory may be named like the option
+    let matches = app().get_matches_from_safe(vec!["lojidoc", "completions"]).unwrap();
+    assert_eq!(Some("completions"), matches.value_of("INPUT"));
+    assert!(!matches.is_present("completions"));
+}
+
+// The labels are set for the whole process, the other tests of the binary do not read them
+#[test]
+fn test_german_interface_page() {
+    use lojidoc::document::document::gen_interface_page;
+    use lojidoc::document::document::written_string;
+    use lojidoc::document::document::PageLayout;
+    use lojidoc::document::render::Markdown;
+    use lojidoc::model::model::ObjectType;
+    use lojidoc::parse::parse::parse_str;
+    use lojidoc::strings::strings::set_labels;
+    use lojidoc::strings::strings::Labels;
+    use lojidoc::strings::strings::Lang;
+    use std::collections::BTreeMap;
+
+    let source = "package com.foo;
+
+                  /**
+                   * Sends the requests
+                   *
+                   * @author Ada
+                   * @since 1.2
+                   * @version 3
+                   */
+                  public interface Client {
+                      void send(String body);
+                  }";
+    let inter = match parse_str(source, None).unwrap().remove(0) {
+        ObjectType::Interface(inter) => inter,
+        _ => panic!("Expected an interface"),
+    };
+
+    set_labels(Labels::new(Lang::De, BTreeMap::new()));
+    let page = written_string(|out| gen_interface_page(out, &Markdown, inter, &PageLayout::default()));
+    set_labels(Labels::new(Lang::En, BTreeMap::new()));
+
+    assert!(page.contains("# Interface Client"));
+    assert!(page.contains("Beschreibung"));
+    assert!(page.contains("Autor: Ada"), "{}", page);
+    assert!(page.contains("Seit: 1.2"));
+    assert!(page.contains("Version: 3"));
+    assert!(page.contains("Zugriff: public"));
+    assert!(!page.contains("description"));
+    assert!(!page.contains("privacy"));
+}